@@ -150,13 +150,15 @@ async fn main() -> Result<()> {
         match swapline.calculate_with_in_amount(&db, env.clone(), in_amount) {
             Ok((out_amount, gas_used, _)) => {
                 println!("{} gas: {}  amount {} -> {}", sp_dto, gas_used, in_amount_f64, NWETH::to_float(out_amount));
-                swapline.amount_out = SwapAmountType::Set(out_amount)
+                swapline.amount_out = SwapAmountType::Set(out_amount);
+                swapline.gas_used = Some(gas_used);
             }
             Err(e) => {
                 error!("calculate_with_in_amount error : {:?}", e);
             }
         }
         let swap = Swap::BackrunSwapLine(swapline);
+        let estimated_gas = swap.pre_estimate_gas();
 
         let calls = swap_encoder.make_calls(&swap)?;
         let (to, payload) = swap_encoder.encode_calls(calls)?;
@@ -168,6 +170,14 @@ async fn main() -> Result<()> {
         let gas_used = match client.estimate_gas(&tx_request).await {
             Ok(gas_needed) => {
                 //info!("Gas required:  {gas_needed}");
+                // Estimate of pools calculation to calibrate pool gas constants against
+                println!(
+                    "{} estimated: {} measured: {} diff: {}",
+                    sp_dto,
+                    estimated_gas,
+                    gas_needed,
+                    estimated_gas as i64 - gas_needed as i64
+                );
                 gas_needed
             }
             Err(e) => {
@@ -292,21 +292,11 @@ async fn main() -> Result<()> {
         _ => info!("Nonce monitor has been initialized"),
     }
 
-    info!("Starting price actor");
-    let mut price_actor = PriceActor::new(client.clone()).only_once();
-    match price_actor.access(market_instance.clone()).start_and_wait() {
-        Err(e) => {
-            error!("{}", e);
-            panic!("Cannot initialize price actor");
-        }
-        _ => info!("Price actor has been initialized"),
-    }
-
     let pool_loaders = Arc::new(PoolLoadersBuilder::default_pool_loaders(client.clone(), PoolsLoadingConfig::default()));
 
     for (pool_name, pool_config) in test_config.pools {
         match pool_config.class {
-            PoolClass::UniswapV2 | PoolClass::UniswapV3 => {
+            PoolClass::UniswapV2 | PoolClass::UniswapV3 | PoolClass::BalancerV2 => {
                 debug!(address=%pool_config.address, class=%pool_config.class, "Loading pool");
                 fetch_and_add_pool_by_pool_id(
                     client.clone(),
@@ -340,6 +330,16 @@ async fn main() -> Result<()> {
         );
     }
 
+    info!("Starting price actor");
+    let mut price_actor = PriceActor::new().only_once();
+    match price_actor.access(market_instance.clone()).access(market_state.clone()).start_and_wait() {
+        Err(e) => {
+            error!("{}", e);
+            panic!("Cannot initialize price actor");
+        }
+        _ => info!("Price actor has been initialized"),
+    }
+
     info!("Starting block history actor");
     let mut block_history_actor = BlockHistoryActor::new(client.clone());
     match block_history_actor
@@ -407,6 +407,7 @@ async fn main() -> Result<()> {
         match swap_router_actor
             .access(tx_signers.clone())
             .access(accounts_state.clone())
+            .consume(market_events_channel.clone())
             .consume(swap_compose_channel.clone())
             .produce(swap_compose_channel.clone())
             .produce(tx_compose_channel.clone())
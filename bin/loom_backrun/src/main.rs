@@ -2,13 +2,13 @@ use alloy::providers::Provider;
 use eyre::Result;
 use tracing::{error, info};
 
-use loom::core::actors::{Accessor, Actor, Consumer, Producer};
+use loom::core::actors::{Accessor, Actor, ActorsManager, Consumer, Producer, DEFAULT_SHUTDOWN_TIMEOUT};
 use loom::core::router::SwapRouterActor;
 use loom::core::topology::{Topology, TopologyConfig};
-use loom::defi::health_monitor::{MetricsRecorderActor, StateHealthMonitorActor, StuffingTxMonitorActor};
+use loom::defi::health_monitor::{BundleInclusionTrackerActor, MetricsRecorderActor, StateHealthMonitorActor, StuffingTxMonitorActor};
 use loom::evm::db::LoomDBType;
 use loom::execution::multicaller::MulticallerSwapEncoder;
-use loom::metrics::InfluxDbWriterActor;
+use loom::metrics::{InfluxDbWriterActor, PrometheusExporterActor, PrometheusMetrics, DEFAULT_IGNORED_TAGS, DEFAULT_MAX_SERIES};
 use loom::strategy::backrun::{BackrunConfig, BackrunConfigSection, StateChangeArbActor};
 use loom::strategy::merger::{ArbSwapPathMergerActor, DiffPathMergerActor, SamePathMergerActor};
 use loom::types::entities::strategy_config::load_from_file;
@@ -24,13 +24,17 @@ async fn main() -> Result<()> {
 
     let topology_config = TopologyConfig::load_from_file("config.toml".to_string())?;
     let influxdb_config = topology_config.influxdb.clone();
+    let prometheus_config = topology_config.prometheus.clone();
+    let mempool_simulation = topology_config.actors.mempool_simulation();
 
     let encoder = MulticallerSwapEncoder::default();
 
     let topology =
         Topology::<LoomDBType>::from_config(topology_config).with_swap_encoder(encoder).build_blockchains().start_clients().await?;
 
-    let mut worker_task_vec = topology.start_actors().await?;
+    let mut actors_manager = ActorsManager::new().with_cancel_token(topology.cancel_token());
+    actors_manager.add_workers("Topology", topology.start_actors().await?);
+    let cancel_token = actors_manager.cancel_token();
 
     //mut worker_task_vec = topology.start_actors().await;
 
@@ -45,6 +49,7 @@ async fn main() -> Result<()> {
 
     let backrun_config: BackrunConfigSection = load_from_file("./config.toml".to_string().into()).await?;
     let backrun_config: BackrunConfig = backrun_config.backrun_strategy;
+    let tips_policy = backrun_config.tips_policy()?;
 
     let block_nr = client.get_block_number().await?;
     info!("Block : {}", block_nr);
@@ -52,7 +57,8 @@ async fn main() -> Result<()> {
     info!("Creating shared state");
 
     info!("Starting state change arb actor");
-    let mut state_change_arb_actor = StateChangeArbActor::new(client.clone(), true, true, backrun_config);
+    let mut state_change_arb_actor =
+        StateChangeArbActor::new(client.clone(), true, true, backrun_config).with_mempool_simulation(mempool_simulation);
     match state_change_arb_actor
         .access(blockchain.mempool())
         .access(blockchain.latest_block())
@@ -64,13 +70,13 @@ async fn main() -> Result<()> {
         .produce(strategy.swap_compose_channel())
         .produce(blockchain.health_monitor_channel())
         .produce(blockchain.influxdb_write_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Err(e) => {
             error!("{}", e)
         }
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("StateChangeArbActor", r);
             info!("State change arb actor started successfully")
         }
     }
@@ -83,13 +89,14 @@ async fn main() -> Result<()> {
     match swap_path_encoder_actor
         .access(tx_signers.clone())
         .access(blockchain.nonce_and_balance())
+        .consume(blockchain.market_events_channel())
         .consume(strategy.swap_compose_channel())
         .produce(strategy.swap_compose_channel())
         .produce(blockchain.tx_compose_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("SwapRouterActor", r);
             info!("Swap path encoder actor started successfully")
         }
         Err(e) => {
@@ -106,10 +113,10 @@ async fn main() -> Result<()> {
         .consume(blockchain.market_events_channel())
         .consume(strategy.swap_compose_channel())
         .produce(strategy.swap_compose_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("ArbSwapPathMergerActor", r);
             info!("Swap path merger actor started successfully")
         }
         Err(e) => {
@@ -125,10 +132,10 @@ async fn main() -> Result<()> {
         .consume(blockchain.market_events_channel())
         .consume(strategy.swap_compose_channel())
         .produce(strategy.swap_compose_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("SamePathMergerActor", r);
             info!("Same path merger actor started successfully")
         }
         Err(e) => {
@@ -143,10 +150,10 @@ async fn main() -> Result<()> {
         .consume(blockchain.market_events_channel())
         .consume(strategy.swap_compose_channel())
         .produce(strategy.swap_compose_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("DiffPathMergerActor", r);
             info!("Diff path merger actor started successfully")
         }
         Err(e) => {
@@ -161,101 +168,141 @@ async fn main() -> Result<()> {
         .access(blockchain_state.market_state())
         .consume(blockchain.tx_compose_channel())
         .consume(blockchain.market_events_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Err(e) => {
             panic!("State health monitor actor failed : {}", e)
         }
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("StateHealthMonitorActor", r);
             info!("State health monitor actor started successfully")
         }
     }
 
     // Monitoring transactions we tried to attach to.
-    let mut stuffing_txs_monitor_actor = StuffingTxMonitorActor::new(client.clone());
+    let mut stuffing_txs_monitor_actor = StuffingTxMonitorActor::new(client.clone()).with_tips_policy(tips_policy.clone());
     match stuffing_txs_monitor_actor
         .access(blockchain.latest_block())
         .consume(blockchain.tx_compose_channel())
         .consume(blockchain.market_events_channel())
         .produce(blockchain.influxdb_write_channel())
-        .start()
+        .start_with_cancel(cancel_token.clone())
     {
         Err(e) => {
             panic!("Stuffing txs monitor actor failed : {}", e)
         }
         Ok(r) => {
-            worker_task_vec.extend(r);
+            actors_manager.add_workers("StuffingTxMonitorActor", r);
             info!("Stuffing txs monitor actor started successfully")
         }
     }
 
+    // Tracking inclusion and realized profit of broadcast bundles
+    let mut bundle_inclusion_tracker_actor = BundleInclusionTrackerActor::new(client.clone()).with_tips_policy(tips_policy);
+    match bundle_inclusion_tracker_actor
+        .access(blockchain.bundle_inclusions())
+        .consume(blockchain.tx_compose_channel())
+        .consume(blockchain.new_block_with_tx_channel())
+        .produce(blockchain.influxdb_write_channel())
+        .start_with_cancel(cancel_token.clone())
+    {
+        Err(e) => {
+            panic!("Bundle inclusion tracker actor failed : {}", e)
+        }
+        Ok(r) => {
+            actors_manager.add_workers("BundleInclusionTrackerActor", r);
+            info!("Bundle inclusion tracker actor started successfully")
+        }
+    }
+
+    let record_metrics = influxdb_config.is_some() || prometheus_config.is_some();
+
+    // Serving Prometheus metrics built from InfluxDB write queries
+    if let Some(prometheus_config) = prometheus_config {
+        let mut prometheus_exporter_actor = PrometheusExporterActor::new(
+            PrometheusMetrics::new(
+                prometheus_config.namespace.unwrap_or("loom".to_string()),
+                prometheus_config.labels,
+                prometheus_config.ignore_tags.unwrap_or(DEFAULT_IGNORED_TAGS.iter().map(|t| t.to_string()).collect()),
+                prometheus_config.histograms,
+                prometheus_config.buckets,
+            )
+            .with_max_series(prometheus_config.max_series.unwrap_or(DEFAULT_MAX_SERIES)),
+        )
+        .with_host(prometheus_config.host.unwrap_or("127.0.0.1:9090".to_string()));
+        match prometheus_exporter_actor.consume(blockchain.influxdb_write_channel()).start_with_cancel(cancel_token.clone()) {
+            Err(e) => {
+                panic!("Prometheus exporter actor failed : {}", e)
+            }
+            Ok(r) => {
+                actors_manager.add_workers("PrometheusExporterActor", r);
+                info!("Prometheus exporter actor started successfully")
+            }
+        }
+    }
+
     // Recording InfluxDB metrics
     if let Some(influxdb_config) = influxdb_config {
         let mut influxdb_writer_actor = InfluxDbWriterActor::new(influxdb_config.url, influxdb_config.database, influxdb_config.tags);
-        match influxdb_writer_actor.consume(blockchain.influxdb_write_channel()).start() {
+        match influxdb_writer_actor.consume(blockchain.influxdb_write_channel()).start_with_cancel(cancel_token.clone()) {
             Err(e) => {
                 panic!("InfluxDB writer actor failed : {}", e)
             }
             Ok(r) => {
-                worker_task_vec.extend(r);
+                actors_manager.add_workers("InfluxDbWriterActor", r);
                 info!("InfluxDB writer actor started successfully")
             }
         }
+    }
 
+    if record_metrics {
         let mut block_latency_recorder_actor = MetricsRecorderActor::new();
         match block_latency_recorder_actor
             .access(blockchain.market())
             .access(blockchain_state.market_state())
             .consume(blockchain.new_block_headers_channel())
             .produce(blockchain.influxdb_write_channel())
-            .start()
+            .start_with_cancel(cancel_token.clone())
         {
             Err(e) => {
                 panic!("Block latency recorder actor failed : {}", e)
             }
             Ok(r) => {
-                worker_task_vec.extend(r);
+                actors_manager.add_workers("MetricsRecorderActor", r);
                 info!("Block latency recorder actor started successfully")
             }
         }
     }
 
-    // Checking workers, logging if some close
+    // Stopping actors on CTRL+C
     tokio::task::spawn(async move {
-        while !worker_task_vec.is_empty() {
-            let (result, _index, remaining_futures) = futures::future::select_all(worker_task_vec).await;
-            match result {
-                Ok(work_result) => match work_result {
-                    Ok(s) => {
-                        info!("ActorWorker {_index} finished : {s}")
-                    }
-                    Err(e) => {
-                        error!("ActorWorker {_index} error : {e}")
-                    }
-                },
-                Err(e) => {
-                    error!("ActorWorker join error {_index} : {e}")
-                }
-            }
-            worker_task_vec = remaining_futures;
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("CTRL+C received, shutting down actors");
+            cancel_token.cancel();
         }
     });
 
-    // listening to MarketEvents in an infinite loop
+    // listening to MarketEvents until shutdown
     let mut s = blockchain.market_events_channel().subscribe();
-    loop {
-        let msg = s.recv().await;
-        if let Ok(msg) = msg {
-            match msg {
-                MarketEvents::BlockTxUpdate { block_number, block_hash } => {
-                    info!("New block received {} {}", block_number, block_hash);
-                }
-                MarketEvents::BlockStateUpdate { block_hash } => {
-                    info!("New block state received {}", block_hash);
+    tokio::task::spawn(async move {
+        loop {
+            let msg = s.recv().await;
+            if let Ok(msg) = msg {
+                match msg {
+                    MarketEvents::BlockTxUpdate { block_number, block_hash } => {
+                        info!("New block received {} {}", block_number, block_hash);
+                    }
+                    MarketEvents::BlockStateUpdate { block_hash } => {
+                        info!("New block state received {}", block_hash);
+                    }
+                    _ => {}
                 }
-                _ => {}
             }
         }
-    }
+    });
+
+    let report = actors_manager.wait_with_shutdown(DEFAULT_SHUTDOWN_TIMEOUT).await;
+    info!("Actors stopped : {report}");
+
+    Ok(())
 }
@@ -1,18 +1,19 @@
 use alloy::network::Ethereum;
 use alloy::primitives::Address;
 use alloy::providers::Provider;
-use axum::Router;
 use eyre::{ErrReport, OptionExt};
+use loom::core::actors::DEFAULT_SHUTDOWN_TIMEOUT;
 use loom::core::blockchain::{Blockchain, BlockchainState, Strategy};
 use loom::core::blockchain_actors::BlockchainActors;
 use loom::core::topology::{BroadcasterConfig, EncoderConfig, TopologyConfig};
 use loom::defi::pools::PoolsLoadingConfig;
 use loom::evm::db::DatabaseLoomExt;
 use loom::execution::multicaller::MulticallerSwapEncoder;
+use loom::metrics::{PrometheusExporterActor, PrometheusMetrics, DEFAULT_IGNORED_TAGS, DEFAULT_MAX_SERIES};
 use loom::node::actor_config::NodeBlockActorConfig;
 use loom::node::debug_provider::DebugProviderExt;
 use loom::node::exex::loom_exex;
-use loom::storage::db::init_db_pool;
+use loom::storage::db::{init_db_pool, run_migrations};
 use loom::strategy::backrun::{BackrunConfig, BackrunConfigSection};
 use loom::types::entities::strategy_config::load_from_file;
 use loom::types::entities::{BlockHistoryState, PoolClass};
@@ -72,7 +73,21 @@ where
     info!(address=?multicaller_address, "Multicaller");
 
     let webserver_host = topology_config.webserver.unwrap_or_default().host;
+    let prometheus_exporter = topology_config.prometheus.map(|prometheus_config| {
+        PrometheusExporterActor::new(
+            PrometheusMetrics::new(
+                prometheus_config.namespace.unwrap_or("loom".to_string()),
+                prometheus_config.labels,
+                prometheus_config.ignore_tags.unwrap_or(DEFAULT_IGNORED_TAGS.iter().map(|t| t.to_string()).collect()),
+                prometheus_config.histograms,
+                prometheus_config.buckets,
+            )
+            .with_max_series(prometheus_config.max_series.unwrap_or(DEFAULT_MAX_SERIES)),
+        )
+    });
+    let web_router = prometheus_exporter.as_ref().map(|exporter| exporter.router()).unwrap_or_default();
     let db_url = topology_config.database.unwrap().url;
+    run_migrations(db_url.clone()).await?;
     let db_pool = init_db_pool(db_url).await?;
 
     // Get flashbots relays from config
@@ -98,6 +113,7 @@ where
         .mempool()?
         .with_wait_for_node_sync()? // wait for node to sync before
         .initialize_signers_with_encrypted_key(private_key_encrypted)? // initialize signer with encrypted key
+        .with_tips_policy(backrun_config.tips_policy()?)? // share tips policy with monitors that observe winning bids
         .with_block_history()? // collect blocks
         .with_price_station()? // calculate price fo tokens
         .with_health_monitor_pools()? // monitor pools health to disable empty
@@ -113,25 +129,44 @@ where
         //.with_curve_pool_protocol_loader()? // load curve + steth + wsteth
         .with_new_pool_loader(pools_config.clone())? // load new pools
         .with_pool_loader(pools_config.clone())?
+        .with_market_db_loader(db_pool.clone(), pools_config.clone())? // load pools known from previous runs
+        .with_market_db_writer(db_pool.clone())? // persist pools, tokens and ready swaps
         .with_swap_path_merger()? // load merger for multiple swap paths
         .with_diff_path_merger()? // load merger for different swap paths
         .with_same_path_merger()? // load merger for same swap paths with different stuffing txes
         .with_backrun_block(backrun_config.clone())? // load backrun searcher for incoming block
         .with_backrun_mempool(backrun_config)? // load backrun searcher for mempool txes
-        .with_web_server(webserver_host, Router::new(), db_pool)? // start web server
+        .with_web_server(webserver_host, web_router, db_pool)? // start web server
     ;
 
     if !is_exex {
         bc_actors.with_block_events(NodeBlockActorConfig::all_enabled())?.with_remote_mempool(provider.clone())?;
     }
 
+    let record_metrics = prometheus_exporter.is_some() || topology_config.influxdb.is_some();
+    if let Some(prometheus_exporter) = prometheus_exporter {
+        bc_actors.with_prometheus_exporter(prometheus_exporter)?;
+    }
+
     if let Some(influxdb_config) = topology_config.influxdb {
-        bc_actors
-            .with_influxdb_writer(influxdb_config.url, influxdb_config.database, influxdb_config.tags)?
-            .with_block_latency_recorder()?;
+        bc_actors.with_influxdb_writer(influxdb_config.url, influxdb_config.database, influxdb_config.tags)?;
     }
 
-    bc_actors.wait().await;
+    if record_metrics {
+        bc_actors.with_block_latency_recorder()?;
+    }
+
+    // stop actors gracefully on CTRL+C, pending broadcasts and db writes are finished
+    let cancel_token = bc_actors.cancel_token();
+    tokio::task::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            info!("CTRL+C received, shutting down actors");
+            cancel_token.cancel();
+        }
+    });
+
+    let report = bc_actors.wait_with_shutdown(DEFAULT_SHUTDOWN_TIMEOUT).await;
+    info!("Actors stopped : {report}");
 
     Ok(())
 }
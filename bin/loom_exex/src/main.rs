@@ -16,8 +16,6 @@ use reth::cli::Cli;
 use reth_node_ethereum::node::EthereumAddOns;
 use reth_node_ethereum::EthereumNode;
 use reth_provider::providers::BlockchainProvider;
-use std::time::Duration;
-use tokio::{signal, task};
 use tracing::{error, info};
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
@@ -111,18 +109,6 @@ fn main() -> eyre::Result<()> {
                     panic!("{}", e)
                 }
 
-                // keep loom running
-                tokio::select! {
-                    _ = signal::ctrl_c() => {
-                    info!("CTRL+C received... exiting");
-                }
-                _ = async {
-                        loop {
-                        tokio::time::sleep(Duration::from_secs(60)).await;
-                        task::yield_now().await;
-                        }
-                    } => {}
-                }
                 Ok::<(), eyre::Error>(())
             })?;
             Ok(())
@@ -0,0 +1,268 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::str::FromStr;
+use std::time::Duration;
+
+use alloy::primitives::{Address, U256};
+use alloy::providers::ProviderBuilder;
+use alloy::rpc::client::ClientBuilder;
+use clap::Args;
+use eyre::{eyre, Result};
+use serde::Serialize;
+use tokio::select;
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{error, info, warn};
+use url::Url;
+
+use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
+use loom_core_blockchain_actors::BlockchainActors;
+use loom_defi_address_book::{UniswapV2PoolAddress, UniswapV3PoolAddress};
+use loom_evm_db::LoomDB;
+use loom_evm_utils::NWETH;
+use loom_execution_multicaller::MulticallerSwapEncoder;
+use loom_node_debug_provider::HttpCachedTransport;
+use loom_node_player::NodeBlockPlayerActor;
+use loom_strategy_backrun::{BackrunConfig, BackrunConfigSection};
+use loom_types_entities::strategy_config::load_from_file;
+use loom_types_entities::{MarketState, PoolClass};
+use loom_types_events::{HealthEvent, SwapComposeMessage};
+
+#[derive(Args, Debug)]
+pub struct BacktestArgs {
+    /// First block to replay
+    #[arg(long)]
+    pub start_block: u64,
+    /// Last block to replay, inclusive
+    #[arg(long)]
+    pub end_block: u64,
+    /// Folder with recorded node responses
+    #[arg(long, default_value = "./.cache")]
+    pub cache_path: String,
+    /// Fetch missing responses from MAINNET_HTTP and record them, by default cache misses fail the backtest
+    #[arg(long)]
+    pub record: bool,
+    /// Pools to load as address:class, e.g. 0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640:uniswap3
+    #[arg(long = "pool", value_parser = parse_pool)]
+    pub pools: Vec<(Address, PoolClass)>,
+    /// Config file with [backrun_strategy] section
+    #[arg(long)]
+    pub config: Option<String>,
+    /// Report output file
+    #[arg(long, default_value = "backtest_report.json")]
+    pub report: String,
+    /// Delay between replayed blocks in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    pub block_interval_ms: u64,
+    /// Stop if no block was replayed for this number of seconds
+    #[arg(long, default_value_t = 60)]
+    pub timeout_sec: u64,
+}
+
+fn parse_pool(value: &str) -> Result<(Address, PoolClass), String> {
+    let (address, class) = value.split_once(':').ok_or("expected address:class".to_string())?;
+    let address = Address::from_str(address).map_err(|e| e.to_string())?;
+    let class = PoolClass::from_str(class).map_err(|e| e.to_string())?;
+    Ok((address, class))
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BlockReport {
+    pub block_number: u64,
+    /// Swaps found by the searcher
+    pub opportunities: usize,
+    /// Swaps that passed estimation
+    pub estimated: usize,
+    pub estimator_failures: usize,
+    /// Best estimated swap profit in wei
+    pub profit: U256,
+    pub profit_eth: f64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BacktestReport {
+    pub start_block: u64,
+    pub end_block: u64,
+    pub blocks_replayed: usize,
+    pub opportunities: usize,
+    pub estimated: usize,
+    pub estimator_failures: usize,
+    pub total_profit: U256,
+    pub total_profit_eth: f64,
+    pub cache_misses: u64,
+    pub completed: bool,
+    pub blocks: Vec<BlockReport>,
+}
+
+impl BacktestReport {
+    fn new(start_block: u64, end_block: u64, blocks: BTreeMap<u64, BlockReport>, cache_misses: u64, completed: bool) -> Self {
+        let blocks: Vec<BlockReport> = blocks.into_values().collect();
+        let total_profit = blocks.iter().fold(U256::ZERO, |acc, b| acc + b.profit);
+        Self {
+            start_block,
+            end_block,
+            blocks_replayed: blocks.len(),
+            opportunities: blocks.iter().map(|b| b.opportunities).sum(),
+            estimated: blocks.iter().map(|b| b.estimated).sum(),
+            estimator_failures: blocks.iter().map(|b| b.estimator_failures).sum(),
+            total_profit,
+            total_profit_eth: NWETH::to_float(total_profit),
+            cache_misses,
+            completed,
+            blocks,
+        }
+    }
+}
+
+/// Replay block range with backrun strategy and write report. Fails on cache misses unless recording.
+pub async fn run_backtest(args: BacktestArgs) -> Result<()> {
+    if args.start_block > args.end_block {
+        return Err(eyre!("INCORRECT_BLOCK_RANGE"));
+    }
+
+    let transport = if args.record {
+        let node_url = Url::parse(env::var("MAINNET_HTTP")?.as_str())?;
+        HttpCachedTransport::new(node_url, Some(args.cache_path.as_str())).await
+    } else {
+        HttpCachedTransport::new_offline(args.cache_path.as_str()).await?
+    };
+    transport.set_block_number(args.start_block);
+
+    let client = ClientBuilder::default().transport(transport.clone(), true).with_poll_interval(Duration::from_millis(50));
+    let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(client);
+
+    let backrun_config: BackrunConfig = match &args.config {
+        Some(config) => load_from_file::<BackrunConfigSection>(config.clone().into()).await?.backrun_strategy,
+        None => BackrunConfig::default(),
+    };
+
+    let pools = if args.pools.is_empty() {
+        vec![(UniswapV3PoolAddress::USDC_WETH_500, PoolClass::UniswapV3), (UniswapV2PoolAddress::USDC_WETH, PoolClass::UniswapV2)]
+    } else {
+        args.pools.clone()
+    };
+
+    let bc = Blockchain::new(1);
+    let bc_state = BlockchainState::new_with_market_state(MarketState::new(LoomDB::empty()));
+    let strategy = Strategy::<LoomDB>::new();
+    let swap_encoder = MulticallerSwapEncoder::default();
+
+    let mut header_sub = bc.new_block_headers_channel().subscribe();
+    let mut compose_sub = strategy.swap_compose_channel().subscribe();
+    let mut health_sub = bc.health_monitor_channel().subscribe();
+
+    let mut bc_actors = BlockchainActors::new(provider.clone(), swap_encoder.clone(), bc.clone(), bc_state.clone(), strategy, vec![]);
+    bc_actors
+        .with_nonce_and_balance_monitor_only_events()?
+        .initialize_signers_with_anvil()?
+        .with_market_state_preloader_virtual(vec![])?
+        .with_preloaded_state(pools, None)?
+        .with_block_history()?
+        .with_swap_encoder(swap_encoder)?
+        .with_evm_estimator()?
+        .with_backrun_block(backrun_config)?;
+
+    bc_actors.start(
+        NodeBlockPlayerActor::new(provider.clone(), args.start_block, args.end_block)
+            .with_block_interval(Duration::from_millis(args.block_interval_ms))
+            .with_transport(transport.clone())
+            .on_bc(&bc, &bc_state),
+    )?;
+    let cancel_token = bc_actors.cancel_token();
+    tokio::task::spawn(bc_actors.wait());
+
+    let mut blocks: BTreeMap<u64, BlockReport> = BTreeMap::new();
+    let mut current_block = args.start_block;
+    let timeout = Duration::from_secs(args.timeout_sec);
+    // results for the last block are collected for one more block interval
+    let mut deadline = tokio::time::Instant::now() + timeout;
+    let mut completed = false;
+
+    loop {
+        select! {
+            header = header_sub.recv() => {
+                match header {
+                    Ok(header) => {
+                        current_block = header.inner.header.number;
+                        info!(block_number = current_block, "Backtest block replayed");
+                        blocks.entry(current_block).or_insert(BlockReport { block_number: current_block, ..BlockReport::default() });
+                        if current_block >= args.end_block {
+                            completed = true;
+                            deadline = tokio::time::Instant::now() + Duration::from_millis(args.block_interval_ms);
+                        } else {
+                            deadline = tokio::time::Instant::now() + timeout;
+                        }
+                    }
+                    Err(RecvError::Lagged(lagged)) => warn!(lagged, "Block headers lagged"),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            compose = compose_sub.recv() => {
+                match compose {
+                    Ok(compose) => {
+                        let block_number = compose.inner.tx_compose.next_block_number.saturating_sub(1).max(args.start_block);
+                        let block_report = blocks.entry(block_number).or_insert(BlockReport { block_number, ..BlockReport::default() });
+                        match &compose.inner {
+                            SwapComposeMessage::Prepare(_) => block_report.opportunities += 1,
+                            SwapComposeMessage::Ready(data) => {
+                                block_report.estimated += 1;
+                                let profit = data.swap.abs_profit_eth();
+                                if profit > block_report.profit {
+                                    block_report.profit = profit;
+                                    block_report.profit_eth = NWETH::to_float(profit);
+                                }
+                            }
+                            SwapComposeMessage::Estimate(_) => {}
+                        }
+                    }
+                    Err(RecvError::Lagged(lagged)) => warn!(lagged, "Swap compose lagged"),
+                    Err(RecvError::Closed) => break,
+                }
+            }
+            health = health_sub.recv() => {
+                if let Ok(health) = health {
+                    if let HealthEvent::SwapLineEstimationError(_) = health.inner {
+                        blocks.entry(current_block).or_insert(BlockReport { block_number: current_block, ..BlockReport::default() }).estimator_failures += 1;
+                    }
+                }
+            }
+            _ = tokio::time::sleep_until(deadline) => {
+                if !completed {
+                    error!(current_block, "Backtest timed out");
+                }
+                break;
+            }
+        }
+    }
+    cancel_token.cancel();
+
+    let report = BacktestReport::new(args.start_block, args.end_block, blocks, transport.cache_misses(), completed);
+    tokio::fs::write(&args.report, serde_json::to_string_pretty(&report)?).await?;
+    info!(
+        report = %args.report,
+        blocks = report.blocks_replayed,
+        opportunities = report.opportunities,
+        profit_eth = report.total_profit_eth,
+        "Backtest report written"
+    );
+
+    if report.cache_misses > 0 {
+        return Err(eyre!("CACHE_MISSES"));
+    }
+    if !report.completed {
+        return Err(eyre!("BACKTEST_NOT_COMPLETED"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_pool() {
+        let (address, class) = parse_pool("0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640:uniswap3").unwrap();
+        assert_eq!(address, UniswapV3PoolAddress::USDC_WETH_500);
+        assert_eq!(class, PoolClass::UniswapV3);
+        assert!(parse_pool("0x88e6a0c2ddd26feeb64f039a2c41296fcb3f5640").is_err());
+    }
+}
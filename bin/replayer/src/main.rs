@@ -8,7 +8,7 @@ use alloy::primitives::{address, Address, U256};
 use alloy::providers::RootProvider;
 use alloy::rpc::types::Header;
 use alloy::{providers::ProviderBuilder, rpc::client::ClientBuilder};
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use eyre::Result;
 use tokio::select;
 use url::Url;
@@ -33,13 +33,25 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{fmt, EnvFilter, Layer};
 
+use crate::backtest::{run_backtest, BacktestArgs};
+
+mod backtest;
+
 #[derive(Parser, Debug)]
 struct Commands {
+    #[command(subcommand)]
+    command: Option<Command>,
     /// Run replayer for the given block number count
     #[arg(short, long)]
     terminate_after_block_count: Option<u64>,
 }
 
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Replay block range with backrun strategy from recorded node responses and write report
+    Backtest(BacktestArgs),
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let start_block_number = 20179184;
@@ -53,6 +65,10 @@ async fn main() -> Result<()> {
     tracing_subscriber::registry().with(fmt_layer).init();
 
     let args = Commands::parse();
+    if let Some(Command::Backtest(backtest_args)) = args.command {
+        return run_backtest(backtest_args).await;
+    }
+
     let node_url = env::var("MAINNET_HTTP")?;
     let node_url = Url::parse(node_url.as_str())?;
 
@@ -77,11 +93,14 @@ async fn main() -> Result<()> {
     let strategy = Strategy::<LoomDB>::new();
 
     let swap_encoder = MulticallerSwapEncoder::default();
+    let multicaller_address = swap_encoder.get_contract_address();
 
     const TARGET_ADDRESS: Address = address!("A69babEF1cA67A37Ffaf7a485DfFF3382056e78C");
 
     let mut required_state = RequiredState::new();
     required_state.add_call(TokenAddressEth::WETH, AbiEncoderHelper::encode_erc20_balance_of(TARGET_ADDRESS));
+    // exchange output is guarded against multicaller balance before the swap
+    required_state.add_call(TokenAddressEth::USDC, AbiEncoderHelper::encode_erc20_balance_of(multicaller_address));
 
     // instead fo code above
     let mut bc_actors =
@@ -96,19 +115,21 @@ async fn main() -> Result<()> {
         .with_evm_estimator()?;
 
     //Start node block player actor
-    if let Err(e) =
-        bc_actors.start(NodeBlockPlayerActor::new(provider.clone(), start_block_number, start_block_number + 200).on_bc(&bc, &bc_state))
-    {
+    if let Err(e) = bc_actors.start(
+        NodeBlockPlayerActor::new(provider.clone(), start_block_number, start_block_number + 200)
+            .with_transport(transport.clone())
+            .on_bc(&bc, &bc_state),
+    ) {
         panic!("Cannot start block player : {}", e);
     }
 
     tokio::task::spawn(bc_actors.wait());
     let compose_channel = strategy.swap_compose_channel();
 
-    let mut header_sub = bc.new_block_headers_channel().subscribe().await;
-    let mut block_sub = bc.new_block_with_tx_channel().subscribe().await;
-    let mut logs_sub = bc.new_block_logs_channel().subscribe().await;
-    let mut state_update_sub = bc.new_block_state_update_channel().subscribe().await;
+    let mut header_sub = bc.new_block_headers_channel().subscribe();
+    let mut block_sub = bc.new_block_with_tx_channel().subscribe();
+    let mut logs_sub = bc.new_block_logs_channel().subscribe();
+    let mut state_update_sub = bc.new_block_state_update_channel().subscribe();
 
     //let memepool = bc.mempool();
     let market = bc.market();
@@ -137,8 +158,20 @@ async fn main() -> Result<()> {
 
                             let swap_path = market.read().await.swap_path(vec![TokenAddressEth::WETH, TokenAddressEth::USDC], vec![PoolId::Address(UniswapV3PoolAddress::USDC_WETH_500)])?;
                             let mut swap_line = SwapLine::from(swap_path);
-                            swap_line.amount_in = SwapAmountType::Set( NWETH::from_float(0.1));
-                            swap_line.gas_used = Some(300000);
+                            let amount_in = NWETH::from_float(0.1);
+                            let state_db = market_state.read().await.state_db.clone();
+                            // amount out is required for exchange min out guard
+                            match swap_line.calculate_with_in_amount(&state_db, env_for_block(header.number + 1, header.timestamp + 12), amount_in) {
+                                Ok((amount_out, _, _)) => {
+                                    swap_line.amount_in = SwapAmountType::Set(amount_in);
+                                    swap_line.amount_out = SwapAmountType::Set(amount_out);
+                                    swap_line.gas_used = Some(300000);
+                                }
+                                Err(e) => {
+                                    error!("swap_line.calculate_with_in_amount : {:?}", e);
+                                    continue
+                                }
+                            }
 
                             let tx_compose_encode_msg = MessageSwapCompose::prepare(
                                 SwapComposeData{
@@ -146,7 +179,7 @@ async fn main() -> Result<()> {
                                         next_block_base_fee : bc.chain_parameters().calc_next_block_base_fee_from_header(&header),
                                         ..TxComposeData::default()
                                     },
-                                    poststate : Some(market_state.read().await.state_db.clone()),
+                                    poststate : Some(state_db),
                                     swap : Swap::ExchangeSwapLine(swap_line),
                                     ..SwapComposeData::default()
                                 });
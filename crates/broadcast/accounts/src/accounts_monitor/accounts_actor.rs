@@ -5,7 +5,7 @@ use alloy_primitives::{Address, Log, U256};
 use alloy_provider::Provider;
 use alloy_rpc_types::BlockTransactions;
 use alloy_sol_types::SolEventInterface;
-use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, Consumer, SharedState, WorkerResult};
+use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, SharedState, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer};
 use loom_core_blockchain::Blockchain;
 use loom_defi_abi::IERC20::IERC20Events;
@@ -21,6 +21,7 @@ pub async fn nonce_and_balance_fetcher_worker<P, N>(
     client: P,
     accounts_state: SharedState<AccountNonceAndBalanceState>,
     only_once: bool,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -48,7 +49,10 @@ where
             break;
         }
 
-        sleep(Duration::from_secs(20)).await;
+        tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            _ = sleep(Duration::from_secs(20)) => {}
+        }
     }
     Ok("Nonce and balance fetcher finished".to_string())
 }
@@ -57,12 +61,16 @@ pub async fn nonce_and_balance_monitor_worker(
     accounts_state: SharedState<AccountNonceAndBalanceState>,
     latest_block: SharedState<LatestBlock>,
     market_events_rx: Broadcaster<MarketEvents>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
-    let mut market_events = market_events_rx.subscribe();
+    subscribe!(market_events_rx);
 
     loop {
         tokio::select! {
-            msg = market_events.recv() => {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
+            msg = market_events_rx.recv() => {
                 let market_event_msg : Result<MarketEvents, RecvError> = msg;
                 if let Ok(market_event_msg) = market_event_msg {
                     match market_event_msg {
@@ -134,6 +142,7 @@ pub async fn nonce_and_balance_monitor_worker(
             }
         }
     }
+    Ok("NonceAndBalanceMonitorWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer)]
@@ -190,7 +199,7 @@ where
     N: Network,
     P: Provider<N> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let mut handles = Vec::new();
 
         if self.with_fetcher {
@@ -198,6 +207,7 @@ where
                 self.client.clone(),
                 self.accounts_nonce_and_balance.clone().unwrap(),
                 self.only_once,
+                cancel_token.clone(),
             ));
 
             if self.only_once {
@@ -216,6 +226,7 @@ where
             self.accounts_nonce_and_balance.clone().unwrap(),
             self.latest_block.clone().unwrap(),
             self.market_events.clone().unwrap(),
+            cancel_token,
         ));
         handles.push(monitor_task);
 
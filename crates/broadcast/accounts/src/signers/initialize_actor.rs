@@ -2,7 +2,7 @@ use alloy_primitives::{hex, Bytes, B256};
 use eyre::eyre;
 use tracing::{error, info};
 
-use loom_core_actors::{Accessor, Actor, ActorResult, SharedState, WorkerResult};
+use loom_core_actors::{Accessor, Actor, ActorResult, CancellationToken, SharedState, WorkerResult};
 use loom_core_actors_macros::Accessor;
 use loom_core_blockchain::Blockchain;
 use loom_types_entities::{AccountNonceAndBalanceState, KeyStore, LoomTxSigner, TxSigners};
@@ -89,7 +89,7 @@ impl Actor for InitializeSignersOneShotBlockingActor {
 
         Ok(())
     }
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, _cancel_token: CancellationToken) -> ActorResult {
         Err(eyre!("NEED_TO_BE_WAITED"))
     }
 
@@ -1,10 +1,9 @@
 use alloy_primitives::Bytes;
 use eyre::{eyre, Result};
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::broadcast::Receiver;
 use tracing::{error, info};
 
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Consumer, Producer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::Blockchain;
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum, LoomTx};
@@ -22,15 +21,16 @@ async fn sign_task<LDT: LoomDataTypes>(
         }
     };
 
-    let rlp_bundle: Vec<RlpState> = sign_request
-        .tx_bundle
-        .clone()
-        .unwrap()
-        .iter()
-        .map(|tx_request| match &tx_request {
+    let mut rlp_bundle: Vec<RlpState> = Vec::new();
+    for tx_request in sign_request.tx_bundle.clone().unwrap_or_default().iter() {
+        let rlp_state = match tx_request {
             TxState::Stuffing(t) => RlpState::Stuffing(t.encode().into()),
             TxState::SignatureRequired(t) => {
-                let tx = signer.sign_sync(t.clone()).unwrap();
+                // signing is async as signer can be a remote service
+                let tx = signer.sign(t.clone()).await.map_err(|e| {
+                    error!("Signing failed : {e}");
+                    eyre!("SIGNING_FAILED")
+                })?;
                 let tx_hash = tx.tx_hash();
                 let signed_tx_bytes = Bytes::from(tx.encode());
 
@@ -39,8 +39,9 @@ async fn sign_task<LDT: LoomDataTypes>(
             }
             TxState::ReadyForBroadcast(t) => RlpState::Backrun(t.clone()),
             TxState::ReadyForBroadcastStuffing(t) => RlpState::Stuffing(t.clone()),
-        })
-        .collect();
+        };
+        rlp_bundle.push(rlp_state);
+    }
 
     if rlp_bundle.iter().any(|item| item.is_none()) {
         error!("Bundle is not ready. Cannot sign");
@@ -61,11 +62,15 @@ async fn sign_task<LDT: LoomDataTypes>(
 async fn request_listener_worker<LDT: LoomDataTypes>(
     compose_channel_rx: Broadcaster<MessageTxCompose<LDT>>,
     compose_channel_tx: Broadcaster<MessageTxCompose<LDT>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
-    let mut compose_channel_rx: Receiver<MessageTxCompose<LDT>> = compose_channel_rx.subscribe();
+    subscribe!(compose_channel_rx);
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = compose_channel_rx.recv() => {
                 let compose_request_msg : Result<MessageTxCompose<LDT>, RecvError> = msg;
                 match compose_request_msg {
@@ -85,6 +90,7 @@ async fn request_listener_worker<LDT: LoomDataTypes>(
             }
         }
     }
+    Ok("RequestListenerWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer)]
@@ -112,9 +118,12 @@ impl<LDT: LoomDataTypes> TxSignersActor<LDT> {
 }
 
 impl<LDT: LoomDataTypes> Actor for TxSignersActor<LDT> {
-    fn start(&self) -> ActorResult {
-        let task =
-            tokio::task::spawn(request_listener_worker(self.compose_channel_rx.clone().unwrap(), self.compose_channel_tx.clone().unwrap()));
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(request_listener_worker(
+            self.compose_channel_rx.clone().unwrap(),
+            self.compose_channel_tx.clone().unwrap(),
+            cancel_token,
+        ));
 
         Ok(vec![task])
     }
@@ -5,10 +5,9 @@ use alloy_provider::Provider;
 use alloy_rpc_types::BlockTransactions;
 use eyre::Result;
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::broadcast::Receiver;
 use tracing::{error, info};
 
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Consumer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer};
 use loom_core_blockchain::Blockchain;
 use loom_node_debug_provider::AnvilProviderExt;
@@ -41,14 +40,17 @@ where
     Ok(())
 }
 
-async fn anvil_broadcaster_worker<P>(client: P, bundle_rx: Broadcaster<MessageTxCompose>) -> WorkerResult
+async fn anvil_broadcaster_worker<P>(client: P, bundle_rx: Broadcaster<MessageTxCompose>, cancel_token: CancellationToken) -> WorkerResult
 where
     P: Provider<Ethereum> + AnvilProviderExt<Ethereum> + Send + Sync + Clone + 'static,
 {
-    let mut bundle_rx: Receiver<MessageTxCompose> = bundle_rx.subscribe();
+    subscribe!(bundle_rx);
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = bundle_rx.recv() => {
                 let broadcast_msg : Result<MessageTxCompose,RecvError> = msg;
                 match broadcast_msg {
@@ -80,6 +82,7 @@ where
             }
         }
     }
+    Ok("AnvilBroadcasterWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer)]
@@ -106,8 +109,8 @@ impl<P> Actor for AnvilBroadcastActor<P>
 where
     P: Provider<Ethereum> + AnvilProviderExt<Ethereum> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
-        let task = tokio::task::spawn(anvil_broadcaster_worker(self.client.clone(), self.tx_compose_rx.clone().unwrap()));
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(anvil_broadcaster_worker(self.client.clone(), self.tx_compose_rx.clone().unwrap(), cancel_token));
         Ok(vec![task])
     }
 
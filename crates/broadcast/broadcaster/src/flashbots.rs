@@ -1,19 +1,26 @@
 use std::sync::Arc;
 
 use alloy_network::Ethereum;
-use alloy_primitives::Bytes;
+use alloy_primitives::{keccak256, Bytes};
 use alloy_provider::Provider;
 use eyre::{eyre, Result};
 use tokio::sync::broadcast::error::RecvError;
-use tracing::error;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info};
 
 use loom_broadcast_flashbots::Flashbots;
-use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, Consumer, WorkerResult};
+use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, SharedState, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer};
 use loom_core_blockchain::Blockchain;
+use loom_types_entities::BundleInclusionHistory;
 use loom_types_events::{MessageTxCompose, RlpState, TxComposeData, TxComposeMessageType};
 
-async fn broadcast_task<P>(broadcast_request: TxComposeData, client: Arc<Flashbots<P>>) -> Result<()>
+async fn broadcast_task<P>(
+    broadcast_request: TxComposeData,
+    client: Arc<Flashbots<P>>,
+    bundle_inclusions: Option<SharedState<BundleInclusionHistory>>,
+) -> Result<()>
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
 {
@@ -27,8 +34,17 @@ where
         if stuffing_rlp_bundle.iter().any(|i| i.is_empty()) || backrun_rlp_bundle.iter().any(|i| i.is_empty()) {
             Err(eyre!("RLP_BUNDLE_IS_INCORRECT"))
         } else {
-            client.broadcast_txes(backrun_rlp_bundle.clone(), block_number).await?;
-            client.broadcast_txes(stuffing_rlp_bundle.clone(), block_number).await?;
+            let (backrun_relays, stuffing_relays) = tokio::try_join!(
+                client.broadcast_txes(backrun_rlp_bundle.clone(), block_number),
+                client.broadcast_txes(stuffing_rlp_bundle.clone(), block_number)
+            )?;
+
+            if let Some(bundle_inclusions) = bundle_inclusions {
+                let mut bundle_inclusions_guard = bundle_inclusions.write().await;
+                for tx_hash in backrun_rlp_bundle.iter().map(keccak256) {
+                    bundle_inclusions_guard.add_relays(&tx_hash, backrun_relays.iter().chain(stuffing_relays.iter()).cloned());
+                }
+            }
 
             Ok(())
         }
@@ -41,30 +57,36 @@ where
 async fn flashbots_broadcaster_worker<P>(
     client: Arc<Flashbots<P>>,
     bundle_rx: Broadcaster<MessageTxCompose>,
+    bundle_inclusions: Option<SharedState<BundleInclusionHistory>>,
     allow_broadcast: bool,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
 {
     subscribe!(bundle_rx);
 
+    // in-flight broadcasts are awaited on shutdown
+    let mut broadcast_tasks: JoinSet<Result<()>> = JoinSet::new();
+
     //let mut current_block: u64 = 0;
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                info!("Waiting for {} pending broadcasts", broadcast_tasks.len());
+                while broadcast_tasks.join_next().await.is_some() {}
+                return Ok("Flashbots broadcaster stopped".to_string());
+            }
+            Some(_) = broadcast_tasks.join_next(), if !broadcast_tasks.is_empty() => {}
             msg = bundle_rx.recv() => {
                 let broadcast_msg : Result<MessageTxCompose, RecvError> = msg;
                 match broadcast_msg {
                     Ok(compose_request) => {
                         if let TxComposeMessageType::Broadcast(broadcast_request)  = compose_request.inner {
                             if allow_broadcast {
-                                      tokio::task::spawn(
-                                        broadcast_task(
-                                            broadcast_request,
-                                            client.clone(),
-                                        )
-                                    );
-                                }
+                                broadcast_tasks.spawn(broadcast_task(broadcast_request, client.clone(), bundle_inclusions.clone()));
+                            }
 
                             //TODO : Move smart mode to Strategy router
                             /*
@@ -114,6 +136,9 @@ pub struct FlashbotsBroadcastActor<P> {
     client: Arc<Flashbots<P>>,
     #[consumer]
     tx_compose_channel_rx: Option<Broadcaster<MessageTxCompose>>,
+    /// Relays that accepted backrun bundles are recorded for inclusion tracking
+    #[accessor]
+    bundle_inclusions: Option<SharedState<BundleInclusionHistory>>,
     allow_broadcast: bool,
 }
 
@@ -122,11 +147,11 @@ where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
 {
     pub fn new(client: Flashbots<P>, allow_broadcast: bool) -> FlashbotsBroadcastActor<P> {
-        FlashbotsBroadcastActor { client: Arc::new(client), tx_compose_channel_rx: None, allow_broadcast }
+        FlashbotsBroadcastActor { client: Arc::new(client), tx_compose_channel_rx: None, bundle_inclusions: None, allow_broadcast }
     }
 
     pub fn on_bc(self, bc: &Blockchain) -> Self {
-        Self { tx_compose_channel_rx: Some(bc.tx_compose_channel()), ..self }
+        Self { tx_compose_channel_rx: Some(bc.tx_compose_channel()), bundle_inclusions: Some(bc.bundle_inclusions()), ..self }
     }
 }
 
@@ -134,11 +159,13 @@ impl<P> Actor for FlashbotsBroadcastActor<P>
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(flashbots_broadcaster_worker(
             self.client.clone(),
             self.tx_compose_channel_rx.clone().unwrap(),
+            self.bundle_inclusions.clone(),
             self.allow_broadcast,
+            cancel_token,
         ));
         Ok(vec![task])
     }
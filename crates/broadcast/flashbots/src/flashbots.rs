@@ -7,6 +7,7 @@ use alloy_primitives::{TxHash, U64};
 use alloy_provider::Provider;
 use alloy_signer_local::PrivateKeySigner;
 use eyre::{eyre, Result};
+use futures_util::future::join_all;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tracing::{debug, error, info};
@@ -59,6 +60,17 @@ where
         }
     }
 
+    /// Simulates bundle with `eth_callBundle` of the relay instead of the provider node
+    pub async fn call_bundle_relay(&self, request: &BundleRequest) -> Result<SimulatedBundle> {
+        match self.flashbots_middleware.relay().request("eth_callBundle", [request]).await {
+            Ok(x) => Ok(x),
+            Err(e) => {
+                error!("{} {}", self.name, e);
+                Err(eyre!("FLASHBOTS_RELAY_ERROR"))
+            }
+        }
+    }
+
     #[allow(dead_code)]
     pub async fn send_bundle(&self, request: &BundleRequest) -> Result<()> {
         match self.flashbots_middleware.send_bundle(request).await {
@@ -102,6 +114,7 @@ pub struct Flashbots<P> {
     signer: PrivateKeySigner,
     provider: P,
     simulation_client: FlashbotsClient<P>,
+    simulate_on_relay: bool,
     clients: Vec<Arc<FlashbotsClient<P>>>,
 }
 
@@ -113,7 +126,13 @@ where
         let signer = signer.unwrap_or(PrivateKeySigner::random());
         let simulation_client = FlashbotsClient::new(provider.clone(), simulation_endpoint);
 
-        Flashbots { req_id: AtomicU64::new(0), signer, provider, clients: vec![], simulation_client }
+        Flashbots { req_id: AtomicU64::new(0), signer, provider, clients: vec![], simulation_client, simulate_on_relay: false }
+    }
+
+    /// Simulates bundles with `eth_callBundle` of given relay, e.g. web server of another loom instance, instead of the provider node
+    pub fn with_simulation_relay(self, url: &str) -> Self {
+        let simulation_client = FlashbotsClient::new_no_sign(self.provider.clone(), url);
+        Self { simulation_client, simulate_on_relay: true, ..self }
     }
 
     pub fn with_default_relays(self) -> Self {
@@ -198,10 +217,15 @@ where
             bundle = bundle.push_transaction(t);
         }
 
-        self.simulation_client.call_bundle(&bundle).await
+        if self.simulate_on_relay {
+            self.simulation_client.call_bundle_relay(&bundle).await
+        } else {
+            self.simulation_client.call_bundle(&bundle).await
+        }
     }
 
-    pub async fn broadcast_txes<TX>(&self, txs: Vec<TX>, target_block: u64) -> Result<()>
+    /// Sends bundle to all relays, returns names of relays that accepted it
+    pub async fn broadcast_txes<TX>(&self, txs: Vec<TX>, target_block: u64) -> Result<Vec<String>>
     where
         BundleTransaction: From<TX>,
     {
@@ -216,26 +240,26 @@ where
 
         let (body, signature) = make_signed_body(next_req_id, "eth_sendBundle", bundle, &self.signer)?;
 
-        for client in self.clients.iter() {
-            let client_clone = client.clone();
+        let broadcasts = self.clients.iter().map(|client| {
             let body_clone = body.clone();
             let signature_clone = signature.clone();
 
-            tokio::task::spawn(async move {
-                debug!("Sending bundle to {}", client_clone.name);
-                let bundle_result = client_clone.send_signed_body(body_clone, signature_clone).await;
-                match bundle_result {
+            async move {
+                debug!("Sending bundle to {}", client.name);
+                match client.send_signed_body(body_clone, signature_clone).await {
                     Ok(_) => {
-                        debug!("Flashbots bundle broadcast successfully {}", client_clone.name);
+                        debug!("Flashbots bundle broadcast successfully {}", client.name);
+                        Some(client.name.clone())
                     }
                     Err(x) => {
-                        error!("Broadcasting error to {} : {}", client_clone.name, x.to_string());
+                        error!("Broadcasting error to {} : {}", client.name, x.to_string());
+                        None
                     }
                 }
-            });
-        }
+            }
+        });
 
-        Ok(())
+        Ok(join_all(broadcasts).await.into_iter().flatten().collect())
     }
 }
 
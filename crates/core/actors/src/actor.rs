@@ -2,6 +2,7 @@ use crate::channels::Broadcaster;
 use crate::shared_state::SharedState;
 use eyre::{eyre, Result};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::info;
 
 pub type WorkerResult = Result<String>;
@@ -31,7 +32,14 @@ pub trait Actor {
         self.wait(handles)
     }
 
-    fn start(&self) -> ActorResult;
+    /// Starts workers with a token that is never cancelled
+    fn start(&self) -> ActorResult {
+        self.start_with_cancel(CancellationToken::new())
+    }
+
+    /// Starts workers that finish pending work and exit when `cancel_token` is cancelled. Workers that don't exit before
+    /// shutdown timeout expires are aborted by the actor manager.
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult;
 
     fn name(&self) -> &'static str;
 }
@@ -1,12 +1,42 @@
+use std::sync::Arc;
+use std::time::Duration;
+
 use eyre::Result;
-use tokio::task::JoinHandle;
-use tracing::{error, info};
+use futures::future::BoxFuture;
+use futures::stream::FuturesUnordered;
+use futures::{FutureExt, StreamExt};
+use tokio::task::{AbortHandle, JoinError, JoinHandle};
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+
+use crate::{Actor, RestartPolicy, ShutdownReport, SupervisorEvent, WorkerExit, WorkerExitStatus, WorkerResult};
+
+pub const DEFAULT_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+pub type SupervisorEventHandler = Arc<dyn Fn(SupervisorEvent) + Send + Sync>;
+
+struct SupervisedActor {
+    actor: Arc<dyn Actor + Send + Sync>,
+    policy: RestartPolicy,
+    restart_count: u32,
+    // Workers of previous generations are aborted, their results are ignored
+    generation: u64,
+    tasks: Vec<JoinHandle<WorkerResult>>,
+    abort_handles: Vec<AbortHandle>,
+}
 
-use crate::{Actor, WorkerResult};
+enum ManagerEvent {
+    WorkerFinished { actor: &'static str, supervised_idx: Option<usize>, generation: u64, result: Result<WorkerResult, JoinError> },
+    RestartDue { supervised_idx: usize },
+}
 
 #[derive(Default)]
 pub struct ActorsManager {
-    tasks: Vec<JoinHandle<WorkerResult>>,
+    tasks: Vec<(&'static str, JoinHandle<WorkerResult>)>,
+    supervised: Vec<SupervisedActor>,
+    event_handler: Option<SupervisorEventHandler>,
+    cancel_token: CancellationToken,
 }
 
 impl ActorsManager {
@@ -14,11 +44,53 @@ impl ActorsManager {
         Self::default()
     }
 
+    /// Handler for restart events of supervised actors
+    pub fn with_event_handler(self, event_handler: SupervisorEventHandler) -> Self {
+        Self { event_handler: Some(event_handler), ..self }
+    }
+
+    /// Use external token to signal shutdown, must be set before actors are started
+    pub fn with_cancel_token(self, cancel_token: CancellationToken) -> Self {
+        Self { cancel_token, ..self }
+    }
+
+    /// Token passed to every started actor, cancelling it starts shutdown
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
+    }
+
     pub fn start(&mut self, actor: impl Actor + 'static) -> Result<()> {
-        match actor.start() {
+        match actor.start_with_cancel(self.cancel_token.clone()) {
             Ok(workers) => {
                 info!("{} started successfully", actor.name());
-                self.tasks.extend(workers);
+                self.add_workers(actor.name(), workers);
+                Ok(())
+            }
+            Err(e) => {
+                error!("Error starting {} : {}", actor.name(), e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Adds workers of an actor that was started outside of actor manager
+    pub fn add_workers(&mut self, actor: &'static str, workers: Vec<JoinHandle<WorkerResult>>) {
+        self.tasks.extend(workers.into_iter().map(|worker| (actor, worker)));
+    }
+
+    /// Starts actor that is restarted according to restart policy when its workers stop
+    pub fn start_supervised(&mut self, actor: impl Actor + Send + Sync + 'static, policy: RestartPolicy) -> Result<()> {
+        match actor.start_with_cancel(self.cancel_token.clone()) {
+            Ok(workers) => {
+                info!("{} started successfully with restart policy {:?}", actor.name(), policy.mode);
+                self.supervised.push(SupervisedActor {
+                    actor: Arc::new(actor),
+                    policy,
+                    restart_count: 0,
+                    generation: 0,
+                    abort_handles: workers.iter().map(|worker| worker.abort_handle()).collect(),
+                    tasks: workers,
+                });
                 Ok(())
             }
             Err(e) => {
@@ -41,27 +113,285 @@ impl ActorsManager {
         }
     }
 
+    fn worker_future(
+        actor: &'static str,
+        supervised_idx: Option<usize>,
+        generation: u64,
+        task: JoinHandle<WorkerResult>,
+    ) -> BoxFuture<'static, ManagerEvent> {
+        task.map(move |result| ManagerEvent::WorkerFinished { actor, supervised_idx, generation, result }).boxed()
+    }
+
+    fn emit(&self, event: SupervisorEvent) {
+        if let Some(event_handler) = &self.event_handler {
+            event_handler(event)
+        }
+    }
+
+    // Aborts remaining workers of the actor and schedules restart, returns None if restart count is exhausted
+    fn schedule_restart(&mut self, supervised_idx: usize) -> Option<BoxFuture<'static, ManagerEvent>> {
+        let supervised = &mut self.supervised[supervised_idx];
+        supervised.generation += 1;
+        for abort_handle in supervised.abort_handles.drain(..) {
+            abort_handle.abort();
+        }
+
+        if supervised.restart_count >= supervised.policy.max_restarts {
+            let (actor, restart_count) = (supervised.actor.name(), supervised.restart_count);
+            error!("{} is not restarted, restart count {} exhausted", actor, restart_count);
+            self.emit(SupervisorEvent::RestartsExhausted { actor, restart_count });
+            return None;
+        }
+
+        let backoff = supervised.policy.backoff(supervised.restart_count);
+        warn!("{} restart scheduled in {:?}", supervised.actor.name(), backoff);
+        let cancel_token = self.cancel_token.clone();
+        Some(
+            async move {
+                tokio::select! {
+                    _ = tokio::time::sleep(backoff) => {}
+                    _ = cancel_token.cancelled() => {}
+                }
+                ManagerEvent::RestartDue { supervised_idx }
+            }
+            .boxed(),
+        )
+    }
+
+    fn restart(&mut self, supervised_idx: usize) -> Result<Vec<BoxFuture<'static, ManagerEvent>>> {
+        let cancel_token = self.cancel_token.clone();
+        let supervised = &mut self.supervised[supervised_idx];
+        supervised.restart_count += 1;
+        let (actor, restart_count, generation) = (supervised.actor.name(), supervised.restart_count, supervised.generation);
+
+        match supervised.actor.start_with_cancel(cancel_token) {
+            Ok(workers) => {
+                info!("{} restarted, restart count {}", actor, restart_count);
+                supervised.abort_handles = workers.iter().map(|worker| worker.abort_handle()).collect();
+                self.emit(SupervisorEvent::ActorRestarted { actor, restart_count });
+                Ok(workers.into_iter().map(|worker| Self::worker_future(actor, Some(supervised_idx), generation, worker)).collect())
+            }
+            Err(e) => {
+                error!("Error restarting {} : {}", actor, e);
+                self.emit(SupervisorEvent::RestartFailed { actor, restart_count, error: e.to_string() });
+                Err(e)
+            }
+        }
+    }
+
+    /// Waits for all workers to finish
     pub async fn wait(self) {
-        let mut f_remaining_futures = self.tasks;
-        let mut futures_counter = f_remaining_futures.len();
-
-        while futures_counter > 0 {
-            let (result, _index, remaining_futures) = futures::future::select_all(f_remaining_futures).await;
-            match result {
-                Ok(work_result) => match work_result {
-                    Ok(s) => {
-                        info!("ActorWorker {_index} finished : {s}")
+        let report = self.wait_with_shutdown(DEFAULT_SHUTDOWN_TIMEOUT).await;
+        info!("All actors finished : {report}");
+    }
+
+    /// Signals all actors to stop and waits for them to finish pending work. Workers still running after `timeout` are aborted.
+    pub async fn shutdown(self, timeout: Duration) -> ShutdownReport {
+        self.cancel_token.cancel();
+        self.wait_with_shutdown(timeout).await
+    }
+
+    /// Waits for all workers to finish. After cancel token is cancelled, workers still running after `shutdown_timeout` are
+    /// aborted.
+    pub async fn wait_with_shutdown(mut self, shutdown_timeout: Duration) -> ShutdownReport {
+        let mut report = ShutdownReport::default();
+        let mut futures: FuturesUnordered<BoxFuture<'static, ManagerEvent>> = FuturesUnordered::new();
+        let mut abort_handles: Vec<AbortHandle> = Vec::new();
+
+        for (actor, task) in std::mem::take(&mut self.tasks) {
+            abort_handles.push(task.abort_handle());
+            futures.push(Self::worker_future(actor, None, 0, task));
+        }
+        for (supervised_idx, supervised) in self.supervised.iter_mut().enumerate() {
+            for task in std::mem::take(&mut supervised.tasks) {
+                futures.push(Self::worker_future(supervised.actor.name(), Some(supervised_idx), supervised.generation, task));
+            }
+        }
+
+        let cancel_token = self.cancel_token.clone();
+        let mut shutdown_deadline: Option<Instant> = None;
+        let mut aborted = false;
+
+        loop {
+            let event = tokio::select! {
+                event = futures.next() => match event {
+                    Some(event) => event,
+                    None => break,
+                },
+                _ = cancel_token.cancelled(), if shutdown_deadline.is_none() => {
+                    info!("Shutting down actors, timeout {:?}", shutdown_timeout);
+                    shutdown_deadline = Some(Instant::now() + shutdown_timeout);
+                    continue;
+                }
+                _ = tokio::time::sleep_until(shutdown_deadline.unwrap_or_else(Instant::now)), if shutdown_deadline.is_some() && !aborted => {
+                    warn!("Shutdown timeout expired, aborting remaining workers");
+                    aborted = true;
+                    abort_handles.iter().for_each(|abort_handle| abort_handle.abort());
+                    self.supervised.iter().flat_map(|supervised| supervised.abort_handles.iter()).for_each(|abort_handle| abort_handle.abort());
+                    continue;
+                }
+            };
+
+            match event {
+                ManagerEvent::WorkerFinished { actor, supervised_idx, generation, result } => {
+                    if let Some(supervised_idx) = supervised_idx {
+                        if self.supervised[supervised_idx].generation != generation {
+                            continue;
+                        }
                     }
-                    Err(e) => {
-                        error!("ActorWorker {_index} finished with error : {e}")
+
+                    let status = match result {
+                        Ok(Ok(s)) => {
+                            info!("ActorWorker {actor} finished : {s}");
+                            WorkerExitStatus::Clean
+                        }
+                        Ok(Err(e)) => {
+                            error!("ActorWorker {actor} finished with error : {e}");
+                            WorkerExitStatus::Failed(e.to_string())
+                        }
+                        Err(e) if e.is_cancelled() => {
+                            warn!("ActorWorker {actor} aborted");
+                            WorkerExitStatus::Aborted
+                        }
+                        Err(e) => {
+                            error!("ActorWorker {actor} join error : {e}");
+                            WorkerExitStatus::Failed(e.to_string())
+                        }
+                    };
+                    let error = match &status {
+                        WorkerExitStatus::Failed(error) => Some(error.clone()),
+                        _ => None,
+                    };
+                    report.workers.push(WorkerExit { actor, status });
+
+                    let Some(supervised_idx) = supervised_idx else {
+                        continue;
+                    };
+                    if cancel_token.is_cancelled() {
+                        continue;
+                    }
+                    let should_restart = self.supervised[supervised_idx].policy.should_restart(error.is_some());
+                    if error.is_some() || should_restart {
+                        self.emit(SupervisorEvent::WorkerStopped { actor, error });
+                    }
+                    if should_restart {
+                        if let Some(restart_future) = self.schedule_restart(supervised_idx) {
+                            futures.push(restart_future);
+                        }
+                    }
+                }
+                ManagerEvent::RestartDue { supervised_idx } => {
+                    if cancel_token.is_cancelled() {
+                        continue;
+                    }
+                    match self.restart(supervised_idx) {
+                        Ok(workers) => futures.extend(workers),
+                        Err(_) => {
+                            if let Some(restart_future) = self.schedule_restart(supervised_idx) {
+                                futures.push(restart_future);
+                            }
+                        }
                     }
-                },
-                Err(e) => {
-                    error!("ActorWorker join error {_index} : {e}")
                 }
             }
-            f_remaining_futures = remaining_futures;
-            futures_counter = f_remaining_futures.len();
         }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{ActorResult, RestartMode};
+    use eyre::eyre;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    struct FailingActor {
+        starts: Arc<AtomicU32>,
+    }
+
+    impl Actor for FailingActor {
+        fn start_with_cancel(&self, _cancel_token: CancellationToken) -> ActorResult {
+            self.starts.fetch_add(1, Ordering::SeqCst);
+            Ok(vec![tokio::task::spawn(async { Err(eyre!("WORKER_FAILED")) })])
+        }
+
+        fn name(&self) -> &'static str {
+            "FailingActor"
+        }
+    }
+
+    struct CancellableActor {}
+
+    impl Actor for CancellableActor {
+        fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+            Ok(vec![tokio::task::spawn(async move {
+                cancel_token.cancelled().await;
+                Ok("cancelled".to_string())
+            })])
+        }
+
+        fn name(&self) -> &'static str {
+            "CancellableActor"
+        }
+    }
+
+    struct StuckActor {}
+
+    impl Actor for StuckActor {
+        fn start_with_cancel(&self, _cancel_token: CancellationToken) -> ActorResult {
+            Ok(vec![tokio::task::spawn(std::future::pending::<WorkerResult>())])
+        }
+
+        fn name(&self) -> &'static str {
+            "StuckActor"
+        }
+    }
+
+    fn collecting_manager() -> (ActorsManager, Arc<Mutex<Vec<SupervisorEvent>>>) {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        let manager = ActorsManager::new().with_event_handler(Arc::new(move |event| events_clone.lock().unwrap().push(event)));
+        (manager, events)
+    }
+
+    #[tokio::test]
+    async fn test_restart_on_error() {
+        let starts = Arc::new(AtomicU32::new(0));
+        let (mut manager, events) = collecting_manager();
+        let policy = RestartPolicy::on_error().with_backoff(Duration::from_millis(1), Duration::from_millis(10)).with_max_restarts(3);
+        manager.start_supervised(FailingActor { starts: starts.clone() }, policy).unwrap();
+        manager.wait().await;
+
+        assert_eq!(starts.load(Ordering::SeqCst), 4);
+        let events = events.lock().unwrap();
+        assert_eq!(events.iter().filter(|event| matches!(event, SupervisorEvent::ActorRestarted { .. })).count(), 3);
+        assert!(matches!(events.last(), Some(SupervisorEvent::RestartsExhausted { restart_count: 3, .. })));
+    }
+
+    #[tokio::test]
+    async fn test_no_restart() {
+        let starts = Arc::new(AtomicU32::new(0));
+        let (mut manager, events) = collecting_manager();
+        manager.start_supervised(FailingActor { starts: starts.clone() }, RestartPolicy::new(RestartMode::Never)).unwrap();
+        manager.wait().await;
+
+        assert_eq!(starts.load(Ordering::SeqCst), 1);
+        assert!(matches!(events.lock().unwrap().as_slice(), [SupervisorEvent::WorkerStopped { error: Some(_), .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        let mut manager = ActorsManager::new();
+        manager.start(CancellableActor {}).unwrap();
+        manager.start(StuckActor {}).unwrap();
+
+        let report = manager.shutdown(Duration::from_millis(50)).await;
+
+        assert!(!report.is_clean());
+        assert_eq!(report.clean_actors(), vec!["CancellableActor"]);
+        assert_eq!(report.aborted_actors(), vec!["StuckActor"]);
     }
 }
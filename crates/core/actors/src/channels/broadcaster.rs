@@ -1,24 +1,88 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
 use eyre::Result;
 use tokio::sync::broadcast;
-use tokio::sync::broadcast::error::SendError;
+use tokio::sync::broadcast::error::{RecvError, SendError, TryRecvError};
 use tokio::sync::broadcast::Receiver;
 
+/// Snapshot of channel metrics
+#[derive(Clone, Debug, Default)]
+pub struct ChannelStats {
+    pub name: String,
+    pub capacity: usize,
+    /// Messages sent with at least one subscriber
+    pub sent: u64,
+    /// Messages sent without subscribers
+    pub dropped: u64,
+    pub subscribers: usize,
+    /// Messages not yet received by the slowest subscriber
+    pub queued: usize,
+    /// Total messages skipped by lagging metered subscribers
+    pub lagged: u64,
+    /// Lag totals by metered subscriber name
+    pub subscribers_lag: Vec<(String, u64)>,
+}
+
+pub trait ChannelStatsProvider {
+    fn stats(&self) -> ChannelStats;
+}
+
+#[derive(Default)]
+struct ChannelMetrics {
+    sent: AtomicU64,
+    dropped: AtomicU64,
+    lagged: AtomicU64,
+    subscribers_lag: Mutex<HashMap<String, u64>>,
+}
+
+impl ChannelMetrics {
+    fn record_lag(&self, subscriber: &str, lag: u64) {
+        self.lagged.fetch_add(lag, Ordering::Relaxed);
+        if let Ok(mut subscribers_lag) = self.subscribers_lag.lock() {
+            *subscribers_lag.entry(subscriber.to_string()).or_default() += lag;
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Broadcaster<T>
 where
     T: Clone + Send + Sync + 'static,
 {
     sender: broadcast::Sender<T>,
+    name: Option<Arc<str>>,
+    capacity: usize,
+    metrics: Arc<ChannelMetrics>,
 }
 
 impl<T: Clone + Send + Sync + 'static> Broadcaster<T> {
     pub fn new(capacity: usize) -> Self {
         let (sender, _) = broadcast::channel(capacity);
-        Self { sender }
+        Self { sender, name: None, capacity, metrics: Arc::new(ChannelMetrics::default()) }
+    }
+
+    /// Named channel, name is used as a tag for exported metrics
+    pub fn new_with_name(capacity: usize, name: &str) -> Self {
+        Self { name: Some(Arc::from(name)), ..Self::new(capacity) }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
     }
 
     pub fn send(&self, value: T) -> Result<usize, SendError<T>> {
-        self.sender.send(value)
+        let result = self.sender.send(value);
+        match result {
+            Ok(_) => self.metrics.sent.fetch_add(1, Ordering::Relaxed),
+            Err(_) => self.metrics.dropped.fetch_add(1, Ordering::Relaxed),
+        };
+        result
     }
 
     /*
@@ -37,7 +101,91 @@ impl<T: Clone + Send + Sync + 'static> Broadcaster<T> {
     }
      */
 
+    /// Subscribes with plain receiver, its lag is not accounted in channel metrics. Actors use `subscribe_metered`
     pub fn subscribe(&self) -> Receiver<T> {
         self.sender.subscribe()
     }
+
+    /// Subscribes with receiver that accounts lag of `subscriber` in channel metrics
+    pub fn subscribe_metered(&self, subscriber: &str) -> MeteredReceiver<T> {
+        MeteredReceiver { receiver: self.sender.subscribe(), subscriber: subscriber.to_string(), metrics: self.metrics.clone() }
+    }
+}
+
+impl<T: Clone + Send + Sync + 'static> ChannelStatsProvider for Broadcaster<T> {
+    fn stats(&self) -> ChannelStats {
+        let mut subscribers_lag: Vec<(String, u64)> = match self.metrics.subscribers_lag.lock() {
+            Ok(subscribers_lag) => subscribers_lag.iter().map(|(subscriber, lag)| (subscriber.clone(), *lag)).collect(),
+            Err(_) => Vec::new(),
+        };
+        subscribers_lag.sort();
+
+        ChannelStats {
+            name: self.name.as_deref().unwrap_or("unnamed").to_string(),
+            capacity: self.capacity,
+            sent: self.metrics.sent.load(Ordering::Relaxed),
+            dropped: self.metrics.dropped.load(Ordering::Relaxed),
+            subscribers: self.sender.receiver_count(),
+            queued: self.sender.len(),
+            lagged: self.metrics.lagged.load(Ordering::Relaxed),
+            subscribers_lag,
+        }
+    }
+}
+
+/// Broadcast receiver that records lag to channel metrics
+pub struct MeteredReceiver<T> {
+    receiver: Receiver<T>,
+    subscriber: String,
+    metrics: Arc<ChannelMetrics>,
+}
+
+impl<T: Clone> MeteredReceiver<T> {
+    pub async fn recv(&mut self) -> Result<T, RecvError> {
+        let result = self.receiver.recv().await;
+        if let Err(RecvError::Lagged(lag)) = &result {
+            self.metrics.record_lag(&self.subscriber, *lag);
+        }
+        result
+    }
+
+    pub fn try_recv(&mut self) -> Result<T, TryRecvError> {
+        let result = self.receiver.try_recv();
+        if let Err(TryRecvError::Lagged(lag)) = &result {
+            self.metrics.record_lag(&self.subscriber, *lag);
+        }
+        result
+    }
+
+    pub fn into_inner(self) -> Receiver<T> {
+        self.receiver
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_channel_stats() {
+        let broadcaster: Broadcaster<u64> = Broadcaster::new_with_name(2, "test");
+        assert!(broadcaster.send(0).is_err());
+
+        let mut receiver = broadcaster.subscribe_metered("slow_subscriber");
+        for i in 0..5 {
+            broadcaster.send(i).unwrap();
+        }
+        assert!(matches!(receiver.recv().await, Err(RecvError::Lagged(3))));
+        assert_eq!(receiver.recv().await.unwrap(), 3);
+
+        let stats = broadcaster.stats();
+        assert_eq!(stats.name, "test");
+        assert_eq!(stats.capacity, 2);
+        assert_eq!(stats.sent, 5);
+        assert_eq!(stats.dropped, 1);
+        assert_eq!(stats.subscribers, 1);
+        assert_eq!(stats.queued, 1);
+        assert_eq!(stats.lagged, 3);
+        assert_eq!(stats.subscribers_lag, vec![("slow_subscriber".to_string(), 3)]);
+    }
 }
@@ -1,12 +1,17 @@
 pub use actor::{Accessor, Actor, ActorResult, Consumer, Producer, WorkerResult};
-pub use actor_manager::ActorsManager;
-pub use channels::{Broadcaster, MultiProducer};
+pub use actor_manager::{ActorsManager, SupervisorEventHandler, DEFAULT_SHUTDOWN_TIMEOUT};
+pub use channels::{Broadcaster, ChannelStats, ChannelStatsProvider, MeteredReceiver, MultiProducer};
 pub use shared_state::SharedState;
+pub use shutdown::{ShutdownReport, WorkerExit, WorkerExitStatus};
+pub use supervisor::{RestartMode, RestartPolicy, SupervisorEvent};
+pub use tokio_util::sync::CancellationToken;
 
 mod actor;
 mod actor_manager;
 mod channels;
 mod shared_state;
+mod shutdown;
+mod supervisor;
 
 #[macro_export]
 macro_rules! run_async {
@@ -29,13 +34,13 @@ macro_rules! run_sync {
 }
 
 #[inline]
-pub fn subscribe_helper<A: Clone + Send + Sync>(broadcaster: &Broadcaster<A>) -> tokio::sync::broadcast::Receiver<A> {
-    broadcaster.subscribe()
+pub fn subscribe_helper<A: Clone + Send + Sync>(broadcaster: &Broadcaster<A>, subscriber: &str) -> MeteredReceiver<A> {
+    broadcaster.subscribe_metered(subscriber)
 }
 
 #[macro_export]
 macro_rules! subscribe {
     ($name:ident) => {
-        let mut $name = $crate::subscribe_helper(&$name);
+        let mut $name = $crate::subscribe_helper(&$name, concat!(module_path!(), "::", stringify!($name)));
     };
 }
@@ -0,0 +1,62 @@
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WorkerExitStatus {
+    /// Worker returned Ok
+    Clean,
+    /// Worker returned an error or panicked
+    Failed(String),
+    /// Worker did not finish before shutdown timeout and was aborted
+    Aborted,
+}
+
+#[derive(Clone, Debug)]
+pub struct WorkerExit {
+    pub actor: &'static str,
+    pub status: WorkerExitStatus,
+}
+
+/// Exit status of every worker started by actor manager, in order of completion
+#[derive(Clone, Debug, Default)]
+pub struct ShutdownReport {
+    pub workers: Vec<WorkerExit>,
+}
+
+impl ShutdownReport {
+    pub fn is_clean(&self) -> bool {
+        self.workers.iter().all(|worker| worker.status == WorkerExitStatus::Clean)
+    }
+
+    /// Actors with all workers exited cleanly
+    pub fn clean_actors(&self) -> Vec<&'static str> {
+        let mut actors: Vec<&'static str> = self.workers.iter().map(|worker| worker.actor).collect();
+        actors.sort();
+        actors.dedup();
+        actors.retain(|actor| {
+            self.workers.iter().filter(|worker| worker.actor == *actor).all(|worker| worker.status == WorkerExitStatus::Clean)
+        });
+        actors
+    }
+
+    pub fn failed_actors(&self) -> Vec<&'static str> {
+        self.actors_with_status(|status| matches!(status, WorkerExitStatus::Failed(_)))
+    }
+
+    pub fn aborted_actors(&self) -> Vec<&'static str> {
+        self.actors_with_status(|status| matches!(status, WorkerExitStatus::Aborted))
+    }
+
+    fn actors_with_status(&self, filter: impl Fn(&WorkerExitStatus) -> bool) -> Vec<&'static str> {
+        let mut actors: Vec<&'static str> =
+            self.workers.iter().filter(|worker| filter(&worker.status)).map(|worker| worker.actor).collect();
+        actors.sort();
+        actors.dedup();
+        actors
+    }
+}
+
+impl Display for ShutdownReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "clean: {:?} failed: {:?} aborted: {:?}", self.clean_actors(), self.failed_actors(), self.aborted_actors())
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(60);
+const DEFAULT_MAX_RESTARTS: u32 = 10;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RestartMode {
+    /// Worker stays stopped
+    #[default]
+    Never,
+    /// Actor is restarted when one of its workers returns an error or panics
+    OnError,
+    /// Actor is restarted whenever one of its workers stops
+    Always,
+}
+
+/// Restart policy of a supervised actor. When a worker stops, all other workers of the actor are aborted and `Actor::start` is
+/// invoked again after an exponential backoff, at most `max_restarts` times.
+#[derive(Clone, Debug)]
+pub struct RestartPolicy {
+    pub mode: RestartMode,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_restarts: u32,
+}
+
+impl Default for RestartPolicy {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+impl RestartPolicy {
+    pub fn new(mode: RestartMode) -> Self {
+        Self { mode, initial_backoff: DEFAULT_INITIAL_BACKOFF, max_backoff: DEFAULT_MAX_BACKOFF, max_restarts: DEFAULT_MAX_RESTARTS }
+    }
+
+    pub fn never() -> Self {
+        Self::new(RestartMode::Never)
+    }
+
+    pub fn on_error() -> Self {
+        Self::new(RestartMode::OnError)
+    }
+
+    pub fn always() -> Self {
+        Self::new(RestartMode::Always)
+    }
+
+    pub fn with_backoff(self, initial_backoff: Duration, max_backoff: Duration) -> Self {
+        Self { initial_backoff, max_backoff, ..self }
+    }
+
+    pub fn with_max_restarts(self, max_restarts: u32) -> Self {
+        Self { max_restarts, ..self }
+    }
+
+    pub fn should_restart(&self, failed: bool) -> bool {
+        match self.mode {
+            RestartMode::Never => false,
+            RestartMode::OnError => failed,
+            RestartMode::Always => true,
+        }
+    }
+
+    /// Delay before restart number `restart_count`, starting from zero
+    pub fn backoff(&self, restart_count: u32) -> Duration {
+        self.initial_backoff.saturating_mul(2u32.saturating_pow(restart_count)).min(self.max_backoff)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum SupervisorEvent {
+    WorkerStopped { actor: &'static str, error: Option<String> },
+    ActorRestarted { actor: &'static str, restart_count: u32 },
+    RestartFailed { actor: &'static str, restart_count: u32, error: String },
+    RestartsExhausted { actor: &'static str, restart_count: u32 },
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RestartPolicy::on_error().with_backoff(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3), Duration::from_millis(800));
+        assert_eq!(policy.backoff(4), Duration::from_secs(1));
+        assert_eq!(policy.backoff(100), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_should_restart() {
+        assert!(!RestartPolicy::never().should_restart(true));
+        assert!(RestartPolicy::on_error().should_restart(true));
+        assert!(!RestartPolicy::on_error().should_restart(false));
+        assert!(RestartPolicy::always().should_restart(false));
+    }
+}
@@ -3,7 +3,9 @@ use alloy_primitives::{BlockHash, BlockNumber};
 use alloy_provider::Provider;
 use alloy_rpc_types::Header;
 use eyre::{eyre, Result};
-use loom_core_actors::{run_sync, subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    run_sync, subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, BlockchainState};
 use loom_evm_db::DatabaseLoomExt;
@@ -17,10 +19,67 @@ use std::ops::DerefMut;
 use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error, info, trace, warn};
 
+/// Rolls market state back to the last block shared by the old and the new chain. Returns reorg depth.
+fn rollback_reorg<DB>(
+    block_history: &BlockHistory<DB>,
+    market_state: &mut MarketState<DB>,
+    market_events_tx: &Broadcaster<MarketEvents>,
+    old_head_number: BlockNumber,
+    old_head: BlockHash,
+    new_head: BlockHash,
+) -> usize
+where
+    DB: Clone,
+{
+    let Some(common_ancestor) = block_history.find_common_ancestor(&old_head) else {
+        warn!(%old_head, %new_head, "Common ancestor not found for old chain head");
+        return 0;
+    };
+    if common_ancestor.hash() == old_head {
+        return 0;
+    }
+
+    let (ancestor_number, ancestor_hash) = (common_ancestor.number(), common_ancestor.hash());
+    let depth = old_head_number.saturating_sub(ancestor_number) as usize;
+
+    info!(depth, %old_head, %new_head, %ancestor_number, %ancestor_hash, "Re-org detected");
+
+    let mut orphaned_blocks = vec![];
+    let mut orphaned_hash = old_head;
+    while orphaned_hash != ancestor_hash {
+        let Some(entry) = block_history.get_block_history_entry(&orphaned_hash) else {
+            break;
+        };
+        orphaned_blocks.push(orphaned_hash);
+        orphaned_hash = entry.parent_hash();
+    }
+
+    if market_state.block_hash == old_head || market_state.block_number > ancestor_number {
+        match block_history.get_block_state(&ancestor_hash) {
+            Some(db) => {
+                market_state.state_db = db.clone();
+                market_state.block_hash = ancestor_hash;
+                market_state.block_number = ancestor_number;
+                debug!(%ancestor_number, %ancestor_hash, "Market state rolled back");
+            }
+            None => {
+                warn!(%ancestor_number, %ancestor_hash, "Market state for common ancestor not found");
+            }
+        }
+    }
+
+    if let Err(e) = market_events_tx.send(MarketEvents::Reorg { depth, old_head, new_head, orphaned_blocks }) {
+        error!("market_events_tx.send : {}", e);
+    }
+
+    depth
+}
+
 pub async fn set_chain_head<P, DB>(
     block_history_manager: &BlockHistoryManager<P, DB>,
     block_history: &mut BlockHistory<DB>,
     latest_block: &mut LatestBlock,
+    market_state: &mut MarketState<DB>,
     market_events_tx: Broadcaster<MarketEvents>,
     header: Header,
     chain_parameters: &ChainParameters,
@@ -31,16 +90,19 @@ where
 {
     let block_number = header.number;
     let block_hash = header.hash;
+    let (old_head_number, old_head) = latest_block.number_and_hash();
 
     debug!(%block_number, %block_hash, "set_chain_head block_number");
 
     match block_history_manager.set_chain_head(block_history, header.clone()).await {
-        Ok((is_new_block, reorg_depth)) => {
-            if reorg_depth > 0 {
-                debug!("Re-org detected. Block {} Depth {} New hash {}", block_number, reorg_depth, block_hash);
-            }
+        Ok((is_new_block, _)) => {
+            let mut reorg_depth = 0;
 
             if is_new_block {
+                if !old_head.is_zero() && old_head != block_hash {
+                    reorg_depth = rollback_reorg(block_history, market_state, &market_events_tx, old_head_number, old_head, block_hash);
+                }
+
                 let base_fee = header.base_fee_per_gas.unwrap_or_default();
                 let next_base_fee = chain_parameters.calc_next_block_base_fee(header.gas_used, header.gas_limit, base_fee);
 
@@ -76,6 +138,7 @@ pub async fn new_block_history_worker<P, DB>(
     log_update_rx: Broadcaster<MessageBlockLogs>,
     state_update_rx: Broadcaster<MessageBlockStateUpdate>,
     market_events_tx: Broadcaster<MarketEvents>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     P: Provider<Ethereum> + DebugProviderExt<Ethereum> + Send + Sync + Clone + 'static,
@@ -92,12 +155,16 @@ where
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = block_header_update_rx.recv() => {
                 let block_update : Result<MessageBlockHeader, RecvError>  = msg;
                 match block_update {
                     Ok(block_header)=>{
                         let mut block_history_guard = block_history.write().await;
                         let mut latest_block_guard = latest_block.write().await;
+                        let mut market_state_guard = market_state.write().await;
 
                         debug!("Block Header, Update {} {}", block_header.header.number, block_header.header.hash_slow());
 
@@ -106,6 +173,7 @@ where
                             &block_history_manager,
                             block_history_guard.borrow_mut(),
                             latest_block_guard.borrow_mut(),
+                            market_state_guard.borrow_mut(),
                             market_events_tx.clone(),
                             block_header.inner.header,
                             &chain_parameters
@@ -130,11 +198,13 @@ where
 
                         let mut block_history_guard = block_history.write().await;
                         let mut latest_block_guard = latest_block.write().await;
+                        let mut market_state_guard = market_state.write().await;
 
                         match set_chain_head(
                             &block_history_manager,
                             block_history_guard.borrow_mut(),
                             latest_block_guard.borrow_mut(),
+                            market_state_guard.borrow_mut(),
                             market_events_tx.clone(),
                             block_header,
                             &chain_parameters
@@ -180,11 +250,13 @@ where
 
                         let mut block_history_guard = block_history.write().await;
                         let mut latest_block_guard = latest_block.write().await;
+                        let mut market_state_guard = market_state.write().await;
 
                         match set_chain_head(
                             &block_history_manager,
                             block_history_guard.borrow_mut(),
                             latest_block_guard.borrow_mut(),
+                            market_state_guard.borrow_mut(),
                             market_events_tx.clone(),
                             block_header,
                             &chain_parameters
@@ -242,7 +314,7 @@ where
 
 
                 if let Err(e) = set_chain_head(&block_history_manager, block_history_guard.borrow_mut(),
-                    latest_block_guard.borrow_mut(),market_events_tx.clone(), msg_block_header, &chain_parameters).await {
+                    latest_block_guard.borrow_mut(), market_state_guard.borrow_mut(), market_events_tx.clone(), msg_block_header, &chain_parameters).await {
                     error!("set_chain_head : {}", e);
                     continue
                 }
@@ -340,6 +412,7 @@ where
             }
         }
     }
+    Ok("BlockHistoryWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer)]
@@ -405,7 +478,7 @@ where
     P: Provider<Ethereum> + DebugProviderExt<Ethereum> + Sync + Send + Clone + 'static,
     DB: BlockHistoryState + DatabaseRef + DatabaseCommit + DatabaseLoomExt + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(new_block_history_worker(
             self.client.clone(),
             self.chain_parameters.clone(),
@@ -417,6 +490,7 @@ where
             self.log_update_rx.clone().unwrap(),
             self.state_update_rx.clone().unwrap(),
             self.market_events_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
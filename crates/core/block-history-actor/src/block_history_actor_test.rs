@@ -24,7 +24,7 @@ mod test {
     };
     use loom_types_blockchain::{GethStateUpdate, GethStateUpdateVec};
     use loom_types_entities::MarketState;
-    use loom_types_events::{BlockHeader, Message};
+    use loom_types_events::{BlockHeader, MarketEvents, Message};
     use std::time::Duration;
     use tracing::info;
 
@@ -305,4 +305,79 @@ mod test {
 
         Ok(())
     }
+
+    fn account_state_update(nonce: u64, balance: u64) -> GethStateUpdateVec {
+        vec![geth_state_update_add_account(
+            GethStateUpdate::default(),
+            Address::repeat_byte(1),
+            account_state_with_nonce_and_balance(nonce, U256::from(balance)),
+        )]
+    }
+
+    #[tokio::test]
+    async fn test_actor_block_history_actor_reorg_depth_2() -> eyre::Result<()> {
+        let _ = env_logger::try_init_from_env(env_logger::Env::default().default_filter_or("info,tokio_tungstenite=off,tungstenite=off"));
+
+        const ADDR_01: Address = Address::repeat_byte(1);
+
+        let anvil = Anvil::new().try_spawn()?;
+        let client_anvil = ClientBuilder::default().http(anvil.endpoint_url());
+        let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(client_anvil);
+
+        let bc = Blockchain::new(1);
+        let bc_state = BlockchainState::<LoomDB>::new_with_market_state(MarketState::new(LoomDB::empty()));
+
+        BlockHistoryActor::new(provider.clone()).on_bc(&bc, &bc_state).start()?;
+
+        let mut market_events_rx = bc.market_events_channel().subscribe();
+
+        let block_0 = provider.get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Full).await?.unwrap();
+        broadcast_latest_block(provider.clone(), &bc, Some(account_state_update(1, 1))).await?; // block 0
+
+        let snap = provider.anvil_snapshot().await?;
+        provider.anvil_mine(Some(1), None).await?; // mine block 1#0
+        let block_1_0 = provider.get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Full).await?.unwrap();
+        broadcast_latest_block(provider.clone(), &bc, Some(account_state_update(2, 2))).await?;
+        provider.anvil_mine(Some(1), None).await?; // mine block 2#0
+        let block_2_0 = provider.get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Full).await?.unwrap();
+        broadcast_latest_block(provider.clone(), &bc, Some(account_state_update(3, 3))).await?;
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(bc_state.market_state().read().await.state_db.basic_ref(ADDR_01)?.unwrap().nonce, 3);
+
+        provider.anvil_revert(snap).await?;
+        provider.anvil_mine(Some(1), None).await?; // mine block 1#1
+        broadcast_latest_block(provider.clone(), &bc, Some(account_state_update(11, 11))).await?; // not a chain head yet
+        provider.anvil_mine(Some(1), None).await?; // mine block 2#1
+        let block_2_1 = provider.get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Full).await?.unwrap();
+        assert_ne!(block_2_1.header.hash, block_2_0.header.hash);
+
+        // new chain head without state update, market state must be rolled back to block 0
+        broadcast_to_channels(&bc, block_2_1.header.clone(), None, None, None).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(bc_state.market_state().read().await.block_hash, block_0.header.hash);
+        assert_eq!(bc_state.market_state().read().await.state_db.basic_ref(ADDR_01)?.unwrap().nonce, 1);
+
+        let mut reorg_event = None;
+        while let Ok(event) = market_events_rx.try_recv() {
+            if let MarketEvents::Reorg { depth, old_head, new_head, orphaned_blocks } = event {
+                reorg_event = Some((depth, old_head, new_head, orphaned_blocks));
+            }
+        }
+        assert_eq!(
+            reorg_event,
+            Some((2, block_2_0.header.hash, block_2_1.header.hash, vec![block_2_0.header.hash, block_1_0.header.hash]))
+        );
+
+        // state of 2#1 is built on top of 1#1
+        broadcast_to_channels(&bc, block_2_1.header.clone(), None, None, Some(account_state_update(12, 12))).await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert_eq!(bc_state.market_state().read().await.block_hash, block_2_1.header.hash);
+        assert_eq!(bc_state.market_state().read().await.state_db.basic_ref(ADDR_01)?.unwrap().nonce, 12);
+        assert_eq!(bc.latest_block().read().await.block_hash, block_2_1.header.hash);
+
+        Ok(())
+    }
 }
@@ -7,13 +7,13 @@ use loom_broadcast_accounts::{InitializeSignersOneShotBlockingActor, NonceAndBal
 use loom_broadcast_broadcaster::FlashbotsBroadcastActor;
 use loom_broadcast_flashbots::client::RelayConfig;
 use loom_broadcast_flashbots::Flashbots;
-use loom_core_actors::{Actor, ActorsManager, SharedState};
+use loom_core_actors::{Actor, ActorsManager, RestartPolicy, SharedState, ShutdownReport, SupervisorEvent};
 use loom_core_block_history::BlockHistoryActor;
 use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
 use loom_core_mempool::MempoolActor;
 use loom_core_router::SwapRouterActor;
 use loom_defi_address_book::TokenAddressEth;
-use loom_defi_health_monitor::{MetricsRecorderActor, PoolHealthMonitorActor, StuffingTxMonitorActor};
+use loom_defi_health_monitor::{BundleInclusionTrackerActor, MetricsRecorderActor, PoolHealthMonitorActor, StuffingTxMonitorActor};
 use loom_defi_market::{
     HistoryPoolLoaderOneShotActor, NewPoolLoaderActor, PoolLoaderActor, ProtocolPoolLoaderOneShotActor, RequiredPoolLoaderActor,
 };
@@ -24,7 +24,7 @@ use loom_evm_db::DatabaseLoomExt;
 use loom_evm_utils::NWETH;
 use loom_execution_estimator::{EvmEstimatorActor, GethEstimatorActor};
 use loom_execution_multicaller::MulticallerSwapEncoder;
-use loom_metrics::InfluxDbWriterActor;
+use loom_metrics::{InfluxDbWriterActor, PrometheusExporterActor};
 use loom_node_actor_config::NodeBlockActorConfig;
 #[cfg(feature = "db-access")]
 use loom_node_db_access::RethDbAccessBlockActor;
@@ -32,16 +32,19 @@ use loom_node_debug_provider::DebugProviderExt;
 use loom_node_grpc::NodeExExGrpcActor;
 use loom_node_json_rpc::{NodeBlockActor, NodeMempoolActor, WaitForNodeSyncOneShotBlockingActor};
 use loom_rpc_handler::WebServerActor;
-use loom_storage_db::DbPool;
+use loom_storage_db::{DbPool, MarketDbLoaderOneShotActor, MarketDbWriterActor};
 use loom_strategy_backrun::{
     BackrunConfig, BlockStateChangeProcessorActor, PendingTxStateChangeProcessorActor, StateChangeArbSearcherActor,
 };
 use loom_strategy_merger::{ArbSwapPathMergerActor, DiffPathMergerActor, SamePathMergerActor};
 use loom_types_entities::required_state::RequiredState;
+use loom_types_entities::tips::TipsPolicy;
 use loom_types_entities::{BlockHistoryState, PoolClass, SwapEncoder, TxSigners};
+use loom_types_events::{HealthEvent, MessageHealthEvent};
 use revm::{Database, DatabaseCommit, DatabaseRef};
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 
 pub struct BlockchainActors<P, DB: Clone + Send + Sync + 'static, E: Clone = MulticallerSwapEncoder> {
@@ -51,12 +54,14 @@ pub struct BlockchainActors<P, DB: Clone + Send + Sync + 'static, E: Clone = Mul
     strategy: Strategy<DB>,
     pub signers: SharedState<TxSigners>,
     actor_manager: ActorsManager,
+    restart_policy: RestartPolicy,
     encoder: Option<E>,
     has_mempool: bool,
     has_state_update: bool,
     has_signers: bool,
     mutlicaller_address: Option<Address>,
     relays: Vec<RelayConfig>,
+    tips_policy: Option<Arc<dyn TipsPolicy>>,
 }
 
 impl<P, DB, E> BlockchainActors<P, DB, E>
@@ -82,19 +87,27 @@ where
         strategy: Strategy<DB>,
         relays: Vec<RelayConfig>,
     ) -> Self {
+        let health_monitor_channel = bc.health_monitor_channel();
+        let actor_manager = ActorsManager::new().with_event_handler(Arc::new(move |event: SupervisorEvent| {
+            // no subscribers is fine, events are also logged by actor manager
+            let _ = health_monitor_channel.send(MessageHealthEvent::new(HealthEvent::Supervisor(event)));
+        }));
+
         Self {
             provider,
             bc,
             state,
             strategy,
             signers: SharedState::new(TxSigners::new()),
-            actor_manager: ActorsManager::new(),
+            actor_manager,
+            restart_policy: RestartPolicy::never(),
             encoder: Some(encoder),
             has_mempool: false,
             has_state_update: false,
             has_signers: false,
             mutlicaller_address: None,
             relays,
+            tips_policy: None,
         }
     }
 
@@ -102,12 +115,39 @@ where
         self.actor_manager.wait().await
     }
 
+    /// Token passed to all started actors, cancelling it starts shutdown
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.actor_manager.cancel_token()
+    }
+
+    /// Waits for all actors to finish, after cancel token is cancelled waits at most `shutdown_timeout`
+    pub async fn wait_with_shutdown(self, shutdown_timeout: Duration) -> ShutdownReport {
+        self.actor_manager.wait_with_shutdown(shutdown_timeout).await
+    }
+
+    /// Signals all actors to stop and waits for pending work to finish
+    pub async fn shutdown(self, timeout: Duration) -> ShutdownReport {
+        self.actor_manager.shutdown(timeout).await
+    }
+
     /// Start a custom actor
     pub fn start(&mut self, actor: impl Actor + 'static) -> Result<&mut Self> {
         self.actor_manager.start(actor)?;
         Ok(self)
     }
 
+    /// Start a custom actor that is restarted according to restart policy
+    pub fn start_supervised(&mut self, actor: impl Actor + Send + Sync + 'static, policy: RestartPolicy) -> Result<&mut Self> {
+        self.actor_manager.start_supervised(actor, policy)?;
+        Ok(self)
+    }
+
+    /// Restart policy for long-running node event and pool loader actors started after this call
+    pub fn with_restart_policy(&mut self, policy: RestartPolicy) -> &mut Self {
+        self.restart_policy = policy;
+        self
+    }
+
     /// Start a custom actor and wait for it to finish
     pub fn start_and_wait(&mut self, actor: impl Actor + Send + Sync + 'static) -> Result<&mut Self> {
         self.actor_manager.start_and_wait(actor)?;
@@ -247,13 +287,14 @@ where
 
     /// Starts token price calculator
     pub fn with_price_station(&mut self) -> Result<&mut Self> {
-        self.actor_manager.start(PriceActor::new(self.provider.clone()).on_bc(&self.bc))?;
+        self.actor_manager.start(PriceActor::new().on_bc(&self.bc, &self.state))?;
         Ok(self)
     }
 
     /// Starts receiving blocks events through RPC
     pub fn with_block_events(&mut self, config: NodeBlockActorConfig) -> Result<&mut Self> {
-        self.actor_manager.start(NodeBlockActor::new(self.provider.clone(), config).on_bc(&self.bc))?;
+        self.actor_manager
+            .start_supervised(NodeBlockActor::new(self.provider.clone(), config).on_bc(&self.bc), self.restart_policy.clone())?;
         Ok(self)
     }
 
@@ -283,7 +324,7 @@ where
     /// Starts local node pending tx provider
     pub fn with_local_mempool_events(&mut self) -> Result<&mut Self> {
         self.mempool()?;
-        self.actor_manager.start(NodeMempoolActor::new(self.provider.clone()).on_bc(&self.bc))?;
+        self.actor_manager.start_supervised(NodeMempoolActor::new(self.provider.clone()).on_bc(&self.bc), self.restart_policy.clone())?;
         Ok(self)
     }
 
@@ -293,7 +334,7 @@ where
         PM: Provider<Ethereum> + Send + Sync + Clone + 'static,
     {
         self.mempool()?;
-        self.actor_manager.start(NodeMempoolActor::new(provider).on_bc(&self.bc))?;
+        self.actor_manager.start_supervised(NodeMempoolActor::new(provider).on_bc(&self.bc), self.restart_policy.clone())?;
         Ok(self)
     }
 
@@ -329,16 +370,31 @@ where
 
      */
 
+    /// Sets tips policy that receives winning bids observed by stuffing tx monitor and bundle inclusion tracker,
+    /// must be called before they are started
+    pub fn with_tips_policy(&mut self, tips_policy: Option<Arc<dyn TipsPolicy>>) -> Result<&mut Self> {
+        self.tips_policy = tips_policy;
+        Ok(self)
+    }
+
     /// Starts stuffing tx monitor
     pub fn with_health_monitor_stuffing_tx(&mut self) -> Result<&mut Self> {
-        self.actor_manager.start(StuffingTxMonitorActor::new(self.provider.clone()).on_bc(&self.bc))?;
+        self.actor_manager
+            .start(StuffingTxMonitorActor::new(self.provider.clone()).with_tips_policy(self.tips_policy.clone()).on_bc(&self.bc))?;
+        Ok(self)
+    }
+
+    /// Starts tracker of broadcast bundles inclusion and realized profit
+    pub fn with_bundle_inclusion_tracker(&mut self) -> Result<&mut Self> {
+        self.actor_manager
+            .start(BundleInclusionTrackerActor::new(self.provider.clone()).with_tips_policy(self.tips_policy.clone()).on_bc(&self.bc))?;
         Ok(self)
     }
 
     /// Start pool loader from new block events
     pub fn with_new_pool_loader(&mut self, pools_config: PoolsLoadingConfig) -> Result<&mut Self> {
         let pool_loader = Arc::new(PoolLoadersBuilder::default_pool_loaders(self.provider.clone(), pools_config));
-        self.actor_manager.start(NewPoolLoaderActor::new(pool_loader).on_bc(&self.bc))?;
+        self.actor_manager.start_supervised(NewPoolLoaderActor::new(pool_loader).on_bc(&self.bc), self.restart_policy.clone())?;
         Ok(self)
     }
 
@@ -352,7 +408,22 @@ where
     /// Start pool loader from new block events
     pub fn with_pool_loader(&mut self, pools_config: PoolsLoadingConfig) -> Result<&mut Self> {
         let pool_loaders = Arc::new(PoolLoadersBuilder::default_pool_loaders(self.provider.clone(), pools_config.clone()));
-        self.actor_manager.start(PoolLoaderActor::new(self.provider.clone(), pool_loaders, pools_config).on_bc(&self.bc, &self.state))?;
+        self.actor_manager.start_supervised(
+            PoolLoaderActor::new(self.provider.clone(), pool_loaders, pools_config).on_bc(&self.bc, &self.state),
+            self.restart_policy.clone(),
+        )?;
+        Ok(self)
+    }
+
+    /// Start market loader from database. Pool loader must be started before
+    pub fn with_market_db_loader(&mut self, db_pool: DbPool, pools_config: PoolsLoadingConfig) -> Result<&mut Self> {
+        self.actor_manager.start(MarketDbLoaderOneShotActor::new(db_pool, pools_config).on_bc(&self.bc))?;
+        Ok(self)
+    }
+
+    /// Start writer persisting loaded pools, tokens and ready swaps to database
+    pub fn with_market_db_writer(&mut self, db_pool: DbPool) -> Result<&mut Self> {
+        self.actor_manager.start(MarketDbWriterActor::new(db_pool).on_bc(&self.bc, &self.strategy))?;
         Ok(self)
     }
 
@@ -476,9 +547,15 @@ where
         Ok(self)
     }
 
+    /// Start prometheus exporter, its router must be passed to the web server to serve `/metrics`
+    pub fn with_prometheus_exporter(&mut self, prometheus_exporter: PrometheusExporterActor) -> Result<&mut Self> {
+        self.actor_manager.start(prometheus_exporter.on_bc(&self.bc))?;
+        Ok(self)
+    }
+
     /// Start block latency recorder
     pub fn with_block_latency_recorder(&mut self) -> Result<&mut Self> {
-        self.actor_manager.start(MetricsRecorderActor::new().on_bc(&self.bc, &self.state))?;
+        self.actor_manager.start(MetricsRecorderActor::new().on_bc(&self.bc, &self.state, &self.strategy))?;
         Ok(self)
     }
 
@@ -488,7 +565,11 @@ where
         S: Clone + Send + Sync + 'static,
         Router: From<Router<S>>,
     {
-        self.actor_manager.start(WebServerActor::new(host, router, db_pool, CancellationToken::new()).on_bc(&self.bc, &self.state))?;
+        self.actor_manager.start(WebServerActor::new(host, router, db_pool, self.actor_manager.cancel_token()).on_bc(
+            &self.bc,
+            &self.state,
+            &self.strategy,
+        ))?;
         Ok(self)
     }
 
@@ -2,15 +2,53 @@ use crate::blockchain_tokens::add_default_tokens_to_market;
 use alloy::primitives::BlockHash;
 use alloy::primitives::ChainId;
 use influxdb::WriteQuery;
-use loom_core_actors::{Broadcaster, SharedState};
+use loom_core_actors::{Broadcaster, ChannelStats, ChannelStatsProvider, SharedState};
 use loom_types_blockchain::{ChainParameters, Mempool};
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
-use loom_types_entities::{AccountNonceAndBalanceState, LatestBlock, Market};
+use loom_types_entities::{AccountNonceAndBalanceState, BundleInclusionHistory, LatestBlock, Market};
 use loom_types_events::{
     LoomTask, MarketEvents, MempoolEvents, MessageBlock, MessageBlockHeader, MessageBlockLogs, MessageBlockStateUpdate, MessageHealthEvent,
     MessageMempoolDataUpdate, MessageTxCompose,
 };
-use tracing::error;
+use tracing::{error, warn};
+
+/// Capacities of blockchain and strategy channels
+#[derive(Clone, Debug)]
+pub struct ChannelCapacities {
+    pub new_block_headers: usize,
+    pub new_block_with_tx: usize,
+    pub new_block_state_update: usize,
+    pub new_block_logs: usize,
+    pub new_mempool_tx: usize,
+    pub market_events: usize,
+    pub mempool_events: usize,
+    pub tx_compose: usize,
+    pub health_monitor: usize,
+    pub influxdb_write: usize,
+    pub tasks: usize,
+    pub swap_compose: usize,
+    pub state_update: usize,
+}
+
+impl Default for ChannelCapacities {
+    fn default() -> Self {
+        Self {
+            new_block_headers: 10,
+            new_block_with_tx: 10,
+            new_block_state_update: 10,
+            new_block_logs: 10,
+            new_mempool_tx: 5000,
+            market_events: 100,
+            mempool_events: 2000,
+            tx_compose: 2000,
+            health_monitor: 1000,
+            influxdb_write: 1000,
+            tasks: 1000,
+            swap_compose: 100,
+            state_update: 100,
+        }
+    }
+}
 
 #[derive(Clone)]
 pub struct Blockchain<LDT: LoomDataTypes + 'static = LoomDataTypesEthereum> {
@@ -20,6 +58,7 @@ pub struct Blockchain<LDT: LoomDataTypes + 'static = LoomDataTypesEthereum> {
     latest_block: SharedState<LatestBlock<LDT>>,
     mempool: SharedState<Mempool<LDT>>,
     account_nonce_and_balance: SharedState<AccountNonceAndBalanceState<LDT>>,
+    bundle_inclusions: SharedState<BundleInclusionHistory>,
 
     new_block_headers_channel: Broadcaster<MessageBlockHeader<LDT>>,
     new_block_with_tx_channel: Broadcaster<MessageBlock<LDT>>,
@@ -37,34 +76,57 @@ pub struct Blockchain<LDT: LoomDataTypes + 'static = LoomDataTypesEthereum> {
 
 impl Blockchain<LoomDataTypesEthereum> {
     pub fn new(chain_id: ChainId) -> Blockchain<LoomDataTypesEthereum> {
-        let new_block_headers_channel: Broadcaster<MessageBlockHeader> = Broadcaster::new(10);
-        let new_block_with_tx_channel: Broadcaster<MessageBlock> = Broadcaster::new(10);
-        let new_block_state_update_channel: Broadcaster<MessageBlockStateUpdate> = Broadcaster::new(10);
-        let new_block_logs_channel: Broadcaster<MessageBlockLogs> = Broadcaster::new(10);
+        let chain_parameters = ChainParameters::try_from(chain_id).unwrap_or_else(|error| {
+            warn!(%error, chain_id, "Chain is not in registry, using custom chain parameters");
+            ChainParameters::custom(chain_id)
+        });
+        Self::new_with_chain_parameters(chain_parameters)
+    }
+
+    pub fn new_with_chain_parameters(chain_parameters: ChainParameters) -> Blockchain<LoomDataTypesEthereum> {
+        Self::new_with_channel_capacities(chain_parameters, &ChannelCapacities::default())
+    }
+
+    pub fn new_with_channel_capacities(
+        chain_parameters: ChainParameters,
+        capacities: &ChannelCapacities,
+    ) -> Blockchain<LoomDataTypesEthereum> {
+        let chain_id = chain_parameters.chain_id;
 
-        let new_mempool_tx_channel: Broadcaster<MessageMempoolDataUpdate> = Broadcaster::new(5000);
+        let new_block_headers_channel: Broadcaster<MessageBlockHeader> =
+            Broadcaster::new_with_name(capacities.new_block_headers, "new_block_headers");
+        let new_block_with_tx_channel: Broadcaster<MessageBlock> =
+            Broadcaster::new_with_name(capacities.new_block_with_tx, "new_block_with_tx");
+        let new_block_state_update_channel: Broadcaster<MessageBlockStateUpdate> =
+            Broadcaster::new_with_name(capacities.new_block_state_update, "new_block_state_update");
+        let new_block_logs_channel: Broadcaster<MessageBlockLogs> = Broadcaster::new_with_name(capacities.new_block_logs, "new_block_logs");
 
-        let market_events_channel: Broadcaster<MarketEvents> = Broadcaster::new(100);
-        let mempool_events_channel: Broadcaster<MempoolEvents> = Broadcaster::new(2000);
-        let tx_compose_channel: Broadcaster<MessageTxCompose> = Broadcaster::new(2000);
+        let new_mempool_tx_channel: Broadcaster<MessageMempoolDataUpdate> =
+            Broadcaster::new_with_name(capacities.new_mempool_tx, "new_mempool_tx");
 
-        let pool_health_monitor_channel: Broadcaster<MessageHealthEvent> = Broadcaster::new(1000);
-        let influx_write_channel: Broadcaster<WriteQuery> = Broadcaster::new(1000);
-        let tasks_channel: Broadcaster<LoomTask> = Broadcaster::new(1000);
+        let market_events_channel: Broadcaster<MarketEvents> = Broadcaster::new_with_name(capacities.market_events, "market_events");
+        let mempool_events_channel: Broadcaster<MempoolEvents> = Broadcaster::new_with_name(capacities.mempool_events, "mempool_events");
+        let tx_compose_channel: Broadcaster<MessageTxCompose> = Broadcaster::new_with_name(capacities.tx_compose, "tx_compose");
+
+        let pool_health_monitor_channel: Broadcaster<MessageHealthEvent> =
+            Broadcaster::new_with_name(capacities.health_monitor, "health_monitor");
+        let influx_write_channel: Broadcaster<WriteQuery> = Broadcaster::new_with_name(capacities.influxdb_write, "influxdb_write");
+        let tasks_channel: Broadcaster<LoomTask> = Broadcaster::new_with_name(capacities.tasks, "tasks");
 
         let mut market_instance = Market::default();
 
-        if let Err(error) = add_default_tokens_to_market(&mut market_instance, chain_id) {
+        if let Err(error) = add_default_tokens_to_market(&mut market_instance, &chain_parameters) {
             error!(%error, "Failed to add default tokens to market");
         }
 
         Blockchain {
             chain_id,
-            chain_parameters: ChainParameters::ethereum(),
+            chain_parameters,
             market: SharedState::new(market_instance),
             mempool: SharedState::new(Mempool::<LoomDataTypesEthereum>::new()),
             latest_block: SharedState::new(LatestBlock::new(0, BlockHash::ZERO)),
             account_nonce_and_balance: SharedState::new(AccountNonceAndBalanceState::new()),
+            bundle_inclusions: SharedState::new(BundleInclusionHistory::new()),
             new_block_headers_channel,
             new_block_with_tx_channel,
             new_block_state_update_channel,
@@ -105,6 +167,10 @@ impl<LDT: LoomDataTypes> Blockchain<LDT> {
         self.account_nonce_and_balance.clone()
     }
 
+    pub fn bundle_inclusions(&self) -> SharedState<BundleInclusionHistory> {
+        self.bundle_inclusions.clone()
+    }
+
     pub fn new_block_headers_channel(&self) -> Broadcaster<MessageBlockHeader<LDT>> {
         self.new_block_headers_channel.clone()
     }
@@ -148,4 +214,20 @@ impl<LDT: LoomDataTypes> Blockchain<LDT> {
     pub fn tasks_channel(&self) -> Broadcaster<LoomTask> {
         self.tasks_channel.clone()
     }
+
+    pub fn channels_stats(&self) -> Vec<ChannelStats> {
+        vec![
+            self.new_block_headers_channel.stats(),
+            self.new_block_with_tx_channel.stats(),
+            self.new_block_state_update_channel.stats(),
+            self.new_block_logs_channel.stats(),
+            self.new_mempool_tx_channel.stats(),
+            self.market_events_channel.stats(),
+            self.mempool_events_channel.stats(),
+            self.tx_compose_channel.stats(),
+            self.pool_health_monitor_channel.stats(),
+            self.influxdb_write_channel.stats(),
+            self.tasks_channel.stats(),
+        ]
+    }
 }
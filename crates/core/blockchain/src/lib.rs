@@ -1,4 +1,4 @@
-pub use blockchain::Blockchain;
+pub use blockchain::{Blockchain, ChannelCapacities};
 pub use blockchain_state::BlockchainState;
 
 pub use strategy::Strategy;
@@ -1,4 +1,5 @@
-use loom_core_actors::Broadcaster;
+use crate::ChannelCapacities;
+use loom_core_actors::{Broadcaster, ChannelStats, ChannelStatsProvider};
 use loom_evm_db::DatabaseLoomExt;
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
 use loom_types_entities::BlockHistoryState;
@@ -23,8 +24,14 @@ impl<DB: DatabaseRef + Database + DatabaseCommit + BlockHistoryState + DatabaseL
     Strategy<DB, LoomDataTypesEthereum>
 {
     pub fn new() -> Self {
-        let compose_channel: Broadcaster<MessageSwapCompose<DB, LoomDataTypesEthereum>> = Broadcaster::new(100);
-        let state_update_channel: Broadcaster<StateUpdateEvent<DB, LoomDataTypesEthereum>> = Broadcaster::new(100);
+        Self::new_with_channel_capacities(&ChannelCapacities::default())
+    }
+
+    pub fn new_with_channel_capacities(capacities: &ChannelCapacities) -> Self {
+        let compose_channel: Broadcaster<MessageSwapCompose<DB, LoomDataTypesEthereum>> =
+            Broadcaster::new_with_name(capacities.swap_compose, "swap_compose");
+        let state_update_channel: Broadcaster<StateUpdateEvent<DB, LoomDataTypesEthereum>> =
+            Broadcaster::new_with_name(capacities.state_update, "state_update");
         Strategy { swap_compose_channel: compose_channel, state_update_channel }
     }
 }
@@ -37,4 +44,8 @@ impl<DB: Send + Sync + Clone + 'static> Strategy<DB, LoomDataTypesEthereum> {
     pub fn state_update_channel(&self) -> Broadcaster<StateUpdateEvent<DB, LoomDataTypesEthereum>> {
         self.state_update_channel.clone()
     }
+
+    pub fn channels_stats(&self) -> Vec<ChannelStats> {
+        vec![self.swap_compose_channel.stats(), self.state_update_channel.stats()]
+    }
 }
@@ -5,7 +5,9 @@ use influxdb::{Timestamp, WriteQuery};
 use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error, info, trace};
 
-use loom_core_actors::{run_sync, subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    run_sync, subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::Blockchain;
 use loom_types_blockchain::{ChainParameters, Mempool, MempoolTx};
@@ -20,6 +22,7 @@ pub async fn new_mempool_worker<LDT: LoomDataTypes>(
     block_with_txs_rx: Broadcaster<MessageBlock<LDT>>,
     broadcaster: Broadcaster<MempoolEvents<LDT>>,
     influxdb_write_channel_tx: Broadcaster<WriteQuery>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(mempool_update_rx);
     subscribe!(block_header_rx);
@@ -30,6 +33,9 @@ pub async fn new_mempool_worker<LDT: LoomDataTypes>(
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = mempool_update_rx.recv() => {
                 let mempool_update_msg = match msg {
                     Ok(mempool_update_msg) => mempool_update_msg,
@@ -183,6 +189,7 @@ pub async fn new_mempool_worker<LDT: LoomDataTypes>(
             }
         }
     }
+    Ok("MempoolWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer)]
@@ -235,7 +242,7 @@ impl<LDT: LoomDataTypes> MempoolActor<LDT> {
 }
 
 impl<LDT: LoomDataTypes> Actor for MempoolActor<LDT> {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(new_mempool_worker(
             self.chain_parameters.clone(),
             self.mempool.clone().unwrap(),
@@ -244,6 +251,7 @@ impl<LDT: LoomDataTypes> Actor for MempoolActor<LDT> {
             self.block_with_txs_rx.clone().unwrap(),
             self.mempool_events_tx.clone().unwrap(),
             self.influxdb_write_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
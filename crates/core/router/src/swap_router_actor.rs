@@ -1,13 +1,18 @@
 use eyre::{eyre, Result};
-use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, Strategy};
 use loom_types_entities::{AccountNonceAndBalanceState, TxSigners};
-use loom_types_events::{MessageSwapCompose, MessageTxCompose, SwapComposeData, SwapComposeMessage, TxComposeData};
+use loom_types_events::{MarketEvents, MessageSwapCompose, MessageTxCompose, SwapComposeData, SwapComposeMessage, TxComposeData};
 use revm::DatabaseRef;
+use std::collections::VecDeque;
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::broadcast::Receiver;
-use tracing::{debug, error, info};
+use tracing::{debug, error, info, warn};
+
+/// Number of orphaned blocks remembered to drop swaps calculated on them
+const ORPHANED_BLOCKS_LEN: usize = 64;
 
 /// encoder task performs initial routing for swap request
 async fn router_task_prepare<DB: DatabaseRef + Send + Sync + Clone + 'static>(
@@ -68,20 +73,45 @@ async fn router_task_broadcast<DB: DatabaseRef + Send + Sync + Clone + 'static>(
 async fn swap_router_worker<DB: DatabaseRef + Clone + Send + Sync + 'static>(
     signers: SharedState<TxSigners>,
     account_monitor: SharedState<AccountNonceAndBalanceState>,
+    market_events_rx: Broadcaster<MarketEvents>,
     swap_compose_channel_rx: Broadcaster<MessageSwapCompose<DB>>,
     swap_compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
     tx_compose_channel_tx: Broadcaster<MessageTxCompose>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
-    let mut compose_channel_rx: Receiver<MessageSwapCompose<DB>> = swap_compose_channel_rx.subscribe();
+    subscribe!(market_events_rx);
+    subscribe!(swap_compose_channel_rx);
+
+    let mut orphaned_blocks = VecDeque::with_capacity(ORPHANED_BLOCKS_LEN);
 
     info!("swap router worker started");
 
     loop {
         tokio::select! {
-            msg = compose_channel_rx.recv() => {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
+            msg = market_events_rx.recv() => {
+                if let Ok(MarketEvents::Reorg { depth, old_head, new_head, orphaned_blocks: reorg_orphaned_blocks }) = msg {
+                    warn!(depth, %old_head, %new_head, "Re-org, dropping swaps calculated on orphaned blocks");
+                    for orphaned_block in reorg_orphaned_blocks {
+                        if orphaned_blocks.len() >= ORPHANED_BLOCKS_LEN {
+                            orphaned_blocks.pop_front();
+                        }
+                        orphaned_blocks.push_back(orphaned_block);
+                    }
+                }
+            }
+            msg = swap_compose_channel_rx.recv() => {
                 let msg : Result<MessageSwapCompose<DB>, RecvError> = msg;
                 match msg {
                     Ok(compose_request) => {
+                        if let Some(parent_block_hash) = compose_request.inner.data().tx_compose.parent_block_hash {
+                            if orphaned_blocks.contains(&parent_block_hash) {
+                                debug!(%parent_block_hash, swap=%compose_request.inner.data().swap, "Swap calculated on orphaned block dropped");
+                                continue;
+                            }
+                        }
                         match compose_request.inner {
                             SwapComposeMessage::Prepare(swap_compose_request)=>{
                                 debug!("MessageSwapComposeRequest::Prepare received. stuffing: {:?} swap: {}", swap_compose_request.tx_compose.stuffing_txs_hashes, swap_compose_request.swap);
@@ -112,6 +142,7 @@ async fn swap_router_worker<DB: DatabaseRef + Clone + Send + Sync + 'static>(
             }
         }
     }
+    Ok("SwapRouterWorker stopped".to_string())
 }
 
 #[derive(Consumer, Producer, Accessor, Default)]
@@ -121,6 +152,8 @@ pub struct SwapRouterActor<DB: Send + Sync + Clone + 'static> {
     #[accessor]
     account_nonce_balance: Option<SharedState<AccountNonceAndBalanceState>>,
     #[consumer]
+    market_events_rx: Option<Broadcaster<MarketEvents>>,
+    #[consumer]
     swap_compose_channel_rx: Option<Broadcaster<MessageSwapCompose<DB>>>,
     #[producer]
     swap_compose_channel_tx: Option<Broadcaster<MessageSwapCompose<DB>>>,
@@ -136,6 +169,7 @@ where
         SwapRouterActor {
             signers: None,
             account_nonce_balance: None,
+            market_events_rx: None,
             swap_compose_channel_rx: None,
             swap_compose_channel_tx: None,
             tx_compose_channel_tx: None,
@@ -151,6 +185,7 @@ where
             swap_compose_channel_rx: Some(strategy.swap_compose_channel()),
             swap_compose_channel_tx: Some(strategy.swap_compose_channel()),
             account_nonce_balance: Some(bc.nonce_and_balance()),
+            market_events_rx: Some(bc.market_events_channel()),
             tx_compose_channel_tx: Some(bc.tx_compose_channel()),
             ..self
         }
@@ -161,13 +196,15 @@ impl<DB> Actor for SwapRouterActor<DB>
 where
     DB: DatabaseRef + Send + Sync + Clone + Default + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(swap_router_worker(
             self.signers.clone().unwrap(),
             self.account_nonce_balance.clone().unwrap(),
+            self.market_events_rx.clone().unwrap(),
             self.swap_compose_channel_rx.clone().unwrap(),
             self.swap_compose_channel_tx.clone().unwrap(),
             self.tx_compose_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::topology_config::TransportType;
 use crate::topology_config::{BroadcasterConfig, ClientConfig, EncoderConfig, EstimatorConfig, SignersConfig, TopologyConfig};
 use alloy_primitives::Address;
 use alloy_provider::network::Ethereum;
 use alloy_provider::{Network, Provider, ProviderBuilder, RootProvider};
-use alloy_rpc_client::ClientBuilder;
+use alloy_rpc_client::{ClientBuilder, RpcClient};
+use alloy_transport::{BoxTransport, TransportErrorKind};
 use alloy_transport_ipc::IpcConnect;
 use alloy_transport_ws::WsConnect;
 use eyre::{eyre, ErrReport, Result};
@@ -28,14 +30,17 @@ use loom_execution_multicaller::MulticallerSwapEncoder;
 use loom_node_actor_config::NodeBlockActorConfig;
 #[cfg(feature = "db-access")]
 use loom_node_db_access::RethDbAccessBlockActor;
+use loom_node_debug_provider::FailoverTransport;
 use loom_node_grpc::NodeExExGrpcActor;
 use loom_node_json_rpc::{NodeBlockActor, NodeMempoolActor};
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
 use loom_types_entities::pool_config::PoolsLoadingConfig;
-use loom_types_entities::{BlockHistoryState, MarketState, PoolLoaders, SwapEncoder, TxSigners};
+use loom_types_entities::{BlockHistoryState, LoomTxSigner, MarketState, PoolLoaders, SwapEncoder, TxSignerRemote, TxSigners};
 use revm::{Database, DatabaseCommit, DatabaseRef};
 use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
+use url::Url;
 
 pub struct Topology<
     DB: Clone + Send + Sync + 'static,
@@ -56,6 +61,7 @@ pub struct Topology<
     default_signer_name: Option<String>,
     swap_encoder: E,
     pool_loaders: Arc<PoolLoaders<P, N, LDT>>,
+    cancel_token: CancellationToken,
 }
 
 impl<
@@ -90,6 +96,7 @@ impl<
             default_signer_name: None,
             swap_encoder: encoder,
             pool_loaders,
+            cancel_token: CancellationToken::new(),
         }
     }
 
@@ -111,6 +118,7 @@ impl<
             default_signer_name: self.default_signer_name,
             pool_loaders: self.pool_loaders,
             swap_encoder,
+            cancel_token: self.cancel_token,
         }
     }
 
@@ -131,6 +139,7 @@ impl<
             default_signer_name: self.default_signer_name,
             swap_encoder: self.swap_encoder,
             pool_loaders: Arc::new(pool_loaders),
+            cancel_token: self.cancel_token,
         }
     }
 
@@ -148,7 +157,14 @@ impl<
                     let transport = IpcConnect::from(config_params.url);
                     ClientBuilder::default().ipc(transport).await
                 }
-                _ => {
+                TransportType::Http => {
+                    info!("Starting HTTP connection");
+                    match config_params.url.parse::<Url>() {
+                        Ok(url) => Ok(ClientBuilder::default().http(url)),
+                        Err(e) => Err(TransportErrorKind::custom(e)),
+                    }
+                }
+                TransportType::Ws => {
                     info!("Starting WS connection");
                     let transport = WsConnect { url: config_params.url, auth: None, config: None };
                     ClientBuilder::default().ws(transport).await
@@ -167,6 +183,41 @@ impl<
 
             clients.insert(name.clone(), provider);
         }
+
+        for (name, group) in self.config.client_groups.iter().flatten() {
+            if clients.contains_key(name) {
+                error!("Client group {name} has the same name as client");
+                continue;
+            }
+
+            let endpoints: Vec<(String, BoxTransport)> = group
+                .clients
+                .iter()
+                .filter_map(|client_name| clients.get(client_name).map(|client| (client_name.clone(), client.client().transport().clone())))
+                .collect();
+            if endpoints.is_empty() {
+                error!("No connected clients in client group {name}");
+                continue;
+            }
+
+            info!("Starting client group {name} : {:?}", endpoints.iter().map(|(client_name, _)| client_name).collect::<Vec<_>>());
+
+            let transport = FailoverTransport::new(endpoints)
+                .with_max_block_lag(group.max_block_lag.unwrap_or(1))
+                .with_request_timeout(Duration::from_millis(group.request_timeout_ms.unwrap_or(5000)));
+            let monitor = transport.spawn_head_monitor(Duration::from_millis(group.poll_interval_ms.unwrap_or(1000)));
+            let cancel_token = self.cancel_token.clone();
+            tokio::task::spawn(async move {
+                cancel_token.cancelled().await;
+                monitor.abort();
+            });
+
+            let provider =
+                ProviderBuilder::<_, _, Ethereum>::new().disable_recommended_fillers().on_client(RpcClient::new(transport, false));
+
+            clients.insert(name.clone(), provider);
+        }
+
         Ok(Topology { clients, ..self })
     }
 
@@ -193,10 +244,17 @@ impl<
         }
 
         for (k, params) in self.config.blockchains.iter() {
-            let blockchain = Blockchain::new(params.chain_id.unwrap_or(1) as u64);
+            let (chain_parameters, channel_capacities) = match (params.chain_parameters(), params.channel_capacities()) {
+                (Ok(chain_parameters), Ok(channel_capacities)) => (chain_parameters, channel_capacities),
+                (Err(e), _) | (_, Err(e)) => {
+                    error!("Invalid blockchain config {k} error : {}", e);
+                    continue;
+                }
+            };
+            let blockchain = Blockchain::new_with_channel_capacities(chain_parameters, &channel_capacities);
             let market_state = MarketState::new(DB::default());
             let blockchain_state = BlockchainState::<DB>::new_with_market_state(market_state);
-            let strategy = Strategy::<DB>::new();
+            let strategy = Strategy::<DB>::new_with_channel_capacities(&channel_capacities);
 
             blockchains.insert(k.clone(), blockchain);
 
@@ -208,7 +266,7 @@ impl<
 
         for (name, params) in self.config.signers.iter() {
             match params {
-                SignersConfig::Env(_params) => {
+                SignersConfig::Env(_) | SignersConfig::Remote(_) => {
                     let signers_state = SharedState::new(TxSigners::new());
                     signers.insert(name.clone(), signers_state);
                     default_signer_name = Some(name.clone());
@@ -229,6 +287,11 @@ impl<
         }
     }
 
+    /// Token passed to actors started by topology, cancelling it signals them to shut down
+    pub fn cancel_token(&self) -> CancellationToken {
+        self.cancel_token.clone()
+    }
+
     pub async fn start_actors(&self) -> Result<Vec<JoinHandle<WorkerResult>>> {
         let mut tasks: Vec<JoinHandle<WorkerResult>> = Vec::new();
 
@@ -252,7 +315,7 @@ impl<
                 .consume(blockchain.new_block_logs_channel())
                 .consume(blockchain.new_block_state_update_channel())
                 .produce(blockchain.market_events_channel())
-                .start()
+                .start_with_cancel(self.cancel_token.clone())
             {
                 Ok(r) => {
                     tasks.extend(r);
@@ -272,7 +335,7 @@ impl<
                 .consume(blockchain.new_block_with_tx_channel())
                 .produce(blockchain.mempool_events_channel())
                 .produce(blockchain.influxdb_write_channel())
-                .start()
+                .start_with_cancel(self.cancel_token.clone())
             {
                 Ok(r) => {
                     tasks.extend(r);
@@ -289,7 +352,7 @@ impl<
                 .access(blockchain.market())
                 .consume(blockchain.health_monitor_channel())
                 .produce(blockchain.influxdb_write_channel())
-                .start()
+                .start_with_cancel(self.cancel_token.clone())
             {
                 Ok(r) => {
                     tasks.extend(r);
@@ -303,7 +366,7 @@ impl<
 
         for (name, params) in self.config.signers.iter() {
             let signers = self.get_signers(Some(name))?;
-            match params {
+            let blockchain = match params {
                 SignersConfig::Env(params) => {
                     info!("Starting initialize env signers actor {name}");
                     let blockchain = self.get_blockchain(params.blockchain.as_ref())?;
@@ -317,17 +380,41 @@ impl<
                             panic!("Cannot initialize signers {}", e);
                         }
                     }
+                    blockchain
+                }
+                SignersConfig::Remote(params) => {
+                    info!("Initializing remote signers {name} @ {}", params.url);
+                    let blockchain = self.get_blockchain(params.blockchain.as_ref())?;
 
-                    let mut signers_actor = TxSignersActor::new();
-                    match signers_actor.consume(blockchain.tx_compose_channel()).produce(blockchain.tx_compose_channel()).start() {
-                        Ok(r) => {
-                            tasks.extend(r);
-                            info!("Signers actor has been started")
-                        }
-                        Err(e) => {
-                            panic!("Cannot start signers actor {}", e)
-                        }
+                    let remote_signers = match params.address {
+                        Some(address) => vec![TxSignerRemote::new(params.url.as_str(), address)?],
+                        None => TxSignerRemote::from_accounts(params.url.as_str()).await?,
+                    };
+                    if remote_signers.is_empty() {
+                        panic!("No accounts found in remote signer {}", params.url);
                     }
+                    for remote_signer in remote_signers {
+                        let address = remote_signer.address();
+                        signers.write().await.add_signer(Arc::new(remote_signer));
+                        blockchain.nonce_and_balance().write().await.add_account(address);
+                        info!("Remote signer added {:?}", address);
+                    }
+                    blockchain
+                }
+            };
+
+            let mut signers_actor = TxSignersActor::new();
+            match signers_actor
+                .consume(blockchain.tx_compose_channel())
+                .produce(blockchain.tx_compose_channel())
+                .start_with_cancel(self.cancel_token.clone())
+            {
+                Ok(r) => {
+                    tasks.extend(r);
+                    info!("Signers actor has been started")
+                }
+                Err(e) => {
+                    panic!("Cannot start signers actor {}", e)
                 }
             }
         }
@@ -369,7 +456,7 @@ impl<
                     .produce(blockchain.new_block_logs_channel())
                     .produce(blockchain.new_block_state_update_channel())
                     .produce(blockchain.new_mempool_tx_channel())
-                    .start()
+                    .start_with_cancel(self.cancel_token.clone())
                 {
                     Ok(r) => {
                         tasks.extend(r);
@@ -402,7 +489,7 @@ impl<
                         .produce(blockchain.new_block_with_tx_channel())
                         .produce(blockchain.new_block_logs_channel())
                         .produce(blockchain.new_block_state_update_channel())
-                        .start()
+                        .start_with_cancel(self.cancel_token.clone())
                     {
                         Ok(r) => {
                             tasks.extend(r);
@@ -421,7 +508,7 @@ impl<
                         .produce(blockchain.new_block_with_tx_channel())
                         .produce(blockchain.new_block_logs_channel())
                         .produce(blockchain.new_block_state_update_channel())
-                        .start()
+                        .start_with_cancel(self.cancel_token.clone())
                     {
                         Ok(r) => {
                             tasks.extend(r);
@@ -442,7 +529,7 @@ impl<
                     Ok(client) => {
                         println!("Starting node mempool actor {name}");
                         let mut node_mempool_actor = NodeMempoolActor::new(client).with_name(name.clone());
-                        match node_mempool_actor.produce(blockchain.new_mempool_tx_channel()).start() {
+                        match node_mempool_actor.produce(blockchain.new_mempool_tx_channel()).start_with_cancel(self.cancel_token.clone()) {
                             Ok(r) => {
                                 tasks.extend(r);
                                 info!("Node mempool actor started successfully {name}")
@@ -461,11 +548,17 @@ impl<
 
         if let Some(price_actors) = &self.config.actors.price {
             for (name, c) in price_actors {
-                let client = self.get_client(c.client.as_ref())?;
                 let blockchain = self.get_blockchain(c.blockchain.as_ref())?;
+                let blockchain_state = self.get_blockchain_state(c.blockchain.as_ref())?;
                 info!("Starting price actor");
-                let mut price_actor = PriceActor::new(client);
-                match price_actor.access(blockchain.market()).start() {
+                let mut price_actor = PriceActor::new();
+                match price_actor
+                    .access(blockchain.market())
+                    .access(blockchain_state.market_state())
+                    .access(blockchain_state.block_history())
+                    .consume(blockchain.market_events_channel())
+                    .start_with_cancel(self.cancel_token.clone())
+                {
                     Ok(r) => {
                         tasks.extend(r);
                         info!("Price actor has been initialized : {}", name)
@@ -490,7 +583,7 @@ impl<
                     .access(blockchain.nonce_and_balance())
                     .access(blockchain.latest_block())
                     .consume(blockchain.market_events_channel())
-                    .start()
+                    .start_with_cancel(self.cancel_token.clone())
                 {
                     Ok(r) => {
                         tasks.extend(r);
@@ -514,7 +607,11 @@ impl<
 
                         let flashbots_client = Flashbots::new(client, "https://relay.flashbots.net", None).with_default_relays();
                         let mut flashbots_actor = FlashbotsBroadcastActor::new(flashbots_client, true);
-                        match flashbots_actor.consume(blockchain.tx_compose_channel()).start() {
+                        match flashbots_actor
+                            .consume(blockchain.tx_compose_channel())
+                            .access(blockchain.bundle_inclusions())
+                            .start_with_cancel(self.cancel_token.clone())
+                        {
                             Ok(r) => {
                                 tasks.extend(r);
                                 info!("Flashbots broadcaster actor {name} started successfully for {}", blockchain.chain_id())
@@ -545,7 +642,7 @@ impl<
                     info!("Starting history pools loader {name}");
 
                     let mut history_pools_loader_actor = HistoryPoolLoaderOneShotActor::new(client.clone(), pool_loaders.clone());
-                    match history_pools_loader_actor.produce(blockchain.tasks_channel()).start() {
+                    match history_pools_loader_actor.produce(blockchain.tasks_channel()).start_with_cancel(self.cancel_token.clone()) {
                         Ok(r) => {
                             tasks.extend(r);
                             info!("History pool loader actor started successfully {name}")
@@ -559,7 +656,7 @@ impl<
                     info!("Starting curve pools loader {name}");
 
                     let mut curve_pools_loader_actor = ProtocolPoolLoaderOneShotActor::new(client.clone(), pool_loaders.clone());
-                    match curve_pools_loader_actor.produce(blockchain.tasks_channel()).start() {
+                    match curve_pools_loader_actor.produce(blockchain.tasks_channel()).start_with_cancel(self.cancel_token.clone()) {
                         Err(e) => {
                             panic!("CurvePoolLoaderOneShotActor : {}", e)
                         }
@@ -573,7 +670,11 @@ impl<
                 if params.new {
                     info!("Starting new pool loader actor {name}");
                     let mut new_pool_actor = NewPoolLoaderActor::new(pool_loaders.clone());
-                    match new_pool_actor.consume(blockchain.new_block_logs_channel()).produce(blockchain.tasks_channel()).start() {
+                    match new_pool_actor
+                        .consume(blockchain.new_block_logs_channel())
+                        .produce(blockchain.tasks_channel())
+                        .start_with_cancel(self.cancel_token.clone())
+                    {
                         Ok(r) => {
                             tasks.extend(r);
                             info!("New pool actor started")
@@ -591,7 +692,7 @@ impl<
                     .access(blockchain_state.market_state())
                     .consume(blockchain.tasks_channel())
                     .produce(blockchain.market_events_channel())
-                    .start()
+                    .start_with_cancel(self.cancel_token.clone())
                 {
                     Ok(r) => {
                         tasks.extend(r);
@@ -625,7 +726,7 @@ impl<
                             .produce(strategy.swap_compose_channel())
                             .produce(blockchain.health_monitor_channel())
                             .produce(blockchain.influxdb_write_channel())
-                            .start()
+                            .start_with_cancel(self.cancel_token.clone())
                         {
                             Ok(r) => {
                                 tasks.extend(r);
@@ -645,10 +746,17 @@ impl<
                         let mut encoder = self.swap_encoder.clone();
                         encoder.set_address(multicaller_address);
 
-                        let flashbots_client = Arc::new(Flashbots::new(client, "https://relay.flashbots.net", None).with_default_relays());
+                        let flashbots_client = Flashbots::new(client, "https://relay.flashbots.net", None).with_default_relays();
+                        let flashbots_client = match &params.simulation_relay {
+                            Some(simulation_relay) => Arc::new(flashbots_client.with_simulation_relay(simulation_relay)),
+                            None => Arc::new(flashbots_client),
+                        };
 
                         let mut geth_estimator_actor = GethEstimatorActor::new(flashbots_client, encoder);
-                        match geth_estimator_actor.consume(strategy.swap_compose_channel()).produce(strategy.swap_compose_channel()).start()
+                        match geth_estimator_actor
+                            .consume(strategy.swap_compose_channel())
+                            .produce(strategy.swap_compose_channel())
+                            .start_with_cancel(self.cancel_token.clone())
                         {
                             Ok(r) => {
                                 tasks.extend(r);
@@ -1,13 +1,113 @@
-use eyre::Result;
+use alloy_primitives::Address;
+use eyre::{eyre, Result};
 use loom_broadcast_flashbots::client::RelayConfig;
+use loom_core_blockchain::ChannelCapacities;
+use loom_strategy_backrun::PendingTxSimulation;
+use loom_types_blockchain::ChainParameters;
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fs;
+use std::time::Duration;
 use strum_macros::Display;
 
 #[derive(Clone, Debug, Deserialize)]
 pub struct BlockchainConfig {
     pub chain_id: Option<i64>,
+    pub block_time_ms: Option<u64>,
+    pub base_fee_max_change_denominator: Option<u64>,
+    pub base_fee_elasticity_multiplier: Option<u64>,
+    pub channels: Option<ChannelsConfig>,
+}
+
+/// Channel capacity overrides, channels that are not set keep default capacity
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ChannelsConfig {
+    pub new_block_headers: Option<usize>,
+    pub new_block_with_tx: Option<usize>,
+    pub new_block_state_update: Option<usize>,
+    pub new_block_logs: Option<usize>,
+    pub new_mempool_tx: Option<usize>,
+    pub market_events: Option<usize>,
+    pub mempool_events: Option<usize>,
+    pub tx_compose: Option<usize>,
+    pub health_monitor: Option<usize>,
+    pub influxdb_write: Option<usize>,
+    pub tasks: Option<usize>,
+    pub swap_compose: Option<usize>,
+    pub state_update: Option<usize>,
+}
+
+impl ChannelsConfig {
+    pub fn channel_capacities(&self) -> Result<ChannelCapacities> {
+        let default = ChannelCapacities::default();
+        let capacities = ChannelCapacities {
+            new_block_headers: self.new_block_headers.unwrap_or(default.new_block_headers),
+            new_block_with_tx: self.new_block_with_tx.unwrap_or(default.new_block_with_tx),
+            new_block_state_update: self.new_block_state_update.unwrap_or(default.new_block_state_update),
+            new_block_logs: self.new_block_logs.unwrap_or(default.new_block_logs),
+            new_mempool_tx: self.new_mempool_tx.unwrap_or(default.new_mempool_tx),
+            market_events: self.market_events.unwrap_or(default.market_events),
+            mempool_events: self.mempool_events.unwrap_or(default.mempool_events),
+            tx_compose: self.tx_compose.unwrap_or(default.tx_compose),
+            health_monitor: self.health_monitor.unwrap_or(default.health_monitor),
+            influxdb_write: self.influxdb_write.unwrap_or(default.influxdb_write),
+            tasks: self.tasks.unwrap_or(default.tasks),
+            swap_compose: self.swap_compose.unwrap_or(default.swap_compose),
+            state_update: self.state_update.unwrap_or(default.state_update),
+        };
+
+        // tokio broadcast channel panics on zero capacity
+        let all = [
+            capacities.new_block_headers,
+            capacities.new_block_with_tx,
+            capacities.new_block_state_update,
+            capacities.new_block_logs,
+            capacities.new_mempool_tx,
+            capacities.market_events,
+            capacities.mempool_events,
+            capacities.tx_compose,
+            capacities.health_monitor,
+            capacities.influxdb_write,
+            capacities.tasks,
+            capacities.swap_compose,
+            capacities.state_update,
+        ];
+        if all.contains(&0) {
+            return Err(eyre!("ZERO_CHANNEL_CAPACITY"));
+        }
+
+        Ok(capacities)
+    }
+}
+
+impl BlockchainConfig {
+    /// Chain parameters from the registry with overrides from config applied
+    pub fn chain_parameters(&self) -> Result<ChainParameters> {
+        let chain_id = self.chain_id.unwrap_or(1) as u64;
+        let mut chain_parameters = ChainParameters::try_from(chain_id).unwrap_or_else(|_| ChainParameters::custom(chain_id));
+
+        if let Some(block_time_ms) = self.block_time_ms {
+            chain_parameters = chain_parameters.with_block_time(Duration::from_millis(block_time_ms));
+        }
+        if let Some(max_change_denominator) = self.base_fee_max_change_denominator {
+            if max_change_denominator == 0 {
+                return Err(eyre!("ZERO_BASE_FEE_MAX_CHANGE_DENOMINATOR"));
+            }
+            chain_parameters.base_fee_params.max_change_denominator = max_change_denominator as u128;
+        }
+        if let Some(elasticity_multiplier) = self.base_fee_elasticity_multiplier {
+            if elasticity_multiplier == 0 {
+                return Err(eyre!("ZERO_BASE_FEE_ELASTICITY_MULTIPLIER"));
+            }
+            chain_parameters.base_fee_params.elasticity_multiplier = elasticity_multiplier as u128;
+        }
+
+        Ok(chain_parameters)
+    }
+
+    pub fn channel_capacities(&self) -> Result<ChannelCapacities> {
+        self.channels.clone().unwrap_or_default().channel_capacities()
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Display)]
@@ -39,6 +139,28 @@ pub struct InfluxDbConfig {
     pub tags: HashMap<String, String>,
 }
 
+/// Prometheus exporter served at `/metrics` of the web server, metrics are built from influxdb write queries
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct PrometheusConfig {
+    /// Address of own `/metrics` server for binaries without the web server, defaults to 127.0.0.1:9090
+    pub host: Option<String>,
+    /// Metric name prefix, defaults to `loom`
+    pub namespace: Option<String>,
+    /// Constant labels added to all metrics
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+    /// Influxdb tags that are not converted to labels, defaults to block numbers, hashes and pools tags
+    pub ignore_tags: Option<Vec<String>>,
+    /// Max number of time series, defaults to 10000
+    pub max_series: Option<usize>,
+    /// Measurements exported as histograms instead of gauges
+    #[serde(default)]
+    pub histograms: Vec<String>,
+    /// Histogram buckets
+    #[serde(default)]
+    pub buckets: Vec<f64>,
+}
+
 #[derive(Clone, Debug, Default, Deserialize)]
 pub struct ClientConfig {
     pub url: String,
@@ -51,6 +173,19 @@ pub struct ClientConfig {
     // _n: PhantomData<N>,
 }
 
+/// Clients used together as one failover client, requests go to the fastest client that is not behind the best head block
+#[derive(Clone, Debug, Deserialize)]
+pub struct ClientGroupConfig {
+    /// Names of clients from `[clients]`
+    pub clients: Vec<String>,
+    /// Number of blocks client can be behind the best head before it is deprioritized, defaults to 1
+    pub max_block_lag: Option<u64>,
+    /// Head block and latency polling interval, defaults to 1000 ms
+    pub poll_interval_ms: Option<u64>,
+    /// Time to wait for response of a client before request is sent to the next one, defaults to 5000 ms
+    pub request_timeout_ms: Option<u64>,
+}
+
 /*
 impl<P, N> ClientConfig<P, N>
 where
@@ -95,11 +230,23 @@ pub struct EnvSingerConfig {
     pub blockchain: Option<String>,
 }
 
+#[derive(Clone, Debug, Deserialize)]
+pub struct RemoteSignerConfig {
+    #[serde(rename = "bc")]
+    pub blockchain: Option<String>,
+    /// Url of web3signer compatible signing service
+    pub url: String,
+    /// Signer address, all accounts of the service are used if not set
+    pub address: Option<Address>,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 #[serde(tag = "type")]
 pub enum SignersConfig {
     #[serde(rename = "env")]
     Env(EnvSingerConfig),
+    #[serde(rename = "remote")]
+    Remote(RemoteSignerConfig),
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -129,6 +276,16 @@ pub struct BlockchainClientConfig {
     pub blockchain: Option<String>,
     pub client: Option<String>,
 }
+#[derive(Clone, Debug, Deserialize)]
+pub struct MempoolConfig {
+    #[serde(rename = "bc")]
+    pub blockchain: Option<String>,
+    pub client: Option<String>,
+    /// Backend to get state changes of pending txs from this source, defaults to `trace`
+    #[serde(default)]
+    pub simulation: PendingTxSimulation,
+}
+
 #[derive(Clone, Debug, Deserialize)]
 pub struct ExExClientConfig {
     #[serde(rename = "bc")]
@@ -186,6 +343,8 @@ pub struct GethEstimatorConfig {
     #[serde(rename = "bc")]
     pub blockchain: Option<String>,
     pub encoder: Option<String>,
+    /// Url of relay implementing `eth_callBundle` used instead of the client node
+    pub simulation_relay: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -228,17 +387,26 @@ pub struct ActorConfig {
     pub broadcaster: Option<HashMap<String, BroadcasterConfig>>,
     pub node: Option<HashMap<String, BlockchainClientConfig>>,
     pub node_exex: Option<HashMap<String, ExExClientConfig>>,
-    pub mempool: Option<HashMap<String, BlockchainClientConfig>>,
+    pub mempool: Option<HashMap<String, MempoolConfig>>,
     pub price: Option<HashMap<String, BlockchainClientConfig>>,
     pub pools: Option<HashMap<String, PoolsConfig>>,
     pub noncebalance: Option<HashMap<String, BlockchainClientConfig>>,
     pub estimator: Option<HashMap<String, EstimatorConfig>>,
 }
 
+impl ActorConfig {
+    /// Pending tx simulation backend by mempool source name
+    pub fn mempool_simulation(&self) -> HashMap<String, PendingTxSimulation> {
+        self.mempool.as_ref().map(|m| m.iter().map(|(name, config)| (name.clone(), config.simulation)).collect()).unwrap_or_default()
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct TopologyConfig {
     pub influxdb: Option<InfluxDbConfig>,
+    pub prometheus: Option<PrometheusConfig>,
     pub clients: HashMap<String, ClientConfig>,
+    pub client_groups: Option<HashMap<String, ClientGroupConfig>>,
     pub blockchains: HashMap<String, BlockchainConfig>,
     pub actors: ActorConfig,
     pub signers: HashMap<String, SignersConfig>,
@@ -271,4 +439,33 @@ mod test {
             }
         }
     }
+    #[test]
+    fn test_channel_capacities() {
+        let config: BlockchainConfig = toml::from_str("chain_id = 1\nchannels = { new_mempool_tx = 10000 }").unwrap();
+        let capacities = config.channel_capacities().unwrap();
+        assert_eq!(capacities.new_mempool_tx, 10000);
+        assert_eq!(capacities.market_events, ChannelCapacities::default().market_events);
+
+        let config: BlockchainConfig = toml::from_str("channels = { tasks = 0 }").unwrap();
+        assert!(config.channel_capacities().is_err());
+    }
+
+    #[test]
+    fn test_mempool_config() {
+        let config: HashMap<String, MempoolConfig> = toml::from_str(
+            "local = { client = \"local\", bc = \"mainnet\" }\nremote = { client = \"remote\", bc = \"mainnet\", simulation = \"local\" }",
+        )
+        .unwrap();
+        assert_eq!(config.get("local").unwrap().simulation, PendingTxSimulation::Trace);
+        assert_eq!(config.get("remote").unwrap().simulation, PendingTxSimulation::Local);
+    }
+
+    #[test]
+    fn test_prometheus_config() {
+        let config: PrometheusConfig = toml::from_str("labels = { bot_name = \"loom\" }\nhistograms = [\"block_latency\"]").unwrap();
+        assert_eq!(config.namespace, None);
+        assert_eq!(config.labels.get("bot_name"), Some(&"loom".to_string()));
+        assert_eq!(config.histograms, vec!["block_latency".to_string()]);
+        assert!(config.buckets.is_empty());
+    }
 }
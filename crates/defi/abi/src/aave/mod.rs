@@ -0,0 +1,3 @@
+pub use pool::*;
+
+mod pool;
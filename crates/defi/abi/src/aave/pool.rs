@@ -0,0 +1,29 @@
+use alloy::sol;
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    interface IAavePool {
+        function flashLoanSimple(
+            address receiverAddress,
+            address asset,
+            uint256 amount,
+            bytes calldata params,
+            uint16 referralCode
+        ) external;
+
+        function FLASHLOAN_PREMIUM_TOTAL() external view returns (uint128);
+    }
+}
+
+sol! {
+    #[derive(Debug, PartialEq, Eq)]
+    interface IFlashLoanSimpleReceiver {
+        function executeOperation(
+            address asset,
+            uint256 amount,
+            uint256 premium,
+            address initiator,
+            bytes calldata params
+        ) external returns (bool);
+    }
+}
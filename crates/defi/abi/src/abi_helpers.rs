@@ -1,8 +1,10 @@
 use alloy::primitives::{Address, Bytes, U256};
 use alloy::sol_types::{SolCall, SolInterface};
 
+use crate::aave::IAavePool;
 use crate::balancer::IVault;
 use crate::lido::{IStEth, IWStEth};
+use crate::uniswap3::IUniswapV3Pool;
 use crate::{IMultiCaller, IERC20, IWETH};
 
 pub struct AbiEncoderHelper;
@@ -48,6 +50,16 @@ impl AbiEncoderHelper {
         .into()
     }
 
+    pub fn encode_multicaller_transfer_tips_no_payout(token: Address, min_balance: U256, tips: U256) -> Bytes {
+        IMultiCaller::IMultiCallerCalls::transferTipsMinBalanceNoPayout(IMultiCaller::transferTipsMinBalanceNoPayoutCall {
+            token,
+            min_balance,
+            tips,
+        })
+        .abi_encode()
+        .into()
+    }
+
     pub fn encode_multicaller_uni2_get_in_amount(token_from: Address, token_to: Address, pool: Address, amount: U256, fee: U256) -> Bytes {
         let call = if fee.is_zero() || fee.to::<u32>() == 9970 {
             if token_from > token_to {
@@ -107,6 +119,24 @@ impl AbiEncoderHelper {
         Bytes::from(call.abi_encode())
     }
 
+    pub fn encode_aave_flashloan_simple(receiver: Address, token: Address, amount: U256, params: Bytes) -> Bytes {
+        let call = IAavePool::IAavePoolCalls::flashLoanSimple(IAavePool::flashLoanSimpleCall {
+            receiverAddress: receiver,
+            asset: token,
+            amount,
+            params,
+            referralCode: 0,
+        });
+
+        Bytes::from(call.abi_encode())
+    }
+
+    pub fn encode_uniswap3_flash(recipient: Address, amount0: U256, amount1: U256, data: Bytes) -> Bytes {
+        let call = IUniswapV3Pool::IUniswapV3PoolCalls::flash(IUniswapV3Pool::flashCall { recipient, amount0, amount1, data });
+
+        Bytes::from(call.abi_encode())
+    }
+
     pub fn encode_wsteth_wrap(st_eth_amount: U256) -> Bytes {
         let call = IWStEth::IWStEthCalls::wrap(IWStEth::wrapCall { stETHAmount: st_eth_amount });
 
@@ -0,0 +1,25 @@
+use alloy::sol;
+
+sol! {
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IBalancerPool {
+        function getPoolId() external view returns (bytes32);
+        function getVault() external view returns (address);
+        function getSwapFeePercentage() external view returns (uint256);
+        function getScalingFactors() external view returns (uint256[] memory);
+    }
+
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IBalancerWeightedPool {
+        function getNormalizedWeights() external view returns (uint256[] memory);
+    }
+
+    #[sol(rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface IBalancerStablePool {
+        function getAmplificationParameter() external view returns (uint256 value, bool isUpdating, uint256 precision);
+        function getBptIndex() external view returns (uint256);
+    }
+}
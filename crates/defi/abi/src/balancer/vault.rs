@@ -1,6 +1,7 @@
 use alloy::sol;
 
 sol! {
+    #[sol(rpc)]
     #[derive(Debug, PartialEq, Eq)]
     interface IVault  {
         function getAuthorizer() external view returns (address);
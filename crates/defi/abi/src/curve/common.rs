@@ -35,3 +35,42 @@ sol! {
         function balances(int128) external view returns (uint256);
     }
 }
+
+sol! {
+    #[sol(abi = true, rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface ICurveStableSwapState {
+        function A() external view returns (uint256);
+        function A_precise() external view returns (uint256);
+        function fee() external view returns (uint256);
+        function balances(uint256) external view returns (uint256);
+        function get_virtual_price() external view returns (uint256);
+        function base_virtual_price() external view returns (uint256);
+        function base_cache_updated() external view returns (uint256);
+    }
+}
+
+sol! {
+    #[sol(abi = true, rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface ICurveStableSwapStateI128 {
+        function balances(int128) external view returns (uint256);
+    }
+}
+
+sol! {
+    #[sol(abi = true, rpc)]
+    #[derive(Debug, PartialEq, Eq)]
+    interface ICurveCryptoSwapState {
+        function A() external view returns (uint256);
+        function gamma() external view returns (uint256);
+        function D() external view returns (uint256);
+        function price_scale() external view returns (uint256);
+        function price_scale(uint256) external view returns (uint256);
+        function balances(uint256) external view returns (uint256);
+        function mid_fee() external view returns (uint256);
+        function out_fee() external view returns (uint256);
+        function fee_gamma() external view returns (uint256);
+        function future_A_gamma_time() external view returns (uint256);
+    }
+}
@@ -5,6 +5,7 @@ pub use weth::IWETH;
 
 mod abi_helpers;
 
+pub mod aave;
 pub mod balancer;
 pub mod curve;
 mod erc20;
@@ -17,8 +17,11 @@ sol! {
         function isValidSignature(bytes calldata, bytes calldata) external view returns (bytes4);
         function uniswapV3SwapCallback(int256 , int256 , bytes calldata data) external;
         function swapCallback(int256 , int256 , bytes calldata data) external;
+        function unlockCallback(bytes calldata data) external returns (bytes memory);
         function callFunction(address, DyDxAccountInfo memory, bytes calldata data) external;
         function receiveFlashLoan(address[] memory,uint256[] memory ,uint256[] memory,bytes calldata) external;
+        function executeOperation(address, uint256, uint256, address, bytes calldata params) external returns (bool);
+        function uniswapV3FlashCallback(uint256, uint256, bytes calldata data) external;
         function transferTipsMinBalance(address token, uint256 min_balance, uint256 tips, address owner) external payable;
         function transferTipsMinBalanceWETH(uint256 min_balance, uint256 tips,address owner) external payable;
         function transferTipsMinBalanceNoPayout(address token, uint256 min_balance, uint256 tips) external payable;
@@ -50,6 +50,50 @@ impl TokenAddressBase {
     pub const ETH_NATIVE: Address = Address::ZERO;
     pub const WETH: Address = address!("4200000000000000000000000000000000000006");
     pub const USDC: Address = address!("833589fCD6eDb6E08f4c7C32D4f71b54bdA02913");
+    pub const DAI: Address = address!("50c5725949A6F0c72E6C4a641F24049A917DB0Cb");
+
+    pub fn is_weth(&address: &Address) -> bool {
+        address.eq(&Self::WETH)
+    }
+    pub fn is_eth(&address: &Address) -> bool {
+        address.eq(&Self::ETH_NATIVE)
+    }
+}
+
+#[non_exhaustive]
+pub struct TokenAddressOptimism;
+impl TokenAddressOptimism {
+    pub const ETH_NATIVE: Address = Address::ZERO;
+    pub const WETH: Address = address!("4200000000000000000000000000000000000006");
+    pub const USDC: Address = address!("0b2C639c533813f4Aa9D7837CAf62653d097Ff85");
+    pub const USDT: Address = address!("94b008aA00579c1307B0EF2c499aD98a8ce58e58");
+    pub const DAI: Address = address!("DA10009cBd5D07dd0CeCc66161FC93D7c9000da1");
+    pub const WBTC: Address = address!("68f180fcCe6836688e9084f035309E29Bf0A2095");
+
+    pub fn is_weth(&address: &Address) -> bool {
+        address.eq(&Self::WETH)
+    }
+    pub fn is_eth(&address: &Address) -> bool {
+        address.eq(&Self::ETH_NATIVE)
+    }
+}
+
+#[non_exhaustive]
+pub struct TokenAddressBsc;
+impl TokenAddressBsc {
+    pub const BNB_NATIVE: Address = Address::ZERO;
+    pub const WBNB: Address = address!("bb4CdB9CBd36B01bD1cBaEBF2De08d9173bc095c");
+    pub const ETH: Address = address!("2170Ed0880ac9A755fd29B2688956BD959F933F8");
+    pub const USDT: Address = address!("55d398326f99059fF775485246999027B3197955");
+    pub const USDC: Address = address!("8AC76a51cc950d9822D68b83fE1Ad97B32Cd580d");
+    pub const BTCB: Address = address!("7130d2A12B9BCbFAe4f2634d864A1Ee1Ce3Ead9c");
+
+    pub fn is_wbnb(&address: &Address) -> bool {
+        address.eq(&Self::WBNB)
+    }
+    pub fn is_bnb(&address: &Address) -> bool {
+        address.eq(&Self::BNB_NATIVE)
+    }
 }
 
 #[non_exhaustive]
@@ -79,6 +123,9 @@ impl FactoryAddress {
     pub const MAVERICK_V2: Address = address!("0A7e848Aca42d879EF06507Fca0E7b33A0a63c1e");
 
     pub const UNISWAP_V4_POOL_MANAGER_ADDRESS: Address = address!("000000000004444c5dc75cB358380D2e3dE08A90");
+
+    // Balancer V2 pools are registered in and swapped through the Vault
+    pub const BALANCER_V2_VAULT: Address = address!("BA12222222228d8Ba445958a75a0704d566BF2C8");
 }
 
 #[non_exhaustive]
@@ -96,6 +143,7 @@ impl PeripheryAddress {
     pub const UNISWAPV4_STATE_VIEW_ADDRESS: Address = address!("7fFE42C4a5DEeA5b0feC41C94C136Cf115597227");
     pub const MAVERICK_V2_QUOTER: Address = address!("b40AfdB85a07f37aE217E7D6462e609900dD8D7A");
     pub const MAVERICK_V2_TICK_LENS: Address = address!("6A9EB38DE5D349Fe751E0aDb4c0D9D391f94cc8D");
+    pub const AAVE_V3_POOL: Address = address!("87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2");
 }
 
 #[non_exhaustive]
@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use alloy_network::{Ethereum, ReceiptResponse};
+use alloy_primitives::{keccak256, Address, TxHash, I256, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::Log;
+use alloy_sol_types::SolEvent;
+use influxdb::{Timestamp, WriteQuery};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+
+use loom_core_actors::{
+    subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
+use loom_core_actors_macros::{Accessor, Consumer, Producer};
+use loom_core_blockchain::Blockchain;
+use loom_defi_abi::{IERC20, IWETH};
+use loom_defi_address_book::TokenAddressEth;
+use loom_evm_utils::NWETH;
+use loom_types_entities::tips::TipsPolicy;
+use loom_types_entities::{BundleInclusion, BundleInclusionHistory, BundleInclusionStatus, Token};
+use loom_types_events::{MessageBlock, MessageTxCompose, RlpState, TxComposeMessageType};
+
+fn i256_to_float(value: I256) -> f64 {
+    let float_value = NWETH::to_float(value.unsigned_abs());
+    if value.is_negative() {
+        -float_value
+    } else {
+        float_value
+    }
+}
+
+/// Balance change of token on contract calculated from transaction logs
+fn calc_token_balance_diff(logs: &[Log], contract: Address, token: Address) -> I256 {
+    let mut diff = I256::ZERO;
+    for log in logs.iter().filter(|log| log.address() == token) {
+        if let Ok(transfer) = IERC20::Transfer::decode_log(&log.inner, false) {
+            if transfer.to == contract {
+                diff += I256::from_raw(transfer.value);
+            }
+            if transfer.from == contract {
+                diff -= I256::from_raw(transfer.value);
+            }
+        } else if token == TokenAddressEth::WETH {
+            if let Ok(withdrawal) = IWETH::Withdrawal::decode_log(&log.inner, false) {
+                if withdrawal.src == contract {
+                    diff -= I256::from_raw(withdrawal.wad);
+                }
+            } else if let Ok(deposit) = IWETH::Deposit::decode_log(&log.inner, false) {
+                if deposit.dst == contract {
+                    diff += I256::from_raw(deposit.wad);
+                }
+            }
+        }
+    }
+    diff
+}
+
+fn token_value_to_eth(token: &Token, value: I256) -> I256 {
+    let eth_value = I256::from_raw(token.calc_eth_value(value.unsigned_abs()).unwrap_or_default());
+    if value.is_negative() {
+        -eth_value
+    } else {
+        eth_value
+    }
+}
+
+/// Domain name of relay url without subdomains and top level domain, e.g. `titanbuilder` for `https://rpc.titanbuilder.xyz`
+fn relay_domain_name(relay: &str) -> Option<String> {
+    let host = relay.split("://").last()?.split(['/', ':']).next()?;
+    let labels: Vec<&str> = host.split('.').collect();
+    match labels.len() {
+        0 | 1 => None,
+        len => Some(labels[len - 2].to_lowercase()),
+    }
+}
+
+/// Relay which builder built the block, builders usually put their domain name to block extra data
+fn find_landed_relay(relays: &[String], builder: &str) -> Option<String> {
+    let builder = builder.to_lowercase();
+    relays.iter().find(|relay| relay_domain_name(relay).is_some_and(|name| builder.contains(&name))).cloned()
+}
+
+fn inclusion_write_query(record: &BundleInclusion) -> WriteQuery {
+    let mut write_query = WriteQuery::new(Timestamp::from(chrono::Utc::now()), "bundle_inclusion")
+        .add_field("expected_profit", i256_to_float(record.expected_net_profit_eth()))
+        .add_field("tips", NWETH::to_float(record.tips))
+        .add_field("expected_gas_cost", NWETH::to_float(record.expected_gas_cost))
+        .add_field("relays", record.relays.join(","))
+        .add_field("target_block", record.target_block)
+        .add_field("tx_hash", record.tx_hash.to_string())
+        .add_tag("status", format!("{:?}", record.status).to_lowercase());
+
+    if let Some(realized_profit) = record.realized_profit_eth {
+        write_query = write_query.add_field("realized_profit", i256_to_float(realized_profit));
+    }
+    if let Some(gas_cost) = record.gas_cost {
+        write_query = write_query.add_field("gas_cost", NWETH::to_float(gas_cost));
+    }
+    if let Some(tx_index) = record.tx_index {
+        write_query = write_query.add_field("tx_index", tx_index as u64);
+    }
+    if let Some(builder) = &record.builder {
+        write_query = write_query.add_tag("builder", builder.clone());
+    }
+    if let Some(landed_relay) = &record.landed_relay {
+        write_query = write_query.add_tag("landed_relay", landed_relay.clone());
+    }
+    if let Some(origin) = &record.origin {
+        write_query = write_query.add_tag("origin", origin.clone());
+    }
+    write_query
+}
+
+pub async fn bundle_inclusion_tracker_worker<P: Provider<Ethereum> + Clone + 'static>(
+    client: P,
+    bundle_inclusions: SharedState<BundleInclusionHistory>,
+    tx_compose_channel_rx: Broadcaster<MessageTxCompose>,
+    block_with_tx_rx: Broadcaster<MessageBlock>,
+    influxdb_write_channel_tx: Option<Broadcaster<WriteQuery>>,
+    tips_policy: Option<Arc<dyn TipsPolicy>>,
+    cancel_token: CancellationToken,
+) -> WorkerResult {
+    subscribe!(tx_compose_channel_rx);
+    subscribe!(block_with_tx_rx);
+
+    // profit token of pending transactions
+    let mut profit_tokens: HashMap<TxHash, Arc<Token>> = HashMap::new();
+
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
+            msg = tx_compose_channel_rx.recv() => {
+                match msg {
+                    Ok(tx_compose_msg) => {
+                        let TxComposeMessageType::Broadcast(tx_compose_data) = tx_compose_msg.inner else {
+                            continue;
+                        };
+                        let Some(rlp_bundle) = &tx_compose_data.rlp_bundle else {
+                            continue;
+                        };
+                        for rlp in rlp_bundle.iter() {
+                            let RlpState::Backrun(rlp) = rlp else {
+                                continue;
+                            };
+                            let tx_hash = keccak256(rlp);
+
+                            let mut record = BundleInclusion::new(
+                                tx_hash,
+                                tx_compose_data.next_block_number,
+                                tx_compose_data.swap.as_ref().map(|swap| swap.abs_profit_eth()).unwrap_or_default(),
+                                tx_compose_data.tips.unwrap_or_default(),
+                            );
+                            record.expected_gas_cost = tx_compose_data.gas_cost.unwrap_or_default();
+                            record.origin = tx_compose_data.origin.clone();
+                            if let Some(swap) = &tx_compose_data.swap {
+                                record.swap = format!("{swap}");
+                                if let Some(token) = swap.get_first_token() {
+                                    profit_tokens.insert(tx_hash, token.clone());
+                                }
+                            }
+                            debug!(%tx_hash, target_block = record.target_block, "Bundle broadcast recorded");
+                            bundle_inclusions.write().await.add_pending(record);
+                        }
+                    }
+                    Err(RecvError::Lagged(lagged)) => warn!(lagged, "Tx compose channel lagged"),
+                    Err(RecvError::Closed) => {
+                        error!("Tx compose channel closed");
+                        break;
+                    }
+                }
+            }
+            msg = block_with_tx_rx.recv() => {
+                match msg {
+                    Ok(block_msg) => {
+                        let block = block_msg.inner.block;
+                        let block_number = block.header.number;
+                        let builder = String::from_utf8_lossy(&block.header.extra_data).to_string();
+
+                        let included: Vec<(usize, TxHash)> = {
+                            let bundle_inclusions_guard = bundle_inclusions.read().await;
+                            block.transactions.hashes().enumerate().filter(|(_, tx_hash)| bundle_inclusions_guard.is_pending(tx_hash)).collect()
+                        };
+
+                        for (tx_index, tx_hash) in included {
+                            let receipt = match client.get_transaction_receipt(tx_hash).await {
+                                Ok(receipt) => receipt,
+                                Err(error) => {
+                                    error!(%error, %tx_hash, "Failed to get receipt");
+                                    None
+                                }
+                            };
+                            let profit_token = profit_tokens.remove(&tx_hash);
+
+                            let record = bundle_inclusions.write().await.resolve(&tx_hash, |record| {
+                                record.included_block = Some(block_number);
+                                record.tx_index = Some(tx_index);
+                                record.builder = Some(builder.clone());
+                                record.landed_relay = find_landed_relay(&record.relays, &builder);
+                                record.status = BundleInclusionStatus::Included;
+
+                                if let Some(receipt) = &receipt {
+                                    if !receipt.status() {
+                                        record.status = BundleInclusionStatus::Reverted;
+                                    }
+                                    record.gas_used = Some(receipt.gas_used);
+                                    record.gas_cost = Some(U256::from(receipt.gas_used) * U256::from(receipt.effective_gas_price));
+                                    if let (Some(contract), Some(token)) = (receipt.to, &profit_token) {
+                                        let token_diff = calc_token_balance_diff(receipt.inner.logs(), contract, token.get_address());
+                                        record.realized_profit_eth = Some(token_value_to_eth(token, token_diff));
+                                    }
+                                }
+                            });
+
+                            if let Some(record) = record {
+                                if let (BundleInclusionStatus::Included, Some(tips_policy)) = (&record.status, &tips_policy) {
+                                    // tips are a share of profit after gas cost
+                                    tips_policy.observe_winning_bid(record.expected_profit_eth.saturating_sub(record.expected_gas_cost), record.tips);
+                                }
+                                info!(
+                                    %tx_hash,
+                                    block_number,
+                                    tx_index,
+                                    builder = %builder,
+                                    landed_relay = ?record.landed_relay,
+                                    status = ?record.status,
+                                    expected = i256_to_float(record.expected_net_profit_eth()),
+                                    realized = record.realized_profit_eth.map(i256_to_float),
+                                    "Bundle included"
+                                );
+                                if let Some(influx_channel) = &influxdb_write_channel_tx {
+                                    if let Err(e) = influx_channel.send(inclusion_write_query(&record)) {
+                                        error!("Failed to send bundle inclusion to influxdb: {:?}", e);
+                                    }
+                                }
+                            }
+                        }
+
+                        let missed = bundle_inclusions.write().await.expire(block_number);
+                        for record in missed.iter() {
+                            profit_tokens.remove(&record.tx_hash);
+                            debug!(tx_hash = %record.tx_hash, target_block = record.target_block, "Bundle missed");
+                            if let Some(influx_channel) = &influxdb_write_channel_tx {
+                                if let Err(e) = influx_channel.send(inclusion_write_query(record)) {
+                                    error!("Failed to send bundle inclusion to influxdb: {:?}", e);
+                                }
+                            }
+                        }
+                    }
+                    Err(RecvError::Lagged(lagged)) => warn!(lagged, "Block with tx channel lagged"),
+                    Err(RecvError::Closed) => {
+                        error!("Block with tx channel closed");
+                        break;
+                    }
+                }
+            }
+        }
+    }
+    Ok("BundleInclusionTrackerWorker finished".to_string())
+}
+
+/// Tracks inclusion of broadcast backrun transactions in mined blocks and their realized profit
+#[derive(Accessor, Consumer, Producer)]
+pub struct BundleInclusionTrackerActor<P> {
+    client: P,
+    #[accessor]
+    bundle_inclusions: Option<SharedState<BundleInclusionHistory>>,
+    #[consumer]
+    tx_compose_channel_rx: Option<Broadcaster<MessageTxCompose>>,
+    #[consumer]
+    block_with_tx_rx: Option<Broadcaster<MessageBlock>>,
+    #[producer]
+    influxdb_write_channel_tx: Option<Broadcaster<WriteQuery>>,
+    tips_policy: Option<Arc<dyn TipsPolicy>>,
+}
+
+impl<P: Provider<Ethereum> + Send + Sync + Clone + 'static> BundleInclusionTrackerActor<P> {
+    pub fn new(client: P) -> Self {
+        BundleInclusionTrackerActor {
+            client,
+            bundle_inclusions: None,
+            tx_compose_channel_rx: None,
+            block_with_tx_rx: None,
+            influxdb_write_channel_tx: None,
+            tips_policy: None,
+        }
+    }
+
+    /// Tips policy that receives bids of our included bundles
+    pub fn with_tips_policy(self, tips_policy: Option<Arc<dyn TipsPolicy>>) -> Self {
+        Self { tips_policy, ..self }
+    }
+
+    pub fn on_bc(self, bc: &Blockchain) -> Self {
+        Self {
+            bundle_inclusions: Some(bc.bundle_inclusions()),
+            tx_compose_channel_rx: Some(bc.tx_compose_channel()),
+            block_with_tx_rx: Some(bc.new_block_with_tx_channel()),
+            influxdb_write_channel_tx: Some(bc.influxdb_write_channel()),
+            ..self
+        }
+    }
+}
+
+impl<P> Actor for BundleInclusionTrackerActor<P>
+where
+    P: Provider<Ethereum> + Send + Sync + Clone + 'static,
+{
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(bundle_inclusion_tracker_worker(
+            self.client.clone(),
+            self.bundle_inclusions.clone().unwrap(),
+            self.tx_compose_channel_rx.clone().unwrap(),
+            self.block_with_tx_rx.clone().unwrap(),
+            self.influxdb_write_channel_tx.clone(),
+            self.tips_policy.clone(),
+            cancel_token,
+        ));
+        Ok(vec![task])
+    }
+
+    fn name(&self) -> &'static str {
+        "BundleInclusionTrackerActor"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::{LogData, B256};
+    use influxdb::Query;
+
+    fn transfer_log(token: Address, from: Address, to: Address, value: u64) -> Log {
+        let event = IERC20::Transfer { from, to, value: U256::from(value) };
+        Log { inner: alloy_primitives::Log { address: token, data: event.encode_log_data() }, ..Log::default() }
+    }
+
+    #[test]
+    fn test_calc_token_balance_diff() {
+        let weth = TokenAddressEth::WETH;
+        let contract = Address::repeat_byte(1);
+        let pool = Address::repeat_byte(2);
+
+        let withdrawal = IWETH::Withdrawal { src: contract, wad: U256::from(30) };
+        let logs = vec![
+            transfer_log(weth, contract, pool, 1000),
+            transfer_log(TokenAddressEth::USDC, pool, contract, 5000),
+            transfer_log(weth, pool, contract, 1100),
+            Log { inner: alloy_primitives::Log { address: weth, data: withdrawal.encode_log_data() }, ..Log::default() },
+            Log {
+                inner: alloy_primitives::Log { address: weth, data: LogData::new_unchecked(vec![B256::ZERO], Default::default()) },
+                ..Log::default()
+            },
+        ];
+
+        assert_eq!(calc_token_balance_diff(&logs, contract, weth), I256::try_from(70).unwrap());
+        assert_eq!(calc_token_balance_diff(&logs, contract, TokenAddressEth::USDC), I256::try_from(5000).unwrap());
+        assert_eq!(calc_token_balance_diff(&logs, pool, weth), I256::try_from(-100).unwrap());
+    }
+
+    #[test]
+    fn test_inclusion_write_query() {
+        let mut record = BundleInclusion::new(TxHash::repeat_byte(1), 10, U256::from(100), U256::from(60));
+        record.relays = vec!["https://rpc.titanbuilder.xyz".to_string(), "https://rpc.beaverbuild.org/".to_string()];
+        let line = inclusion_write_query(&record).build().unwrap().get();
+        let (measurement_and_tags, fields) = line.split_once(' ').unwrap();
+
+        // high cardinality values are fields, not tags
+        assert_eq!(measurement_and_tags, "bundle_inclusion,status=pending");
+        assert!(fields.contains(&format!("tx_hash=\"{}\"", record.tx_hash)));
+        assert!(fields.contains("target_block=10"));
+        assert!(fields.contains("relays=\"https://rpc.titanbuilder.xyz,https://rpc.beaverbuild.org/\""));
+
+        record.landed_relay = Some("https://rpc.titanbuilder.xyz".to_string());
+        let line = inclusion_write_query(&record).build().unwrap().get();
+        let (measurement_and_tags, _) = line.split_once(' ').unwrap();
+        assert!(measurement_and_tags.contains(",landed_relay=https://rpc.titanbuilder.xyz"));
+    }
+
+    #[test]
+    fn test_find_landed_relay() {
+        let relays = vec![
+            "https://relay.flashbots.net".to_string(),
+            "https://rpc.titanbuilder.xyz".to_string(),
+            "https://rpc.beaverbuild.org/".to_string(),
+        ];
+
+        assert_eq!(relay_domain_name("https://rpc.titanbuilder.xyz"), Some("titanbuilder".to_string()));
+        assert_eq!(relay_domain_name("https://builder.gmbit.co/rpc"), Some("gmbit".to_string()));
+        assert_eq!(relay_domain_name("http://localhost:8545"), None);
+
+        assert_eq!(find_landed_relay(&relays, "Titan (titanbuilder.xyz)"), Some("https://rpc.titanbuilder.xyz".to_string()));
+        assert_eq!(find_landed_relay(&relays, "beaverbuild.org"), Some("https://rpc.beaverbuild.org/".to_string()));
+        assert_eq!(find_landed_relay(&relays, "rsync-builder.xyz"), None);
+    }
+}
@@ -1,9 +1,11 @@
+mod bundle_inclusion_tracker;
 mod pool_health_monitor;
 mod state_health_monitor;
 mod stuffing_tx_monitor;
 
 mod metrics_recorder_actor;
 
+pub use bundle_inclusion_tracker::BundleInclusionTrackerActor;
 pub use metrics_recorder_actor::MetricsRecorderActor;
 pub use pool_health_monitor::PoolHealthMonitorActor;
 pub use state_health_monitor::StateHealthMonitorActor;
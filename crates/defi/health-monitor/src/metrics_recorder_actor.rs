@@ -1,10 +1,10 @@
 use eyre::eyre;
 use influxdb::{Timestamp, WriteQuery};
 use loom_core_actors::Producer;
-use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, ChannelStats, WorkerResult};
 use loom_core_actors::{Accessor, Consumer, SharedState};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
-use loom_core_blockchain::{Blockchain, BlockchainState};
+use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
 use loom_evm_db::DatabaseLoomExt;
 use loom_types_entities::{Market, MarketState};
 use loom_types_events::MessageBlockHeader;
@@ -19,10 +19,17 @@ async fn metrics_recorder_worker<DB: DatabaseLoomExt + DatabaseRef + Send + Sync
     market_state: SharedState<MarketState<DB>>,
     block_header_update_rx: Broadcaster<MessageBlockHeader>,
     influx_channel_tx: Broadcaster<WriteQuery>,
+    blockchain: Option<Blockchain>,
+    strategy: Option<Strategy<DB>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(block_header_update_rx);
     loop {
-        let block_header = match block_header_update_rx.recv().await {
+        let block_header = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            block_header = block_header_update_rx.recv() => block_header,
+        };
+        let block_header = match block_header {
             Ok(block) => block,
             Err(e) => match e {
                 RecvError::Closed => {
@@ -55,6 +62,11 @@ async fn metrics_recorder_worker<DB: DatabaseLoomExt + DatabaseRef + Send + Sync
         let paths_disabled = market_guard.swap_paths().disabled_len();
         drop(market_guard);
 
+        let mut channels_stats: Vec<ChannelStats> = blockchain.as_ref().map(|bc| bc.channels_stats()).unwrap_or_default();
+        if let Some(strategy) = strategy.as_ref() {
+            channels_stats.extend(strategy.channels_stats());
+        }
+
         let influx_channel_clone = influx_channel_tx.clone();
 
         if let Err(e) = tokio::time::timeout(Duration::from_secs(2), async move {
@@ -113,12 +125,38 @@ async fn metrics_recorder_worker<DB: DatabaseLoomExt + DatabaseRef + Send + Sync
             if let Err(e) = influx_channel_clone.send(write_query) {
                 error!("Failed to send block latency to influxdb: {:?}", e);
             }
+
+            for channel_stats in channels_stats {
+                for (subscriber, lag) in channel_stats.subscribers_lag.iter() {
+                    let write_query = WriteQuery::new(Timestamp::from(current_timestamp), "channel_subscriber_lag")
+                        .add_field("value", *lag)
+                        .add_tag("channel", channel_stats.name.clone())
+                        .add_tag("subscriber", subscriber.clone());
+                    if let Err(e) = influx_channel_clone.send(write_query) {
+                        error!("Failed to send channel subscriber lag to influxdb: {:?}", e);
+                    }
+                }
+
+                let write_query = WriteQuery::new(Timestamp::from(current_timestamp), "channel_stats")
+                    .add_field("capacity", channel_stats.capacity as u64)
+                    .add_field("sent", channel_stats.sent)
+                    .add_field("dropped", channel_stats.dropped)
+                    .add_field("subscribers", channel_stats.subscribers as u64)
+                    .add_field("queued", channel_stats.queued as u64)
+                    .add_field("lagged", channel_stats.lagged)
+                    .add_field("block_number", block_header.inner.header.number)
+                    .add_tag("channel", channel_stats.name);
+                if let Err(e) = influx_channel_clone.send(write_query) {
+                    error!("Failed to send channel stats to influxdb: {:?}", e);
+                }
+            }
         })
         .await
         {
             error!("Failed to send data to influxdb: {:?}", e);
         }
     }
+    Ok("BlockLatencyRecorderWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer, Default)]
@@ -131,6 +169,9 @@ pub struct MetricsRecorderActor<DB: Clone + Send + Sync + 'static> {
     block_header_rx: Option<Broadcaster<MessageBlockHeader>>,
     #[producer]
     influxdb_write_channel_tx: Option<Broadcaster<WriteQuery>>,
+    // channel stats are recorded when started on blockchain
+    blockchain: Option<Blockchain>,
+    strategy: Option<Strategy<DB>>,
 }
 
 impl<DB> MetricsRecorderActor<DB>
@@ -138,15 +179,17 @@ where
     DB: DatabaseRef + DatabaseLoomExt + Clone + Send + Sync + 'static,
 {
     pub fn new() -> Self {
-        Self { market: None, market_state: None, block_header_rx: None, influxdb_write_channel_tx: None }
+        Self { market: None, market_state: None, block_header_rx: None, influxdb_write_channel_tx: None, blockchain: None, strategy: None }
     }
 
-    pub fn on_bc(self, bc: &Blockchain, bc_state: &BlockchainState<DB>) -> Self {
+    pub fn on_bc(self, bc: &Blockchain, bc_state: &BlockchainState<DB>, strategy: &Strategy<DB>) -> Self {
         Self {
             market: Some(bc.market()),
             market_state: Some(bc_state.market_state()),
             block_header_rx: Some(bc.new_block_headers_channel()),
             influxdb_write_channel_tx: Some(bc.influxdb_write_channel()),
+            blockchain: Some(bc.clone()),
+            strategy: Some(strategy.clone()),
         }
     }
 }
@@ -155,12 +198,15 @@ impl<DB> Actor for MetricsRecorderActor<DB>
 where
     DB: DatabaseRef + DatabaseLoomExt + Clone + Send + Sync + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(metrics_recorder_worker(
             self.market.clone().unwrap(),
             self.market_state.clone().unwrap(),
             self.block_header_rx.clone().unwrap(),
             self.influxdb_write_channel_tx.clone().unwrap(),
+            self.blockchain.clone(),
+            self.strategy.clone(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
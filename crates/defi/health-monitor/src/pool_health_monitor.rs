@@ -7,7 +7,9 @@ use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error, info};
 
 use lazy_static::lazy_static;
-use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::Blockchain;
 use loom_defi_address_book::TokenAddressEth;
@@ -31,6 +33,7 @@ pub async fn pool_health_monitor_worker(
     market: SharedState<Market>,
     pool_health_monitor_rx: Broadcaster<MessageHealthEvent>,
     influx_channel_tx: Broadcaster<WriteQuery>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(pool_health_monitor_rx);
 
@@ -39,6 +42,9 @@ pub async fn pool_health_monitor_worker(
 
     loop {
         tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        break;
+                    }
                     msg = pool_health_monitor_rx.recv() => {
 
                         let pool_health_update : Result<MessageHealthEvent, RecvError>  = msg;
@@ -192,6 +198,7 @@ pub async fn pool_health_monitor_worker(
                     }
                 }
     }
+    Ok("PoolHealthMonitorWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer, Default)]
@@ -219,11 +226,12 @@ impl PoolHealthMonitorActor {
 }
 
 impl Actor for PoolHealthMonitorActor {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(pool_health_monitor_worker(
             self.market.clone().unwrap(),
             self.pool_health_update_rx.clone().unwrap(),
             self.influxdb_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -7,10 +7,9 @@ use alloy_provider::Provider;
 use chrono::{DateTime, Duration, Local};
 use eyre::Result;
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::broadcast::Receiver;
 use tracing::{error, info, warn};
 
-use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, Consumer, SharedState, WorkerResult};
+use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, SharedState, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer};
 use loom_core_blockchain::{Blockchain, BlockchainState};
 use loom_evm_db::DatabaseLoomExt;
@@ -60,15 +59,19 @@ pub async fn state_health_monitor_worker<
     market_state: SharedState<MarketState<DB>>,
     tx_compose_channel_rx: Broadcaster<MessageTxCompose>,
     market_events_rx: Broadcaster<MarketEvents>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
-    let mut tx_compose_channel_rx: Receiver<MessageTxCompose> = tx_compose_channel_rx.subscribe();
-    let mut market_events_rx: Receiver<MarketEvents> = market_events_rx.subscribe();
+    subscribe!(tx_compose_channel_rx);
+    subscribe!(market_events_rx);
 
     let mut check_time_map: HashMap<Address, DateTime<Local>> = HashMap::new();
     let mut pool_address_to_verify_vec: Vec<Address> = Vec::new();
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = market_events_rx.recv() => {
                 let market_event_msg : Result<MarketEvents, RecvError> = msg;
                 match market_event_msg {
@@ -120,6 +123,7 @@ pub async fn state_health_monitor_worker<
 
         }
     }
+    Ok("StateHealthMonitorWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer)]
@@ -157,12 +161,13 @@ where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
     DB: DatabaseRef + DatabaseLoomExt + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(state_health_monitor_worker(
             self.client.clone(),
             self.market_state.clone().unwrap(),
             self.tx_compose_channel_rx.clone().unwrap(),
             self.market_events_rx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
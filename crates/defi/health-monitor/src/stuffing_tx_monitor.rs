@@ -7,14 +7,16 @@ use influxdb::{Timestamp, WriteQuery};
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::broadcast::Receiver;
 use tracing::{error, info};
 
 use loom_core_blockchain::Blockchain;
 use loom_evm_utils::NWETH;
+use loom_types_entities::tips::TipsPolicy;
 use loom_types_entities::{LatestBlock, Swap, Token};
 
-use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_types_blockchain::debug_trace_transaction;
 use loom_types_events::{MarketEvents, MessageTxCompose, TxComposeMessageType};
@@ -47,14 +49,19 @@ pub async fn stuffing_tx_monitor_worker<P: Provider<Ethereum> + Clone + 'static>
     tx_compose_channel_rx: Broadcaster<MessageTxCompose>,
     market_events_rx: Broadcaster<MarketEvents>,
     influxdb_write_channel_tx: Broadcaster<WriteQuery>,
+    tips_policy: Option<Arc<dyn TipsPolicy>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
-    let mut tx_compose_channel_rx: Receiver<MessageTxCompose> = tx_compose_channel_rx.subscribe();
-    let mut market_events_rx: Receiver<MarketEvents> = market_events_rx.subscribe();
+    subscribe!(tx_compose_channel_rx);
+    subscribe!(market_events_rx);
 
     let mut txs_to_check: HashMap<TxHash, TxToCheck> = HashMap::new();
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = market_events_rx.recv() => {
                 let market_event_msg : Result<MarketEvents, RecvError> = msg;
                 match market_event_msg {
@@ -71,9 +78,14 @@ pub async fn stuffing_tx_monitor_worker<P: Provider<Ethereum> + Clone + 'static>
                                             let others_tx_hash = others_tx.tx_hash();
                                             let client_clone = client.clone();
                                             let influx_channel_clone = influxdb_write_channel_tx.clone();
+                                            let tips_policy_clone = tips_policy.clone();
                                             info!("Stuffing tx mined {:?} MF tx: {:?} to: {:?}", tx.tx_hash(), others_tx.tx_hash(), others_tx.to().unwrap_or_default() );
                                             tokio::task::spawn( async move {
                                                 if let Ok(coinbase_diff)  = calc_coinbase_diff(client_clone, others_tx_hash, coinbase).await {
+                                                    // tx after our stuffing tx won the same opportunity, its coinbase payment is the winning bid
+                                                    if let Some(tips_policy) = &tips_policy_clone {
+                                                        tips_policy.observe_winning_bid(tx_to_check.profit, coinbase_diff);
+                                                    }
                                                     let start_time_utc =   chrono::Utc::now();
                                                     let bribe = NWETH::to_float(tx_to_check.tips);
                                                     let others_bribe = NWETH::to_float(coinbase_diff);
@@ -159,6 +171,7 @@ pub async fn stuffing_tx_monitor_worker<P: Provider<Ethereum> + Clone + 'static>
             }
         }
     }
+    Ok("StuffingTxMonitorWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer)]
@@ -172,6 +185,7 @@ pub struct StuffingTxMonitorActor<P> {
     market_events_rx: Option<Broadcaster<MarketEvents>>,
     #[producer]
     influxdb_write_channel_tx: Option<Broadcaster<WriteQuery>>,
+    tips_policy: Option<Arc<dyn TipsPolicy>>,
 }
 
 impl<P: Provider<Ethereum> + Send + Sync + Clone + 'static> StuffingTxMonitorActor<P> {
@@ -182,9 +196,15 @@ impl<P: Provider<Ethereum> + Send + Sync + Clone + 'static> StuffingTxMonitorAct
             tx_compose_channel_rx: None,
             market_events_rx: None,
             influxdb_write_channel_tx: None,
+            tips_policy: None,
         }
     }
 
+    /// Tips policy that receives bids of competitors that won stuffing opportunities
+    pub fn with_tips_policy(self, tips_policy: Option<Arc<dyn TipsPolicy>>) -> Self {
+        Self { tips_policy, ..self }
+    }
+
     pub fn on_bc(self, bc: &Blockchain) -> Self {
         Self {
             latest_block: Some(bc.latest_block()),
@@ -200,13 +220,15 @@ impl<P> Actor for StuffingTxMonitorActor<P>
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(stuffing_tx_monitor_worker(
             self.client.clone(),
             self.latest_block.clone().unwrap(),
             self.tx_compose_channel_rx.clone().unwrap(),
             self.market_events_rx.clone().unwrap(),
             self.influxdb_write_channel_tx.clone().unwrap(),
+            self.tips_policy.clone(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
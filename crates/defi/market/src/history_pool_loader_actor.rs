@@ -6,7 +6,7 @@ use std::sync::Arc;
 use tracing::{debug, error, info};
 
 use crate::logs_parser::process_log_entries;
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Producer, WorkerResult};
+use loom_core_actors::{Actor, ActorResult, Broadcaster, CancellationToken, Producer, WorkerResult};
 use loom_core_actors_macros::Producer;
 use loom_core_blockchain::Blockchain;
 use loom_types_blockchain::LoomDataTypesEthereum;
@@ -17,6 +17,7 @@ async fn history_pool_loader_one_shot_worker<P, PL, N>(
     client: P,
     pool_loaders: Arc<PoolLoaders<PL, N, LoomDataTypesEthereum>>,
     tasks_tx: Broadcaster<LoomTask>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -28,7 +29,7 @@ where
     let block_size: u64 = 5;
 
     for _ in 1..10000 {
-        if current_block < block_size + 1 {
+        if current_block < block_size + 1 || cancel_token.is_cancelled() {
             break;
         }
         current_block -= block_size;
@@ -83,11 +84,12 @@ where
     P: Provider<N> + Send + Sync + Clone + 'static,
     PL: Provider<N> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(history_pool_loader_one_shot_worker(
             self.client.clone(),
             self.pool_loaders.clone(),
             self.tasks_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -5,7 +5,7 @@ use std::sync::Arc;
 use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error};
 
-use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, Consumer, Producer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, WorkerResult};
 use loom_core_actors_macros::{Consumer, Producer};
 use loom_core_blockchain::Blockchain;
 use loom_types_entities::PoolLoaders;
@@ -17,6 +17,7 @@ pub async fn new_pool_worker<P, N>(
     log_update_rx: Broadcaster<MessageBlockLogs>,
     pools_loaders: Arc<PoolLoaders<P, N>>,
     tasks_tx: Broadcaster<LoomTask>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -26,6 +27,9 @@ where
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = log_update_rx.recv() => {
                 debug!("Log update");
 
@@ -46,6 +50,7 @@ where
             }
         }
     }
+    Ok("NewPoolWorker stopped".to_string())
 }
 
 #[derive(Consumer, Producer)]
@@ -80,11 +85,12 @@ where
     N: Network,
     P: Provider<N> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(new_pool_worker(
             self.log_update_rx.clone().unwrap(),
             self.pool_loaders.clone(),
             self.tasks_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
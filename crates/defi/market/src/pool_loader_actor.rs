@@ -7,7 +7,7 @@ use alloy_provider::Provider;
 use eyre::Result;
 use tracing::{debug, error, info};
 
-use loom_core_actors::{run_sync, subscribe, Actor, ActorResult, Broadcaster, Producer, SharedState, WorkerResult};
+use loom_core_actors::{run_sync, subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Producer, SharedState, WorkerResult};
 use loom_core_actors::{Accessor, Consumer};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, BlockchainState};
@@ -31,6 +31,7 @@ pub async fn pool_loader_worker<P, PL, N, DB>(
     market_state: SharedState<MarketState<DB>>,
     tasks_rx: Broadcaster<LoomTask>,
     market_events_tx: Broadcaster<MarketEvents>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -39,11 +40,16 @@ where
     DB: Database + DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
 {
     let mut processed_pools = HashMap::new();
-    let semaphore = std::sync::Arc::new(Semaphore::new(pools_config.threads().unwrap_or(MAX_CONCURRENT_TASKS)));
+    let max_concurrent_tasks = pools_config.threads().unwrap_or(MAX_CONCURRENT_TASKS);
+    let semaphore = std::sync::Arc::new(Semaphore::new(max_concurrent_tasks));
 
     subscribe!(tasks_rx);
     loop {
-        if let Ok(task) = tasks_rx.recv().await {
+        let task = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            task = tasks_rx.recv() => task,
+        };
+        if let Ok(task) = task {
             let pools = match task {
                 LoomTask::FetchAndAddPools(pools) => pools,
             };
@@ -93,6 +99,9 @@ where
             }
         }
     }
+    // wait for pools that are being loaded
+    let _ = semaphore.acquire_many(max_concurrent_tasks as u32).await;
+    Ok("PoolLoaderWorker stopped".to_string())
 }
 
 /// Fetch pool data, add it to the market and fetch the required state
@@ -250,7 +259,7 @@ where
     PL: Provider<N> + Send + Sync + Clone + 'static,
     DB: Database + DatabaseRef + DatabaseCommit + Default + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(pool_loader_worker(
             self.client.clone(),
             self.pool_loaders.clone(),
@@ -259,6 +268,7 @@ where
             self.market_state.clone().unwrap(),
             self.tasks_rx.clone().unwrap(),
             self.market_events_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -5,7 +5,7 @@ use alloy_network::Network;
 use alloy_provider::Provider;
 use tracing::{error, info};
 
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Producer, WorkerResult};
+use loom_core_actors::{Actor, ActorResult, Broadcaster, CancellationToken, Producer, WorkerResult};
 use loom_core_actors_macros::Producer;
 use loom_core_blockchain::Blockchain;
 use loom_types_entities::PoolLoaders;
@@ -16,6 +16,7 @@ async fn protocol_pool_loader_worker<P, PL, N>(
     _client: P,
     pool_loaders: Arc<PoolLoaders<PL, N>>,
     tasks_tx: Broadcaster<LoomTask>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -24,10 +25,18 @@ where
 {
     for (pool_class, pool_loader) in pool_loaders.map.iter() {
         let tasks_tx_clone = tasks_tx.clone();
+        let cancel_token_clone = cancel_token.clone();
         if let Ok(mut proto_loader) = pool_loader.clone().protocol_loader() {
             info!("Protocol loader started for {}", pool_class);
             tokio::task::spawn(async move {
-                while let Some((pool_id, pool_class)) = proto_loader.next().await {
+                loop {
+                    let (pool_id, pool_class) = tokio::select! {
+                        _ = cancel_token_clone.cancelled() => break,
+                        pool = proto_loader.next() => match pool {
+                            Some(pool) => pool,
+                            None => break,
+                        },
+                    };
                     if let Err(error) = tasks_tx_clone.send(LoomTask::FetchAndAddPools(vec![(pool_id, pool_class)])) {
                         error!(%error, "tasks_tx.send");
                     }
@@ -76,9 +85,13 @@ where
     P: Provider<N> + Send + Sync + Clone + 'static,
     PL: Provider<N> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
-        let task =
-            tokio::task::spawn(protocol_pool_loader_worker(self.client.clone(), self.pool_loaders.clone(), self.tasks_tx.clone().unwrap()));
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(protocol_pool_loader_worker(
+            self.client.clone(),
+            self.pool_loaders.clone(),
+            self.tasks_tx.clone().unwrap(),
+            cancel_token,
+        ));
 
         Ok(vec![task])
     }
@@ -8,7 +8,7 @@ use std::sync::Arc;
 use tracing::{debug, error, info};
 
 use crate::pool_loader_actor::fetch_and_add_pool_by_pool_id;
-use loom_core_actors::{Accessor, Actor, ActorResult, SharedState, WorkerResult};
+use loom_core_actors::{Accessor, Actor, ActorResult, CancellationToken, SharedState, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer};
 use loom_core_blockchain::{Blockchain, BlockchainState};
 use loom_node_debug_provider::DebugProviderExt;
@@ -22,6 +22,7 @@ async fn required_pools_loader_worker<P, N, DB>(
     required_state: Option<RequiredState>,
     market: SharedState<Market>,
     market_state: SharedState<MarketState<DB>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -29,6 +30,9 @@ where
     DB: Database + DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
 {
     for (pool_id, pool_class) in pools {
+        if cancel_token.is_cancelled() {
+            return Ok("required_pools_loader_worker cancelled".to_string());
+        }
         debug!(class=%pool_class, %pool_id, "Loading pool");
         match fetch_and_add_pool_by_pool_id(client.clone(), market.clone(), market_state.clone(), pool_loaders.clone(), pool_id, pool_class)
             .await
@@ -123,7 +127,7 @@ where
     P: Provider<N> + DebugProviderExt<N> + Send + Sync + Clone + 'static,
     DB: Database + DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(required_pools_loader_worker(
             self.client.clone(),
             self.pool_loaders.clone(),
@@ -131,6 +135,7 @@ where
             self.required_state.clone(),
             self.market.clone().unwrap(),
             self.market_state.clone().unwrap(),
+            cancel_token,
         ));
 
         Ok(vec![task])
@@ -0,0 +1,534 @@
+use std::any::Any;
+
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::providers::{Network, Provider};
+use alloy::sol_types::SolCall;
+use eyre::{eyre, ErrReport, OptionExt, Result};
+use loom_defi_abi::balancer::{IBalancerPool, IBalancerStablePool, IBalancerWeightedPool, IVault};
+use loom_types_entities::required_state::RequiredState;
+use loom_types_entities::{Pool, PoolAbiEncoder, PoolClass, PoolId, PoolProtocol, PreswapRequirement, SwapDirection};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+
+use crate::state_readers::BalancerV2StateReader;
+use crate::virtual_impl::balancer::{FixedPoint, StableMath, WeightedMath};
+
+const WEIGHTED_SWAP_GAS_USED: u64 = 120_000;
+const STABLE_SWAP_GAS_USED: u64 = 160_000;
+
+/// Offset of `SingleSwap.amount` in `IVault.swap` call data
+const SWAP_AMOUNT_OFFSET: u32 = 0x164;
+
+/// Pool state kept by the Vault and the pool, balances and amounts are in token decimals
+struct BalancerV2SwapState {
+    balances: Vec<U256>,
+    scaling_factors: Vec<U256>,
+    swap_fee: U256,
+}
+
+impl BalancerV2SwapState {
+    fn fetch(db: &dyn DatabaseRef<Error = ErrReport>, env: Env, vault: Address, pool_id: B256, pool: Address) -> Result<Self> {
+        let (_, balances) = BalancerV2StateReader::get_pool_tokens(&db, env.clone(), vault, pool_id)?;
+        let scaling_factors = BalancerV2StateReader::get_scaling_factors(&db, env.clone(), pool)?;
+        let swap_fee = BalancerV2StateReader::get_swap_fee_percentage(&db, env, pool)?;
+        if balances.len() != scaling_factors.len() {
+            return Err(eyre!("BAD_SCALING_FACTORS"));
+        }
+        Ok(Self { balances, scaling_factors, swap_fee })
+    }
+
+    // Removes pre-minted BPT of composable pools from the swap state
+    fn without_index(mut self, index: Option<usize>) -> Self {
+        if let Some(index) = index {
+            self.balances.remove(index);
+            self.scaling_factors.remove(index);
+        }
+        self
+    }
+
+    fn upscaled_balances(&self) -> Result<Vec<U256>> {
+        self.balances.iter().zip(self.scaling_factors.iter()).map(|(balance, factor)| FixedPoint::mul_down(*balance, *factor)).collect()
+    }
+
+    fn subtract_swap_fee(&self, amount: U256) -> Result<U256> {
+        Ok(amount - FixedPoint::mul_up(amount, self.swap_fee)?)
+    }
+
+    fn add_swap_fee(&self, amount: U256) -> Result<U256> {
+        FixedPoint::div_up(amount, FixedPoint::complement(self.swap_fee))
+    }
+}
+
+fn token_index(tokens: &[Address], token: &Address) -> Result<usize> {
+    tokens.iter().position(|x| x == token).ok_or_eyre("TOKEN_NOT_FOUND")
+}
+
+fn swap_directions(tokens: &[Address]) -> Vec<SwapDirection> {
+    let mut ret: Vec<SwapDirection> = Vec::new();
+    for token_from in tokens.iter() {
+        for token_to in tokens.iter() {
+            if token_from != token_to {
+                ret.push((*token_from, *token_to).into());
+            }
+        }
+    }
+    ret
+}
+
+fn swap_state_required(vault: Address, pool_id: B256, pool: Address) -> RequiredState {
+    let mut state_required = RequiredState::new();
+    state_required
+        .add_call(vault, IVault::getPoolTokensCall { poolId: pool_id }.abi_encode())
+        .add_call(pool, IBalancerPool::getScalingFactorsCall {}.abi_encode())
+        .add_call(pool, IBalancerPool::getSwapFeePercentageCall {}.abi_encode());
+    state_required
+}
+
+#[derive(Clone)]
+pub struct BalancerV2WeightedPool {
+    address: Address,
+    pool_id: B256,
+    vault: Address,
+    tokens: Vec<Address>,
+    weights: Vec<U256>,
+    fee: U256,
+    encoder: BalancerV2AbiSwapEncoder,
+}
+
+impl BalancerV2WeightedPool {
+    pub fn new(address: Address, pool_id: B256, vault: Address, tokens: Vec<Address>, weights: Vec<U256>, fee: U256) -> Self {
+        Self { address, pool_id, vault, tokens, weights, fee, encoder: BalancerV2AbiSwapEncoder::new(vault, pool_id) }
+    }
+
+    pub fn get_pool_id_bytes(&self) -> B256 {
+        self.pool_id
+    }
+
+    pub fn get_vault(&self) -> Address {
+        self.vault
+    }
+
+    pub fn get_weights(&self) -> Vec<U256> {
+        self.weights.clone()
+    }
+
+    pub async fn fetch_pool_data<N: Network, P: Provider<N> + Send + Sync + Clone + 'static>(client: P, address: Address) -> Result<Self> {
+        let pool = IBalancerPool::new(address, client.clone());
+        let pool_id = pool.getPoolId().call().await?._0;
+        let vault = pool.getVault().call().await?._0;
+        let fee = pool.getSwapFeePercentage().call().await?._0;
+        let tokens = IVault::new(vault, client.clone()).getPoolTokens(pool_id).call().await?.tokens;
+        // checking the pool is a weighted one, swaps use weights from state as LBP and managed pools change them
+        let weights = IBalancerWeightedPool::new(address, client).getNormalizedWeights().call().await?._0;
+
+        if tokens.len() != weights.len() {
+            return Err(eyre!("BAD_WEIGHTS"));
+        }
+
+        Ok(Self::new(address, pool_id, vault, tokens, weights, fee))
+    }
+
+    fn fetch_swap_state(&self, state_db: &dyn DatabaseRef<Error = ErrReport>, env: Env) -> Result<(BalancerV2SwapState, Vec<U256>)> {
+        let state = BalancerV2SwapState::fetch(state_db, env.clone(), self.vault, self.pool_id, self.address)?;
+        let weights = BalancerV2StateReader::get_normalized_weights(&state_db, env, self.address)?;
+        if weights.len() != state.balances.len() {
+            return Err(eyre!("BAD_WEIGHTS"));
+        }
+        Ok((state, weights))
+    }
+}
+
+impl Pool for BalancerV2WeightedPool {
+    fn as_any<'a>(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_class(&self) -> PoolClass {
+        PoolClass::BalancerV2
+    }
+
+    fn get_protocol(&self) -> PoolProtocol {
+        PoolProtocol::BalancerV2
+    }
+
+    fn get_address(&self) -> Address {
+        self.address
+    }
+
+    fn get_pool_id(&self) -> PoolId {
+        PoolId::Address(self.address)
+    }
+
+    fn get_fee(&self) -> U256 {
+        self.fee
+    }
+
+    fn get_tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    fn get_swap_directions(&self) -> Vec<SwapDirection> {
+        swap_directions(&self.tokens)
+    }
+
+    fn calculate_out_amount(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let i = token_index(&self.tokens, token_address_from)?;
+        let j = token_index(&self.tokens, token_address_to)?;
+        let (state, weights) = self.fetch_swap_state(state_db, env)?;
+        let balances = state.upscaled_balances()?;
+
+        let amount_in = FixedPoint::mul_down(state.subtract_swap_fee(in_amount)?, state.scaling_factors[i])?;
+        let amount_out = WeightedMath::calc_out_given_in(balances[i], weights[i], balances[j], weights[j], amount_in)?;
+        let ret = FixedPoint::div_down(amount_out, state.scaling_factors[j])?;
+
+        if ret.is_zero() {
+            Err(eyre!("RETURN_RESULT_IS_ZERO"))
+        } else {
+            Ok((ret, WEIGHTED_SWAP_GAS_USED))
+        }
+    }
+
+    fn calculate_in_amount(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        out_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let i = token_index(&self.tokens, token_address_from)?;
+        let j = token_index(&self.tokens, token_address_to)?;
+        let (state, weights) = self.fetch_swap_state(state_db, env)?;
+        let balances = state.upscaled_balances()?;
+
+        let amount_out = FixedPoint::mul_down(out_amount, state.scaling_factors[j])?;
+        let amount_in = WeightedMath::calc_in_given_out(balances[i], weights[i], balances[j], weights[j], amount_out)?;
+        let ret = state.add_swap_fee(FixedPoint::div_up(amount_in, state.scaling_factors[i])?)?;
+
+        if ret.is_zero() {
+            Err(eyre!("RETURN_RESULT_IS_ZERO"))
+        } else {
+            Ok((ret, WEIGHTED_SWAP_GAS_USED))
+        }
+    }
+
+    fn can_flash_swap(&self) -> bool {
+        false
+    }
+
+    fn can_calculate_in_amount(&self) -> bool {
+        true
+    }
+
+    fn get_abi_encoder(&self) -> Option<&dyn PoolAbiEncoder> {
+        Some(&self.encoder)
+    }
+
+    fn get_read_only_cell_vec(&self) -> Vec<U256> {
+        Vec::new()
+    }
+
+    fn get_state_required(&self) -> Result<RequiredState> {
+        let mut state_required = swap_state_required(self.vault, self.pool_id, self.address);
+        state_required.add_call(self.address, IBalancerWeightedPool::getNormalizedWeightsCall {}.abi_encode());
+        Ok(state_required)
+    }
+
+    fn is_native(&self) -> bool {
+        false
+    }
+
+    fn preswap_requirement(&self) -> PreswapRequirement {
+        PreswapRequirement::Allowance
+    }
+}
+
+#[derive(Clone)]
+pub struct BalancerV2StablePool {
+    address: Address,
+    pool_id: B256,
+    vault: Address,
+    // registered tokens, composable pools have own BPT registered
+    registered_tokens: Vec<Address>,
+    bpt_index: Option<usize>,
+    tokens: Vec<Address>,
+    fee: U256,
+    encoder: BalancerV2AbiSwapEncoder,
+}
+
+impl BalancerV2StablePool {
+    pub fn new(address: Address, pool_id: B256, vault: Address, registered_tokens: Vec<Address>, fee: U256) -> Self {
+        let bpt_index = registered_tokens.iter().position(|x| *x == address);
+        let tokens = registered_tokens.iter().filter(|x| **x != address).cloned().collect();
+        Self { address, pool_id, vault, registered_tokens, bpt_index, tokens, fee, encoder: BalancerV2AbiSwapEncoder::new(vault, pool_id) }
+    }
+
+    pub fn get_pool_id_bytes(&self) -> B256 {
+        self.pool_id
+    }
+
+    pub fn get_vault(&self) -> Address {
+        self.vault
+    }
+
+    pub fn get_registered_tokens(&self) -> Vec<Address> {
+        self.registered_tokens.clone()
+    }
+
+    pub async fn fetch_pool_data<N: Network, P: Provider<N> + Send + Sync + Clone + 'static>(client: P, address: Address) -> Result<Self> {
+        let pool = IBalancerPool::new(address, client.clone());
+        let pool_id = pool.getPoolId().call().await?._0;
+        let vault = pool.getVault().call().await?._0;
+        let fee = pool.getSwapFeePercentage().call().await?._0;
+        let registered_tokens = IVault::new(vault, client.clone()).getPoolTokens(pool_id).call().await?.tokens;
+        // checking the pool is a stable one
+        IBalancerStablePool::new(address, client).getAmplificationParameter().call().await?;
+
+        Ok(Self::new(address, pool_id, vault, registered_tokens, fee))
+    }
+
+    fn fetch_swap_state(&self, state_db: &dyn DatabaseRef<Error = ErrReport>, env: Env) -> Result<(BalancerV2SwapState, U256)> {
+        let state =
+            BalancerV2SwapState::fetch(state_db, env.clone(), self.vault, self.pool_id, self.address)?.without_index(self.bpt_index);
+        let amp = BalancerV2StateReader::get_amplification_parameter(&state_db, env, self.address)?;
+        Ok((state, amp))
+    }
+}
+
+impl Pool for BalancerV2StablePool {
+    fn as_any<'a>(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_class(&self) -> PoolClass {
+        PoolClass::BalancerV2
+    }
+
+    fn get_protocol(&self) -> PoolProtocol {
+        PoolProtocol::BalancerV2
+    }
+
+    fn get_address(&self) -> Address {
+        self.address
+    }
+
+    fn get_pool_id(&self) -> PoolId {
+        PoolId::Address(self.address)
+    }
+
+    fn get_fee(&self) -> U256 {
+        self.fee
+    }
+
+    fn get_tokens(&self) -> Vec<Address> {
+        self.tokens.clone()
+    }
+
+    fn get_swap_directions(&self) -> Vec<SwapDirection> {
+        swap_directions(&self.tokens)
+    }
+
+    fn calculate_out_amount(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let i = token_index(&self.tokens, token_address_from)?;
+        let j = token_index(&self.tokens, token_address_to)?;
+        let (state, amp) = self.fetch_swap_state(state_db, env)?;
+        let balances = state.upscaled_balances()?;
+
+        let amount_in = FixedPoint::mul_down(state.subtract_swap_fee(in_amount)?, state.scaling_factors[i])?;
+        let invariant = StableMath::calculate_invariant(amp, &balances)?;
+        let amount_out = StableMath::calc_out_given_in(amp, &balances, i, j, amount_in, invariant)?;
+        let ret = FixedPoint::div_down(amount_out, state.scaling_factors[j])?;
+
+        if ret.is_zero() {
+            Err(eyre!("RETURN_RESULT_IS_ZERO"))
+        } else {
+            Ok((ret, STABLE_SWAP_GAS_USED))
+        }
+    }
+
+    fn calculate_in_amount(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        out_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let i = token_index(&self.tokens, token_address_from)?;
+        let j = token_index(&self.tokens, token_address_to)?;
+        let (state, amp) = self.fetch_swap_state(state_db, env)?;
+        let balances = state.upscaled_balances()?;
+
+        let amount_out = FixedPoint::mul_down(out_amount, state.scaling_factors[j])?;
+        let invariant = StableMath::calculate_invariant(amp, &balances)?;
+        let amount_in = StableMath::calc_in_given_out(amp, &balances, i, j, amount_out, invariant)?;
+        let ret = state.add_swap_fee(FixedPoint::div_up(amount_in, state.scaling_factors[i])?)?;
+
+        if ret.is_zero() {
+            Err(eyre!("RETURN_RESULT_IS_ZERO"))
+        } else {
+            Ok((ret, STABLE_SWAP_GAS_USED))
+        }
+    }
+
+    fn can_flash_swap(&self) -> bool {
+        false
+    }
+
+    fn can_calculate_in_amount(&self) -> bool {
+        true
+    }
+
+    fn get_abi_encoder(&self) -> Option<&dyn PoolAbiEncoder> {
+        Some(&self.encoder)
+    }
+
+    fn get_read_only_cell_vec(&self) -> Vec<U256> {
+        Vec::new()
+    }
+
+    fn get_state_required(&self) -> Result<RequiredState> {
+        let mut state_required = swap_state_required(self.vault, self.pool_id, self.address);
+        state_required.add_call(self.address, IBalancerStablePool::getAmplificationParameterCall {}.abi_encode());
+        Ok(state_required)
+    }
+
+    fn is_native(&self) -> bool {
+        false
+    }
+
+    fn preswap_requirement(&self) -> PreswapRequirement {
+        PreswapRequirement::Allowance
+    }
+}
+
+/// Encodes `IVault.swap`, tokens are pulled from and sent to the recipient, so the Vault has to be approved by the recipient
+#[derive(Clone, Copy)]
+pub struct BalancerV2AbiSwapEncoder {
+    vault: Address,
+    pool_id: B256,
+}
+
+impl BalancerV2AbiSwapEncoder {
+    pub fn new(vault: Address, pool_id: B256) -> Self {
+        Self { vault, pool_id }
+    }
+
+    pub fn get_vault(&self) -> Address {
+        self.vault
+    }
+
+    fn encode_swap(
+        &self,
+        kind: IVault::SwapKind,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount: U256,
+        limit: U256,
+        recipient: Address,
+    ) -> Bytes {
+        IVault::swapCall {
+            singleSwap: IVault::SingleSwap {
+                poolId: self.pool_id,
+                kind,
+                assetIn: token_from_address,
+                assetOut: token_to_address,
+                amount,
+                userData: Bytes::new(),
+            },
+            funds: IVault::FundManagement { sender: recipient, fromInternalBalance: false, recipient, toInternalBalance: false },
+            limit,
+            deadline: U256::MAX,
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+impl PoolAbiEncoder for BalancerV2AbiSwapEncoder {
+    fn encode_swap_in_amount_provided(
+        &self,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount: U256,
+        recipient: Address,
+        _payload: Bytes,
+    ) -> Result<Bytes> {
+        Ok(self.encode_swap(IVault::SwapKind::GIVEN_IN, token_from_address, token_to_address, amount, U256::ZERO, recipient))
+    }
+
+    fn encode_swap_out_amount_provided(
+        &self,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount: U256,
+        recipient: Address,
+        _payload: Bytes,
+    ) -> Result<Bytes> {
+        Ok(self.encode_swap(IVault::SwapKind::GIVEN_OUT, token_from_address, token_to_address, amount, U256::MAX, recipient))
+    }
+
+    fn swap_in_amount_offset(&self, _token_from_address: Address, _token_to_address: Address) -> Option<u32> {
+        Some(SWAP_AMOUNT_OFFSET)
+    }
+
+    fn swap_out_amount_offset(&self, _token_from_address: Address, _token_to_address: Address) -> Option<u32> {
+        Some(SWAP_AMOUNT_OFFSET)
+    }
+
+    fn swap_out_amount_return_offset(&self, _token_from_address: Address, _token_to_address: Address) -> Option<u32> {
+        Some(0x0)
+    }
+
+    fn swap_in_amount_return_offset(&self, _token_from_address: Address, _token_to_address: Address) -> Option<u32> {
+        Some(0x0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use loom_defi_address_book::FactoryAddress;
+
+    #[test]
+    fn test_swap_amount_offset() {
+        let encoder = BalancerV2AbiSwapEncoder::new(FactoryAddress::BALANCER_V2_VAULT, B256::repeat_byte(0x11));
+        let amount = U256::from(0x123456789u64);
+        let call_data = encoder
+            .encode_swap_in_amount_provided(Address::repeat_byte(1), Address::repeat_byte(2), amount, Address::repeat_byte(3), Bytes::new())
+            .unwrap();
+
+        let offset = SWAP_AMOUNT_OFFSET as usize;
+        assert_eq!(U256::from_be_slice(&call_data[offset..offset + 0x20]), amount);
+    }
+
+    #[test]
+    fn test_stable_pool_tokens() {
+        let address = Address::repeat_byte(0xAA);
+        let pool = BalancerV2StablePool::new(
+            address,
+            B256::ZERO,
+            FactoryAddress::BALANCER_V2_VAULT,
+            vec![Address::repeat_byte(1), address, Address::repeat_byte(2)],
+            U256::ZERO,
+        );
+
+        assert_eq!(pool.bpt_index, Some(1));
+        assert_eq!(pool.get_tokens(), vec![Address::repeat_byte(1), Address::repeat_byte(2)]);
+        assert_eq!(pool.get_swap_directions().len(), 2);
+    }
+}
@@ -6,6 +6,7 @@ use alloy::providers::{Network, Provider};
 use alloy::sol_types::SolCall;
 use eyre::{eyre, ErrReport, OptionExt, Result};
 use lazy_static::lazy_static;
+use loom_defi_abi::curve::{ICurveCryptoSwapState, ICurveStableSwapState, ICurveStableSwapStateI128};
 use loom_defi_abi::IERC20;
 use loom_defi_address_book::TokenAddressEth;
 use loom_evm_utils::evm::evm_call;
@@ -13,14 +14,47 @@ use loom_types_entities::required_state::RequiredState;
 use loom_types_entities::{Pool, PoolAbiEncoder, PoolClass, PoolId, PoolProtocol, PreswapRequirement, SwapDirection};
 use revm::primitives::Env;
 use revm::DatabaseRef;
-use tracing::error;
+use tracing::{error, trace};
 
 use crate::protocols::{CurveCommonContract, CurveContract, CurveProtocol};
+use crate::state_readers::CurveStateReader;
+use crate::virtual_impl::curve::{CryptoSwapState, CryptoSwapVariant, MetaStableSwapState, StableSwapState, StableSwapVariant, PRECISION};
 
 lazy_static! {
     static ref U256_ONE: U256 = U256::from(1);
 }
 
+const STABLE_SWAP_GAS_USED: u64 = 130_000;
+const META_SWAP_GAS_USED: u64 = 250_000;
+const LIQUIDITY_GAS_USED: u64 = 180_000;
+const CRYPTO_SWAP_GAS_USED: u64 = 200_000;
+
+const A_PRECISION: U256 = U256::from_limbs([100, 0, 0, 0]);
+/// Metapools use cached virtual price of base pool for this number of seconds
+const BASE_CACHE_EXPIRES: U256 = U256::from_limbs([600, 0, 0, 0]);
+
+/// Invariant of the pool contract with native implementation
+#[derive(Clone, Copy, Debug)]
+enum CurveInvariant {
+    StableSwap(StableSwapVariant),
+    CryptoSwap(CryptoSwapVariant),
+}
+
+/// Rate multipliers scaling coin amounts to 18 decimals, multiplied by 1e18
+async fn fetch_rate_multipliers<P, N>(client: P, tokens: &[Address]) -> Result<Vec<U256>>
+where
+    N: Network,
+    P: Provider<N> + Send + Sync + Clone + 'static,
+{
+    let mut rates = Vec::new();
+    for token in tokens.iter() {
+        let decimals = IERC20::new(*token, client.clone()).decimals().call().await?._0;
+        let exp = U256::from(36).checked_sub(decimals).ok_or_eyre("BAD_DECIMALS")?;
+        rates.push(U256::from(10).pow(exp));
+    }
+    Ok(rates)
+}
+
 pub struct CurvePool<P, N, E = CurvePoolAbiEncoder<P, N>>
 where
     N: Network,
@@ -36,6 +70,10 @@ where
     abi_encoder: Option<Arc<E>>,
     is_meta: bool,
     is_native: bool,
+    rates: Vec<U256>,
+    base_pool: Option<Address>,
+    base_rates: Vec<U256>,
+    state_reader: Arc<CurveStateReader>,
 }
 
 impl<P, N, E> Clone for CurvePool<P, N, E>
@@ -55,6 +93,10 @@ where
             abi_encoder: self.abi_encoder.clone(),
             is_meta: self.is_meta,
             is_native: self.is_native,
+            rates: self.rates.clone(),
+            base_pool: self.base_pool,
+            base_rates: self.base_rates.clone(),
+            state_reader: Arc::clone(&self.state_reader),
         }
     }
 }
@@ -113,6 +155,217 @@ where
         self.pool_contract.get_dy(i, j, amount_in).await
     }
 
+    fn invariant(&self) -> Result<CurveInvariant> {
+        match self.pool_contract.as_ref() {
+            CurveContract::I128_2(_) | CurveContract::I128_3(_) | CurveContract::I128_4(_) | CurveContract::I128_2ToMeta(_) => {
+                Ok(CurveInvariant::StableSwap(StableSwapVariant::Precise))
+            }
+            CurveContract::I128_2To(_) => Ok(CurveInvariant::StableSwap(StableSwapVariant::Factory)),
+            CurveContract::U256_3Eth(_) => Ok(CurveInvariant::CryptoSwap(CryptoSwapVariant::Tricrypto)),
+            CurveContract::U256_3EthTo(_) | CurveContract::U256_3EthTo2(_) => {
+                Ok(CurveInvariant::CryptoSwap(CryptoSwapVariant::TricryptoNg))
+            }
+            CurveContract::U256_2(_) | CurveContract::U256_2To(_) | CurveContract::U256_2EthTo(_) => {
+                Ok(CurveInvariant::CryptoSwap(CryptoSwapVariant::Crypto2))
+            }
+        }
+    }
+
+    fn read_u256<C: SolCall>(&self, db: &dyn DatabaseRef<Error = ErrReport>, env: &Env, address: Address, call: C) -> Result<U256> {
+        self.state_reader.call_u256(db, env, address, call)
+    }
+
+    fn read_stable_swap_balance(&self, db: &dyn DatabaseRef<Error = ErrReport>, env: &Env, address: Address, i: usize) -> Result<U256> {
+        self.read_u256(db, env, address, ICurveStableSwapState::balancesCall { _0: U256::from(i) })
+            .or_else(|_| self.read_u256(db, env, address, ICurveStableSwapStateI128::balancesCall { _0: i as i128 }))
+    }
+
+    /// Reads StableSwap pool state, pools without `A_precise` getter are treated as legacy ones
+    fn stable_swap_state(
+        &self,
+        db: &dyn DatabaseRef<Error = ErrReport>,
+        env: &Env,
+        address: Address,
+        rates: &[U256],
+        variant: StableSwapVariant,
+    ) -> Result<StableSwapState> {
+        if rates.is_empty() {
+            return Err(eyre!("CURVE_RATES_NOT_SET"));
+        }
+        let balances = (0..rates.len()).map(|i| self.read_stable_swap_balance(db, env, address, i)).collect::<Result<Vec<U256>>>()?;
+
+        let (variant, amp, a_precision) = match self.read_u256(db, env, address, ICurveStableSwapState::A_preciseCall {}) {
+            Ok(amp) => (variant, amp, A_PRECISION),
+            Err(_) => (StableSwapVariant::Legacy, self.read_u256(db, env, address, ICurveStableSwapState::ACall {})?, U256::from(1)),
+        };
+        let fee = self.read_u256(db, env, address, ICurveStableSwapState::feeCall {})?;
+
+        Ok(StableSwapState { variant, balances, rates: rates.to_vec(), amp, a_precision, fee, total_supply: U256::ZERO })
+    }
+
+    fn meta_stable_swap_state(
+        &self,
+        db: &dyn DatabaseRef<Error = ErrReport>,
+        env: &Env,
+        variant: StableSwapVariant,
+    ) -> Result<MetaStableSwapState> {
+        let base_pool = self.base_pool.ok_or_eyre("CURVE_BASE_POOL_NOT_SET")?;
+        let base_lp_token = *self.tokens.get(1).ok_or_eyre("CURVE_BASE_LP_NOT_SET")?;
+
+        let mut base = self.stable_swap_state(db, env, base_pool, &self.base_rates, StableSwapVariant::Precise)?;
+        base.total_supply = self.read_u256(db, env, base_lp_token, IERC20::totalSupplyCall {})?;
+
+        let base_cache_updated = self.read_u256(db, env, self.address, ICurveStableSwapState::base_cache_updatedCall {})?;
+        let vp_rate = if env.block.timestamp > base_cache_updated + BASE_CACHE_EXPIRES {
+            base.get_virtual_price()?
+        } else {
+            self.read_u256(db, env, self.address, ICurveStableSwapState::base_virtual_priceCall {})?
+        };
+
+        let meta_rates = vec![*self.rates.first().ok_or_eyre("CURVE_RATES_NOT_SET")?, vp_rate];
+        let meta = self.stable_swap_state(db, env, self.address, &meta_rates, variant)?;
+
+        Ok(MetaStableSwapState { meta, base })
+    }
+
+    fn crypto_swap_state(&self, db: &dyn DatabaseRef<Error = ErrReport>, env: &Env, variant: CryptoSwapVariant) -> Result<CryptoSwapState> {
+        if self.rates.len() != self.tokens.len() {
+            return Err(eyre!("CURVE_RATES_NOT_SET"));
+        }
+        let address = self.address;
+        let n_coins = self.tokens.len();
+
+        let balances = (0..n_coins)
+            .map(|i| self.read_u256(db, env, address, ICurveCryptoSwapState::balancesCall { _0: U256::from(i) }))
+            .collect::<Result<Vec<U256>>>()?;
+        let price_scale = if n_coins == 2 {
+            vec![self.read_u256(db, env, address, ICurveCryptoSwapState::price_scale_0Call {})?]
+        } else {
+            (0..n_coins - 1)
+                .map(|k| self.read_u256(db, env, address, ICurveCryptoSwapState::price_scale_1Call { _0: U256::from(k) }))
+                .collect::<Result<Vec<U256>>>()?
+        };
+
+        let future_a_gamma_time = self.read_u256(db, env, address, ICurveCryptoSwapState::future_A_gamma_timeCall {})?;
+        let recalc_d = match variant {
+            CryptoSwapVariant::Tricrypto => false,
+            CryptoSwapVariant::Crypto2 => !future_a_gamma_time.is_zero(),
+            CryptoSwapVariant::TricryptoNg => future_a_gamma_time > env.block.timestamp,
+        };
+
+        Ok(CryptoSwapState {
+            variant,
+            balances,
+            precisions: self.rates.iter().map(|rate| rate / PRECISION).collect(),
+            price_scale,
+            a: self.read_u256(db, env, address, ICurveCryptoSwapState::ACall {})?,
+            gamma: self.read_u256(db, env, address, ICurveCryptoSwapState::gammaCall {})?,
+            d: self.read_u256(db, env, address, ICurveCryptoSwapState::DCall {})?,
+            mid_fee: self.read_u256(db, env, address, ICurveCryptoSwapState::mid_feeCall {})?,
+            out_fee: self.read_u256(db, env, address, ICurveCryptoSwapState::out_feeCall {})?,
+            fee_gamma: self.read_u256(db, env, address, ICurveCryptoSwapState::fee_gammaCall {})?,
+            recalc_d,
+        })
+    }
+
+    /// Quotes with native invariant math on parameters read from pool storage, returns value of the view function of the pool
+    fn calculate_out_amount_native(
+        &self,
+        db: &dyn DatabaseRef<Error = ErrReport>,
+        env: &Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64)> {
+        match self.invariant()? {
+            CurveInvariant::StableSwap(variant) => {
+                if self.is_meta {
+                    let state = self.meta_stable_swap_state(db, env, variant)?;
+                    match (self.get_coin_idx(*token_address_from), self.get_coin_idx(*token_address_to)) {
+                        (Ok(i), Ok(j)) => Ok((state.meta.get_dy(i as usize, j as usize, in_amount)?, STABLE_SWAP_GAS_USED)),
+                        _ => {
+                            let i = self.get_meta_coin_idx(*token_address_from)? as usize;
+                            let j = self.get_meta_coin_idx(*token_address_to)? as usize;
+                            Ok((state.get_dy_underlying(i, j, in_amount)?, META_SWAP_GAS_USED))
+                        }
+                    }
+                } else {
+                    let mut state = self.stable_swap_state(db, env, self.address, &self.rates, variant)?;
+                    match self.lp_token {
+                        Some(lp_token) if *token_address_from == lp_token => {
+                            state.total_supply = self.read_u256(db, env, lp_token, IERC20::totalSupplyCall {})?;
+                            let i = self.get_coin_idx(*token_address_to)? as usize;
+                            Ok((state.calc_withdraw_one_coin(in_amount, i)?, LIQUIDITY_GAS_USED))
+                        }
+                        Some(lp_token) if *token_address_to == lp_token => {
+                            state.total_supply = self.read_u256(db, env, lp_token, IERC20::totalSupplyCall {})?;
+                            let mut amounts = vec![U256::ZERO; self.tokens.len()];
+                            amounts[self.get_coin_idx(*token_address_from)? as usize] = in_amount;
+                            Ok((state.calc_token_amount(&amounts, true)?, LIQUIDITY_GAS_USED))
+                        }
+                        _ => {
+                            let i = self.get_coin_idx(*token_address_from)? as usize;
+                            let j = self.get_coin_idx(*token_address_to)? as usize;
+                            Ok((state.get_dy(i, j, in_amount)?, STABLE_SWAP_GAS_USED))
+                        }
+                    }
+                }
+            }
+            CurveInvariant::CryptoSwap(variant) => {
+                let i = self.get_coin_idx(*token_address_from)? as usize;
+                let j = self.get_coin_idx(*token_address_to)? as usize;
+                let state = self.crypto_swap_state(db, env, variant)?;
+                Ok((state.get_dy(i, j, in_amount)?, CRYPTO_SWAP_GAS_USED))
+            }
+        }
+    }
+
+    /// Quotes calling view function of the pool in EVM
+    fn calculate_out_amount_evm(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64)> {
+        let mut env = env;
+        env.tx.gas_limit = 500_000;
+
+        let call_data = if self.is_meta {
+            let i: Result<u32> = self.get_coin_idx(*token_address_from);
+            let j: Result<u32> = self.get_coin_idx(*token_address_to);
+            if i.is_ok() && j.is_ok() {
+                self.pool_contract.get_dy_call_data(i.unwrap(), j.unwrap(), in_amount)?
+            } else {
+                let i: u32 = self.get_meta_coin_idx(*token_address_from)?;
+                let j: u32 = self.get_meta_coin_idx(*token_address_to)?;
+                self.pool_contract.get_dy_underlying_call_data(i, j, in_amount)?
+            }
+        } else if let Some(lp_token) = self.lp_token {
+            if *token_address_from == lp_token {
+                let i: u32 = self.get_coin_idx(*token_address_to)?;
+                self.pool_contract.calc_withdraw_one_coin_call_data(i, in_amount)?
+            } else if *token_address_to == lp_token {
+                let i: u32 = self.get_coin_idx(*token_address_from)?;
+                self.pool_contract.calc_token_amount_call_data(i, in_amount)?
+            } else {
+                let i: u32 = self.get_coin_idx(*token_address_from)?;
+                let j: u32 = self.get_coin_idx(*token_address_to)?;
+                self.pool_contract.get_dy_call_data(i, j, in_amount)?
+            }
+        } else {
+            let i: u32 = self.get_coin_idx(*token_address_from)?;
+            let j: u32 = self.get_coin_idx(*token_address_to)?;
+            self.pool_contract.get_dy_call_data(i, j, in_amount)?
+        };
+
+        let (value, gas_used) = evm_call(state_db, env, self.get_address(), call_data.to_vec())?;
+
+        let ret = if value.len() > 32 { U256::from_be_slice(&value[0..32]) } else { U256::from_be_slice(&value[0..]) };
+        Ok((ret, gas_used))
+    }
+
     pub async fn fetch_pool_data(client: P, pool_contract: CurveContract<P, N>) -> Result<Self> {
         let pool_contract = Arc::new(pool_contract);
 
@@ -136,9 +389,14 @@ where
             CurveContract::I128_2ToMeta(_interface) => (CurveProtocol::<P, N>::get_underlying_tokens(tokens[1])?, true),
             _ => (vec![], false),
         };
+        let base_pool = if is_meta { CurveProtocol::<P, N>::get_base_pool(tokens[1]).ok() } else { None };
 
         let balances = CurveCommonContract::balances(client.clone(), pool_contract.get_address()).await?;
 
+        // native math is used only when decimals of all coins are known
+        let rates = fetch_rate_multipliers::<P, N>(client.clone(), &tokens).await.unwrap_or_default();
+        let base_rates = fetch_rate_multipliers::<P, N>(client.clone(), &underlying_tokens).await.unwrap_or_default();
+
         // let abi_encoder = Arc::new(CurveAbiSwapEncoder::new(
         //     pool_contract.get_address(),
         //     tokens.clone(),
@@ -159,6 +417,10 @@ where
             lp_token,
             is_meta,
             is_native,
+            rates,
+            base_pool,
+            base_rates,
+            state_reader: Arc::new(CurveStateReader::new()),
         })
     }
 }
@@ -191,9 +453,14 @@ where
             CurveContract::I128_2ToMeta(_interface) => (CurveProtocol::<P, N>::get_underlying_tokens(tokens[1])?, true),
             _ => (vec![], false),
         };
+        let base_pool = if is_meta { CurveProtocol::<P, N>::get_base_pool(tokens[1]).ok() } else { None };
 
         let balances = CurveCommonContract::balances(client.clone(), pool_contract.get_address()).await?;
 
+        // native math is used only when decimals of all coins are known
+        let rates = fetch_rate_multipliers::<P, N>(client.clone(), &tokens).await.unwrap_or_default();
+        let base_rates = fetch_rate_multipliers::<P, N>(client.clone(), &underlying_tokens).await.unwrap_or_default();
+
         let mut pool = CurvePool {
             address: pool_contract.get_address(),
             abi_encoder: None,
@@ -204,6 +471,10 @@ where
             lp_token,
             is_meta,
             is_native,
+            rates,
+            base_pool,
+            base_rates,
+            state_reader: Arc::new(CurveStateReader::new()),
         };
 
         let abi_encoder = Arc::new(CurvePoolAbiEncoder::new(&pool));
@@ -281,41 +552,14 @@ where
         token_address_to: &Address,
         in_amount: U256,
     ) -> Result<(U256, u64)> {
-        let mut env = env;
-        env.tx.gas_limit = 500_000;
-
-        let call_data = if self.is_meta {
-            let i: Result<u32> = self.get_coin_idx(*token_address_from);
-            let j: Result<u32> = self.get_coin_idx(*token_address_to);
-            if i.is_ok() && j.is_ok() {
-                self.pool_contract.get_dy_call_data(i.unwrap(), j.unwrap(), in_amount)?
-            } else {
-                let i: u32 = self.get_meta_coin_idx(*token_address_from)?;
-                let j: u32 = self.get_meta_coin_idx(*token_address_to)?;
-                self.pool_contract.get_dy_underlying_call_data(i, j, in_amount)?
-            }
-        } else if let Some(lp_token) = self.lp_token {
-            if *token_address_from == lp_token {
-                let i: u32 = self.get_coin_idx(*token_address_to)?;
-                self.pool_contract.calc_withdraw_one_coin_call_data(i, in_amount)?
-            } else if *token_address_to == lp_token {
-                let i: u32 = self.get_coin_idx(*token_address_from)?;
-                self.pool_contract.calc_token_amount_call_data(i, in_amount)?
-            } else {
-                let i: u32 = self.get_coin_idx(*token_address_from)?;
-                let j: u32 = self.get_coin_idx(*token_address_to)?;
-                self.pool_contract.get_dy_call_data(i, j, in_amount)?
+        let (ret, gas_used) = match self.calculate_out_amount_native(state_db, &env, token_address_from, token_address_to, in_amount) {
+            Ok(ret) => ret,
+            Err(e) => {
+                trace!(address = %self.address, error = %e, "Native curve math failed, falling back to evm call");
+                self.calculate_out_amount_evm(state_db, env, token_address_from, token_address_to, in_amount)?
             }
-        } else {
-            let i: u32 = self.get_coin_idx(*token_address_from)?;
-            let j: u32 = self.get_coin_idx(*token_address_to)?;
-            self.pool_contract.get_dy_call_data(i, j, in_amount)?
         };
 
-        let (value, gas_used) = evm_call(state_db, env, self.get_address(), call_data.to_vec())?;
-
-        let ret = if value.len() > 32 { U256::from_be_slice(&value[0..32]) } else { U256::from_be_slice(&value[0..]) };
-
         if ret.is_zero() {
             Err(eyre!("ZERO_OUT_AMOUNT"))
         } else {
@@ -594,9 +838,9 @@ where
 mod tests {
     use eyre::Result;
 
-    use alloy::primitives::U256;
+    use alloy::primitives::{Address, U256};
     use alloy::providers::network::primitives::BlockTransactionsKind;
-    use alloy::providers::Provider;
+    use alloy::providers::{Network, Provider};
     use alloy::rpc::types::BlockNumberOrTag;
     use env_logger::Env as EnvLog;
     use loom_evm_db::{DatabaseLoomExt, LoomDBType};
@@ -605,9 +849,18 @@ mod tests {
     use loom_types_entities::{MarketState, Pool};
     use tracing::debug;
 
+    use super::CurveInvariant;
     use crate::protocols::CurveProtocol;
     use crate::CurvePool;
 
+    fn assert_quote_matches<P, N>(pool: &CurvePool<P, N>, native: U256, evm: U256)
+    where
+        N: Network,
+        P: Provider<N> + Send + Sync + Clone + 'static,
+    {
+        assert_eq!(native, evm, "{} {:?} native={} evm={}", pool.get_address(), pool.invariant().unwrap(), native, evm);
+    }
+
     #[tokio::test]
     async fn test_pool() -> Result<()> {
         let _ = env_logger::try_init_from_env(EnvLog::default().default_filter_or("info,alloy_rpc_client=off"));
@@ -742,4 +995,67 @@ mod tests {
         }
         Ok(())
     }
+    #[tokio::test]
+    async fn test_native_math() -> Result<()> {
+        let _ = env_logger::try_init_from_env(EnvLog::default().default_filter_or("info,alloy_rpc_client=off"));
+
+        let node_url = std::env::var("MAINNET_WS")?;
+
+        let client = AnvilDebugProviderFactory::from_node_on_block(node_url, 20045799).await?;
+
+        let mut market_state = MarketState::new(LoomDBType::new());
+
+        let block_header = client.get_block_by_number(BlockNumberOrTag::Latest, BlockTransactionsKind::Hashes).await?.unwrap().header;
+        let mut evm_env = revm::primitives::Env::default();
+        evm_env.block.number = U256::from(block_header.number);
+        evm_env.block.timestamp = U256::from(block_header.timestamp);
+
+        for curve_contract in CurveProtocol::get_contracts_vec(client.clone()).into_iter() {
+            let pool = CurvePool::fetch_pool_data_with_default_encoder(client.clone(), curve_contract).await?;
+            let state_required = pool.get_state_required()?;
+            let state_required = RequiredStateReader::fetch_calls_and_slots(client.clone(), state_required, None).await?;
+            market_state.state_db.apply_geth_update(state_required);
+
+            let mut directions: Vec<(Address, Address, U256)> = Vec::new();
+            for i in 0..pool.tokens.len() {
+                for j in 0..pool.tokens.len() {
+                    if i != j {
+                        for divider in [10000u64, 100, 10] {
+                            directions.push((pool.tokens[i], pool.tokens[j], pool.balances[i] / U256::from(divider)));
+                        }
+                    }
+                }
+                if let Some(lp_token) = pool.lp_token {
+                    directions.push((pool.tokens[i], lp_token, pool.balances[i] / U256::from(1000)));
+                }
+            }
+
+            for (token_from, token_to, in_amount) in directions {
+                let (native_amount, _) =
+                    pool.calculate_out_amount_native(&market_state.state_db, &evm_env, &token_from, &token_to, in_amount).unwrap();
+                let (evm_amount, _) =
+                    pool.calculate_out_amount_evm(&market_state.state_db, evm_env.clone(), &token_from, &token_to, in_amount).unwrap();
+                debug!(
+                    "Native {} {} -> {} : {} -> {} evm : {}",
+                    pool.get_address(),
+                    token_from,
+                    token_to,
+                    in_amount,
+                    native_amount,
+                    evm_amount
+                );
+                assert_quote_matches(&pool, native_amount, evm_amount);
+
+                // burning received LP back to coin
+                if Some(token_to) == pool.lp_token {
+                    let (native_amount, _) =
+                        pool.calculate_out_amount_native(&market_state.state_db, &evm_env, &token_to, &token_from, evm_amount).unwrap();
+                    let (evm_amount, _) =
+                        pool.calculate_out_amount_evm(&market_state.state_db, evm_env.clone(), &token_to, &token_from, evm_amount).unwrap();
+                    assert_quote_matches(&pool, native_amount, evm_amount);
+                }
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,176 @@
+use std::ops::{Shl, Shr};
+
+use alloy::primitives::{keccak256, Address, Signed, Uint, I256, U256};
+use eyre::Result;
+use lazy_static::lazy_static;
+use revm::DatabaseRef;
+use tracing::trace;
+
+use loom_evm_utils::remv_db_direct_access::{try_read_cell, try_read_hashmap_cell};
+
+/// Direct storage access to Maverick V1 pools
+pub struct MaverickDBReader {}
+
+pub const STATE_SLOT: u64 = 0;
+pub const BINS_SLOT: u64 = 3;
+pub const BIN_POSITIONS_SLOT: u64 = 4;
+pub const BIN_MAP_SLOT: u64 = 5;
+
+/// Bin kinds: static, right, left, both
+pub const NUMBER_OF_KINDS: i32 = 4;
+
+lazy_static! {
+    static ref BITS128MASK: U256 = U256::from(1).shl(128) - U256::from(1);
+    static ref BITS64MASK: U256 = U256::from(1).shl(64) - U256::from(1);
+    static ref BITS32MASK: U256 = U256::from(1).shl(32) - U256::from(1);
+    static ref BITS8MASK: U256 = U256::from(1).shl(8) - U256::from(1);
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaverickState {
+    pub active_tick: i32,
+    pub status: u8,
+    pub bin_counter: u128,
+    pub protocol_fee_ratio: u64,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct MaverickBinState {
+    pub reserve_a: u128,
+    pub reserve_b: u128,
+    pub merge_bin_balance: u128,
+    pub merge_id: u128,
+    pub total_supply: u128,
+    pub kind: u8,
+    pub lower_tick: i32,
+}
+
+impl MaverickDBReader {
+    fn int_key(value: i32) -> Result<U256> {
+        Ok(U256::from_be_bytes(I256::try_from(value)?.to_be_bytes::<32>()))
+    }
+
+    fn int32(cell: U256) -> i32 {
+        let value: Uint<32, 1> = (cell & *BITS32MASK).to();
+        Signed::<32, 1>::from_raw(value).as_i32()
+    }
+
+    pub fn state<DB: DatabaseRef>(db: &DB, address: Address) -> Result<MaverickState> {
+        let cell = try_read_cell(db, &address, &U256::from(STATE_SLOT))?;
+
+        Ok(MaverickState {
+            active_tick: Self::int32(cell),
+            status: (Shr::<U256>::shr(cell, U256::from(32)) & *BITS8MASK).to(),
+            bin_counter: (Shr::<U256>::shr(cell, U256::from(32 + 8)) & *BITS128MASK).to(),
+            protocol_fee_ratio: (Shr::<U256>::shr(cell, U256::from(32 + 8 + 128)) & *BITS64MASK).to(),
+        })
+    }
+
+    /// Bitmap word of `binMap`, each tick has `NUMBER_OF_KINDS` bits, one per bin kind
+    pub fn bin_map<DB: DatabaseRef>(db: &DB, address: Address, word: i32) -> Result<U256> {
+        let cell = try_read_hashmap_cell(db, &address, &U256::from(BIN_MAP_SLOT), &Self::int_key(word)?)?;
+        trace!("binMap {address} {word} {cell}");
+        Ok(cell)
+    }
+
+    /// Id of the bin of `kind` at `tick`, zero if there is no bin
+    pub fn bin_positions<DB: DatabaseRef>(db: &DB, address: Address, tick: i32, kind: u8) -> Result<u128> {
+        let mut buf = Self::int_key(tick)?.to_be_bytes::<32>().to_vec();
+        buf.extend_from_slice(&U256::from(BIN_POSITIONS_SLOT).to_be_bytes::<32>());
+        let tick_mapping_cell: U256 = keccak256(buf).into();
+
+        let cell = try_read_hashmap_cell(db, &address, &tick_mapping_cell, &U256::from(kind))?;
+        Ok((cell & *BITS128MASK).to())
+    }
+
+    pub fn bin<DB: DatabaseRef>(db: &DB, address: Address, bin_id: u128) -> Result<MaverickBinState> {
+        let mut buf = U256::from(bin_id).to_be_bytes::<32>().to_vec();
+        buf.extend_from_slice(&U256::from(BINS_SLOT).to_be_bytes::<32>());
+        let bin_cell: U256 = keccak256(buf).into();
+
+        let reserves = try_read_cell(db, &address, &bin_cell)?;
+        let merge = try_read_cell(db, &address, &(bin_cell + U256::from(1)))?;
+        let supply = try_read_cell(db, &address, &(bin_cell + U256::from(2)))?;
+
+        Ok(MaverickBinState {
+            reserve_a: (reserves & *BITS128MASK).to(),
+            reserve_b: Shr::<U256>::shr(reserves, U256::from(128)).to(),
+            merge_bin_balance: (merge & *BITS128MASK).to(),
+            merge_id: Shr::<U256>::shr(merge, U256::from(128)).to(),
+            total_supply: (supply & *BITS128MASK).to(),
+            kind: (Shr::<U256>::shr(supply, U256::from(128)) & *BITS8MASK).to(),
+            lower_tick: Self::int32(Shr::<U256>::shr(supply, U256::from(128 + 8))),
+        })
+    }
+
+    /// Sum of reserves of all bins at `tick`
+    pub fn tick_reserves<DB: DatabaseRef>(db: &DB, address: Address, tick: i32) -> Result<(U256, U256)> {
+        let mut reserve_a = U256::ZERO;
+        let mut reserve_b = U256::ZERO;
+        for kind in 0..NUMBER_OF_KINDS as u8 {
+            let bin_id = Self::bin_positions(db, address, tick, kind)?;
+            if bin_id != 0 {
+                let bin = Self::bin(db, address, bin_id)?;
+                reserve_a += U256::from(bin.reserve_a);
+                reserve_b += U256::from(bin.reserve_b);
+            }
+        }
+        Ok((reserve_a, reserve_b))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy::primitives::{Address, U256};
+    use eyre::Result;
+    use std::env;
+
+    use loom_defi_abi::maverick::IMaverickPool::IMaverickPoolInstance;
+    use loom_evm_db::LoomDBType;
+    use loom_node_debug_provider::AnvilDebugProviderFactory;
+    use loom_types_entities::required_state::RequiredStateReader;
+    use loom_types_entities::{MarketState, Pool};
+
+    use crate::db_reader::{MaverickDBReader, NUMBER_OF_KINDS};
+    use crate::MaverickPool;
+
+    #[tokio::test]
+    async fn test_reader() -> Result<()> {
+        let node_url = env::var("MAINNET_WS")?;
+
+        let client = AnvilDebugProviderFactory::from_node_on_block(node_url, 20045799).await?;
+
+        let pool_address: Address = "0x352B186090068Eb35d532428676cE510E17AB581".parse()?;
+        let pool = MaverickPool::fetch_pool_data(client.clone(), pool_address).await?;
+        let pool_contract = IMaverickPoolInstance::new(pool_address, client.clone());
+
+        let state_required = RequiredStateReader::fetch_calls_and_slots(client.clone(), pool.get_state_required()?, None).await?;
+        let mut market_state = MarketState::new(LoomDBType::default());
+        market_state.state_db.apply_geth_update(state_required);
+
+        let state_evm = pool_contract.getState().call().await?._0;
+        let state_db = MaverickDBReader::state(&market_state.state_db, pool_address)?;
+        assert_eq!(state_db.active_tick, state_evm.activeTick);
+        assert_eq!(state_db.bin_counter, state_evm.binCounter);
+        assert_eq!(state_db.protocol_fee_ratio, state_evm.protocolFeeRatio);
+
+        let tick = state_db.active_tick;
+        let word = (tick * NUMBER_OF_KINDS) >> 8;
+        assert_eq!(MaverickDBReader::bin_map(&market_state.state_db, pool_address, word)?, pool_contract.binMap(word).call().await?._0);
+
+        for kind in 0..NUMBER_OF_KINDS as u8 {
+            let bin_id_evm = pool_contract.binPositions(tick, U256::from(kind)).call().await?._0;
+            let bin_id_db = MaverickDBReader::bin_positions(&market_state.state_db, pool_address, tick, kind)?;
+            assert_eq!(bin_id_db, bin_id_evm);
+            if bin_id_db != 0 {
+                let bin_evm = pool_contract.getBin(bin_id_evm).call().await?.bin;
+                let bin_db = MaverickDBReader::bin(&market_state.state_db, pool_address, bin_id_db)?;
+                assert_eq!(bin_db.reserve_a, bin_evm.reserveA);
+                assert_eq!(bin_db.reserve_b, bin_evm.reserveB);
+                assert_eq!(bin_db.lower_tick, bin_evm.lowerTick);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,3 +1,9 @@
+pub use maverick::{MaverickBinState, MaverickDBReader, MaverickState, NUMBER_OF_KINDS};
+pub use pancakev3::PancakeV3DBReader;
 pub use uniswapv3::UniswapV3DBReader;
+pub use uniswapv4::{UniswapV4DBReader, UniswapV4Slot0, LIQUIDITY_OFFSET, POOLS_SLOT, TICKS_OFFSET, TICK_BITMAP_OFFSET};
 
+mod maverick;
+mod pancakev3;
 mod uniswapv3;
+mod uniswapv4;
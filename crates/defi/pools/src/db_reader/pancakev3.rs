@@ -0,0 +1,108 @@
+use std::ops::{BitAnd, Shl, Shr};
+
+use alloy::primitives::{Address, Signed, Uint, I256};
+use alloy::primitives::{U160, U256};
+use eyre::Result;
+use lazy_static::lazy_static;
+use revm::DatabaseRef;
+use tracing::trace;
+
+use loom_defi_abi::pancake::IPancakeV3Pool::slot0Return;
+use loom_evm_utils::remv_db_direct_access::{try_read_cell, try_read_hashmap_cell};
+
+/// Direct storage access to PancakeV3 pools. Layout follows UniswapV3 but `feeProtocol` is uint32 and does not fit into
+/// the first slot, so `feeProtocol` and `unlocked` are stored in slot 1 and the rest of the storage is shifted by one slot.
+pub struct PancakeV3DBReader {}
+
+pub const SLOT0_SLOT: u64 = 0;
+pub const SLOT0_FEE_PROTOCOL_SLOT: u64 = 1;
+pub const LIQUIDITY_SLOT: u64 = 5;
+pub const TICKS_SLOT: u64 = 6;
+pub const TICK_BITMAP_SLOT: u64 = 7;
+
+lazy_static! {
+    static ref BITS160MASK: U256 = U256::from(1).shl(160) - U256::from(1);
+    static ref BITS32MASK: U256 = U256::from(1).shl(32) - U256::from(1);
+    static ref BITS24MASK: U256 = U256::from(1).shl(24) - U256::from(1);
+    static ref BITS16MASK: U256 = U256::from(1).shl(16) - U256::from(1);
+    static ref BITS1MASK: U256 = U256::from(1);
+}
+
+impl PancakeV3DBReader {
+    pub fn liquidity<DB: DatabaseRef>(db: &DB, address: Address) -> Result<u128> {
+        let cell = try_read_cell(&db, &address, &U256::from(LIQUIDITY_SLOT))?;
+        let cell: u128 = cell.saturating_to();
+        Ok(cell)
+    }
+
+    pub fn ticks_liquidity_net<DB: DatabaseRef>(db: &DB, address: Address, tick: i32) -> Result<i128> {
+        //i24
+        let cell =
+            try_read_hashmap_cell(&db, &address, &U256::from(TICKS_SLOT), &U256::from_be_bytes(I256::try_from(tick)?.to_be_bytes::<32>()))?;
+        let unsigned_liqudity: u128 = cell.shr(U256::from(128)).to();
+        trace!("ticks_liquidity_net {address} {tick} {cell}");
+        Ok(unsigned_liqudity as i128)
+    }
+
+    pub fn tick_bitmap<DB: DatabaseRef>(db: &DB, address: Address, tick: i16) -> Result<U256> {
+        //i16
+        let cell = try_read_hashmap_cell(
+            &db,
+            &address,
+            &U256::from(TICK_BITMAP_SLOT),
+            &U256::from_be_bytes(I256::try_from(tick)?.to_be_bytes::<32>()),
+        )?;
+        trace!("tickBitmap {address} {tick} {cell}");
+        Ok(cell)
+    }
+
+    pub fn slot0<DB: DatabaseRef>(db: &DB, address: Address) -> Result<slot0Return> {
+        let cell = try_read_cell(&db, &address, &U256::from(SLOT0_SLOT))?;
+        let tick: Uint<24, 1> = ((Shr::<U256>::shr(cell, U256::from(160))) & *BITS24MASK).to();
+        let tick: Signed<24, 1> = Signed::<24, 1>::from_raw(tick);
+
+        let sqrt_price_x96: U160 = cell.bitand(*BITS160MASK).to();
+
+        let fee_protocol_cell = try_read_cell(&db, &address, &U256::from(SLOT0_FEE_PROTOCOL_SLOT))?;
+
+        Ok(slot0Return {
+            sqrtPriceX96: sqrt_price_x96,
+            tick,
+            observationIndex: ((Shr::<U256>::shr(cell, U256::from(160 + 24))) & *BITS16MASK).to(),
+            observationCardinality: ((Shr::<U256>::shr(cell, U256::from(160 + 24 + 16))) & *BITS16MASK).to(),
+            observationCardinalityNext: ((Shr::<U256>::shr(cell, U256::from(160 + 24 + 16 + 16))) & *BITS16MASK).to(),
+            feeProtocol: (fee_protocol_cell & *BITS32MASK).to(),
+            unlocked: !((Shr::<U256>::shr(fee_protocol_cell, U256::from(32))) & *BITS1MASK).is_zero(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use loom_evm_db::LoomDBType;
+
+    #[test]
+    fn test_slot0_layout() -> Result<()> {
+        let address = Address::repeat_byte(1);
+        let mut db = LoomDBType::new();
+
+        // sqrtPriceX96 = 2^96, tick = -1, observationIndex = 2, observationCardinality = 3, observationCardinalityNext = 4
+        let cell =
+            U256::from(1).shl(96) | (*BITS24MASK).shl(160) | U256::from(2).shl(184) | U256::from(3).shl(200) | U256::from(4).shl(216);
+        db.insert_account_storage(address, U256::from(SLOT0_SLOT), cell)?;
+        // feeProtocol = 32003200, unlocked = true
+        db.insert_account_storage(address, U256::from(SLOT0_FEE_PROTOCOL_SLOT), U256::from(32003200u32) | U256::from(1).shl(32))?;
+
+        let slot0 = PancakeV3DBReader::slot0(&db, address)?;
+        assert_eq!(slot0.sqrtPriceX96, U160::from(1).shl(96));
+        assert_eq!(slot0.tick.as_i32(), -1);
+        assert_eq!(slot0.observationIndex, 2);
+        assert_eq!(slot0.observationCardinality, 3);
+        assert_eq!(slot0.observationCardinalityNext, 4);
+        assert_eq!(slot0.feeProtocol, 32003200);
+        assert!(slot0.unlocked);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use std::ops::{BitAnd, Shl, Shr};
+
+use alloy::primitives::{keccak256, Address, Signed, Uint, B256, I256, U256};
+use eyre::Result;
+use lazy_static::lazy_static;
+use revm::DatabaseRef;
+use tracing::trace;
+
+use loom_evm_utils::remv_db_direct_access::{try_read_cell, try_read_hashmap_cell};
+
+/// Reads pool state directly from the PoolManager storage, layout matches v4-core StateLibrary
+pub struct UniswapV4DBReader {}
+
+pub const POOLS_SLOT: u64 = 6;
+pub const LIQUIDITY_OFFSET: u64 = 3;
+pub const TICKS_OFFSET: u64 = 4;
+pub const TICK_BITMAP_OFFSET: u64 = 5;
+
+lazy_static! {
+    static ref BITS160MASK: U256 = U256::from(1).shl(160) - U256::from(1);
+    static ref BITS24MASK: U256 = U256::from(1).shl(24) - U256::from(1);
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UniswapV4Slot0 {
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub protocol_fee: u32,
+    pub lp_fee: u32,
+}
+
+impl UniswapV4DBReader {
+    /// Storage cell of `Pool.State` of the pool in `_pools` mapping
+    pub fn pool_state_cell(pool_id: B256) -> U256 {
+        let mut buf = pool_id.to_vec();
+        buf.extend(U256::from(POOLS_SLOT).to_be_bytes::<32>());
+        keccak256(buf).into()
+    }
+
+    pub fn slot0<DB: DatabaseRef>(db: &DB, pool_manager: Address, pool_id: B256) -> Result<UniswapV4Slot0> {
+        let cell = try_read_cell(db, &pool_manager, &Self::pool_state_cell(pool_id))?;
+
+        let tick: Uint<24, 1> = (Shr::<U256>::shr(cell, U256::from(160)) & *BITS24MASK).to();
+        let tick: i32 = Signed::<24, 1>::from_raw(tick).as_i32();
+
+        Ok(UniswapV4Slot0 {
+            sqrt_price_x96: cell.bitand(*BITS160MASK),
+            tick,
+            protocol_fee: (Shr::<U256>::shr(cell, U256::from(160 + 24)) & *BITS24MASK).to(),
+            lp_fee: (Shr::<U256>::shr(cell, U256::from(160 + 24 + 24)) & *BITS24MASK).to(),
+        })
+    }
+
+    pub fn liquidity<DB: DatabaseRef>(db: &DB, pool_manager: Address, pool_id: B256) -> Result<u128> {
+        let cell = try_read_cell(db, &pool_manager, &(Self::pool_state_cell(pool_id) + U256::from(LIQUIDITY_OFFSET)))?;
+        Ok(cell.saturating_to())
+    }
+
+    pub fn ticks_liquidity_net<DB: DatabaseRef>(db: &DB, pool_manager: Address, pool_id: B256, tick: i32) -> Result<i128> {
+        let ticks_cell = Self::pool_state_cell(pool_id) + U256::from(TICKS_OFFSET);
+        let cell = try_read_hashmap_cell(db, &pool_manager, &ticks_cell, &U256::from_be_bytes(I256::try_from(tick)?.to_be_bytes::<32>()))?;
+        let liquidity_net: u128 = cell.shr(U256::from(128)).to();
+        trace!("ticks_liquidity_net {pool_id} {tick} {cell}");
+        Ok(liquidity_net as i128)
+    }
+
+    pub fn tick_bitmap<DB: DatabaseRef>(db: &DB, pool_manager: Address, pool_id: B256, tick: i16) -> Result<U256> {
+        let tick_bitmap_cell = Self::pool_state_cell(pool_id) + U256::from(TICK_BITMAP_OFFSET);
+        let cell =
+            try_read_hashmap_cell(db, &pool_manager, &tick_bitmap_cell, &U256::from_be_bytes(I256::try_from(tick)?.to_be_bytes::<32>()))?;
+        trace!("tickBitmap {pool_id} {tick} {cell}");
+        Ok(cell)
+    }
+}
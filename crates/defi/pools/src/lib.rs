@@ -1,5 +1,6 @@
 extern crate core;
 
+pub use balancerv2pool::{BalancerV2AbiSwapEncoder, BalancerV2StablePool, BalancerV2WeightedPool};
 pub use curvepool::{CurvePool, CurvePoolAbiEncoder};
 pub use loaders::*;
 pub use loom_types_entities::pool_config::PoolsLoadingConfig;
@@ -7,12 +8,15 @@ pub use maverickpool::MaverickPool;
 pub use pancakev3pool::PancakeV3Pool;
 pub use uniswapv2pool::UniswapV2Pool;
 pub use uniswapv3pool::{Slot0, UniswapV3Pool};
+pub use uniswapv4pool::{UniswapV4Pool, UniswapV4PoolKey};
 
+mod balancerv2pool;
 pub mod db_reader;
 mod maverickpool;
 pub mod state_readers;
 mod uniswapv2pool;
 mod uniswapv3pool;
+mod uniswapv4pool;
 
 mod curvepool;
 pub mod protocols;
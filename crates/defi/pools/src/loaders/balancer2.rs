@@ -0,0 +1,155 @@
+use crate::{pool_loader, BalancerV2StablePool, BalancerV2WeightedPool};
+use alloy::primitives::Log as EVMLog;
+use alloy::primitives::{Address, Bytes, B256};
+use alloy::providers::network::Ethereum;
+use alloy::rpc::types::Filter;
+use alloy::sol_types::{SolEvent, SolEventInterface};
+use async_stream::stream;
+use eyre::{eyre, ErrReport};
+use futures::Stream;
+use loom_defi_abi::balancer::IVault::{IVaultEvents, PoolRegistered};
+use loom_defi_address_book::FactoryAddress;
+use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
+use loom_types_entities::{PoolClass, PoolId, PoolLoader, PoolWrapper};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tracing::{debug, error};
+
+/// Block of Vault deployment on mainnet
+const VAULT_DEPLOYMENT_BLOCK: u64 = 12_272_146;
+const LOGS_BLOCK_RANGE: u64 = 10_000;
+
+pool_loader!(BalancerV2PoolLoader);
+
+impl<P> BalancerV2PoolLoader<P, Ethereum, LoomDataTypesEthereum>
+where
+    P: Provider<Ethereum> + Clone + 'static,
+{
+    /// Balancer pool id starts with the pool address
+    pub fn pool_address_from_id(pool_id: B256) -> Address {
+        Address::from_slice(&pool_id[0..20])
+    }
+}
+
+impl<P> PoolLoader<P, Ethereum, LoomDataTypesEthereum> for BalancerV2PoolLoader<P, Ethereum, LoomDataTypesEthereum>
+where
+    P: Provider<Ethereum> + Clone + 'static,
+{
+    fn get_pool_class_by_log(
+        &self,
+        log_entry: &<LoomDataTypesEthereum as LoomDataTypes>::Log,
+    ) -> Option<(PoolId<LoomDataTypesEthereum>, PoolClass)> {
+        if log_entry.address() != FactoryAddress::BALANCER_V2_VAULT {
+            return None;
+        }
+        let log_entry: Option<EVMLog> = EVMLog::new(log_entry.address(), log_entry.topics().to_vec(), log_entry.data().data.clone());
+        match log_entry {
+            Some(log_entry) => match IVaultEvents::decode_log(&log_entry, false) {
+                Ok(event) => match event.data {
+                    IVaultEvents::PoolRegistered(event) => Some((PoolId::Address(event.poolAddress), PoolClass::BalancerV2)),
+                    IVaultEvents::Swap(event) => Some((PoolId::Address(Self::pool_address_from_id(event.poolId)), PoolClass::BalancerV2)),
+                    IVaultEvents::PoolBalanceChanged(event) => {
+                        Some((PoolId::Address(Self::pool_address_from_id(event.poolId)), PoolClass::BalancerV2))
+                    }
+                    _ => None,
+                },
+                Err(_) => None,
+            },
+            None => None,
+        }
+    }
+
+    fn fetch_pool_by_id<'a>(
+        &'a self,
+        pool_id: PoolId<LoomDataTypesEthereum>,
+    ) -> Pin<Box<dyn Future<Output = eyre::Result<PoolWrapper<LoomDataTypesEthereum>>> + Send + 'a>> {
+        Box::pin(async move {
+            if let Some(provider) = &self.provider {
+                self.fetch_pool_by_id_from_provider(pool_id, provider.clone()).await
+            } else {
+                Err(eyre!("NO_PROVIDER"))
+            }
+        })
+    }
+
+    fn fetch_pool_by_id_from_provider<'a>(
+        &'a self,
+        pool_id: PoolId<LoomDataTypesEthereum>,
+        provider: P,
+    ) -> Pin<Box<dyn Future<Output = eyre::Result<PoolWrapper<LoomDataTypesEthereum>>> + Send + 'a>> {
+        Box::pin(async move {
+            let pool_address = pool_id.address()?;
+
+            // pool type is detected by the interface it responds to
+            if let Ok(pool) = BalancerV2WeightedPool::fetch_pool_data(provider.clone(), pool_address).await {
+                return Ok(PoolWrapper::new(Arc::new(pool)));
+            }
+            match BalancerV2StablePool::fetch_pool_data(provider, pool_address).await {
+                Ok(pool) => Ok(PoolWrapper::new(Arc::new(pool))),
+                Err(e) => {
+                    debug!("Balancer pool {} is not weighted or stable : {}", pool_address, e);
+                    Err(eyre!("POOL_TYPE_NOT_SUPPORTED"))
+                }
+            }
+        })
+    }
+
+    fn fetch_pool_by_id_from_evm(
+        &self,
+        _pool_id: PoolId<LoomDataTypesEthereum>,
+        _db: &dyn DatabaseRef<Error = ErrReport>,
+        _env: Env,
+    ) -> eyre::Result<PoolWrapper<LoomDataTypesEthereum>> {
+        Err(eyre!("NOT_IMPLEMENTED"))
+    }
+
+    fn is_code(&self, _code: &Bytes) -> bool {
+        false
+    }
+
+    fn protocol_loader(&self) -> eyre::Result<Pin<Box<dyn Stream<Item = (PoolId, PoolClass)> + Send>>> {
+        let provider_clone = self.provider.clone();
+
+        if let Some(client) = provider_clone {
+            Ok(Box::pin(stream! {
+                let last_block = match client.get_block_number().await {
+                    Ok(last_block) => last_block,
+                    Err(e) => {
+                        error!("Error fetching block number : {}", e);
+                        return;
+                    }
+                };
+
+                let mut from_block = VAULT_DEPLOYMENT_BLOCK;
+                while from_block <= last_block {
+                    let to_block = (from_block + LOGS_BLOCK_RANGE - 1).min(last_block);
+                    let filter = Filter::new()
+                        .address(FactoryAddress::BALANCER_V2_VAULT)
+                        .event_signature(PoolRegistered::SIGNATURE_HASH)
+                        .from_block(from_block)
+                        .to_block(to_block);
+
+                    match client.get_logs(&filter).await {
+                        Ok(logs) => {
+                            for log in logs {
+                                if let Ok(event) = PoolRegistered::decode_log(&log.inner, false) {
+                                    yield (PoolId::Address(event.data.poolAddress), PoolClass::BalancerV2)
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error fetching PoolRegistered logs from {} to {} : {}", from_block, to_block, e);
+                        }
+                    }
+                    debug!("BalancerV2 PoolRegistered logs loaded up to block {}", to_block);
+                    from_block = to_block + 1;
+                }
+            }))
+        } else {
+            Err(eyre!("NO_PROVIDER"))
+        }
+    }
+}
@@ -1,17 +1,21 @@
+mod balancer2;
 mod curve;
 mod maverick;
 mod uniswap2;
 mod uniswap3;
+mod uniswap4;
 
 use crate::loaders::curve::CurvePoolLoader;
 use alloy::providers::network::Ethereum;
 use alloy::providers::{Network, Provider, RootProvider};
+pub use balancer2::BalancerV2PoolLoader;
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
 use loom_types_entities::pool_config::PoolsLoadingConfig;
 use loom_types_entities::{PoolClass, PoolLoader, PoolLoaders};
 pub use maverick::MaverickPoolLoader;
 pub use uniswap2::UniswapV2PoolLoader;
 pub use uniswap3::UniswapV3PoolLoader;
+pub use uniswap4::UniswapV4PoolLoader;
 
 /// creates  pool loader and imports necessary crates
 #[macro_export]
@@ -123,7 +127,9 @@ where
             .add_loader(PoolClass::Maverick, MaverickPoolLoader::with_provider(provider.clone()))
             .add_loader(PoolClass::UniswapV2, UniswapV2PoolLoader::with_provider(provider.clone()))
             .add_loader(PoolClass::UniswapV3, UniswapV3PoolLoader::with_provider(provider.clone()))
+            .add_loader(PoolClass::UniswapV4, UniswapV4PoolLoader::with_provider(provider.clone()))
             .add_loader(PoolClass::Curve, CurvePoolLoader::with_provider(provider.clone()))
+            .add_loader(PoolClass::BalancerV2, BalancerV2PoolLoader::with_provider(provider.clone()))
             .build();
 
         pool_loader
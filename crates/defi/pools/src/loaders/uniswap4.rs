@@ -0,0 +1,220 @@
+use crate::{pool_loader, UniswapV4Pool, UniswapV4PoolKey};
+use alloy::primitives::Log as EVMLog;
+use alloy::primitives::{Bytes, B256};
+use alloy::providers::network::Ethereum;
+use alloy::rpc::types::Filter;
+use alloy::sol_types::{SolEvent, SolEventInterface};
+use async_stream::stream;
+use eyre::{eyre, ErrReport};
+use futures::Stream;
+use lazy_static::lazy_static;
+use loom_defi_abi::uniswap4::IUniswapV4PoolManagerEvents::{IUniswapV4PoolManagerEventsEvents, Initialize};
+use loom_defi_address_book::FactoryAddress;
+use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
+use loom_types_entities::{PoolClass, PoolId, PoolLoader, PoolWrapper};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use tracing::{debug, error};
+
+/// Block of PoolManager deployment on mainnet
+const POOL_MANAGER_DEPLOYMENT_BLOCK: u64 = 21_688_329;
+const LOGS_BLOCK_RANGE: u64 = 10_000;
+
+lazy_static! {
+    /// Pool keys by pool id taken from `Initialize` events, `None` if pool was not initialized by the PoolManager.
+    /// Keeps Swap and ModifyLiquidity events of known unsupported pools from triggering full history log requests
+    static ref POOL_KEYS: RwLock<HashMap<B256, Option<UniswapV4PoolKey>>> = RwLock::new(HashMap::new());
+}
+
+pool_loader!(UniswapV4PoolLoader);
+
+impl<P> UniswapV4PoolLoader<P, Ethereum, LoomDataTypesEthereum>
+where
+    P: Provider<Ethereum> + Clone + 'static,
+{
+    /// Pool key is not stored by the PoolManager, so it is taken from the `Initialize` event
+    pub fn pool_key_from_event(event: &Initialize) -> UniswapV4PoolKey {
+        UniswapV4PoolKey::new(event.currency0, event.currency1, event.fee.to(), event.tickSpacing.as_i32(), event.hooks)
+    }
+
+    /// Pools with hooks changing swap amounts or dynamic fee cannot be calculated and are skipped
+    pub fn is_pool_key_supported(key: &UniswapV4PoolKey) -> bool {
+        key.is_swap_supported() && !key.is_native()
+    }
+
+    fn cached_pool_key(pool_id: &B256) -> Option<Option<UniswapV4PoolKey>> {
+        POOL_KEYS.read().ok().and_then(|pool_keys| pool_keys.get(pool_id).copied())
+    }
+
+    fn cache_pool_key(pool_id: B256, key: Option<UniswapV4PoolKey>) {
+        if let Ok(mut pool_keys) = POOL_KEYS.write() {
+            pool_keys.insert(pool_id, key);
+        }
+    }
+
+    /// Pool id is skipped if its key is known to be unsupported or it was not initialized
+    fn is_pool_id_supported(pool_id: &B256) -> bool {
+        match Self::cached_pool_key(pool_id) {
+            Some(Some(key)) => Self::is_pool_key_supported(&key),
+            Some(None) => false,
+            None => true,
+        }
+    }
+
+    async fn fetch_pool_key(provider: P, pool_id: B256) -> eyre::Result<UniswapV4PoolKey> {
+        if let Some(cached_key) = Self::cached_pool_key(&pool_id) {
+            return cached_key.ok_or_else(|| eyre!("INITIALIZE_EVENT_NOT_FOUND"));
+        }
+
+        let filter = Filter::new()
+            .address(FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS)
+            .event_signature(Initialize::SIGNATURE_HASH)
+            .topic1(pool_id)
+            .from_block(POOL_MANAGER_DEPLOYMENT_BLOCK);
+
+        let logs = provider.get_logs(&filter).await?;
+        let Some(log) = logs.first() else {
+            Self::cache_pool_key(pool_id, None);
+            return Err(eyre!("INITIALIZE_EVENT_NOT_FOUND"));
+        };
+        let event = Initialize::decode_log(&log.inner, false)?;
+        let key = Self::pool_key_from_event(&event.data);
+        Self::cache_pool_key(pool_id, Some(key));
+
+        Ok(key)
+    }
+}
+
+impl<P> PoolLoader<P, Ethereum, LoomDataTypesEthereum> for UniswapV4PoolLoader<P, Ethereum, LoomDataTypesEthereum>
+where
+    P: Provider<Ethereum> + Clone + 'static,
+{
+    fn get_pool_class_by_log(
+        &self,
+        log_entry: &<LoomDataTypesEthereum as LoomDataTypes>::Log,
+    ) -> Option<(PoolId<LoomDataTypesEthereum>, PoolClass)> {
+        if log_entry.address() != FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS {
+            return None;
+        }
+        let log_entry: Option<EVMLog> = EVMLog::new(log_entry.address(), log_entry.topics().to_vec(), log_entry.data().data.clone());
+        match log_entry {
+            Some(log_entry) => match IUniswapV4PoolManagerEventsEvents::decode_log(&log_entry, false) {
+                Ok(event) => match event.data {
+                    IUniswapV4PoolManagerEventsEvents::Initialize(event) => {
+                        let key = Self::pool_key_from_event(&event);
+                        Self::cache_pool_key(event.id, Some(key));
+                        Self::is_pool_key_supported(&key).then_some((PoolId::Bytes32(event.id), PoolClass::UniswapV4))
+                    }
+                    IUniswapV4PoolManagerEventsEvents::Swap(event) => {
+                        Self::is_pool_id_supported(&event.id).then_some((PoolId::Bytes32(event.id), PoolClass::UniswapV4))
+                    }
+                    IUniswapV4PoolManagerEventsEvents::ModifyLiquidity(event) => {
+                        Self::is_pool_id_supported(&event.id).then_some((PoolId::Bytes32(event.id), PoolClass::UniswapV4))
+                    }
+                    _ => None,
+                },
+                Err(_) => None,
+            },
+            None => None,
+        }
+    }
+
+    fn fetch_pool_by_id<'a>(
+        &'a self,
+        pool_id: PoolId<LoomDataTypesEthereum>,
+    ) -> Pin<Box<dyn Future<Output = eyre::Result<PoolWrapper<LoomDataTypesEthereum>>> + Send + 'a>> {
+        Box::pin(async move {
+            if let Some(provider) = &self.provider {
+                self.fetch_pool_by_id_from_provider(pool_id, provider.clone()).await
+            } else {
+                Err(eyre!("NO_PROVIDER"))
+            }
+        })
+    }
+
+    fn fetch_pool_by_id_from_provider<'a>(
+        &'a self,
+        pool_id: PoolId<LoomDataTypesEthereum>,
+        provider: P,
+    ) -> Pin<Box<dyn Future<Output = eyre::Result<PoolWrapper<LoomDataTypesEthereum>>> + Send + 'a>> {
+        Box::pin(async move {
+            let pool_id = pool_id.bytes32()?;
+            let key = Self::fetch_pool_key(provider.clone(), pool_id).await?;
+            if !key.is_swap_supported() {
+                return Err(eyre!("SWAP_HOOKS_NOT_SUPPORTED"));
+            }
+            if key.is_native() {
+                return Err(eyre!("NATIVE_CURRENCY_NOT_SUPPORTED"));
+            }
+
+            Ok(PoolWrapper::new(Arc::new(
+                UniswapV4Pool::fetch_pool_data(provider, FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS, key).await?,
+            )))
+        })
+    }
+
+    fn fetch_pool_by_id_from_evm(
+        &self,
+        _pool_id: PoolId<LoomDataTypesEthereum>,
+        _db: &dyn DatabaseRef<Error = ErrReport>,
+        _env: Env,
+    ) -> eyre::Result<PoolWrapper<LoomDataTypesEthereum>> {
+        // Pool key cannot be restored from the PoolManager storage, use UniswapV4Pool::fetch_pool_data_evm with a known key
+        Err(eyre!("NOT_IMPLEMENTED"))
+    }
+
+    fn is_code(&self, _code: &Bytes) -> bool {
+        false
+    }
+
+    fn protocol_loader(&self) -> eyre::Result<Pin<Box<dyn Stream<Item = (PoolId, PoolClass)> + Send>>> {
+        let provider_clone = self.provider.clone();
+
+        if let Some(client) = provider_clone {
+            Ok(Box::pin(stream! {
+                let last_block = match client.get_block_number().await {
+                    Ok(last_block) => last_block,
+                    Err(e) => {
+                        error!("Error fetching block number : {}", e);
+                        return;
+                    }
+                };
+
+                let mut from_block = POOL_MANAGER_DEPLOYMENT_BLOCK;
+                while from_block <= last_block {
+                    let to_block = (from_block + LOGS_BLOCK_RANGE - 1).min(last_block);
+                    let filter = Filter::new()
+                        .address(FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS)
+                        .event_signature(Initialize::SIGNATURE_HASH)
+                        .from_block(from_block)
+                        .to_block(to_block);
+
+                    match client.get_logs(&filter).await {
+                        Ok(logs) => {
+                            for log in logs {
+                                if let Ok(event) = Initialize::decode_log(&log.inner, false) {
+                                    let key = Self::pool_key_from_event(&event.data);
+                                    Self::cache_pool_key(event.data.id, Some(key));
+                                    if Self::is_pool_key_supported(&key) {
+                                        yield (PoolId::Bytes32(event.data.id), PoolClass::UniswapV4)
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            error!("Error fetching Initialize logs from {} to {} : {}", from_block, to_block, e);
+                        }
+                    }
+                    debug!("UniswapV4 Initialize logs loaded up to block {}", to_block);
+                    from_block = to_block + 1;
+                }
+            }))
+        } else {
+            Err(eyre!("NO_PROVIDER"))
+        }
+    }
+}
@@ -16,7 +16,8 @@ use revm::DatabaseRef;
 use std::any::Any;
 use tracing::error;
 
-use crate::state_readers::UniswapV3StateReader;
+use crate::state_readers::MaverickStateReader;
+use crate::virtual_impl::MaverickPoolVirtual;
 
 lazy_static! {
     static ref U256_ONE: U256 = U256::from(1);
@@ -33,6 +34,8 @@ pub struct MaverickPool {
     liquidity1: U256,
     fee: U256,
     spacing: u32,
+    token_a_scale: U256,
+    token_b_scale: U256,
     slot0: Option<State>,
     factory: Address,
     protocol: PoolProtocol,
@@ -49,6 +52,8 @@ impl MaverickPool {
             liquidity1: U256::ZERO,
             fee: U256::ZERO,
             spacing: 0,
+            token_a_scale: U256::ZERO,
+            token_b_scale: U256::ZERO,
             slot0: None,
             factory: Address::ZERO,
             protocol: PoolProtocol::Maverick,
@@ -56,6 +61,18 @@ impl MaverickPool {
         }
     }
 
+    pub fn tick_spacing(&self) -> u32 {
+        self.spacing
+    }
+
+    pub fn token_a_scale(&self) -> U256 {
+        self.token_a_scale
+    }
+
+    pub fn token_b_scale(&self) -> U256 {
+        self.token_b_scale
+    }
+
     pub fn get_tick_bitmap_index(tick: i32, spacing: u32) -> i32 {
         let tick_bitmap_index = tick / (spacing as i32);
 
@@ -91,6 +108,8 @@ impl MaverickPool {
         let slot0 = pool.getState().call().await?._0;
         let factory: Address = pool.factory().call().await?._0;
         let spacing: u32 = pool.tickSpacing().call().await?._0.to();
+        let token_a_scale: U256 = pool.tokenAScale().call().await?._0;
+        let token_b_scale: U256 = pool.tokenBScale().call().await?._0;
 
         let token0_erc20 = IERC20::IERC20Instance::new(token0, client.clone());
         let token1_erc20 = IERC20::IERC20Instance::new(token1, client.clone());
@@ -111,17 +130,21 @@ impl MaverickPool {
             factory,
             protocol,
             spacing,
+            token_a_scale,
+            token_b_scale,
             encoder: MaverickAbiSwapEncoder { pool_address: address },
         };
 
         Ok(ret)
     }
     pub fn fetch_pool_data_evm(db: &dyn DatabaseRef<Error = ErrReport>, env: Env, address: Address) -> Result<Self> {
-        let token0: Address = UniswapV3StateReader::token0(&db, env.clone(), address)?;
-        let token1: Address = UniswapV3StateReader::token1(&db, env.clone(), address)?;
-        let fee = UniswapV3StateReader::fee(&db, env.clone(), address)?;
-        let factory: Address = UniswapV3StateReader::factory(&db, env.clone(), address)?;
-        let spacing: u32 = UniswapV3StateReader::tick_spacing(&db, env.clone(), address)?;
+        let token0: Address = MaverickStateReader::token_a(&db, env.clone(), address)?;
+        let token1: Address = MaverickStateReader::token_b(&db, env.clone(), address)?;
+        let fee = MaverickStateReader::fee(&db, env.clone(), address)?;
+        let factory: Address = MaverickStateReader::factory(&db, env.clone(), address)?;
+        let spacing: u32 = MaverickStateReader::tick_spacing(&db, env.clone(), address)?;
+        let token_a_scale = MaverickStateReader::token_a_scale(&db, env.clone(), address)?;
+        let token_b_scale = MaverickStateReader::token_b_scale(&db, env.clone(), address)?;
 
         let protocol = Self::get_protocol_by_factory(factory);
 
@@ -131,8 +154,10 @@ impl MaverickPool {
             token1,
             liquidity0: Default::default(),
             liquidity1: Default::default(),
-            fee: U256::from(fee),
+            fee,
             spacing,
+            token_a_scale,
+            token_b_scale,
             slot0: None,
             factory,
             protocol,
@@ -141,6 +166,70 @@ impl MaverickPool {
 
         Ok(ret)
     }
+
+    fn quote_evm(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_a_in: bool,
+        amount: U256,
+        exact_output: bool,
+    ) -> Result<(U256, u64), ErrReport> {
+        if amount >= U256::from(U128::MAX) {
+            let err = if exact_output { "OUT_AMOUNT_EXCEEDS_MAX" } else { "IN_AMOUNT_EXCEEDS_MAX" };
+            error!("{} {}", err, self.get_address().to_checksum(None));
+            return Err(eyre!(err));
+        }
+
+        let mut env = env;
+        env.tx.gas_limit = if exact_output { 500_000 } else { 1_500_000 };
+
+        let call_data_vec = IMaverickQuoterCalls::calculateSwap(calculateSwapCall {
+            pool: self.address,
+            amount: amount.to(),
+            tokenAIn: token_a_in,
+            exactOutput: exact_output,
+            sqrtPriceLimit: U256::ZERO,
+        })
+        .abi_encode();
+
+        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::MAVERICK_QUOTER, call_data_vec)?;
+
+        Ok((calculateSwapCall::abi_decode_returns(&value, false)?.returnAmount, gas_used))
+    }
+
+    /// Quotes with bins simulation if `virtual-calculation` is enabled, with the quoter otherwise
+    fn quote(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        amount: U256,
+        exact_output: bool,
+    ) -> Result<(U256, u64), ErrReport> {
+        let token_a_in = MaverickPool::get_zero_for_one(token_address_from, token_address_to);
+
+        if cfg!(feature = "virtual-calculation") {
+            let ret_virtual = if exact_output {
+                MaverickPoolVirtual::simulate_swap_out_amount_provided(&state_db, self, *token_address_from, amount)?
+            } else {
+                MaverickPoolVirtual::simulate_swap_in_amount_provided(&state_db, self, *token_address_from, amount)?
+            };
+
+            #[cfg(feature = "debug-calculation")]
+            {
+                let (ret_evm, gas_used) = self.quote_evm(state_db, env, token_a_in, amount, exact_output)?;
+                println!("quote exact_output: {} ret_evm: {:?} ret: {:?} gas_used: {:?}", exact_output, ret_evm, ret_virtual, gas_used);
+                if ret_virtual != ret_evm {
+                    error!(%ret_virtual, %ret_evm, exact_output, "quote RETURN_RESULT_IS_INCORRECT");
+                }
+            }
+            Ok((ret_virtual, 200_000))
+        } else {
+            self.quote_evm(state_db, env, token_a_in, amount, exact_output)
+        }
+    }
 }
 
 impl Pool for MaverickPool {
@@ -184,29 +273,7 @@ impl Pool for MaverickPool {
         token_address_to: &Address,
         in_amount: U256,
     ) -> Result<(U256, u64), ErrReport> {
-        if in_amount >= U256::from(U128::MAX) {
-            error!("IN_AMOUNT_EXCEEDS_MAX {}", self.get_address().to_checksum(None));
-            return Err(eyre!("IN_AMOUNT_EXCEEDS_MAX"));
-        }
-
-        let token_a_in = MaverickPool::get_zero_for_one(token_address_from, token_address_to);
-        //let sqrt_price_limit = MaverickPool::get_price_limit(token_address_from, token_address_to);
-
-        let mut env = env;
-        env.tx.gas_limit = 1_500_000;
-
-        let call_data_vec = IMaverickQuoterCalls::calculateSwap(calculateSwapCall {
-            pool: self.address,
-            amount: in_amount.to(),
-            tokenAIn: token_a_in,
-            exactOutput: false,
-            sqrtPriceLimit: U256::ZERO,
-        })
-        .abi_encode();
-
-        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::MAVERICK_QUOTER, call_data_vec)?;
-
-        let ret = calculateSwapCall::abi_decode_returns(&value, false)?.returnAmount;
+        let (ret, gas_used) = self.quote(state_db, env, token_address_from, token_address_to, in_amount, false)?;
 
         if ret.is_zero() {
             Err(eyre!("ZERO_OUT_AMOUNT"))
@@ -223,29 +290,7 @@ impl Pool for MaverickPool {
         token_address_to: &Address,
         out_amount: U256,
     ) -> Result<(U256, u64), ErrReport> {
-        let mut env = env;
-        env.tx.gas_limit = 500_000;
-
-        if out_amount >= U256::from(U128::MAX) {
-            error!("OUT_AMOUNT_EXCEEDS_MAX {} ", self.get_address().to_checksum(None));
-            return Err(eyre!("OUT_AMOUNT_EXCEEDS_MAX"));
-        }
-
-        let token_a_in = MaverickPool::get_zero_for_one(token_address_from, token_address_to);
-        //let sqrt_price_limit = MaverickPool::get_price_limit(token_address_from, token_address_to);
-
-        let call_data_vec = IMaverickQuoterCalls::calculateSwap(calculateSwapCall {
-            pool: self.address,
-            amount: out_amount.to(),
-            tokenAIn: token_a_in,
-            exactOutput: true,
-            sqrtPriceLimit: U256::ZERO,
-        })
-        .abi_encode();
-
-        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::MAVERICK_QUOTER, call_data_vec)?;
-
-        let ret = calculateSwapCall::abi_decode_returns(&value, false)?.returnAmount;
+        let (ret, gas_used) = self.quote(state_db, env, token_address_from, token_address_to, out_amount, true)?;
 
         if ret.is_zero() {
             Err(eyre!("ZERO_IN_AMOUNT"))
@@ -515,4 +560,38 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_virtual_swap() -> Result<()> {
+        let node_url = env::var("MAINNET_WS")?;
+
+        let client = AnvilDebugProviderFactory::from_node_on_block(node_url, 20045799).await?;
+
+        let pool_address: Address = "0x352B186090068Eb35d532428676cE510E17AB581".parse().unwrap();
+        let pool = MaverickPool::fetch_pool_data(client.clone(), pool_address).await?;
+
+        let state_update = RequiredStateReader::fetch_calls_and_slots(client.clone(), pool.get_state_required()?, None).await?;
+        let mut market_state = MarketState::new(LoomDBType::default());
+        market_state.state_db.apply_geth_update(state_update);
+
+        let block_number = client.get_block_number().await?;
+        let block = client.get_block_by_number(BlockNumberOrTag::Number(block_number), BlockTransactionsKind::Hashes).await?.unwrap();
+        let evm_env = env_for_block(block.header.number, block.header.timestamp);
+
+        for (token_from, token_to, amount) in
+            [(pool.token0, pool.token1, pool.liquidity0 / U256::from(1000)), (pool.token1, pool.token0, pool.liquidity1 / U256::from(1000))]
+        {
+            let token_a_in = MaverickPool::get_zero_for_one(&token_from, &token_to);
+
+            let (out_evm, _) = pool.quote_evm(&market_state.state_db, evm_env.clone(), token_a_in, amount, false)?;
+            let out_virtual = MaverickPoolVirtual::simulate_swap_in_amount_provided(&market_state.state_db, &pool, token_from, amount)?;
+            assert_eq!(out_virtual, out_evm);
+
+            let (in_evm, _) = pool.quote_evm(&market_state.state_db, evm_env.clone(), token_a_in, out_evm, true)?;
+            let in_virtual = MaverickPoolVirtual::simulate_swap_out_amount_provided(&market_state.state_db, &pool, token_from, out_evm)?;
+            assert_eq!(in_virtual, in_evm);
+        }
+
+        Ok(())
+    }
 }
@@ -3,6 +3,7 @@ use std::fmt::Debug;
 use std::ops::Sub;
 
 use crate::state_readers::UniswapV3StateReader;
+use crate::virtual_impl::PancakeV3PoolVirtual;
 use alloy::primitives::aliases::{I24, U24};
 use alloy::primitives::{Address, Bytes, I256, U160, U256};
 use alloy::providers::{Network, Provider};
@@ -20,6 +21,8 @@ use loom_types_entities::required_state::RequiredState;
 use loom_types_entities::{Pool, PoolAbiEncoder, PoolClass, PoolId, PoolProtocol, PreswapRequirement, SwapDirection};
 use revm::primitives::Env;
 use revm::DatabaseRef;
+#[cfg(feature = "debug-calculation")]
+use tracing::error;
 
 #[allow(dead_code)]
 #[derive(Clone, Debug, Default)]
@@ -81,6 +84,10 @@ impl PancakeV3Pool {
         }
     }
 
+    pub fn tick_spacing(&self) -> u32 {
+        Self::get_price_step(self.fee_u32)
+    }
+
     pub fn get_price_step(fee: u32) -> u32 {
         match fee {
             10000 => 200,
@@ -180,6 +187,62 @@ impl PancakeV3Pool {
 
         Ok(ret)
     }
+
+    fn quote_exact_input_evm(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let mut env = env;
+        env.tx.gas_limit = 1_000_000;
+
+        let call_data = IPancakeQuoterV2Calls::quoteExactInputSingle(IPancakeQuoterV2::quoteExactInputSingleCall {
+            params: IPancakeQuoterV2::QuoteExactInputSingleParams {
+                tokenIn: *token_address_from,
+                tokenOut: *token_address_to,
+                amountIn: in_amount,
+                fee: self.fee,
+                sqrtPriceLimitX96: PancakeV3Pool::get_price_limit(token_address_from, token_address_to),
+            },
+        })
+        .abi_encode();
+
+        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::PANCAKE_V3_QUOTER, call_data)?;
+
+        let ret = IPancakeQuoterV2::quoteExactInputSingleCall::abi_decode_returns(&value, false)?;
+        Ok((ret.amountOut, gas_used))
+    }
+
+    fn quote_exact_output_evm(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        out_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let mut env = env;
+        env.tx.gas_limit = 1_000_000;
+
+        let call_data = IPancakeQuoterV2Calls::quoteExactOutputSingle(IPancakeQuoterV2::quoteExactOutputSingleCall {
+            params: IPancakeQuoterV2::QuoteExactOutputSingleParams {
+                tokenIn: *token_address_from,
+                tokenOut: *token_address_to,
+                amount: out_amount,
+                fee: self.fee,
+                sqrtPriceLimitX96: PancakeV3Pool::get_price_limit(token_address_from, token_address_to),
+            },
+        })
+        .abi_encode();
+
+        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::PANCAKE_V3_QUOTER, call_data)?;
+
+        let ret = IPancakeQuoterV2::quoteExactOutputSingleCall::abi_decode_returns(&value, false)?;
+        Ok((ret.amountIn, gas_used))
+    }
 }
 
 impl Pool for PancakeV3Pool {
@@ -222,28 +285,26 @@ impl Pool for PancakeV3Pool {
         token_address_to: &Address,
         in_amount: U256,
     ) -> Result<(U256, u64), ErrReport> {
-        let mut env = env;
-        env.tx.gas_limit = 1_000_000;
-
-        let call_data = IPancakeQuoterV2Calls::quoteExactInputSingle(IPancakeQuoterV2::quoteExactInputSingleCall {
-            params: IPancakeQuoterV2::QuoteExactInputSingleParams {
-                tokenIn: *token_address_from,
-                tokenOut: *token_address_to,
-                amountIn: in_amount,
-                fee: self.fee,
-                sqrtPriceLimitX96: PancakeV3Pool::get_price_limit(token_address_from, token_address_to),
-            },
-        })
-        .abi_encode();
-
-        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::PANCAKE_V3_QUOTER, call_data)?;
-
-        let ret = IPancakeQuoterV2::quoteExactInputSingleCall::abi_decode_returns(&value, false)?;
+        let (ret, gas_used) = if cfg!(feature = "virtual-calculation") && self.get_protocol() == PoolProtocol::PancakeV3 {
+            let ret_virtual = PancakeV3PoolVirtual::simulate_swap_in_amount_provided(&state_db, self, *token_address_from, in_amount)?;
+
+            #[cfg(feature = "debug-calculation")]
+            {
+                let (ret_evm, gas_used) = self.quote_exact_input_evm(state_db, env, token_address_from, token_address_to, in_amount)?;
+                println!("calculate_out_amount ret_evm: {:?} ret: {:?} gas_used: {:?}", ret_evm, ret_virtual, gas_used);
+                if ret_virtual != ret_evm {
+                    error!(%ret_virtual, %ret_evm, "calculate_out_amount RETURN_RESULT_IS_INCORRECT");
+                }
+            }
+            (ret_virtual, 150_000)
+        } else {
+            self.quote_exact_input_evm(state_db, env, token_address_from, token_address_to, in_amount)?
+        };
 
-        if ret.amountOut.is_zero() {
+        if ret.is_zero() {
             Err(eyre!("ZERO_OUT_AMOUNT"))
         } else {
-            Ok((ret.amountOut - U256::from(1), gas_used))
+            Ok((ret - U256::from(1), gas_used))
         }
     }
 
@@ -255,28 +316,26 @@ impl Pool for PancakeV3Pool {
         token_address_to: &Address,
         out_amount: U256,
     ) -> Result<(U256, u64), ErrReport> {
-        let mut env = env;
-        env.tx.gas_limit = 1_000_000;
-
-        let call_data = IPancakeQuoterV2Calls::quoteExactOutputSingle(IPancakeQuoterV2::quoteExactOutputSingleCall {
-            params: IPancakeQuoterV2::QuoteExactOutputSingleParams {
-                tokenIn: *token_address_from,
-                tokenOut: *token_address_to,
-                amount: out_amount,
-                fee: self.fee,
-                sqrtPriceLimitX96: PancakeV3Pool::get_price_limit(token_address_from, token_address_to),
-            },
-        })
-        .abi_encode();
-
-        let (value, gas_used) = evm_call(state_db, env, PeripheryAddress::PANCAKE_V3_QUOTER, call_data)?;
-
-        let ret = IPancakeQuoterV2::quoteExactOutputSingleCall::abi_decode_returns(&value, false)?;
+        let (ret, gas_used) = if cfg!(feature = "virtual-calculation") && self.get_protocol() == PoolProtocol::PancakeV3 {
+            let ret_virtual = PancakeV3PoolVirtual::simulate_swap_out_amount_provided(&state_db, self, *token_address_from, out_amount)?;
+
+            #[cfg(feature = "debug-calculation")]
+            {
+                let (ret_evm, gas_used) = self.quote_exact_output_evm(state_db, env, token_address_from, token_address_to, out_amount)?;
+                println!("calculate_in_amount ret_evm: {:?} ret: {:?} gas_used: {:?}", ret_evm, ret_virtual, gas_used);
+                if ret_virtual != ret_evm {
+                    error!(%ret_virtual, %ret_evm, "calculate_in_amount RETURN_RESULT_IS_INCORRECT");
+                }
+            }
+            (ret_virtual, 150_000)
+        } else {
+            self.quote_exact_output_evm(state_db, env, token_address_from, token_address_to, out_amount)?
+        };
 
-        if ret.amountIn.is_zero() {
+        if ret.is_zero() {
             Err(eyre!("ZERO_IN_AMOUNT"))
         } else {
-            Ok((ret.amountIn + U256::from(1), gas_used))
+            Ok((ret + U256::from(1), gas_used))
         }
     }
 
@@ -568,4 +627,36 @@ mod tests {
         assert_ne!(out_amount, U256::ZERO);
         assert!(gas_used > 100_000, "gas used check failed");
     }
+
+    #[tokio::test]
+    async fn test_virtual_swap() {
+        let node_url = env::var("MAINNET_WS").unwrap();
+
+        let client = AnvilDebugProviderFactory::from_node_on_block(node_url, 19931897).await.unwrap();
+
+        let pool_address: Address = "0x9b5699d18dff51fc65fb8ad6f70d93287c36349f".parse().unwrap();
+        let pool = PancakeV3Pool::fetch_pool_data(client.clone(), pool_address).await.unwrap();
+
+        let state_update =
+            RequiredStateReader::fetch_calls_and_slots(client.clone(), pool.get_state_required().unwrap(), None).await.unwrap();
+        let mut market_state = MarketState::new(LoomDBType::default());
+        market_state.state_db.apply_geth_update(state_update);
+
+        let evm_env = Env::default();
+
+        for (token_from, token_to, amount) in
+            [(pool.token0, pool.token1, pool.liquidity0 / U256::from(100)), (pool.token1, pool.token0, pool.liquidity1 / U256::from(100))]
+        {
+            let (out_evm, _) = pool.quote_exact_input_evm(&market_state.state_db, evm_env.clone(), &token_from, &token_to, amount).unwrap();
+            let out_virtual =
+                PancakeV3PoolVirtual::simulate_swap_in_amount_provided(&market_state.state_db, &pool, token_from, amount).unwrap();
+            assert_eq!(out_virtual, out_evm);
+
+            let (in_evm, _) =
+                pool.quote_exact_output_evm(&market_state.state_db, evm_env.clone(), &token_from, &token_to, out_evm).unwrap();
+            let in_virtual =
+                PancakeV3PoolVirtual::simulate_swap_out_amount_provided(&market_state.state_db, &pool, token_from, out_evm).unwrap();
+            assert_eq!(in_virtual, in_evm);
+        }
+    }
 }
@@ -441,6 +441,17 @@ where
         }
     }
 
+    /// Base pool of LP token paired in metapools
+    pub fn get_base_pool(meta_token_address: Address) -> Result<Address> {
+        if meta_token_address == address!("6c3F90f043a72FA612cbac8115EE7e52BDe6E490") {
+            Ok(address!("bEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7"))
+        } else if meta_token_address == address!("3175Df0976dFA876431C2E9eE6Bc45b65d3473CC") {
+            Ok(address!("DcEF968d416a41Cdac0ED8702fAC8128A64241A2"))
+        } else {
+            Err(eyre!("META_POOL_NOT_FOUND"))
+        }
+    }
+
     pub fn new_i128_2(client: P, address: Address) -> CurveContract<P, N> {
         let contract = ICurveI128_2Instance::new(address, client);
         CurveContract::I128_2(contract)
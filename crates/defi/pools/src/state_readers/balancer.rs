@@ -0,0 +1,42 @@
+use alloy::primitives::{Address, B256, U256};
+use alloy::sol_types::SolCall;
+use eyre::Result;
+use revm::primitives::Env;
+use revm::DatabaseRef;
+
+use loom_defi_abi::balancer::{IBalancerPool, IBalancerStablePool, IBalancerWeightedPool, IVault};
+use loom_evm_utils::evm::evm_call;
+
+pub struct BalancerV2StateReader {}
+
+impl BalancerV2StateReader {
+    pub fn get_pool_tokens<DB: DatabaseRef>(db: &DB, env: Env, vault: Address, pool_id: B256) -> Result<(Vec<Address>, Vec<U256>)> {
+        let call_data_result = evm_call(db, env, vault, IVault::getPoolTokensCall { poolId: pool_id }.abi_encode())?.0;
+        let call_return = IVault::getPoolTokensCall::abi_decode_returns(&call_data_result, false)?;
+        Ok((call_return.tokens, call_return.balances))
+    }
+
+    pub fn get_swap_fee_percentage<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> Result<U256> {
+        let call_data_result = evm_call(db, env, pool, IBalancerPool::getSwapFeePercentageCall {}.abi_encode())?.0;
+        let call_return = IBalancerPool::getSwapFeePercentageCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn get_scaling_factors<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> Result<Vec<U256>> {
+        let call_data_result = evm_call(db, env, pool, IBalancerPool::getScalingFactorsCall {}.abi_encode())?.0;
+        let call_return = IBalancerPool::getScalingFactorsCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn get_normalized_weights<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> Result<Vec<U256>> {
+        let call_data_result = evm_call(db, env, pool, IBalancerWeightedPool::getNormalizedWeightsCall {}.abi_encode())?.0;
+        let call_return = IBalancerWeightedPool::getNormalizedWeightsCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn get_amplification_parameter<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> Result<U256> {
+        let call_data_result = evm_call(db, env, pool, IBalancerStablePool::getAmplificationParameterCall {}.abi_encode())?.0;
+        let call_return = IBalancerStablePool::getAmplificationParameterCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return.value)
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use alloy::primitives::{Address, Bytes, U256};
+use alloy::sol_types::SolCall;
+use eyre::{eyre, ErrReport, Result};
+use revm::interpreter::{opcode, Interpreter};
+use revm::primitives::{Env, ExecutionResult, Output, TransactTo, CANCUN};
+use revm::{inspector_handle_register, Database, DatabaseRef, Evm, EvmContext, Inspector};
+
+/// Records storage cells and balances read by a call
+#[derive(Default)]
+struct CellReadInspector {
+    storage: Vec<(Address, U256)>,
+    balances: Vec<Address>,
+    reads_block_env: bool,
+}
+
+impl<DB: Database> Inspector<DB> for CellReadInspector {
+    fn step(&mut self, interp: &mut Interpreter, _context: &mut EvmContext<DB>) {
+        match interp.current_opcode() {
+            opcode::SLOAD => {
+                if let Ok(slot) = interp.stack().peek(0) {
+                    self.storage.push((interp.contract.target_address, slot));
+                }
+            }
+            opcode::BALANCE => {
+                if let Ok(address) = interp.stack().peek(0) {
+                    self.balances.push(Address::from_word(address.into()));
+                }
+            }
+            opcode::SELFBALANCE => self.balances.push(interp.contract.target_address),
+            opcode::TIMESTAMP | opcode::NUMBER => self.reads_block_env = true,
+            _ => {}
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum CellRead {
+    Storage(Address, U256, U256),
+    Balance(Address, U256),
+}
+
+impl CellRead {
+    fn is_current(&self, db: &dyn DatabaseRef<Error = ErrReport>) -> Result<bool> {
+        match self {
+            CellRead::Storage(address, slot, value) => Ok(db.storage_ref(*address, *slot)? == *value),
+            CellRead::Balance(address, value) => Ok(db.basic_ref(*address)?.map(|account| account.balance).unwrap_or_default() == *value),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CachedCall {
+    reads: Vec<CellRead>,
+    timestamp: Option<U256>,
+    /// None if call reverted
+    output: Option<Bytes>,
+}
+
+impl CachedCall {
+    /// Storage slot of `to` if the call returns value of this single cell
+    fn direct_cell(&self, to: Address) -> Option<U256> {
+        match (self.reads.as_slice(), &self.output, self.timestamp) {
+            ([CellRead::Storage(address, slot, value)], Some(output), None)
+                if *address == to && output.as_ref() == value.to_be_bytes::<32>() =>
+            {
+                Some(*slot)
+            }
+            _ => None,
+        }
+    }
+
+    fn is_current(&self, db: &dyn DatabaseRef<Error = ErrReport>, env: &Env) -> Result<bool> {
+        if self.timestamp.is_some_and(|timestamp| timestamp != env.block.timestamp) {
+            return Ok(false);
+        }
+        for read in self.reads.iter() {
+            if !read.is_current(db)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Reads Curve pool parameters with view calls mapped to storage cells the calls depend on. A call is executed in EVM once
+/// and repeated only when one of the cells changes, getters returning a single storage cell are read from storage directly.
+#[derive(Debug, Default)]
+pub struct CurveStateReader {
+    calls: RwLock<HashMap<(Address, Bytes), CachedCall>>,
+}
+
+impl CurveStateReader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn trace_call(db: &dyn DatabaseRef<Error = ErrReport>, env: &Env, to: Address, call_data: Bytes) -> Result<CachedCall> {
+        let mut env = env.clone();
+        env.tx.transact_to = TransactTo::Call(to);
+        env.tx.data = call_data;
+        env.tx.gas_limit = 500_000;
+
+        let mut evm = Evm::builder()
+            .with_ref_db(db)
+            .with_spec_id(CANCUN)
+            .with_env(Box::new(env))
+            .with_external_context(CellReadInspector::default())
+            .append_handler_register(inspector_handle_register)
+            .build();
+
+        let result = evm.transact().map_err(|_| eyre!("CURVE_STATE_CALL_FAILED"))?.result;
+        let output = match result {
+            ExecutionResult::Success { output: Output::Call(output), .. } => Some(output),
+            _ => None,
+        };
+        let block_timestamp = evm.context.evm.env.block.timestamp;
+        let inspector = evm.context.external;
+
+        let mut reads: Vec<CellRead> = Vec::new();
+        for (address, slot) in inspector.storage {
+            let read = CellRead::Storage(address, slot, db.storage_ref(address, slot)?);
+            if !reads.contains(&read) {
+                reads.push(read);
+            }
+        }
+        for address in inspector.balances {
+            let read = CellRead::Balance(address, db.basic_ref(address)?.map(|account| account.balance).unwrap_or_default());
+            if !reads.contains(&read) {
+                reads.push(read);
+            }
+        }
+
+        Ok(CachedCall { reads, timestamp: inspector.reads_block_env.then_some(block_timestamp), output })
+    }
+
+    /// Returns output of the call or error if it reverted
+    pub fn call(&self, db: &dyn DatabaseRef<Error = ErrReport>, env: &Env, to: Address, call_data: Bytes) -> Result<Bytes> {
+        let key = (to, call_data);
+        let cached = self.calls.read().map_err(|_| eyre!("CURVE_STATE_LOCK_POISONED"))?.get(&key).cloned();
+
+        if let Some(cached) = cached {
+            if let Some(slot) = cached.direct_cell(to) {
+                return Ok(Bytes::from(db.storage_ref(to, slot)?.to_be_bytes::<32>()));
+            }
+            if cached.is_current(db, env)? {
+                return cached.output.ok_or_else(|| eyre!("CURVE_STATE_CALL_REVERTED"));
+            }
+        }
+
+        let cached = Self::trace_call(db, env, to, key.1.clone())?;
+        let output = cached.output.clone();
+        self.calls.write().map_err(|_| eyre!("CURVE_STATE_LOCK_POISONED"))?.insert(key, cached);
+        output.ok_or_else(|| eyre!("CURVE_STATE_CALL_REVERTED"))
+    }
+
+    pub fn call_u256<C: SolCall>(&self, db: &dyn DatabaseRef<Error = ErrReport>, env: &Env, to: Address, call: C) -> Result<U256> {
+        let output = self.call(db, env, to, call.abi_encode().into())?;
+        if output.len() < 32 {
+            return Err(eyre!("CURVE_STATE_BAD_OUTPUT"));
+        }
+        Ok(U256::from_be_slice(&output[0..32]))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy::primitives::hex;
+    use loom_evm_db::LoomDBType;
+    use revm::primitives::{AccountInfo, Bytecode};
+
+    // SLOAD(0) returned as uint256
+    const RETURN_SLOT_0: [u8; 10] = hex!("5f5460005260206000f3");
+    // SLOAD(0) + TIMESTAMP returned as uint256
+    const RETURN_SLOT_0_PLUS_TIMESTAMP: [u8; 12] = hex!("5f54420160005260206000f3");
+
+    fn db_with_code(address: Address, code: &[u8], value: U256) -> LoomDBType {
+        let mut db = LoomDBType::new();
+        db.insert_account_info(address, AccountInfo::default().with_code(Bytecode::new_raw(Bytes::copy_from_slice(code))));
+        db.insert_account_storage(address, U256::ZERO, value).unwrap();
+        db
+    }
+
+    #[test]
+    fn test_direct_cell() {
+        let address = Address::repeat_byte(1);
+        let reader = CurveStateReader::new();
+        let env = Env::default();
+
+        let db = db_with_code(address, &RETURN_SLOT_0, U256::from(10));
+        assert_eq!(U256::from_be_slice(&reader.call(&db, &env, address, Bytes::new()).unwrap()), U256::from(10));
+
+        // value is read from storage without execution
+        let mut db = LoomDBType::new();
+        db.insert_account_storage(address, U256::ZERO, U256::from(20)).unwrap();
+        assert_eq!(U256::from_be_slice(&reader.call(&db, &env, address, Bytes::new()).unwrap()), U256::from(20));
+    }
+
+    #[test]
+    fn test_block_env_dependency() {
+        let address = Address::repeat_byte(1);
+        let reader = CurveStateReader::new();
+        let mut env = Env::default();
+        env.block.timestamp = U256::from(100);
+
+        let db = db_with_code(address, &RETURN_SLOT_0_PLUS_TIMESTAMP, U256::from(10));
+        assert_eq!(U256::from_be_slice(&reader.call(&db, &env, address, Bytes::new()).unwrap()), U256::from(110));
+
+        env.block.timestamp = U256::from(200);
+        assert_eq!(U256::from_be_slice(&reader.call(&db, &env, address, Bytes::new()).unwrap()), U256::from(210));
+
+        let db = db_with_code(address, &RETURN_SLOT_0_PLUS_TIMESTAMP, U256::from(20));
+        assert_eq!(U256::from_be_slice(&reader.call(&db, &env, address, Bytes::new()).unwrap()), U256::from(220));
+    }
+}
@@ -0,0 +1,59 @@
+use alloy::primitives::{Address, U256};
+use alloy::sol_types::{SolCall, SolInterface};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+
+use loom_defi_abi::maverick::IMaverickPool;
+use loom_evm_utils::evm::evm_call;
+
+pub struct MaverickStateReader {}
+
+impl MaverickStateReader {
+    pub fn factory<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<Address> {
+        let call_data_result =
+            evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::factory(IMaverickPool::factoryCall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::factoryCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn token_a<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<Address> {
+        let call_data_result =
+            evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::tokenA(IMaverickPool::tokenACall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::tokenACall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn token_b<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<Address> {
+        let call_data_result =
+            evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::tokenB(IMaverickPool::tokenBCall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::tokenBCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn fee<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<U256> {
+        let call_data_result = evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::fee(IMaverickPool::feeCall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::feeCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn tick_spacing<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<u32> {
+        let call_data_result =
+            evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::tickSpacing(IMaverickPool::tickSpacingCall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::tickSpacingCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0.try_into()?)
+    }
+
+    pub fn token_a_scale<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<U256> {
+        let call_data_result =
+            evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::tokenAScale(IMaverickPool::tokenAScaleCall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::tokenAScaleCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+
+    pub fn token_b_scale<DB: DatabaseRef>(db: &DB, env: Env, pool: Address) -> eyre::Result<U256> {
+        let call_data_result =
+            evm_call(db, env, pool, IMaverickPool::IMaverickPoolCalls::tokenBScale(IMaverickPool::tokenBScaleCall {}).abi_encode())?.0;
+        let call_return = IMaverickPool::tokenBScaleCall::abi_decode_returns(&call_data_result, false)?;
+        Ok(call_return._0)
+    }
+}
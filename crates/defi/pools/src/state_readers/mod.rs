@@ -1,8 +1,14 @@
+pub use balancer::BalancerV2StateReader;
+pub use curve::CurveStateReader;
 pub use erc20::ERC20StateReader;
+pub use maverick::MaverickStateReader;
 pub use uniswapv2::UniswapV2StateReader;
 pub use uniswapv3::UniswapV3StateReader;
 pub use uniswapv3_quoter::{UniswapV3QuoterV2Encoder, UniswapV3QuoterV2StateReader};
 
+mod balancer;
+mod curve;
+mod maverick;
 mod uniswapv2;
 mod uniswapv3;
 
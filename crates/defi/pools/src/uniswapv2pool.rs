@@ -78,6 +78,10 @@ impl UniswapV2Pool {
         Self { fee, ..self }
     }
 
+    pub fn set_reserves_cell(self, reserves_cell: Option<U256>) -> Self {
+        Self { reserves_cell, ..self }
+    }
+
     pub fn get_zero_for_one(token_address_from: Address, token_address_to: Address) -> bool {
         token_address_from < token_address_to
     }
@@ -260,7 +260,8 @@ impl Pool for UniswapV3Pool {
         in_amount: U256,
     ) -> Result<(U256, u64), ErrReport> {
         let (ret, gas_used) = if self.get_protocol() == PoolProtocol::UniswapV3 {
-            let ret_virtual = UniswapV3PoolVirtual::simulate_swap_in_amount_provider(&state_db, self, *token_address_from, in_amount)?;
+            let (ret_virtual, gas_virtual) =
+                UniswapV3PoolVirtual::simulate_swap_in_amount_provided_with_gas(&state_db, self, *token_address_from, in_amount)?;
 
             #[cfg(feature = "debug-calculation")]
             {
@@ -275,12 +276,15 @@ impl Pool for UniswapV3Pool {
                     self.fee.try_into()?,
                     in_amount,
                 )?;
-                println!("calculate_out_amount ret_evm: {:?} ret: {:?} gas_used: {:?}", ret_evm, ret_virtual, gas_used);
+                println!(
+                    "calculate_out_amount ret_evm: {:?} ret: {:?} gas_used: {:?} gas_virtual: {:?}",
+                    ret_evm, ret_virtual, gas_used, gas_virtual
+                );
                 if ret_virtual != ret_evm {
                     error!(%ret_virtual, %ret_evm, "calculate_out_amount RETURN_RESULT_IS_INCORRECT");
                 };
             }
-            (ret_virtual, 150_000)
+            (ret_virtual, gas_virtual)
         } else {
             let mut env = _env;
             env.tx.gas_limit = 1_000_000;
@@ -313,7 +317,8 @@ impl Pool for UniswapV3Pool {
         out_amount: U256,
     ) -> Result<(U256, u64), ErrReport> {
         let (ret, gas_used) = if self.get_protocol() == PoolProtocol::UniswapV3 {
-            let ret_virtual = UniswapV3PoolVirtual::simulate_swap_out_amount_provided(&state_db, self, *token_address_from, out_amount)?;
+            let (ret_virtual, gas_virtual) =
+                UniswapV3PoolVirtual::simulate_swap_out_amount_provided_with_gas(&state_db, self, *token_address_from, out_amount)?;
 
             #[cfg(feature = "debug-calculation")]
             {
@@ -328,13 +333,16 @@ impl Pool for UniswapV3Pool {
                     self.fee.try_into()?,
                     out_amount,
                 )?;
-                println!("calculate_out_amount ret_evm: {:?} ret: {:?} gas_used: {:?}", ret_evm, ret_virtual, gas_used);
+                println!(
+                    "calculate_out_amount ret_evm: {:?} ret: {:?} gas_used: {:?} gas_virtual: {:?}",
+                    ret_evm, ret_virtual, gas_used, gas_virtual
+                );
 
                 if ret_virtual != ret_evm {
                     error!(%ret_virtual, %ret_evm,"calculate_in_amount RETURN_RESULT_IS_INCORRECT");
                 }
             }
-            (ret_virtual, 150000)
+            (ret_virtual, gas_virtual)
         } else {
             let mut env = _env;
             env.tx.gas_limit = 1_000_000;
@@ -638,7 +646,7 @@ mod test {
                 "Mismatch for pool={:?}, token_out={}, amount_in={}",
                 pool_address, &pool.token1, amount_in
             );
-            assert_eq!(gas_used, 150_000);
+            assert!((100_000..200_000).contains(&gas_used), "Unexpected gas estimate {gas_used} for pool={pool_address:?}");
 
             //// CASE: token1 -> token0
             let amount_in = U256::from(10u64).pow(token1_decimals);
@@ -658,7 +666,7 @@ mod test {
                 "Mismatch for pool={:?}, token_out={}, amount_in={}",
                 pool_address, &pool.token0, amount_in
             );
-            assert_eq!(gas_used, 150_000);
+            assert!((100_000..200_000).contains(&gas_used), "Unexpected gas estimate {gas_used} for pool={pool_address:?}");
         }
 
         Ok(())
@@ -699,7 +707,7 @@ mod test {
                 "Mismatch for pool={:?}, token_in={:?}, amount_out={}",
                 pool_address, &pool.token0, amount_out
             );
-            assert_eq!(gas_used, 150_000);
+            assert!((100_000..200_000).contains(&gas_used), "Unexpected gas estimate {gas_used} for pool={pool_address:?}");
 
             //// CASE: token1 -> token0
             let amount_out = U256::from(10u64).pow(token0_decimals);
@@ -719,7 +727,7 @@ mod test {
                 "Mismatch for pool={:?}, token_in={:?}, amount_out={}",
                 pool_address, &pool.token1, amount_out
             );
-            assert_eq!(gas_used, 150_000);
+            assert!((100_000..200_000).contains(&gas_used), "Unexpected gas estimate {gas_used} for pool={pool_address:?}");
         }
 
         Ok(())
@@ -759,7 +767,7 @@ mod test {
                 "Mismatch for pool={:?}, token_in={:?}, amount_out={}",
                 pool_address, &pool.token0, amount_out
             );
-            assert_eq!(gas_used, 150_000);
+            assert!((100_000..200_000).contains(&gas_used), "Unexpected gas estimate {gas_used} for pool={pool_address:?}");
 
             //// CASE: token1 -> token0
             let amount_out = U256::from(10u64).pow(token0_decimals);
@@ -779,7 +787,7 @@ mod test {
                 "Mismatch for pool={:?}, token_in={:?}, amount_out={}",
                 pool_address, &pool.token1, amount_out
             );
-            assert_eq!(gas_used, 150_000);
+            assert!((100_000..200_000).contains(&gas_used), "Unexpected gas estimate {gas_used} for pool={pool_address:?}");
         }
 
         Ok(())
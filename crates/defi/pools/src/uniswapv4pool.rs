@@ -0,0 +1,451 @@
+use std::any::Any;
+
+use crate::db_reader::{UniswapV4DBReader, LIQUIDITY_OFFSET};
+use crate::virtual_impl::UniswapV4PoolVirtual;
+use alloy::primitives::{keccak256, Address, Bytes, Signed, Uint, B256, I256, U160, U256};
+use alloy::providers::{Network, Provider};
+use alloy::sol_types::{SolCall, SolValue};
+use eyre::{eyre, ErrReport, OptionExt, Result};
+use loom_defi_abi::uniswap4::{IStateView, IUniswapV4PoolManager, IV4Quoter, PoolKey};
+use loom_defi_address_book::PeripheryAddress;
+use loom_defi_uniswap_v3_math::tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO};
+use loom_types_entities::required_state::RequiredState;
+use loom_types_entities::{Pool, PoolAbiEncoder, PoolClass, PoolId, PoolProtocol, PreswapRequirement, SwapDirection};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+
+// Hook permissions are encoded in the lowest 14 bits of the hooks address
+pub const BEFORE_SWAP_FLAG: u16 = 1 << 7;
+pub const AFTER_SWAP_FLAG: u16 = 1 << 6;
+pub const BEFORE_SWAP_RETURNS_DELTA_FLAG: u16 = 1 << 3;
+pub const AFTER_SWAP_RETURNS_DELTA_FLAG: u16 = 1 << 2;
+pub const SWAP_HOOKS_MASK: u16 = BEFORE_SWAP_FLAG | AFTER_SWAP_FLAG | BEFORE_SWAP_RETURNS_DELTA_FLAG | AFTER_SWAP_RETURNS_DELTA_FLAG;
+
+/// Fee value of pools with fee set by hooks
+pub const DYNAMIC_FEE_FLAG: u32 = 0x800000;
+
+const SWAP_GAS_USED: u64 = 150_000;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct UniswapV4PoolKey {
+    pub currency0: Address,
+    pub currency1: Address,
+    pub fee: u32,
+    pub tick_spacing: i32,
+    pub hooks: Address,
+}
+
+impl UniswapV4PoolKey {
+    pub fn new(currency0: Address, currency1: Address, fee: u32, tick_spacing: i32, hooks: Address) -> Self {
+        Self { currency0, currency1, fee, tick_spacing, hooks }
+    }
+
+    pub fn to_abi(&self) -> PoolKey {
+        PoolKey {
+            currency0: self.currency0,
+            currency1: self.currency1,
+            fee: Uint::<24, 1>::from(self.fee),
+            tickSpacing: Signed::<24, 1>::try_from(self.tick_spacing).unwrap_or_default(),
+            hooks: self.hooks,
+        }
+    }
+
+    /// Pool id is keccak256 of abi encoded pool key
+    pub fn pool_id(&self) -> B256 {
+        keccak256(self.to_abi().abi_encode())
+    }
+
+    pub fn hooks_flags(&self) -> u16 {
+        let hooks = self.hooks.as_slice();
+        u16::from_be_bytes([hooks[18], hooks[19]]) & 0x3FFF
+    }
+
+    /// Swaps are not affected by hooks and fee is static, so the pool can be calculated natively
+    pub fn is_swap_supported(&self) -> bool {
+        self.hooks_flags() & SWAP_HOOKS_MASK == 0 && self.fee & DYNAMIC_FEE_FLAG == 0
+    }
+
+    /// Native currency is settled with value, multicaller works with WETH only
+    pub fn is_native(&self) -> bool {
+        self.currency0.is_zero()
+    }
+}
+
+#[derive(Clone)]
+pub struct UniswapV4Pool {
+    pool_manager: Address,
+    pool_id: B256,
+    pub key: UniswapV4PoolKey,
+    pub liquidity: u128,
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    encoder: UniswapV4AbiSwapEncoder,
+}
+
+impl UniswapV4Pool {
+    pub fn new(pool_manager: Address, key: UniswapV4PoolKey) -> Self {
+        Self::new_with_data(pool_manager, key, 0, U256::ZERO, 0)
+    }
+
+    pub fn new_with_data(pool_manager: Address, key: UniswapV4PoolKey, liquidity: u128, sqrt_price_x96: U256, tick: i32) -> Self {
+        UniswapV4Pool {
+            pool_manager,
+            pool_id: key.pool_id(),
+            key,
+            liquidity,
+            sqrt_price_x96,
+            tick,
+            encoder: UniswapV4AbiSwapEncoder::new(key),
+        }
+    }
+
+    pub fn get_pool_manager(&self) -> Address {
+        self.pool_manager
+    }
+
+    pub fn get_pool_id_bytes(&self) -> B256 {
+        self.pool_id
+    }
+
+    pub fn get_zero_for_one(token_address_from: &Address, token_address_to: &Address) -> bool {
+        token_address_from.lt(token_address_to)
+    }
+
+    pub fn get_price_limit(token_address_from: &Address, token_address_to: &Address) -> U160 {
+        if Self::get_zero_for_one(token_address_from, token_address_to) {
+            (MIN_SQRT_RATIO + U256::from(1)).to()
+        } else {
+            (MAX_SQRT_RATIO - U256::from(1)).to()
+        }
+    }
+
+    pub fn fetch_pool_data_evm(db: &dyn DatabaseRef<Error = ErrReport>, pool_manager: Address, key: UniswapV4PoolKey) -> Result<Self> {
+        let pool_id = key.pool_id();
+        let slot0 = UniswapV4DBReader::slot0(&db, pool_manager, pool_id)?;
+        if slot0.sqrt_price_x96.is_zero() {
+            return Err(eyre!("POOL_NOT_INITIALIZED"));
+        }
+        let liquidity = UniswapV4DBReader::liquidity(&db, pool_manager, pool_id)?;
+
+        Ok(Self::new_with_data(pool_manager, key, liquidity, slot0.sqrt_price_x96, slot0.tick))
+    }
+
+    pub async fn fetch_pool_data<N: Network, P: Provider<N> + Send + Sync + Clone + 'static>(
+        client: P,
+        pool_manager: Address,
+        key: UniswapV4PoolKey,
+    ) -> Result<Self> {
+        let pool_id = key.pool_id();
+        let state_view = IStateView::new(PeripheryAddress::UNISWAPV4_STATE_VIEW_ADDRESS, client);
+
+        let slot0 = state_view.getSlot0(pool_id).call().await?;
+        if slot0.sqrtPriceX96.is_zero() {
+            return Err(eyre!("POOL_NOT_INITIALIZED"));
+        }
+        let liquidity = state_view.getLiquidity(pool_id).call().await?.liquidity;
+
+        Ok(Self::new_with_data(pool_manager, key, liquidity, slot0.sqrtPriceX96.to(), slot0.tick.as_i32()))
+    }
+
+    fn quote_exact_input_encode(&self, zero_for_one: bool, amount: u128) -> Bytes {
+        IV4Quoter::quoteExactInputSingleCall {
+            params: IV4Quoter::QuoteExactSingleParams {
+                poolKey: self.key.to_abi(),
+                zeroForOne: zero_for_one,
+                exactAmount: amount,
+                hookData: Bytes::new(),
+            },
+        }
+        .abi_encode()
+        .into()
+    }
+}
+
+impl Pool for UniswapV4Pool {
+    fn as_any<'a>(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_class(&self) -> PoolClass {
+        PoolClass::UniswapV4
+    }
+
+    fn get_protocol(&self) -> PoolProtocol {
+        PoolProtocol::UniswapV4
+    }
+
+    fn get_address(&self) -> Address {
+        self.pool_manager
+    }
+
+    fn get_pool_id(&self) -> PoolId {
+        PoolId::Bytes32(self.pool_id)
+    }
+
+    fn get_fee(&self) -> U256 {
+        U256::from(self.key.fee)
+    }
+
+    fn get_tokens(&self) -> Vec<Address> {
+        vec![self.key.currency0, self.key.currency1]
+    }
+
+    fn get_swap_directions(&self) -> Vec<SwapDirection> {
+        vec![(self.key.currency0, self.key.currency1).into(), (self.key.currency1, self.key.currency0).into()]
+    }
+
+    fn calculate_out_amount(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        _env: Env,
+        token_address_from: &Address,
+        _token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let ret = UniswapV4PoolVirtual::simulate_swap_in_amount_provided(&state_db, self, *token_address_from, in_amount)?;
+
+        if ret.is_zero() {
+            Err(eyre!("RETURN_RESULT_IS_ZERO"))
+        } else {
+            Ok((ret.checked_sub(U256::from(1)).ok_or_eyre("SUB_OVERFLOWN")?, SWAP_GAS_USED))
+        }
+    }
+
+    fn calculate_in_amount(
+        &self,
+        state_db: &dyn DatabaseRef<Error = ErrReport>,
+        _env: Env,
+        token_address_from: &Address,
+        _token_address_to: &Address,
+        out_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let ret = UniswapV4PoolVirtual::simulate_swap_out_amount_provided(&state_db, self, *token_address_from, out_amount)?;
+
+        if ret.is_zero() {
+            Err(eyre!("RETURN_RESULT_IS_ZERO"))
+        } else {
+            Ok((ret.checked_add(U256::from(1)).ok_or_eyre("ADD_OVERFLOWN")?, SWAP_GAS_USED))
+        }
+    }
+
+    fn can_flash_swap(&self) -> bool {
+        false
+    }
+
+    fn can_calculate_in_amount(&self) -> bool {
+        true
+    }
+
+    fn get_abi_encoder(&self) -> Option<&dyn PoolAbiEncoder> {
+        Some(&self.encoder)
+    }
+
+    fn get_read_only_cell_vec(&self) -> Vec<U256> {
+        Vec::new()
+    }
+
+    fn get_state_required(&self) -> Result<RequiredState> {
+        if self.key.tick_spacing <= 0 {
+            return Err(eyre!("BAD_TICK_SPACING"));
+        }
+        let tick_bitmap_index = (self.tick / self.key.tick_spacing) >> 8;
+
+        let mut state_required = RequiredState::new();
+        state_required
+            .add_call(PeripheryAddress::UNISWAPV4_STATE_VIEW_ADDRESS, IStateView::getSlot0Call { poolId: self.pool_id }.abi_encode())
+            .add_call(PeripheryAddress::UNISWAPV4_STATE_VIEW_ADDRESS, IStateView::getLiquidityCall { poolId: self.pool_id }.abi_encode());
+
+        for i in -4..=3 {
+            state_required.add_call(
+                PeripheryAddress::UNISWAPV4_STATE_VIEW_ADDRESS,
+                IStateView::getTickBitmapCall { poolId: self.pool_id, tick: (tick_bitmap_index + i) as i16 }.abi_encode(),
+            );
+        }
+
+        // Quotes moving the price by ~5% to fetch liquidity of crossed ticks
+        if self.liquidity > 0 && !self.sqrt_price_x96.is_zero() {
+            let liquidity = U256::from(self.liquidity);
+            let amount0: u128 = ((liquidity << 96) / self.sqrt_price_x96 / U256::from(20)).saturating_to();
+            let amount1: u128 = (((liquidity * self.sqrt_price_x96) >> 96) / U256::from(20)).saturating_to();
+
+            state_required
+                .add_call(PeripheryAddress::UNISWAP_V4_QUOTER, self.quote_exact_input_encode(true, amount0))
+                .add_call(PeripheryAddress::UNISWAP_V4_QUOTER, self.quote_exact_input_encode(false, amount1));
+        }
+
+        Ok(state_required)
+    }
+
+    fn is_native(&self) -> bool {
+        self.key.is_native()
+    }
+
+    fn preswap_requirement(&self) -> PreswapRequirement {
+        PreswapRequirement::Callback
+    }
+
+    fn get_pool_manager_cells(&self) -> Vec<(Address, Vec<U256>)> {
+        let state_cell = UniswapV4DBReader::pool_state_cell(self.pool_id);
+        vec![(self.pool_manager, vec![state_cell, state_cell + U256::from(LIQUIDITY_OFFSET)])]
+    }
+}
+
+/// Encodes `PoolManager.swap`, the call has to be made inside of `unlock` callback and settled with `sync`, `settle` and `take`
+#[derive(Clone, Copy)]
+struct UniswapV4AbiSwapEncoder {
+    key: UniswapV4PoolKey,
+}
+
+impl UniswapV4AbiSwapEncoder {
+    pub fn new(key: UniswapV4PoolKey) -> Self {
+        Self { key }
+    }
+
+    fn encode_swap(&self, token_from_address: Address, token_to_address: Address, amount_specified: I256) -> Bytes {
+        let swap_call = IUniswapV4PoolManager::swapCall {
+            key: self.key.to_abi(),
+            params: IUniswapV4PoolManager::SwapParams {
+                zeroForOne: UniswapV4Pool::get_zero_for_one(&token_from_address, &token_to_address),
+                amountSpecified: amount_specified,
+                sqrtPriceLimitX96: UniswapV4Pool::get_price_limit(&token_from_address, &token_to_address),
+            },
+            hookData: Bytes::new(),
+        };
+        Bytes::from(swap_call.abi_encode())
+    }
+}
+
+impl PoolAbiEncoder for UniswapV4AbiSwapEncoder {
+    // Negative amount specified is exact input in V4
+    fn encode_swap_in_amount_provided(
+        &self,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount: U256,
+        _recipient: Address,
+        _payload: Bytes,
+    ) -> Result<Bytes> {
+        Ok(self.encode_swap(token_from_address, token_to_address, -I256::from_raw(amount)))
+    }
+
+    fn encode_swap_out_amount_provided(
+        &self,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount: U256,
+        _recipient: Address,
+        _payload: Bytes,
+    ) -> Result<Bytes> {
+        Ok(self.encode_swap(token_from_address, token_to_address, I256::from_raw(amount)))
+    }
+
+    fn swap_in_amount_offset(&self, _token_from_address: Address, _token_to_address: Address) -> Option<u32> {
+        Some(0xC4)
+    }
+
+    fn swap_out_amount_offset(&self, _token_from_address: Address, _token_to_address: Address) -> Option<u32> {
+        Some(0xC4)
+    }
+
+    // BalanceDelta is amount0 in upper and amount1 in lower 128 bits
+    fn swap_in_amount_return_offset(&self, token_from_address: Address, token_to_address: Address) -> Option<u32> {
+        if UniswapV4Pool::get_zero_for_one(&token_from_address, &token_to_address) {
+            Some(0x10)
+        } else {
+            Some(0x0)
+        }
+    }
+
+    fn swap_out_amount_return_offset(&self, token_from_address: Address, token_to_address: Address) -> Option<u32> {
+        if UniswapV4Pool::get_zero_for_one(&token_from_address, &token_to_address) {
+            Some(0x0)
+        } else {
+            Some(0x10)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::db_reader::UniswapV3DBReader;
+    use crate::virtual_impl::UniswapV3PoolVirtual;
+    use crate::UniswapV3Pool;
+    use alloy::primitives::address;
+    use loom_defi_address_book::FactoryAddress;
+    use loom_defi_address_book::TokenAddressEth;
+    use loom_evm_db::LoomDBType;
+
+    const SQRT_PRICE_X96: &str = "1771595571142957166518320255467520";
+    const LIQUIDITY: u128 = 10u128.pow(22);
+
+    #[test]
+    fn test_hooks_filter() {
+        let key = UniswapV4PoolKey::new(TokenAddressEth::USDC, TokenAddressEth::WETH, 3000, 60, Address::ZERO);
+        assert!(key.is_swap_supported());
+
+        // before add liquidity hook only
+        let key = UniswapV4PoolKey { hooks: address!("0000000000000000000000000000000000000800"), ..key };
+        assert!(key.is_swap_supported());
+
+        let key = UniswapV4PoolKey { hooks: address!("0000000000000000000000000000000000000080"), ..key };
+        assert!(!key.is_swap_supported());
+
+        let key = UniswapV4PoolKey { hooks: address!("00000000000000000000000000000000000000c4"), ..key };
+        assert!(!key.is_swap_supported());
+
+        let key = UniswapV4PoolKey { hooks: Address::ZERO, fee: DYNAMIC_FEE_FLAG, ..key };
+        assert!(!key.is_swap_supported());
+    }
+
+    #[test]
+    fn test_pool_id() {
+        let key = UniswapV4PoolKey::new(TokenAddressEth::USDC, TokenAddressEth::WETH, 3000, 60, Address::ZERO);
+        let pool = UniswapV4Pool::new(FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS, key);
+        assert_eq!(pool.get_pool_id(), PoolId::Bytes32(keccak256(key.to_abi().abi_encode())));
+        assert_eq!(pool.get_address(), FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS);
+        assert_eq!(pool.get_pool_manager_cells()[0].1[0], UniswapV4DBReader::pool_state_cell(pool.pool_id));
+    }
+
+    #[test]
+    fn test_calculate_amounts_match_uniswap3() -> Result<()> {
+        // Same price and liquidity in V3 pool and V4 pool manager storage, no initialized ticks
+        let sqrt_price_x96 = U256::from_str_radix(SQRT_PRICE_X96, 10)?;
+        let tick = loom_defi_uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(sqrt_price_x96)?;
+        let tick_cell = U256::from_be_bytes(I256::try_from(tick)?.to_be_bytes::<32>()) & ((U256::from(1) << 24) - U256::from(1));
+        let slot0_cell = sqrt_price_x96 | (tick_cell << 160);
+
+        let mut state_db = LoomDBType::default();
+
+        let v3_address = Address::repeat_byte(3);
+        let v3_pool =
+            UniswapV3Pool::new_with_data(v3_address, TokenAddressEth::USDC, TokenAddressEth::WETH, LIQUIDITY, 3000, None, Address::ZERO);
+        state_db.insert_account_storage(v3_address, U256::ZERO, slot0_cell)?;
+        state_db.insert_account_storage(v3_address, U256::from(4), U256::from(LIQUIDITY))?;
+        assert_eq!(UniswapV3DBReader::slot0(&state_db, v3_address)?.tick.as_i32(), tick);
+
+        let pool_manager = FactoryAddress::UNISWAP_V4_POOL_MANAGER_ADDRESS;
+        let key = UniswapV4PoolKey::new(TokenAddressEth::USDC, TokenAddressEth::WETH, 3000, 60, Address::ZERO);
+        let state_cell = UniswapV4DBReader::pool_state_cell(key.pool_id());
+        state_db.insert_account_storage(pool_manager, state_cell, slot0_cell | (U256::from(3000) << 208))?;
+        state_db.insert_account_storage(pool_manager, state_cell + U256::from(LIQUIDITY_OFFSET), U256::from(LIQUIDITY))?;
+
+        let v4_pool = UniswapV4Pool::fetch_pool_data_evm(&state_db, pool_manager, key)?;
+        assert_eq!(v4_pool.tick, tick);
+        assert_eq!(v4_pool.liquidity, LIQUIDITY);
+
+        for (token_from, amount) in [(TokenAddressEth::USDC, U256::from(10u64.pow(10))), (TokenAddressEth::WETH, U256::from(10u64.pow(18)))]
+        {
+            let v3_amount_out = UniswapV3PoolVirtual::simulate_swap_in_amount_provider(&state_db, &v3_pool, token_from, amount)?;
+            let v4_amount_out = UniswapV4PoolVirtual::simulate_swap_in_amount_provided(&state_db, &v4_pool, token_from, amount)?;
+            assert!(!v4_amount_out.is_zero());
+            assert_eq!(v4_amount_out, v3_amount_out);
+
+            let v3_amount_in = UniswapV3PoolVirtual::simulate_swap_out_amount_provided(&state_db, &v3_pool, token_from, v3_amount_out)?;
+            let v4_amount_in = UniswapV4PoolVirtual::simulate_swap_out_amount_provided(&state_db, &v4_pool, token_from, v4_amount_out)?;
+            assert_eq!(v4_amount_in, v3_amount_in);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,93 @@
+use alloy::primitives::U256;
+use eyre::{eyre, Result};
+
+use super::log_exp_math::LogExpMath;
+
+pub const ONE: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+const TWO: U256 = U256::from_limbs([2_000_000_000_000_000_000, 0, 0, 0]);
+const FOUR: U256 = U256::from_limbs([4_000_000_000_000_000_000, 0, 0, 0]);
+// 10^4 as 18 decimals is 10^-14 relative error of LogExpMath.pow
+const MAX_POW_RELATIVE_ERROR: U256 = U256::from_limbs([10_000, 0, 0, 0]);
+
+/// Port of Balancer V2 FixedPoint, 18 decimals with explicit rounding direction
+pub struct FixedPoint;
+
+impl FixedPoint {
+    pub fn mul_down(a: U256, b: U256) -> Result<U256> {
+        Ok(a.checked_mul(b).ok_or_else(|| eyre!("MUL_OVERFLOW"))? / ONE)
+    }
+
+    pub fn mul_up(a: U256, b: U256) -> Result<U256> {
+        let product = a.checked_mul(b).ok_or_else(|| eyre!("MUL_OVERFLOW"))?;
+        if product.is_zero() {
+            Ok(U256::ZERO)
+        } else {
+            Ok((product - U256::from(1)) / ONE + U256::from(1))
+        }
+    }
+
+    pub fn div_down(a: U256, b: U256) -> Result<U256> {
+        if b.is_zero() {
+            return Err(eyre!("ZERO_DIVISION"));
+        }
+        Ok(a.checked_mul(ONE).ok_or_else(|| eyre!("DIV_INTERNAL"))? / b)
+    }
+
+    pub fn div_up(a: U256, b: U256) -> Result<U256> {
+        if b.is_zero() {
+            return Err(eyre!("ZERO_DIVISION"));
+        }
+        if a.is_zero() {
+            Ok(U256::ZERO)
+        } else {
+            Ok((a.checked_mul(ONE).ok_or_else(|| eyre!("DIV_INTERNAL"))? - U256::from(1)) / b + U256::from(1))
+        }
+    }
+
+    pub fn pow_down(x: U256, y: U256) -> Result<U256> {
+        if y == ONE {
+            Ok(x)
+        } else if y == TWO {
+            Self::mul_down(x, x)
+        } else if y == FOUR {
+            let square = Self::mul_down(x, x)?;
+            Self::mul_down(square, square)
+        } else {
+            let raw = LogExpMath::pow(x, y)?;
+            let max_error = Self::mul_up(raw, MAX_POW_RELATIVE_ERROR)? + U256::from(1);
+            Ok(raw.saturating_sub(max_error))
+        }
+    }
+
+    pub fn pow_up(x: U256, y: U256) -> Result<U256> {
+        if y == ONE {
+            Ok(x)
+        } else if y == TWO {
+            Self::mul_up(x, x)
+        } else if y == FOUR {
+            let square = Self::mul_up(x, x)?;
+            Self::mul_up(square, square)
+        } else {
+            let raw = LogExpMath::pow(x, y)?;
+            let max_error = Self::mul_up(raw, MAX_POW_RELATIVE_ERROR)? + U256::from(1);
+            Ok(raw + max_error)
+        }
+    }
+
+    /// 1 - x, saturating at zero
+    pub fn complement(x: U256) -> U256 {
+        ONE.saturating_sub(x)
+    }
+}
+
+/// Integer division rounding up as Balancer Math.divUp
+pub fn div_up_raw(a: U256, b: U256) -> Result<U256> {
+    if b.is_zero() {
+        return Err(eyre!("ZERO_DIVISION"));
+    }
+    if a.is_zero() {
+        Ok(U256::ZERO)
+    } else {
+        Ok((a - U256::from(1)) / b + U256::from(1))
+    }
+}
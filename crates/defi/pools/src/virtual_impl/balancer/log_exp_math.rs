@@ -0,0 +1,214 @@
+use alloy::primitives::{I256, U256};
+use eyre::{eyre, Result};
+use lazy_static::lazy_static;
+
+// Port of Balancer V2 LogExpMath, all values are 18 decimals fixed point unless noted otherwise
+
+fn i256(value: &str) -> I256 {
+    I256::from_dec_str(value).unwrap()
+}
+
+lazy_static! {
+    static ref ONE_18: I256 = i256("1000000000000000000");
+    static ref ONE_20: I256 = i256("100000000000000000000");
+    static ref ONE_36: I256 = i256("1000000000000000000000000000000000000");
+    static ref MAX_NATURAL_EXPONENT: I256 = i256("130000000000000000000");
+    static ref MIN_NATURAL_EXPONENT: I256 = i256("-41000000000000000000");
+    static ref LN_36_LOWER_BOUND: I256 = i256("900000000000000000");
+    static ref LN_36_UPPER_BOUND: I256 = i256("1100000000000000000");
+    static ref MILD_EXPONENT_BOUND: U256 = (U256::from(1) << 254) / U256::from(100000000000000000000u128);
+
+    // 18 decimals, a0 and a1 have no decimals
+    static ref X0: I256 = i256("128000000000000000000");
+    static ref A0: I256 = i256("38877084059945950922200000000000000000000000000000000000");
+    static ref X1: I256 = i256("64000000000000000000");
+    static ref A1: I256 = i256("6235149080811616882910000000");
+
+    // 20 decimals
+    static ref X2: I256 = i256("3200000000000000000000");
+    static ref A2: I256 = i256("7896296018268069516100000000000000");
+    static ref X3: I256 = i256("1600000000000000000000");
+    static ref A3: I256 = i256("888611052050787263676000000");
+    static ref X4: I256 = i256("800000000000000000000");
+    static ref A4: I256 = i256("298095798704172827474000");
+    static ref X5: I256 = i256("400000000000000000000");
+    static ref A5: I256 = i256("5459815003314423907810");
+    static ref X6: I256 = i256("200000000000000000000");
+    static ref A6: I256 = i256("738905609893065022723");
+    static ref X7: I256 = i256("100000000000000000000");
+    static ref A7: I256 = i256("271828182845904523536");
+    static ref X8: I256 = i256("50000000000000000000");
+    static ref A8: I256 = i256("164872127070012814685");
+    static ref X9: I256 = i256("25000000000000000000");
+    static ref A9: I256 = i256("128402541668774148407");
+    static ref X10: I256 = i256("12500000000000000000");
+    static ref A10: I256 = i256("113314845306682631683");
+    static ref X11: I256 = i256("6250000000000000000");
+    static ref A11: I256 = i256("106449445891785942956");
+}
+
+pub struct LogExpMath;
+
+impl LogExpMath {
+    /// x^y, both arguments and result are 18 decimals fixed point
+    pub fn pow(x: U256, y: U256) -> Result<U256> {
+        if y.is_zero() {
+            return Ok(ONE_18.into_raw());
+        }
+        if x.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        if x.bit(255) {
+            return Err(eyre!("X_OUT_OF_BOUNDS"));
+        }
+        if y >= *MILD_EXPONENT_BOUND {
+            return Err(eyre!("Y_OUT_OF_BOUNDS"));
+        }
+        let x = I256::from_raw(x);
+        let y = I256::from_raw(y);
+
+        let logx_times_y = if *LN_36_LOWER_BOUND < x && x < *LN_36_UPPER_BOUND {
+            let ln_36_x = Self::ln_36(x);
+            (ln_36_x / *ONE_18) * y + ((ln_36_x % *ONE_18) * y) / *ONE_18
+        } else {
+            Self::ln(x) * y
+        };
+        let logx_times_y = logx_times_y / *ONE_18;
+
+        if logx_times_y < *MIN_NATURAL_EXPONENT || logx_times_y > *MAX_NATURAL_EXPONENT {
+            return Err(eyre!("PRODUCT_OUT_OF_BOUNDS"));
+        }
+
+        Ok(Self::exp(logx_times_y)?.into_raw())
+    }
+
+    /// Natural exponentiation e^x
+    pub fn exp(x: I256) -> Result<I256> {
+        if x < *MIN_NATURAL_EXPONENT || x > *MAX_NATURAL_EXPONENT {
+            return Err(eyre!("INVALID_EXPONENT"));
+        }
+        if x.is_negative() {
+            return Ok((*ONE_18 * *ONE_18) / Self::exp(-x)?);
+        }
+
+        let mut x = x;
+        let first_an = if x >= *X0 {
+            x -= *X0;
+            *A0
+        } else if x >= *X1 {
+            x -= *X1;
+            *A1
+        } else {
+            I256::ONE
+        };
+
+        // switching to 20 decimals for higher precision
+        x *= I256::try_from(100).unwrap();
+
+        let mut product = *ONE_20;
+        for (xn, an) in [(*X2, *A2), (*X3, *A3), (*X4, *A4), (*X5, *A5), (*X6, *A6), (*X7, *A7), (*X8, *A8), (*X9, *A9)] {
+            if x >= xn {
+                x -= xn;
+                product = (product * an) / *ONE_20;
+            }
+        }
+
+        // Taylor series for the remainder
+        let mut series_sum = *ONE_20;
+        let mut term = x;
+        series_sum += term;
+        for i in 2..=12 {
+            term = ((term * x) / *ONE_20) / I256::try_from(i).unwrap();
+            series_sum += term;
+        }
+
+        Ok((((product * series_sum) / *ONE_20) * first_an) / I256::try_from(100).unwrap())
+    }
+
+    fn ln(a: I256) -> I256 {
+        if a < *ONE_18 {
+            return -Self::ln((*ONE_18 * *ONE_18) / a);
+        }
+
+        let mut a = a;
+        let mut sum = I256::ZERO;
+        if a >= *A0 * *ONE_18 {
+            a /= *A0;
+            sum += *X0;
+        }
+        if a >= *A1 * *ONE_18 {
+            a /= *A1;
+            sum += *X1;
+        }
+
+        // switching to 20 decimals for higher precision
+        let hundred = I256::try_from(100).unwrap();
+        sum *= hundred;
+        a *= hundred;
+
+        for (xn, an) in
+            [(*X2, *A2), (*X3, *A3), (*X4, *A4), (*X5, *A5), (*X6, *A6), (*X7, *A7), (*X8, *A8), (*X9, *A9), (*X10, *A10), (*X11, *A11)]
+        {
+            if a >= an {
+                a = (a * *ONE_20) / an;
+                sum += xn;
+            }
+        }
+
+        // ln(a) = 2 * artanh((a - 1) / (a + 1))
+        let z = ((a - *ONE_20) * *ONE_20) / (a + *ONE_20);
+        let z_squared = (z * z) / *ONE_20;
+
+        let mut num = z;
+        let mut series_sum = num;
+        for i in [3, 5, 7, 9, 11] {
+            num = (num * z_squared) / *ONE_20;
+            series_sum += num / I256::try_from(i).unwrap();
+        }
+        series_sum *= I256::try_from(2).unwrap();
+
+        (sum + series_sum) / hundred
+    }
+
+    /// Natural logarithm with 36 decimals result for x close to one
+    fn ln_36(x: I256) -> I256 {
+        let x = x * *ONE_18;
+
+        let z = ((x - *ONE_36) * *ONE_36) / (x + *ONE_36);
+        let z_squared = (z * z) / *ONE_36;
+
+        let mut num = z;
+        let mut series_sum = num;
+        for i in [3, 5, 7, 9, 11, 13, 15] {
+            num = (num * z_squared) / *ONE_36;
+            series_sum += num / I256::try_from(i).unwrap();
+        }
+
+        series_sum * I256::try_from(2).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn assert_approx(value: U256, expected: U256) {
+        let diff = if value > expected { value - expected } else { expected - value };
+        assert!(diff * U256::from(10).pow(U256::from(14)) <= expected, "{value} != {expected}");
+    }
+
+    #[test]
+    fn test_pow() {
+        let one = U256::from(10).pow(U256::from(18));
+        assert_eq!(LogExpMath::pow(U256::from(2) * one, U256::ZERO).unwrap(), one);
+        // sqrt(2)
+        assert_approx(LogExpMath::pow(U256::from(2) * one, one / U256::from(2)).unwrap(), U256::from(1414213562373095048u64));
+        // 1.05^3
+        assert_approx(
+            LogExpMath::pow(U256::from(105) * one / U256::from(100), U256::from(3) * one).unwrap(),
+            U256::from(1157625000000000000u64),
+        );
+        // 0.5^0.25
+        assert_approx(LogExpMath::pow(one / U256::from(2), one / U256::from(4)).unwrap(), U256::from(840896415253714543u64));
+    }
+}
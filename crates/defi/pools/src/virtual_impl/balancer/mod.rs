@@ -0,0 +1,8 @@
+pub use fixed_point::{FixedPoint, ONE};
+pub use stable_math::{StableMath, AMP_PRECISION};
+pub use weighted_math::WeightedMath;
+
+mod fixed_point;
+mod log_exp_math;
+mod stable_math;
+mod weighted_math;
@@ -0,0 +1,138 @@
+use alloy::primitives::U256;
+use eyre::{eyre, Result};
+
+use super::fixed_point::div_up_raw;
+
+pub const AMP_PRECISION: U256 = U256::from_limbs([1_000, 0, 0, 0]);
+const MAX_ITERATIONS: usize = 255;
+
+/// Port of Balancer V2 StableMath, amplification is multiplied by AMP_PRECISION and balances are upscaled to 18 decimals
+pub struct StableMath;
+
+impl StableMath {
+    pub fn calculate_invariant(amplification_parameter: U256, balances: &[U256]) -> Result<U256> {
+        let sum: U256 = balances.iter().sum();
+        if sum.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        let num_tokens = U256::from(balances.len());
+
+        let mut invariant = sum;
+        let amp_times_total = amplification_parameter * num_tokens;
+
+        for _ in 0..MAX_ITERATIONS {
+            let mut d_p = invariant;
+            for balance in balances.iter() {
+                d_p = (d_p * invariant) / (*balance * num_tokens);
+            }
+
+            let prev_invariant = invariant;
+            invariant = ((amp_times_total * sum / AMP_PRECISION + d_p * num_tokens) * invariant)
+                / ((amp_times_total - AMP_PRECISION) * invariant / AMP_PRECISION + (num_tokens + U256::from(1)) * d_p);
+
+            if invariant.abs_diff(prev_invariant) <= U256::from(1) {
+                return Ok(invariant);
+            }
+        }
+
+        Err(eyre!("STABLE_INVARIANT_DIDNT_CONVERGE"))
+    }
+
+    pub fn calc_out_given_in(
+        amplification_parameter: U256,
+        balances: &[U256],
+        token_index_in: usize,
+        token_index_out: usize,
+        token_amount_in: U256,
+        invariant: U256,
+    ) -> Result<U256> {
+        let mut balances = balances.to_vec();
+        balances[token_index_in] += token_amount_in;
+
+        let final_balance_out =
+            Self::get_token_balance_given_invariant_and_all_other_balances(amplification_parameter, &balances, invariant, token_index_out)?;
+
+        balances[token_index_out]
+            .checked_sub(final_balance_out)
+            .and_then(|x| x.checked_sub(U256::from(1)))
+            .ok_or_else(|| eyre!("SUB_OVERFLOW"))
+    }
+
+    pub fn calc_in_given_out(
+        amplification_parameter: U256,
+        balances: &[U256],
+        token_index_in: usize,
+        token_index_out: usize,
+        token_amount_out: U256,
+        invariant: U256,
+    ) -> Result<U256> {
+        let mut balances = balances.to_vec();
+        balances[token_index_out] = balances[token_index_out].checked_sub(token_amount_out).ok_or_else(|| eyre!("SUB_OVERFLOW"))?;
+
+        let final_balance_in =
+            Self::get_token_balance_given_invariant_and_all_other_balances(amplification_parameter, &balances, invariant, token_index_in)?;
+
+        Ok(final_balance_in.checked_sub(balances[token_index_in]).ok_or_else(|| eyre!("SUB_OVERFLOW"))? + U256::from(1))
+    }
+
+    fn get_token_balance_given_invariant_and_all_other_balances(
+        amplification_parameter: U256,
+        balances: &[U256],
+        invariant: U256,
+        token_index: usize,
+    ) -> Result<U256> {
+        let num_tokens = U256::from(balances.len());
+        let amp_times_total = amplification_parameter * num_tokens;
+
+        let mut sum = balances[0];
+        let mut p_d = balances[0] * num_tokens;
+        for balance in balances.iter().skip(1) {
+            p_d = (p_d * *balance * num_tokens) / invariant;
+            sum += *balance;
+        }
+        sum -= balances[token_index];
+
+        let inv2 = invariant * invariant;
+        // c is rounded up to round up the token balance
+        let c = div_up_raw(inv2, amp_times_total * p_d)? * AMP_PRECISION * balances[token_index];
+        let b = sum + (invariant / amp_times_total) * AMP_PRECISION;
+
+        let mut token_balance = div_up_raw(inv2 + c, invariant + b)?;
+
+        for _ in 0..MAX_ITERATIONS {
+            let prev_token_balance = token_balance;
+            token_balance = div_up_raw(token_balance * token_balance + c, token_balance * U256::from(2) + b - invariant)?;
+
+            if token_balance.abs_diff(prev_token_balance) <= U256::from(1) {
+                return Ok(token_balance);
+            }
+        }
+
+        Err(eyre!("STABLE_GET_BALANCE_DIDNT_CONVERGE"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::virtual_impl::balancer::ONE;
+
+    #[test]
+    fn test_balanced_pool() {
+        let amp = U256::from(200) * AMP_PRECISION;
+        let balances = vec![U256::from(1_000_000) * ONE; 3];
+
+        let invariant = StableMath::calculate_invariant(amp, &balances).unwrap();
+        assert!(invariant.abs_diff(U256::from(3_000_000) * ONE) <= U256::from(1));
+
+        let amount_in = U256::from(1_000) * ONE;
+        let amount_out = StableMath::calc_out_given_in(amp, &balances, 0, 1, amount_in, invariant).unwrap();
+        // price is close to one in balanced pool
+        assert!(amount_out < amount_in);
+        assert!(amount_out > amount_in * U256::from(999) / U256::from(1000));
+
+        let amount_in_back = StableMath::calc_in_given_out(amp, &balances, 0, 1, amount_out, invariant).unwrap();
+        assert!(amount_in_back >= amount_in);
+        assert!(amount_in_back - amount_in < U256::from(10).pow(U256::from(6)));
+    }
+}
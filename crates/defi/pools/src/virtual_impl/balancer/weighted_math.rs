@@ -0,0 +1,64 @@
+use alloy::primitives::U256;
+use eyre::{eyre, Result};
+
+use super::fixed_point::{FixedPoint, ONE};
+
+// Swap limits are 30% of the balance
+const MAX_IN_RATIO: U256 = U256::from_limbs([300_000_000_000_000_000, 0, 0, 0]);
+const MAX_OUT_RATIO: U256 = U256::from_limbs([300_000_000_000_000_000, 0, 0, 0]);
+
+/// Port of Balancer V2 WeightedMath, balances and amounts are upscaled to 18 decimals
+pub struct WeightedMath;
+
+impl WeightedMath {
+    pub fn calc_out_given_in(balance_in: U256, weight_in: U256, balance_out: U256, weight_out: U256, amount_in: U256) -> Result<U256> {
+        if amount_in > FixedPoint::mul_down(balance_in, MAX_IN_RATIO)? {
+            return Err(eyre!("MAX_IN_RATIO"));
+        }
+
+        let denominator = balance_in + amount_in;
+        let base = FixedPoint::div_up(balance_in, denominator)?;
+        let exponent = FixedPoint::div_down(weight_in, weight_out)?;
+        let power = FixedPoint::pow_up(base, exponent)?;
+
+        FixedPoint::mul_down(balance_out, FixedPoint::complement(power))
+    }
+
+    pub fn calc_in_given_out(balance_in: U256, weight_in: U256, balance_out: U256, weight_out: U256, amount_out: U256) -> Result<U256> {
+        if amount_out > FixedPoint::mul_down(balance_out, MAX_OUT_RATIO)? {
+            return Err(eyre!("MAX_OUT_RATIO"));
+        }
+
+        let base = FixedPoint::div_up(balance_out, balance_out - amount_out)?;
+        let exponent = FixedPoint::div_up(weight_out, weight_in)?;
+        let power = FixedPoint::pow_up(base, exponent)?;
+
+        let ratio = power - ONE;
+        FixedPoint::mul_up(balance_in, ratio)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_equal_weights_match_constant_product() {
+        let weight = ONE / U256::from(2);
+        let balance_in = U256::from(1_000) * ONE;
+        let balance_out = U256::from(2_000) * ONE;
+        let amount_in = U256::from(10) * ONE;
+
+        let amount_out = WeightedMath::calc_out_given_in(balance_in, weight, balance_out, weight, amount_in).unwrap();
+        let expected = balance_out * amount_in / (balance_in + amount_in);
+        // result is rounded down in favour of the pool
+        assert!(amount_out <= expected);
+        assert!(expected - amount_out < U256::from(10).pow(U256::from(6)));
+
+        let amount_in_back = WeightedMath::calc_in_given_out(balance_in, weight, balance_out, weight, amount_out).unwrap();
+        assert!(amount_in_back <= amount_in);
+        assert!(amount_in - amount_in_back < U256::from(10).pow(U256::from(6)));
+
+        assert!(WeightedMath::calc_out_given_in(balance_in, weight, balance_out, weight, balance_in).is_err());
+    }
+}
@@ -0,0 +1,10 @@
+use alloy::primitives::U256;
+
+pub use crypto_swap::{get_y_ng, newton_d, newton_d_ng, newton_y, CryptoSwapState, CryptoSwapVariant};
+pub use stable_swap::{MetaStableSwapState, StableSwapState, StableSwapVariant};
+
+mod crypto_swap;
+mod stable_swap;
+
+pub const PRECISION: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+pub const FEE_DENOMINATOR: U256 = U256::from_limbs([10_000_000_000, 0, 0, 0]);
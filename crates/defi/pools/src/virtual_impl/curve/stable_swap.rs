@@ -0,0 +1,320 @@
+use alloy::primitives::U256;
+use eyre::{eyre, Result};
+
+use super::{FEE_DENOMINATOR, PRECISION};
+
+const MAX_ITERATIONS: usize = 255;
+
+/// Differences between StableSwap contract generations that change rounding of results
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StableSwapVariant {
+    /// Early pools like 3pool, amplification without precision and fee taken after conversion to coin units
+    Legacy,
+    /// Pools with `A_PRECISION`, fee taken in 18 decimals before conversion to coin units
+    Precise,
+    /// Factory pools dividing `D_P` by `N^N` once per iteration of invariant calculation
+    Factory,
+}
+
+/// Port of Curve StableSwap math. Rates scale balances to 18 decimals and are multiplied by 1e18,
+/// for lending pools they include exchange rate of the wrapped coin, for metapools the base pool virtual price.
+#[derive(Clone, Debug)]
+pub struct StableSwapState {
+    pub variant: StableSwapVariant,
+    pub balances: Vec<U256>,
+    pub rates: Vec<U256>,
+    /// Amplification multiplied by `a_precision`
+    pub amp: U256,
+    pub a_precision: U256,
+    pub fee: U256,
+    /// Total supply of LP token, required only for liquidity calculations
+    pub total_supply: U256,
+}
+
+impl StableSwapState {
+    fn n_coins(&self) -> U256 {
+        U256::from(self.balances.len())
+    }
+
+    pub fn xp(&self) -> Vec<U256> {
+        self.xp_mem(&self.balances)
+    }
+
+    fn xp_mem(&self, balances: &[U256]) -> Vec<U256> {
+        balances.iter().zip(self.rates.iter()).map(|(balance, rate)| balance * rate / PRECISION).collect()
+    }
+
+    pub fn get_d(&self, xp: &[U256]) -> Result<U256> {
+        let n_coins = self.n_coins();
+        let sum: U256 = xp.iter().sum();
+        if sum.is_zero() {
+            return Ok(U256::ZERO);
+        }
+
+        let mut d = sum;
+        let ann = self.amp * n_coins;
+        let n_pow_n = n_coins.pow(n_coins);
+
+        for _ in 0..MAX_ITERATIONS {
+            let mut d_p = d;
+            for x in xp.iter() {
+                if x.is_zero() {
+                    return Err(eyre!("CURVE_ZERO_BALANCE"));
+                }
+                d_p = match self.variant {
+                    StableSwapVariant::Factory => d_p * d / x,
+                    _ => d_p * d / (x * n_coins),
+                };
+            }
+            if self.variant == StableSwapVariant::Factory {
+                d_p /= n_pow_n;
+            }
+
+            let d_prev = d;
+            d = (ann * sum / self.a_precision + d_p * n_coins) * d
+                / ((ann - self.a_precision) * d / self.a_precision + (n_coins + U256::from(1)) * d_p);
+
+            if d.abs_diff(d_prev) <= U256::from(1) {
+                return Ok(d);
+            }
+        }
+
+        Err(eyre!("CURVE_D_DIDNT_CONVERGE"))
+    }
+
+    /// Balance of coin `j` keeping invariant `d` after balance of coin `i` set to `x`, `i` is ignored when `None`
+    fn get_y_d(&self, i: Option<usize>, j: usize, x: U256, xp: &[U256], d: U256) -> Result<U256> {
+        let n_coins = self.n_coins();
+        let ann = self.amp * n_coins;
+
+        let mut c = d;
+        let mut s = U256::ZERO;
+        for (k, xp_k) in xp.iter().enumerate() {
+            let x_k = if Some(k) == i {
+                x
+            } else if k != j {
+                *xp_k
+            } else {
+                continue;
+            };
+            if x_k.is_zero() {
+                return Err(eyre!("CURVE_ZERO_BALANCE"));
+            }
+            s += x_k;
+            c = c * d / (x_k * n_coins);
+        }
+        c = c * d * self.a_precision / (ann * n_coins);
+        let b = s + d * self.a_precision / ann;
+
+        let mut y = d;
+        for _ in 0..MAX_ITERATIONS {
+            let y_prev = y;
+            y = (y * y + c) / (U256::from(2) * y + b).checked_sub(d).ok_or_else(|| eyre!("CURVE_Y_UNDERFLOW"))?;
+            if y.abs_diff(y_prev) <= U256::from(1) {
+                return Ok(y);
+            }
+        }
+
+        Err(eyre!("CURVE_Y_DIDNT_CONVERGE"))
+    }
+
+    pub fn get_y(&self, i: usize, j: usize, x: U256, xp: &[U256]) -> Result<U256> {
+        if i == j || i >= xp.len() || j >= xp.len() {
+            return Err(eyre!("CURVE_BAD_COIN_INDEX"));
+        }
+        let d = self.get_d(xp)?;
+        self.get_y_d(Some(i), j, x, xp, d)
+    }
+
+    pub fn get_dy(&self, i: usize, j: usize, dx: U256) -> Result<U256> {
+        let xp = self.xp();
+        let x = xp[i] + dx * self.rates[i] / PRECISION;
+        let y = self.get_y(i, j, x, &xp)?;
+        let dy = xp[j].checked_sub(y + U256::from(1)).ok_or_else(|| eyre!("CURVE_DY_UNDERFLOW"))?;
+
+        match self.variant {
+            StableSwapVariant::Legacy => {
+                let dy = dy * PRECISION / self.rates[j];
+                Ok(dy - self.fee * dy / FEE_DENOMINATOR)
+            }
+            _ => Ok((dy - self.fee * dy / FEE_DENOMINATOR) * PRECISION / self.rates[j]),
+        }
+    }
+
+    /// LP amount minted or burnt for balanced `amounts`, slippage is accounted and fees are not
+    pub fn calc_token_amount(&self, amounts: &[U256], deposit: bool) -> Result<U256> {
+        let d0 = self.get_d(&self.xp())?;
+        if d0.is_zero() {
+            return Err(eyre!("CURVE_EMPTY_POOL"));
+        }
+
+        let mut balances = self.balances.clone();
+        for (balance, amount) in balances.iter_mut().zip(amounts.iter()) {
+            *balance =
+                if deposit { *balance + amount } else { balance.checked_sub(*amount).ok_or_else(|| eyre!("CURVE_BALANCE_UNDERFLOW"))? };
+        }
+        let d1 = self.get_d(&self.xp_mem(&balances))?;
+
+        let diff = if deposit { d1.checked_sub(d0) } else { d0.checked_sub(d1) }.ok_or_else(|| eyre!("CURVE_D_UNDERFLOW"))?;
+        Ok(diff * self.total_supply / d0)
+    }
+
+    /// Amount of coin `i` received burning `token_amount` of LP
+    pub fn calc_withdraw_one_coin(&self, token_amount: U256, i: usize) -> Result<U256> {
+        if self.total_supply.is_zero() || i >= self.balances.len() {
+            return Err(eyre!("CURVE_BAD_WITHDRAW"));
+        }
+        let n_coins = self.n_coins();
+        let xp = self.xp();
+        let d0 = self.get_d(&xp)?;
+        let d1 = d0.checked_sub(token_amount * d0 / self.total_supply).ok_or_else(|| eyre!("CURVE_D_UNDERFLOW"))?;
+        let new_y = self.get_y_d(None, i, U256::ZERO, &xp, d1)?;
+
+        let fee = self.fee * n_coins / (U256::from(4) * (n_coins - U256::from(1)));
+        let mut xp_reduced = xp.clone();
+        for (k, xp_k) in xp.iter().enumerate() {
+            let dx_expected = if k == i { (xp_k * d1 / d0).saturating_sub(new_y) } else { xp_k - xp_k * d1 / d0 };
+            xp_reduced[k] -= fee * dx_expected / FEE_DENOMINATOR;
+        }
+
+        let dy =
+            xp_reduced[i].checked_sub(self.get_y_d(None, i, U256::ZERO, &xp_reduced, d1)?).ok_or_else(|| eyre!("CURVE_DY_UNDERFLOW"))?;
+        Ok(dy.saturating_sub(U256::from(1)) * PRECISION / self.rates[i])
+    }
+
+    pub fn get_virtual_price(&self) -> Result<U256> {
+        if self.total_supply.is_zero() {
+            return Err(eyre!("CURVE_EMPTY_POOL"));
+        }
+        Ok(self.get_d(&self.xp())? * PRECISION / self.total_supply)
+    }
+}
+
+/// Metapool paired with its base pool, second coin of the metapool is LP token of the base pool
+#[derive(Clone, Debug)]
+pub struct MetaStableSwapState {
+    /// Metapool state with virtual price of base pool as rate of the second coin
+    pub meta: StableSwapState,
+    pub base: StableSwapState,
+}
+
+impl MetaStableSwapState {
+    /// Swap between metapool coin and base pool coins, index 0 is metapool coin, base pool coins start from index 1
+    pub fn get_dy_underlying(&self, i: usize, j: usize, dx: U256) -> Result<U256> {
+        let max_coin = self.meta.balances.len() - 1;
+        let rates = &self.meta.rates;
+        let xp = self.meta.xp();
+
+        let (meta_i, meta_j) = (i.min(max_coin), j.min(max_coin));
+
+        let x = if i < max_coin {
+            xp[i] + dx * rates[i] / PRECISION
+        } else if j < max_coin {
+            let mut base_inputs = vec![U256::ZERO; self.base.balances.len()];
+            base_inputs[i - max_coin] = dx;
+            let x = self.base.calc_token_amount(&base_inputs, true)? * rates[max_coin] / PRECISION;
+            let x = x - x * self.base.fee / (U256::from(2) * FEE_DENOMINATOR);
+            x + xp[max_coin]
+        } else {
+            return self.base.get_dy(i - max_coin, j - max_coin, dx);
+        };
+
+        let y = self.meta.get_y(meta_i, meta_j, x, &xp)?;
+        let dy = xp[meta_j].checked_sub(y + U256::from(1)).ok_or_else(|| eyre!("CURVE_DY_UNDERFLOW"))?;
+        let dy = dy - self.meta.fee * dy / FEE_DENOMINATOR;
+
+        if j < max_coin {
+            Ok(dy * PRECISION / rates[meta_j])
+        } else {
+            self.base.calc_withdraw_one_coin(dy * PRECISION / rates[max_coin], j - max_coin)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn e(decimals: u64) -> U256 {
+        U256::from(10).pow(U256::from(decimals))
+    }
+
+    fn three_pool(variant: StableSwapVariant) -> StableSwapState {
+        let a_precision = if variant == StableSwapVariant::Legacy { U256::from(1) } else { U256::from(100) };
+        StableSwapState {
+            variant,
+            balances: vec![U256::from(1_000_000) * e(18), U256::from(1_000_000) * e(6), U256::from(1_000_000) * e(6)],
+            rates: vec![e(18), e(30), e(30)],
+            amp: U256::from(2000) * a_precision,
+            a_precision,
+            fee: U256::from(1_000_000),
+            total_supply: U256::from(2_900_000) * e(18),
+        }
+    }
+
+    #[test]
+    fn test_get_dy_balanced() {
+        for variant in [StableSwapVariant::Legacy, StableSwapVariant::Precise, StableSwapVariant::Factory] {
+            let state = three_pool(variant);
+            let dy = state.get_dy(0, 1, U256::from(1000) * e(18)).unwrap();
+            // 1bp fee and small slippage
+            assert!(dy < U256::from(999_900_000u64), "{variant:?} {dy}");
+            assert!(dy > U256::from(999_800_000u64), "{variant:?} {dy}");
+
+            let dx = state.get_dy(1, 0, dy).unwrap();
+            assert!(dx < U256::from(1000) * e(18));
+        }
+    }
+
+    #[test]
+    fn test_invariant_kept() {
+        let state = three_pool(StableSwapVariant::Precise);
+        let xp = state.xp();
+        let d = state.get_d(&xp).unwrap();
+        let y = state.get_y(0, 2, xp[0] + U256::from(100_000) * e(18), &xp).unwrap();
+
+        let mut new_xp = xp.clone();
+        new_xp[0] += U256::from(100_000) * e(18);
+        new_xp[2] = y;
+        assert!(state.get_d(&new_xp).unwrap().abs_diff(d) <= U256::from(3));
+    }
+
+    #[test]
+    fn test_liquidity() {
+        let state = three_pool(StableSwapVariant::Legacy);
+        let lp_amount = state.calc_token_amount(&[U256::ZERO, U256::from(1000) * e(6), U256::ZERO], true).unwrap();
+        let virtual_price = state.get_virtual_price().unwrap();
+        assert!(lp_amount * virtual_price / e(18) <= U256::from(1000) * e(18));
+
+        let amount = state.calc_withdraw_one_coin(lp_amount, 1).unwrap();
+        assert!(amount < U256::from(1000) * e(6));
+        assert!(amount > U256::from(999) * e(6));
+    }
+
+    #[test]
+    fn test_meta_get_dy_underlying() {
+        let base = three_pool(StableSwapVariant::Legacy);
+        let meta = StableSwapState {
+            variant: StableSwapVariant::Precise,
+            balances: vec![U256::from(1_000_000) * e(18), U256::from(1_000_000) * e(18)],
+            rates: vec![e(18), base.get_virtual_price().unwrap()],
+            amp: U256::from(100) * U256::from(100),
+            a_precision: U256::from(100),
+            fee: U256::from(4_000_000),
+            total_supply: U256::from(2_000_000) * e(18),
+        };
+        let state = MetaStableSwapState { meta, base };
+
+        // meta coin to USDC and back
+        let usdc_out = state.get_dy_underlying(0, 2, U256::from(1000) * e(18)).unwrap();
+        assert!(usdc_out > U256::from(990) * e(6) && usdc_out < U256::from(1000) * e(6), "{usdc_out}");
+        let meta_out = state.get_dy_underlying(2, 0, usdc_out).unwrap();
+        assert!(meta_out > U256::from(980) * e(18) && meta_out < U256::from(1000) * e(18), "{meta_out}");
+
+        // base pool coins are swapped in base pool
+        assert_eq!(
+            state.get_dy_underlying(1, 2, U256::from(1000) * e(18)).unwrap(),
+            state.base.get_dy(0, 1, U256::from(1000) * e(18)).unwrap()
+        );
+    }
+}
@@ -0,0 +1,296 @@
+use alloy::primitives::{Address, U256};
+use eyre::{eyre, OptionExt, Result};
+use loom_defi_uniswap_v3_math::full_math::{mul_div, mul_div_rounding_up};
+use loom_defi_uniswap_v3_math::tick_math::get_sqrt_ratio_at_tick;
+use loom_types_entities::Pool;
+use revm::DatabaseRef;
+
+use crate::db_reader::{MaverickDBReader, NUMBER_OF_KINDS};
+use crate::MaverickPool;
+
+/// 18 decimals fixed point one, Maverick keeps prices, liquidity and bin reserves in this precision
+const ONE: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+const Q96: U256 = U256::from_limbs([0, 1 << 32, 0, 0]);
+
+/// Max number of ticks to look for the next active bin before giving up
+const MAX_TICK_SEARCH: i32 = 4096;
+
+#[derive(Debug, Clone, Copy)]
+struct TickLiquidity {
+    sqrt_price: U256,
+    sqrt_lower_tick_price: U256,
+    sqrt_upper_tick_price: U256,
+    liquidity: U256,
+    reserve_a: U256,
+    reserve_b: U256,
+}
+
+/// Maverick V1 swap simulation over bins read from pool storage. Swap walks active ticks with the tick liquidity of all
+/// bins at the tick, same as `Pool.swap`, but without bin movements that are applied after the swap.
+pub struct MaverickPoolVirtual;
+
+impl MaverickPoolVirtual {
+    fn mul(a: U256, b: U256) -> Result<U256> {
+        Ok(mul_div(a, b, ONE)?)
+    }
+
+    fn mul_up(a: U256, b: U256) -> Result<U256> {
+        Ok(mul_div_rounding_up(a, b, ONE)?)
+    }
+
+    fn div(a: U256, b: U256) -> Result<U256> {
+        Ok(mul_div(a, ONE, b)?)
+    }
+
+    fn div_up(a: U256, b: U256) -> Result<U256> {
+        Ok(mul_div_rounding_up(a, ONE, b)?)
+    }
+
+    fn sqrt(a: U256) -> Result<U256> {
+        Ok(a.checked_mul(ONE).ok_or_eyre("SQRT_OVERFLOW")?.root(2))
+    }
+
+    /// Token amount in 18 decimals, scale is `10 ** decimals` of the token
+    fn to_internal(amount: U256, scale: U256) -> Result<U256> {
+        Ok(mul_div(amount, ONE, scale)?)
+    }
+
+    fn from_internal(amount: U256, scale: U256, round_up: bool) -> Result<U256> {
+        if round_up {
+            Ok(mul_div_rounding_up(amount, scale, ONE)?)
+        } else {
+            Ok(mul_div(amount, scale, ONE)?)
+        }
+    }
+
+    /// sqrt(1.0001 ^ (tick * tick_spacing)) in 18 decimals
+    pub fn tick_sqrt_price(tick_spacing: u32, tick: i32) -> Result<U256> {
+        let tick = tick.checked_mul(tick_spacing as i32).ok_or_eyre("TICK_OVERFLOW")?;
+        Ok(mul_div(get_sqrt_ratio_at_tick(tick)?, ONE, Q96)?)
+    }
+
+    /// Liquidity of reserves within tick price range, positive root of
+    /// `(reserve_a + L * sqrt_lower) * (reserve_b + L / sqrt_upper) = L^2`
+    pub fn get_tick_l(reserve_a: U256, reserve_b: U256, sqrt_lower_tick_price: U256, sqrt_upper_tick_price: U256) -> Result<U256> {
+        let diff = sqrt_upper_tick_price - sqrt_lower_tick_price;
+        let b = Self::div(reserve_a, sqrt_upper_tick_price)? + Self::mul(reserve_b, sqrt_lower_tick_price)?;
+
+        if reserve_a.is_zero() || reserve_b.is_zero() {
+            Ok(mul_div(b, sqrt_upper_tick_price, diff)?)
+        } else {
+            let b = b >> 1;
+            let c = Self::mul(Self::mul(reserve_a, reserve_b)?, Self::div(diff, sqrt_upper_tick_price)?)?;
+            Ok(mul_div(b + Self::sqrt(Self::mul(b, b)? + c)?, sqrt_upper_tick_price, diff)?)
+        }
+    }
+
+    pub fn get_sqrt_price(
+        reserve_a: U256,
+        reserve_b: U256,
+        sqrt_lower_tick_price: U256,
+        sqrt_upper_tick_price: U256,
+        liquidity: U256,
+    ) -> Result<U256> {
+        if reserve_a.is_zero() {
+            return Ok(sqrt_lower_tick_price);
+        }
+        if reserve_b.is_zero() {
+            return Ok(sqrt_upper_tick_price);
+        }
+        let sqrt_price = Self::sqrt(Self::div(
+            reserve_a + Self::mul(liquidity, sqrt_lower_tick_price)?,
+            reserve_b + Self::div(liquidity, sqrt_upper_tick_price)?,
+        )?)?;
+        Ok(sqrt_price.clamp(sqrt_lower_tick_price, sqrt_upper_tick_price))
+    }
+
+    fn tick_liquidity<DB: DatabaseRef>(db: &DB, pool: &MaverickPool, tick: i32) -> Result<TickLiquidity> {
+        let (reserve_a, reserve_b) = MaverickDBReader::tick_reserves(db, pool.get_address(), tick)?;
+        let sqrt_lower_tick_price = Self::tick_sqrt_price(pool.tick_spacing(), tick)?;
+        let sqrt_upper_tick_price = Self::tick_sqrt_price(pool.tick_spacing(), tick + 1)?;
+        let liquidity = Self::get_tick_l(reserve_a, reserve_b, sqrt_lower_tick_price, sqrt_upper_tick_price)?;
+        let sqrt_price = Self::get_sqrt_price(reserve_a, reserve_b, sqrt_lower_tick_price, sqrt_upper_tick_price, liquidity)?;
+
+        Ok(TickLiquidity { sqrt_price, sqrt_lower_tick_price, sqrt_upper_tick_price, liquidity, reserve_a, reserve_b })
+    }
+
+    /// Next tick with at least one bin in the swap direction, tokenA in moves the price up
+    fn next_active_tick<DB: DatabaseRef>(db: &DB, address: Address, tick: i32, up: bool) -> Result<i32> {
+        let mut cached_word: Option<(i32, U256)> = None;
+        let mut tick = tick;
+
+        for _ in 0..MAX_TICK_SEARCH {
+            tick = if up { tick + 1 } else { tick - 1 };
+            let offset = tick * NUMBER_OF_KINDS;
+            let word = offset >> 8;
+
+            let bits = match cached_word {
+                Some((cached, bits)) if cached == word => bits,
+                _ => {
+                    let bits = MaverickDBReader::bin_map(db, address, word)?;
+                    cached_word = Some((word, bits));
+                    bits
+                }
+            };
+
+            if !((bits >> (offset & 0xff) as usize) & U256::from(0xf)).is_zero() {
+                return Ok(tick);
+            }
+        }
+        Err(eyre!("NOT_ENOUGH_LIQUIDITY"))
+    }
+
+    fn with_fee(amount: U256, fee: U256) -> Result<U256> {
+        Ok(amount + Self::mul_up(amount, fee)?)
+    }
+
+    pub fn simulate_swap_in_amount_provided<DB: DatabaseRef>(
+        db: &DB,
+        pool: &MaverickPool,
+        token_in: Address,
+        amount_in: U256,
+    ) -> Result<U256> {
+        if amount_in.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        let token_a_in = token_in == pool.token0;
+        let (scale_in, scale_out) =
+            if token_a_in { (pool.token_a_scale(), pool.token_b_scale()) } else { (pool.token_b_scale(), pool.token_a_scale()) };
+        let fee = pool.get_fee();
+        let address = pool.get_address();
+
+        let mut remaining = Self::to_internal(amount_in, scale_in)?;
+        let mut amount_out = U256::ZERO;
+        let mut tick = MaverickDBReader::state(db, address)?.active_tick;
+
+        while !remaining.is_zero() {
+            let tick_liquidity = Self::tick_liquidity(db, pool, tick)?;
+
+            let (max_amount_in, reserve_out) = if token_a_in {
+                (
+                    Self::mul(tick_liquidity.liquidity, tick_liquidity.sqrt_upper_tick_price - tick_liquidity.sqrt_price)?,
+                    tick_liquidity.reserve_b,
+                )
+            } else {
+                (
+                    Self::div(tick_liquidity.liquidity, tick_liquidity.sqrt_lower_tick_price)?
+                        - Self::div(tick_liquidity.liquidity, tick_liquidity.sqrt_price)?,
+                    tick_liquidity.reserve_a,
+                )
+            };
+            let max_amount_in_with_fee = Self::with_fee(max_amount_in, fee)?;
+
+            if remaining >= max_amount_in_with_fee {
+                amount_out += reserve_out;
+                remaining -= max_amount_in_with_fee;
+                tick = Self::next_active_tick(db, address, tick, token_a_in)?;
+                continue;
+            }
+
+            let net_amount_in = mul_div(remaining, ONE, ONE + fee)?;
+            let tick_amount_out = if token_a_in {
+                let sqrt_price_next = tick_liquidity.sqrt_price + Self::div(net_amount_in, tick_liquidity.liquidity)?;
+                Self::div(Self::div(net_amount_in, tick_liquidity.sqrt_price)?, sqrt_price_next)?
+            } else {
+                let sqrt_price_next = Self::div_up(
+                    tick_liquidity.liquidity,
+                    Self::div(tick_liquidity.liquidity, tick_liquidity.sqrt_price)? + net_amount_in,
+                )?;
+                Self::mul(tick_liquidity.liquidity, tick_liquidity.sqrt_price.saturating_sub(sqrt_price_next))?
+            };
+            amount_out += tick_amount_out.min(reserve_out);
+            remaining = U256::ZERO;
+        }
+
+        Self::from_internal(amount_out, scale_out, false)
+    }
+
+    pub fn simulate_swap_out_amount_provided<DB: DatabaseRef>(
+        db: &DB,
+        pool: &MaverickPool,
+        token_in: Address,
+        amount_out: U256,
+    ) -> Result<U256> {
+        if amount_out.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        let token_a_in = token_in == pool.token0;
+        let (scale_in, scale_out) =
+            if token_a_in { (pool.token_a_scale(), pool.token_b_scale()) } else { (pool.token_b_scale(), pool.token_a_scale()) };
+        let fee = pool.get_fee();
+        let address = pool.get_address();
+
+        let mut remaining = Self::to_internal(amount_out, scale_out)?;
+        let mut amount_in = U256::ZERO;
+        let mut tick = MaverickDBReader::state(db, address)?.active_tick;
+
+        while !remaining.is_zero() {
+            let tick_liquidity = Self::tick_liquidity(db, pool, tick)?;
+            let reserve_out = if token_a_in { tick_liquidity.reserve_b } else { tick_liquidity.reserve_a };
+
+            if remaining >= reserve_out {
+                let max_amount_in = if token_a_in {
+                    Self::mul_up(tick_liquidity.liquidity, tick_liquidity.sqrt_upper_tick_price - tick_liquidity.sqrt_price)?
+                } else {
+                    Self::div_up(tick_liquidity.liquidity, tick_liquidity.sqrt_lower_tick_price)?
+                        .saturating_sub(Self::div(tick_liquidity.liquidity, tick_liquidity.sqrt_price)?)
+                };
+                amount_in += Self::with_fee(max_amount_in, fee)?;
+                remaining -= reserve_out;
+                tick = Self::next_active_tick(db, address, tick, token_a_in)?;
+                continue;
+            }
+
+            let net_amount_in = if token_a_in {
+                let inverse_price_next = Self::div(tick_liquidity.liquidity, tick_liquidity.sqrt_price)?
+                    .checked_sub(remaining)
+                    .filter(|x| !x.is_zero())
+                    .ok_or_eyre("NOT_ENOUGH_LIQUIDITY")?;
+                let sqrt_price_next = Self::div_up(tick_liquidity.liquidity, inverse_price_next)?;
+                Self::mul_up(tick_liquidity.liquidity, sqrt_price_next - tick_liquidity.sqrt_price)?
+            } else {
+                let sqrt_price_next = tick_liquidity
+                    .sqrt_price
+                    .checked_sub(Self::div_up(remaining, tick_liquidity.liquidity)?)
+                    .filter(|x| !x.is_zero())
+                    .ok_or_eyre("NOT_ENOUGH_LIQUIDITY")?;
+                Self::div_up(tick_liquidity.liquidity, sqrt_price_next)?
+                    .saturating_sub(Self::div(tick_liquidity.liquidity, tick_liquidity.sqrt_price)?)
+            };
+            amount_in += Self::with_fee(net_amount_in, fee)?;
+            remaining = U256::ZERO;
+        }
+
+        Self::from_internal(amount_in, scale_in, true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tick_liquidity_roundtrip() -> Result<()> {
+        let sqrt_lower = MaverickPoolVirtual::tick_sqrt_price(10, 0)?;
+        let sqrt_upper = MaverickPoolVirtual::tick_sqrt_price(10, 1)?;
+        assert_eq!(sqrt_lower, ONE);
+
+        let reserve_a = U256::from(1_000u64) * ONE;
+        let reserve_b = U256::from(2_000u64) * ONE;
+        let liquidity = MaverickPoolVirtual::get_tick_l(reserve_a, reserve_b, sqrt_lower, sqrt_upper)?;
+        let sqrt_price = MaverickPoolVirtual::get_sqrt_price(reserve_a, reserve_b, sqrt_lower, sqrt_upper, liquidity)?;
+        assert!(sqrt_price > sqrt_lower && sqrt_price < sqrt_upper);
+
+        // reserves are restored from liquidity and price within rounding
+        let reserve_a_calc = MaverickPoolVirtual::mul(liquidity, sqrt_price - sqrt_lower)?;
+        let reserve_b_calc = MaverickPoolVirtual::div(liquidity, sqrt_price)? - MaverickPoolVirtual::div(liquidity, sqrt_upper)?;
+        assert!(reserve_a_calc.abs_diff(reserve_a) < U256::from(1_000_000u64));
+        assert!(reserve_b_calc.abs_diff(reserve_b) < U256::from(1_000_000u64));
+
+        // single sided tick is at the edge of the range
+        let liquidity = MaverickPoolVirtual::get_tick_l(U256::ZERO, reserve_b, sqrt_lower, sqrt_upper)?;
+        assert_eq!(MaverickPoolVirtual::get_sqrt_price(U256::ZERO, reserve_b, sqrt_lower, sqrt_upper, liquidity)?, sqrt_lower);
+
+        Ok(())
+    }
+}
@@ -1,4 +1,12 @@
+pub use maverick::MaverickPoolVirtual;
+pub use pancakev3::PancakeV3PoolVirtual;
 pub use uniswapv3::UniswapV3PoolVirtual;
+pub use uniswapv4::UniswapV4PoolVirtual;
 
+pub mod balancer;
+pub mod curve;
+mod maverick;
+mod pancakev3;
 pub mod tick_provider;
 mod uniswapv3;
+mod uniswapv4;
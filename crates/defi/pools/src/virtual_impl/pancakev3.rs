@@ -0,0 +1,134 @@
+use alloy::primitives::{Address, I256, U256};
+use eyre::eyre;
+use loom_defi_uniswap_v3_math::tick_math::{MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO, MIN_TICK};
+use loom_types_entities::Pool;
+use revm::DatabaseRef;
+
+use crate::db_reader::PancakeV3DBReader;
+use crate::virtual_impl::tick_provider::TickProviderPancakeV3EVMDB;
+use crate::PancakeV3Pool;
+
+const U256_1: U256 = U256::from_limbs([1, 0, 0, 0]);
+
+struct CurrentState {
+    amount_specified_remaining: I256,
+    amount_calculated: U256,
+    sqrt_price_x_96: U256,
+    tick: i32,
+    liquidity: u128,
+}
+
+/// PancakeV3 swap simulation on `loom_defi_uniswap_v3_math` with state read from PancakeV3 storage layout.
+pub struct PancakeV3PoolVirtual;
+
+impl PancakeV3PoolVirtual {
+    pub fn simulate_swap_in_amount_provided<DB: DatabaseRef>(
+        db: &DB,
+        pool: &PancakeV3Pool,
+        token_in: Address,
+        amount_in: U256,
+    ) -> eyre::Result<U256> {
+        if amount_in.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        Self::simulate_swap(db, pool, token_in == pool.token0, I256::from_raw(amount_in))
+    }
+
+    pub fn simulate_swap_out_amount_provided<DB: DatabaseRef>(
+        db: &DB,
+        pool: &PancakeV3Pool,
+        token_in: Address,
+        amount_out: U256,
+    ) -> eyre::Result<U256> {
+        if amount_out.is_zero() {
+            return Ok(U256::ZERO);
+        }
+        Self::simulate_swap(db, pool, token_in == pool.token0, -I256::from_raw(amount_out))
+    }
+
+    // Positive amount_specified is exact input and returns amount out, negative is exact output and returns amount in
+    fn simulate_swap<DB: DatabaseRef>(db: &DB, pool: &PancakeV3Pool, zero_for_one: bool, amount_specified: I256) -> eyre::Result<U256> {
+        let exact_in = amount_specified.is_positive();
+        let sqrt_price_limit_x_96 = if zero_for_one { MIN_SQRT_RATIO + U256_1 } else { MAX_SQRT_RATIO - U256_1 };
+
+        let tick_spacing = pool.tick_spacing();
+        if tick_spacing == 0 {
+            return Err(eyre!("BAD_PRICE_STEP"));
+        }
+        let fee: u32 = pool.get_fee().to();
+        let pool_address = pool.get_address();
+
+        let slot0 = PancakeV3DBReader::slot0(db, pool_address)?;
+        if slot0.sqrtPriceX96.is_zero() {
+            return Err(eyre!("POOL_NOT_INITIALIZED"));
+        }
+        let liquidity = PancakeV3DBReader::liquidity(db, pool_address)?;
+
+        let mut current_state = CurrentState {
+            sqrt_price_x_96: slot0.sqrtPriceX96.to(),
+            amount_calculated: U256::ZERO,
+            amount_specified_remaining: amount_specified,
+            tick: slot0.tick.as_i32(),
+            liquidity,
+        };
+
+        let tick_provider = TickProviderPancakeV3EVMDB::new(db, pool_address);
+
+        while current_state.amount_specified_remaining != I256::ZERO && current_state.sqrt_price_x_96 != sqrt_price_limit_x_96 {
+            let sqrt_price_start_x_96 = current_state.sqrt_price_x_96;
+
+            let (tick_next, initialized) = loom_defi_uniswap_v3_math::tick_bitmap::next_initialized_tick_within_one_word(
+                &tick_provider,
+                current_state.tick,
+                tick_spacing as i32,
+                zero_for_one,
+            )?;
+            let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+
+            let sqrt_price_next_x96 = loom_defi_uniswap_v3_math::tick_math::get_sqrt_ratio_at_tick(tick_next)?;
+
+            let swap_target_sqrt_ratio =
+                if zero_for_one { sqrt_price_next_x96.max(sqrt_price_limit_x_96) } else { sqrt_price_next_x96.min(sqrt_price_limit_x_96) };
+
+            let (sqrt_price_x_96, amount_in, amount_out, fee_amount) = loom_defi_uniswap_v3_math::swap_math::compute_swap_step(
+                current_state.sqrt_price_x_96,
+                swap_target_sqrt_ratio,
+                current_state.liquidity,
+                current_state.amount_specified_remaining,
+                fee,
+            )?;
+            current_state.sqrt_price_x_96 = sqrt_price_x_96;
+
+            if exact_in {
+                current_state.amount_specified_remaining -= I256::from_raw(amount_in + fee_amount);
+                current_state.amount_calculated += amount_out;
+            } else {
+                current_state.amount_specified_remaining += I256::from_raw(amount_out);
+                current_state.amount_calculated += amount_in + fee_amount;
+            }
+
+            if current_state.sqrt_price_x_96 == sqrt_price_next_x96 {
+                if initialized {
+                    let mut liquidity_net = PancakeV3DBReader::ticks_liquidity_net(db, pool_address, tick_next).unwrap_or_default();
+                    if zero_for_one {
+                        liquidity_net = -liquidity_net;
+                    }
+                    current_state.liquidity = if liquidity_net < 0 {
+                        current_state.liquidity.checked_sub(liquidity_net.unsigned_abs()).ok_or_else(|| eyre!("LIQUIDITY_UNDERFLOW"))?
+                    } else {
+                        current_state.liquidity + liquidity_net as u128
+                    };
+                }
+                current_state.tick = if zero_for_one { tick_next.wrapping_sub(1) } else { tick_next };
+            } else if current_state.sqrt_price_x_96 != sqrt_price_start_x_96 {
+                current_state.tick = loom_defi_uniswap_v3_math::tick_math::get_tick_at_sqrt_ratio(current_state.sqrt_price_x_96)?;
+            }
+        }
+
+        if current_state.amount_specified_remaining.is_zero() {
+            Ok(current_state.amount_calculated)
+        } else {
+            Err(eyre!("NOT_ENOUGH_LIQUIDITY"))
+        }
+    }
+}
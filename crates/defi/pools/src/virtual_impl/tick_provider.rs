@@ -1,5 +1,5 @@
-use crate::db_reader::UniswapV3DBReader;
-use alloy::primitives::{Address, U256};
+use crate::db_reader::{PancakeV3DBReader, UniswapV3DBReader, UniswapV4DBReader};
+use alloy::primitives::{Address, B256, U256};
 use loom_defi_uniswap_v3_math::tick_provider::TickProvider;
 use revm::DatabaseRef;
 
@@ -25,3 +25,50 @@ where
         UniswapV3DBReader::tick_bitmap(&self.db, self.pool_address, tick)
     }
 }
+
+pub struct TickProviderPancakeV3EVMDB<DB> {
+    pub db: DB,
+    pub pool_address: Address,
+}
+
+impl<DB> TickProviderPancakeV3EVMDB<DB>
+where
+    DB: DatabaseRef,
+{
+    pub fn new(db: DB, pool_address: Address) -> Self {
+        TickProviderPancakeV3EVMDB { db, pool_address }
+    }
+}
+
+impl<DB> TickProvider for TickProviderPancakeV3EVMDB<DB>
+where
+    DB: DatabaseRef,
+{
+    fn get_tick(&self, tick: i16) -> eyre::Result<U256> {
+        PancakeV3DBReader::tick_bitmap(&self.db, self.pool_address, tick)
+    }
+}
+
+pub struct TickProviderUniswapV4EVMDB<DB> {
+    pub db: DB,
+    pub pool_manager: Address,
+    pub pool_id: B256,
+}
+
+impl<DB> TickProviderUniswapV4EVMDB<DB>
+where
+    DB: DatabaseRef,
+{
+    pub fn new(db: DB, pool_manager: Address, pool_id: B256) -> Self {
+        TickProviderUniswapV4EVMDB { db, pool_manager, pool_id }
+    }
+}
+
+impl<DB> TickProvider for TickProviderUniswapV4EVMDB<DB>
+where
+    DB: DatabaseRef,
+{
+    fn get_tick(&self, tick: i16) -> eyre::Result<U256> {
+        UniswapV4DBReader::tick_bitmap(&self.db, self.pool_manager, self.pool_id, tick)
+    }
+}
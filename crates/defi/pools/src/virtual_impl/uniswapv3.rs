@@ -1,6 +1,9 @@
+use std::cell::{Cell, RefCell};
+
 use alloy::primitives::{Address, I256, U256};
 use eyre::eyre;
 use loom_defi_uniswap_v3_math::tick_math::{MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO, MIN_TICK};
+use loom_defi_uniswap_v3_math::tick_provider::TickProvider;
 use revm::DatabaseRef;
 
 use crate::db_reader::UniswapV3DBReader;
@@ -47,8 +50,71 @@ pub const U256_1: U256 = U256::from_limbs([1, 0, 0, 0]);
 
 // Uniswap V3 specific
 
+/// Gas of `UniswapV3Pool.swap` without tick crossings: slot0 and liquidity reads, slot0 update, balance check and callback
+pub const SWAP_BASE_GAS: u64 = 45_000;
+/// Gas of a token transfer, swap makes two of them: to the pool in callback and from the pool to recipient
+pub const TOKEN_TRANSFER_GAS: u64 = 30_000;
+/// Gas of crossing an initialized tick: reading and updating cold `Tick.Info` and updating liquidity
+pub const TICK_CROSS_GAS: u64 = 22_000;
+/// Gas of the first read of a tick bitmap word
+pub const BITMAP_WORD_COLD_GAS: u64 = 2_600;
+/// Gas of repeated read of an already accessed tick bitmap word
+pub const BITMAP_WORD_WARM_GAS: u64 = 400;
+/// Gas of a swap step math
+pub const SWAP_STEP_GAS: u64 = 1_500;
+
 // Others
 
+/// Storage accesses of a simulated swap the gas estimate is derived from
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UniswapV3SwapGasStats {
+    pub steps: u64,
+    pub initialized_ticks_crossed: u64,
+    pub bitmap_words_cold: u64,
+    pub bitmap_words_warm: u64,
+}
+
+impl UniswapV3SwapGasStats {
+    pub fn gas_estimate(&self) -> u64 {
+        SWAP_BASE_GAS
+            + 2 * TOKEN_TRANSFER_GAS
+            + self.steps * SWAP_STEP_GAS
+            + self.initialized_ticks_crossed * TICK_CROSS_GAS
+            + self.bitmap_words_cold * BITMAP_WORD_COLD_GAS
+            + self.bitmap_words_warm * BITMAP_WORD_WARM_GAS
+    }
+}
+
+/// Tick provider counting distinct and repeated bitmap word reads
+struct GasTrackingTickProvider<P> {
+    inner: P,
+    words: RefCell<Vec<i16>>,
+    warm_reads: Cell<u64>,
+}
+
+impl<P: TickProvider> GasTrackingTickProvider<P> {
+    fn new(inner: P) -> Self {
+        Self { inner, words: RefCell::new(Vec::new()), warm_reads: Cell::new(0) }
+    }
+
+    fn apply(&self, stats: &mut UniswapV3SwapGasStats) {
+        stats.bitmap_words_cold = self.words.borrow().len() as u64;
+        stats.bitmap_words_warm = self.warm_reads.get();
+    }
+}
+
+impl<P: TickProvider> TickProvider for GasTrackingTickProvider<P> {
+    fn get_tick(&self, tick: i16) -> eyre::Result<U256> {
+        let mut words = self.words.borrow_mut();
+        if words.contains(&tick) {
+            self.warm_reads.set(self.warm_reads.get() + 1);
+        } else {
+            words.push(tick);
+        }
+        self.inner.get_tick(tick)
+    }
+}
+
 pub struct CurrentState {
     amount_specified_remaining: I256,
     amount_calculated: I256,
@@ -87,8 +153,27 @@ impl UniswapV3PoolVirtual {
         token_in: Address,
         amount_in: U256,
     ) -> eyre::Result<U256> {
+        Ok(Self::simulate_swap_in_amount_provided_with_gas(db, pool, token_in, amount_in)?.0)
+    }
+
+    pub fn simulate_swap_out_amount_provided<DB: DatabaseRef>(
+        db: &DB,
+        pool: &UniswapV3Pool,
+        token_in: Address,
+        amount_out: U256,
+    ) -> eyre::Result<U256> {
+        Ok(Self::simulate_swap_out_amount_provided_with_gas(db, pool, token_in, amount_out)?.0)
+    }
+
+    /// Returns amount out and estimated gas of the swap
+    pub fn simulate_swap_in_amount_provided_with_gas<DB: DatabaseRef>(
+        db: &DB,
+        pool: &UniswapV3Pool,
+        token_in: Address,
+        amount_in: U256,
+    ) -> eyre::Result<(U256, u64)> {
         if amount_in.is_zero() {
-            return Ok(U256::ZERO);
+            return Ok((U256::ZERO, 0));
         }
 
         let zero_for_one = token_in == pool.get_tokens()[0];
@@ -112,9 +197,12 @@ impl UniswapV3PoolVirtual {
             liquidity,                                             //Current available liquidity in the tick range
         };
 
-        let tick_provider = TickProviderEVMDB::new(db, pool_address);
+        let tick_provider = GasTrackingTickProvider::new(TickProviderEVMDB::new(db, pool_address));
+        let mut gas_stats = UniswapV3SwapGasStats::default();
 
         while current_state.amount_specified_remaining != I256::ZERO && current_state.sqrt_price_x_96 != sqrt_price_limit_x_96 {
+            gas_stats.steps += 1;
+
             // Initialize a new step struct to hold the dynamic state of the pool at each step
             let mut step = StepComputations {
                 // Set the sqrt_price_start_x_96 to the current sqrt_price_x_96
@@ -171,6 +259,7 @@ impl UniswapV3PoolVirtual {
             // If the price moved all the way to the next price, recompute the liquidity change for the next iteration
             if current_state.sqrt_price_x_96 == step.sqrt_price_next_x96 {
                 if step.initialized {
+                    gas_stats.initialized_ticks_crossed += 1;
                     let mut liquidity_net: i128 =
                         UniswapV3DBReader::ticks_liquidity_net(&db, pool_address, step.tick_next).unwrap_or_default();
 
@@ -200,21 +289,23 @@ impl UniswapV3PoolVirtual {
 
         if current_state.amount_specified_remaining.is_zero() {
             let amount_out = (-current_state.amount_calculated).into_raw();
-            tracing::trace!("AmountOut : {amount_out}");
-            Ok(amount_out)
+            tick_provider.apply(&mut gas_stats);
+            tracing::trace!("AmountOut : {amount_out} {gas_stats:?}");
+            Ok((amount_out, gas_stats.gas_estimate()))
         } else {
             Err(eyre!("NOT_ENOUGH_LIQUIDITY"))
         }
     }
 
-    pub fn simulate_swap_out_amount_provided<DB: DatabaseRef>(
+    /// Returns amount in and estimated gas of the swap
+    pub fn simulate_swap_out_amount_provided_with_gas<DB: DatabaseRef>(
         db: &DB,
         pool: &UniswapV3Pool,
         token_in: Address,
         amount_out: U256,
-    ) -> eyre::Result<U256> {
+    ) -> eyre::Result<(U256, u64)> {
         if amount_out.is_zero() {
-            return Ok(U256::ZERO);
+            return Ok((U256::ZERO, 0));
         }
 
         let zero_for_one = token_in == pool.get_tokens()[0];
@@ -238,7 +329,12 @@ impl UniswapV3PoolVirtual {
             liquidity,                                               //Current available liquidity in the tick range
         };
 
+        let tick_provider = GasTrackingTickProvider::new(TickProviderEVMDB::new(db, pool_address));
+        let mut gas_stats = UniswapV3SwapGasStats::default();
+
         while current_state.amount_specified_remaining != I256::ZERO && current_state.sqrt_price_x_96 != sqrt_price_limit_x_96 {
+            gas_stats.steps += 1;
+
             // Initialize a new step struct to hold the dynamic state of the pool at each step
             let mut step = StepComputations {
                 // Set the sqrt_price_start_x_96 to the current sqrt_price_x_96
@@ -246,8 +342,6 @@ impl UniswapV3PoolVirtual {
                 ..Default::default()
             };
 
-            let tick_provider = TickProviderEVMDB::new(&db, pool_address);
-
             // Get the next tick from the current tick
             (step.tick_next, step.initialized) = loom_defi_uniswap_v3_math::tick_bitmap::next_initialized_tick_within_one_word(
                 &tick_provider,
@@ -296,6 +390,7 @@ impl UniswapV3PoolVirtual {
             // If the price moved all the way to the next price, recompute the liquidity change for the next iteration
             if current_state.sqrt_price_x_96 == step.sqrt_price_next_x96 {
                 if step.initialized {
+                    gas_stats.initialized_ticks_crossed += 1;
                     let mut liquidity_net: i128 =
                         UniswapV3DBReader::ticks_liquidity_net(db, pool_address, step.tick_next).unwrap_or_default();
 
@@ -325,10 +420,11 @@ impl UniswapV3PoolVirtual {
 
         if current_state.amount_specified_remaining.is_zero() {
             let amount_in = current_state.amount_calculated.into_raw();
+            tick_provider.apply(&mut gas_stats);
 
-            tracing::trace!("Amount In : {amount_in}");
+            tracing::trace!("Amount In : {amount_in} {gas_stats:?}");
 
-            Ok(amount_in)
+            Ok((amount_in, gas_stats.gas_estimate()))
         } else {
             Err(eyre!("NOT_ENOUGH_LIQUIDITY"))
         }
@@ -337,9 +433,40 @@ impl UniswapV3PoolVirtual {
 
 #[cfg(test)]
 mod test {
+    use super::*;
     use alloy::primitives::U256;
     use loom_defi_uniswap_v3_math::full_math::mul_div_rounding_up;
 
+    struct ConstTickProvider;
+
+    impl TickProvider for ConstTickProvider {
+        fn get_tick(&self, _tick: i16) -> eyre::Result<U256> {
+            Ok(U256::ZERO)
+        }
+    }
+
+    #[test]
+    fn test_gas_tracking_tick_provider() {
+        let tick_provider = GasTrackingTickProvider::new(ConstTickProvider);
+        for word in [0, 1, 0, 0, -1] {
+            tick_provider.get_tick(word).unwrap();
+        }
+        let mut stats = UniswapV3SwapGasStats { steps: 2, initialized_ticks_crossed: 1, ..Default::default() };
+        tick_provider.apply(&mut stats);
+
+        assert_eq!(stats.bitmap_words_cold, 3);
+        assert_eq!(stats.bitmap_words_warm, 2);
+        assert_eq!(
+            stats.gas_estimate(),
+            SWAP_BASE_GAS
+                + 2 * TOKEN_TRANSFER_GAS
+                + 2 * SWAP_STEP_GAS
+                + TICK_CROSS_GAS
+                + 3 * BITMAP_WORD_COLD_GAS
+                + 2 * BITMAP_WORD_WARM_GAS
+        );
+    }
+
     #[test]
     fn test_mul_rounding_up() {
         let amount = U256::from_limbs([1230267133767, 0, 0, 0]);
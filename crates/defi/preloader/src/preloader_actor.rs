@@ -8,7 +8,7 @@ use alloy_primitives::{Address, Bytes, U256};
 use alloy_provider::Provider;
 use alloy_rpc_types_trace::geth::AccountState;
 use eyre::{eyre, Result};
-use loom_core_actors::{Accessor, Actor, ActorResult, SharedState, WorkerResult};
+use loom_core_actors::{Accessor, Actor, ActorResult, CancellationToken, SharedState, WorkerResult};
 use loom_core_actors_macros::Accessor;
 use loom_core_blockchain::{Blockchain, BlockchainState};
 use loom_defi_address_book::TokenAddressEth;
@@ -235,7 +235,7 @@ where
         Ok(())
     }
 
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, _cancel_token: CancellationToken) -> ActorResult {
         Err(eyre!("NEED_TO_BE_WAITED"))
     }
 
@@ -1,3 +1,5 @@
 mod price_actor;
+mod price_graph;
 
 pub use price_actor::PriceActor;
+pub use price_graph::{PriceGraph, PriceGraphConfig, TokenGraph};
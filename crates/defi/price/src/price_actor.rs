@@ -1,133 +1,252 @@
-use std::collections::HashMap;
-use std::marker::PhantomData;
-use std::ops::{Div, Mul};
-use std::time::Duration;
-
-use alloy_network::Network;
-use alloy_primitives::{address, Address, U256};
-use alloy_provider::Provider;
-use loom_core_actors::{Accessor, Actor, ActorResult, SharedState, WorkerResult};
-use loom_core_actors_macros::Accessor;
-use loom_core_blockchain::Blockchain;
-use loom_defi_address_book::TokenAddressEth;
-use loom_defi_pools::protocols::CurveProtocol;
-use loom_defi_pools::CurvePool;
-use loom_types_entities::{Market, Pool};
-use tracing::{debug, error, info};
-
-async fn price_worker<N: Network, P: Provider<N> + Clone + 'static>(client: P, market: SharedState<Market>, once: bool) -> WorkerResult {
-    let curve_tricrypto_usdc = CurveProtocol::new_u256_3_eth_to(client.clone(), address!("7F86Bf177Dd4F3494b841a37e810A34dD56c829B"));
-    let curve_tricrypto_usdt = CurveProtocol::new_u256_3_eth_to(client.clone(), address!("f5f5b97624542d72a9e06f04804bf81baa15e2b4"));
-
-    let mut coins_hash_map: HashMap<Address, CurvePool<P, N>> = HashMap::new();
-
-    let curve_tricrypto_usdc_pool = CurvePool::fetch_pool_data(client.clone(), curve_tricrypto_usdc).await?;
-
-    let curve_tricrypto_usdt_pool = CurvePool::fetch_pool_data(client.clone(), curve_tricrypto_usdt).await?;
-
-    coins_hash_map.insert(TokenAddressEth::USDC, curve_tricrypto_usdc_pool.clone());
-    coins_hash_map.insert(TokenAddressEth::WBTC, curve_tricrypto_usdc_pool.clone());
-    coins_hash_map.insert(TokenAddressEth::USDT, curve_tricrypto_usdt_pool.clone());
-
-    let one_ether = U256::from(10).pow(U256::from(18));
-    let weth_amount = one_ether.mul(U256::from(5));
-
-    match market.read().await.get_token(&TokenAddressEth::WETH) {
-        Some(token) => {
-            token.set_eth_price(Some(one_ether));
-        }
-        _ => {
-            error!("WETH_NOT_FOUND")
-        }
-    }
+use std::collections::HashSet;
+use std::time::Instant;
 
-    loop {
-        for (token_address, curve_pool) in coins_hash_map.iter() {
-            debug!("Fetching price of {} at {}", token_address, curve_pool.get_address());
-
-            match curve_pool.fetch_out_amount(TokenAddressEth::WETH, *token_address, weth_amount).await {
-                Ok(out_amount) => {
-                    let price = out_amount.mul(one_ether).div(weth_amount);
-                    info!("Price of ETH in {token_address:#20x} is {price}");
-                    match market.read().await.get_token(token_address) {
-                        Some(tkn) => {
-                            tkn.set_eth_price(Some(price));
-                            debug!("Price is set");
-                        }
-                        _ => {
-                            error!(address=%token_address, "Token not found");
-                        }
+use alloy_primitives::{BlockHash, U256};
+use eyre::ErrReport;
+use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, SharedState, WorkerResult};
+use loom_core_actors_macros::{Accessor, Consumer};
+use loom_core_blockchain::{Blockchain, BlockchainState};
+use loom_evm_utils::evm_env::env_for_block;
+use loom_types_blockchain::{ChainParameters, GethStateUpdateVec};
+use loom_types_entities::{BlockHistory, Market, MarketState, PoolId};
+use loom_types_events::MarketEvents;
+use revm::primitives::Env;
+use revm::{DatabaseCommit, DatabaseRef};
+use tokio::sync::broadcast::error::RecvError;
+use tracing::{debug, error, info, warn};
+
+use crate::price_graph::{PriceGraph, PriceGraphConfig, TokenGraph};
+
+/// Pools with state changed in the update, pools of pool managers are matched by storage cells
+fn touched_pools(market: &Market, state_update: &GethStateUpdateVec) -> HashSet<PoolId> {
+    let mut pool_ids = HashSet::new();
+    for state_update_record in state_update.iter() {
+        for (address, account_state) in state_update_record.iter() {
+            if market.is_pool_manager(address) {
+                for cell in account_state.storage.keys() {
+                    if let Some(pool_id) = market.get_pool_id_for_cell(address, &U256::from_be_slice(cell.as_slice())) {
+                        pool_ids.insert(*pool_id);
                     }
                 }
-                Err(error) => {
-                    error!(%error, "fetch_out_amount")
-                }
+            } else if market.is_pool(&PoolId::Address(*address)) {
+                pool_ids.insert(PoolId::Address(*address));
             }
         }
+    }
+    pool_ids
+}
 
-        let usdt_price = market.read().await.get_token_or_default(&TokenAddressEth::USDT).get_eth_price();
-        let usdc_price = market.read().await.get_token_or_default(&TokenAddressEth::USDC).get_eth_price();
+/// Env of the block following the given one, latest known block is used if block hash is not set
+async fn next_block_env<DB>(
+    block_history: &Option<SharedState<BlockHistory<DB>>>,
+    chain_parameters: &ChainParameters,
+    block_hash: Option<BlockHash>,
+) -> Option<Env> {
+    let block_history = block_history.as_ref()?.read().await;
+    let block_hash = match block_hash {
+        Some(block_hash) => block_hash,
+        None => block_history.get_block_hash_for_block_number(block_history.latest_block_number)?,
+    };
+    let entry = block_history.get_block_history_entry(&block_hash)?;
+    Some(env_for_block(entry.number() + 1, chain_parameters.calc_block_timestamp(entry.timestamp(), 1)))
+}
 
-        let mut usd_price: Option<U256> = None;
-        if let Some(usdc_price) = usdc_price {
-            if let Some(usdt_price) = usdt_price {
-                usd_price = Some((usdc_price + usdt_price) >> 1);
-            }
+async fn update_prices<DB: DatabaseRef<Error = ErrReport> + Send + Sync + Clone + 'static>(
+    mut price_graph: PriceGraph,
+    market: &SharedState<Market>,
+    market_state: &SharedState<MarketState<DB>>,
+    env: Env,
+) -> PriceGraph {
+    let start_time = Instant::now();
+
+    // locks are released before calculation, it runs on blocking thread with snapshots
+    let token_graph = TokenGraph::new(&*market.read().await);
+    let state_db = market_state.read().await.state_db.clone();
+
+    let config = price_graph.config().clone();
+    let wrapped_native_token = price_graph.wrapped_native_token();
+    let (price_graph, prices) = match tokio::task::spawn_blocking(move || {
+        let prices = price_graph.calculate_eth_prices(&token_graph, &state_db, env);
+        (price_graph, prices)
+    })
+    .await
+    {
+        Ok(result) => result,
+        Err(e) => {
+            error!("Price calculation failed : {}", e);
+            return PriceGraph::new(config, wrapped_native_token);
         }
+    };
 
-        if let Some(usd_price) = usd_price {
-            match market.read().await.get_token(&TokenAddressEth::DAI) {
-                Some(tkn) => {
-                    tkn.set_eth_price(Some(U256::from(10).pow(U256::from(12)).mul(usd_price)));
-                }
-                _ => {
-                    error!("Token {:#20x} not found", TokenAddressEth::DAI);
+    let market_guard = market.read().await;
+    let mut updated = 0usize;
+    for (token_address, price) in prices.iter() {
+        if let Some(token) = market_guard.get_token(token_address) {
+            token.set_eth_price(Some(*price));
+            updated += 1;
+        }
+    }
+    debug!(priced = prices.len(), updated, elapsed = ?start_time.elapsed(), "Token prices updated");
+
+    price_graph
+}
+
+async fn price_worker<DB: DatabaseRef<Error = ErrReport> + Send + Sync + Clone + 'static>(
+    market: SharedState<Market>,
+    market_state: SharedState<MarketState<DB>>,
+    block_history: Option<SharedState<BlockHistory<DB>>>,
+    market_events_rx: Option<Broadcaster<MarketEvents>>,
+    config: PriceGraphConfig,
+    chain_parameters: ChainParameters,
+    once: bool,
+    cancel_token: CancellationToken,
+) -> WorkerResult {
+    let env = next_block_env(&block_history, &chain_parameters, None).await.unwrap_or_else(|| {
+        warn!("No block in block history, prices are initialized with default env");
+        Env::default()
+    });
+    let mut price_graph = update_prices(PriceGraph::new(config, chain_parameters.wrapped_native_token), &market, &market_state, env).await;
+    info!("Token prices initialized");
+
+    if once {
+        return Ok("PriceWorker finished".to_string());
+    }
+
+    let Some(market_events_rx) = market_events_rx else {
+        warn!("No market events channel, prices are not refreshed");
+        return Ok("PriceWorker finished".to_string());
+    };
+
+    subscribe!(market_events_rx);
+
+    loop {
+        let market_event = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            market_event = market_events_rx.recv() => market_event,
+        };
+        match market_event {
+            Ok(MarketEvents::BlockStateUpdate { block_hash }) => {
+                let Some(env) = next_block_env(&block_history, &chain_parameters, Some(block_hash)).await else {
+                    error!(%block_hash, "Block not found in block history, prices are not updated");
+                    continue;
+                };
+
+                let state_update = match &block_history {
+                    Some(block_history) => {
+                        block_history.read().await.get_block_history_entry(&block_hash).and_then(|entry| entry.state_update.clone())
+                    }
+                    None => None,
+                };
+                match state_update {
+                    Some(state_update) => {
+                        let pool_ids = touched_pools(&*market.read().await, &state_update);
+                        price_graph.invalidate_pools(&pool_ids);
+                    }
+                    None => {
+                        // without state update every pool could have changed
+                        warn!(%block_hash, "Block has no state update, all pool quotes are recalculated");
+                        price_graph.clear();
+                    }
                 }
+
+                price_graph = update_prices(price_graph, &market, &market_state, env).await;
+            }
+            Ok(MarketEvents::Reorg { depth, old_head, new_head, .. }) => {
+                // quotes of pools touched by orphaned blocks are not invalidated by state updates of the new chain
+                warn!(depth, %old_head, %new_head, "Re-org, all pool quotes are recalculated");
+                price_graph.clear();
+            }
+            Ok(_) => {}
+            Err(RecvError::Lagged(lagged)) => {
+                warn!(lagged, "Market events lagged");
+                // skipped state updates could have touched any pool
+                price_graph.clear();
+            }
+            Err(RecvError::Closed) => {
+                error!("Market events channel closed");
+                break;
             }
         }
-        if once {
-            break;
-        }
-
-        let _ = tokio::time::sleep(Duration::new(60, 0)).await;
     }
     Ok("PriceWorker finished".to_string())
 }
 
-#[derive(Accessor)]
-pub struct PriceActor<P, N> {
-    client: P,
+/// Sets ETH prices of market tokens, refreshed on every block state update for pools touched by the update
+#[derive(Accessor, Consumer)]
+pub struct PriceActor<DB: Clone + Send + Sync + 'static> {
+    config: PriceGraphConfig,
+    chain_parameters: ChainParameters,
     only_once: bool,
     #[accessor]
     market: Option<SharedState<Market>>,
-    _n: PhantomData<N>,
+    #[accessor]
+    market_state: Option<SharedState<MarketState<DB>>>,
+    #[accessor]
+    block_history: Option<SharedState<BlockHistory<DB>>>,
+    #[consumer]
+    market_events_rx: Option<Broadcaster<MarketEvents>>,
 }
 
-impl<P, N> PriceActor<P, N>
+impl<DB> PriceActor<DB>
 where
-    N: Network,
-    P: Provider<N> + Send + Sync + Clone + 'static,
+    DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + 'static,
 {
-    pub fn new(client: P) -> Self {
-        Self { client, only_once: false, market: None, _n: PhantomData }
+    pub fn new() -> Self {
+        Self {
+            config: PriceGraphConfig::default(),
+            chain_parameters: ChainParameters::ethereum(),
+            only_once: false,
+            market: None,
+            market_state: None,
+            block_history: None,
+            market_events_rx: None,
+        }
     }
 
     pub fn only_once(self) -> Self {
         Self { only_once: true, ..self }
     }
 
-    pub fn on_bc(self, bc: &Blockchain) -> Self {
-        Self { market: Some(bc.market()), ..self }
+    pub fn with_config(self, config: PriceGraphConfig) -> Self {
+        Self { config, ..self }
+    }
+
+    pub fn on_bc(self, bc: &Blockchain, state: &BlockchainState<DB>) -> Self {
+        Self {
+            chain_parameters: bc.chain_parameters(),
+            market: Some(bc.market()),
+            market_state: Some(state.market_state()),
+            block_history: Some(state.block_history()),
+            market_events_rx: Some(bc.market_events_channel()),
+            ..self
+        }
+    }
+}
+
+impl<DB> Default for PriceActor<DB>
+where
+    DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + 'static,
+{
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-impl<P, N> Actor for PriceActor<P, N>
+impl<DB> Actor for PriceActor<DB>
 where
-    N: Network,
-    P: Provider<N> + Send + Sync + Clone + 'static,
+    DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
-        let task = tokio::task::spawn(price_worker(self.client.clone(), self.market.clone().unwrap(), self.only_once));
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(price_worker(
+            self.market.clone().unwrap(),
+            self.market_state.clone().unwrap(),
+            self.block_history.clone(),
+            self.market_events_rx.clone(),
+            self.config.clone(),
+            self.chain_parameters.clone(),
+            self.only_once,
+            cancel_token,
+        ));
         Ok(vec![task])
     }
 
@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+
+use alloy_primitives::{Address, U256};
+use eyre::ErrReport;
+use loom_types_entities::{Market, PoolId, PoolWrapper};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+use tracing::trace;
+
+const ONE_ETHER: U256 = U256::from_limbs([1_000_000_000_000_000_000u64, 0, 0, 0]);
+const PROBE_DIVIDER: u64 = 100;
+
+#[derive(Clone, Debug)]
+pub struct PriceGraphConfig {
+    /// Max number of pools between wrapped native token and priced token
+    pub max_hops: usize,
+    /// Value of swap in ETH used to price pools
+    pub probe_eth_amount: U256,
+    /// Pools losing more than this on probe amount compared to 1/100 of it are considered dust
+    pub max_price_impact_bps: u32,
+}
+
+impl Default for PriceGraphConfig {
+    fn default() -> Self {
+        Self { max_hops: 3, probe_eth_amount: ONE_ETHER, max_price_impact_bps: 500 }
+    }
+}
+
+struct PriceCandidate {
+    price: U256,
+    // price impact of probe amount in bps, lower is deeper
+    impact: U256,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct PoolQuote {
+    amount_in: U256,
+    amount_out: U256,
+    impact: U256,
+}
+
+/// Snapshot of enabled market pools by token, taken to price tokens without holding the market lock
+#[derive(Clone, Default)]
+pub struct TokenGraph {
+    edges: HashMap<Address, Vec<(Address, PoolWrapper)>>,
+}
+
+impl TokenGraph {
+    pub fn new(market: &Market) -> Self {
+        let mut edges: HashMap<Address, Vec<(Address, PoolWrapper)>> = HashMap::new();
+        for (pool_id, pool) in market.pools().iter() {
+            if market.is_pool_disabled(pool_id) {
+                continue;
+            }
+            for swap_direction in pool.get_swap_directions() {
+                edges.entry(*swap_direction.from()).or_default().push((*swap_direction.to(), pool.clone()));
+            }
+        }
+        Self { edges }
+    }
+}
+
+/// Calculates ETH prices of tokens walking the pool graph from wrapped native token, token price is amount of token for one ETH.
+/// Each token is priced with the lowest price impact pool of the shortest path from wrapped native token.
+///
+/// Pool quotes are cached until the pool is invalidated, so only pools touched by a state update are recalculated.
+pub struct PriceGraph {
+    config: PriceGraphConfig,
+    wrapped_native_token: Address,
+    // None for pools that failed to quote or were considered dust
+    quotes: HashMap<(PoolId, Address, Address), Option<PoolQuote>>,
+}
+
+impl PriceGraph {
+    pub fn new(config: PriceGraphConfig, wrapped_native_token: Address) -> Self {
+        Self { config, wrapped_native_token, quotes: HashMap::new() }
+    }
+
+    pub fn config(&self) -> &PriceGraphConfig {
+        &self.config
+    }
+
+    pub fn wrapped_native_token(&self) -> Address {
+        self.wrapped_native_token
+    }
+
+    /// Drops all cached quotes
+    pub fn clear(&mut self) {
+        self.quotes.clear();
+    }
+
+    /// Drops cached quotes of pools, they are recalculated on the next prices calculation
+    pub fn invalidate_pools(&mut self, pool_ids: &HashSet<PoolId>) {
+        self.quotes.retain(|(pool_id, _, _), _| !pool_ids.contains(pool_id));
+    }
+
+    fn quote<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        pool: &PoolWrapper,
+        state_db: &DB,
+        env: &Env,
+        token_from: &Address,
+        token_to: &Address,
+        big_amount: U256,
+    ) -> Option<PoolQuote> {
+        let small_amount = big_amount / U256::from(PROBE_DIVIDER);
+
+        let (Ok((small_out, _)), Ok((big_out, _))) = (
+            pool.calculate_out_amount(state_db, env.clone(), token_from, token_to, small_amount),
+            pool.calculate_out_amount(state_db, env.clone(), token_from, token_to, big_amount),
+        ) else {
+            return None;
+        };
+
+        let expected_out = small_out * U256::from(PROBE_DIVIDER);
+        if expected_out.is_zero() || big_out.is_zero() || big_out > expected_out {
+            return None;
+        }
+        let impact = (expected_out - big_out) * U256::from(10000) / expected_out;
+        if impact > U256::from(self.config.max_price_impact_bps) {
+            trace!(pool = %pool.get_address(), %token_from, %token_to, %impact, "Skipping dust pool");
+            return None;
+        }
+
+        Some(PoolQuote { amount_in: big_amount, amount_out: big_out, impact })
+    }
+
+    pub fn calculate_eth_prices<DB: DatabaseRef<Error = ErrReport>>(
+        &mut self,
+        token_graph: &TokenGraph,
+        state_db: &DB,
+        env: Env,
+    ) -> HashMap<Address, U256> {
+        let root = self.wrapped_native_token;
+
+        let mut prices: HashMap<Address, U256> = HashMap::new();
+        prices.insert(root, ONE_ETHER);
+
+        let mut layer: Vec<Address> = vec![root];
+
+        for _ in 0..self.config.max_hops {
+            let mut candidates: HashMap<Address, PriceCandidate> = HashMap::new();
+
+            for token_from in layer.iter() {
+                let Some(token_from_price) = prices.get(token_from).cloned() else {
+                    continue;
+                };
+                let big_amount =
+                    token_from_price * self.config.probe_eth_amount / ONE_ETHER / U256::from(PROBE_DIVIDER) * U256::from(PROBE_DIVIDER);
+                if big_amount.is_zero() {
+                    continue;
+                }
+
+                let Some(edges) = token_graph.edges.get(token_from) else {
+                    continue;
+                };
+
+                for (token_to, pool) in edges.iter() {
+                    if prices.contains_key(token_to) {
+                        continue;
+                    }
+
+                    let key = (pool.get_pool_id(), *token_from, *token_to);
+                    let quote = match self.quotes.get(&key) {
+                        Some(quote) => *quote,
+                        None => {
+                            let quote = self.quote(pool, state_db, &env, token_from, token_to, big_amount);
+                            self.quotes.insert(key, quote);
+                            quote
+                        }
+                    };
+                    let Some(quote) = quote else {
+                        continue;
+                    };
+
+                    // cached quote could be taken for another amount, its rate is applied to current price of token_from
+                    let price = token_from_price * quote.amount_out / quote.amount_in;
+                    match candidates.get(token_to) {
+                        Some(candidate) if candidate.impact <= quote.impact => {}
+                        _ => {
+                            candidates.insert(*token_to, PriceCandidate { price, impact: quote.impact });
+                        }
+                    }
+                }
+            }
+
+            if candidates.is_empty() {
+                break;
+            }
+
+            layer = candidates.keys().cloned().collect();
+            for (token, candidate) in candidates {
+                prices.insert(token, candidate.price);
+            }
+        }
+
+        prices
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use loom_defi_address_book::TokenAddressEth;
+    use loom_types_entities::MockUniswapV2Pool;
+    use revm::db::EmptyDBTyped;
+
+    #[test]
+    fn test_calculate_eth_prices() {
+        let weth = TokenAddressEth::WETH;
+        let usdc = TokenAddressEth::USDC;
+        let dai = TokenAddressEth::DAI;
+        let crv = TokenAddressEth::CRV;
+        let usdc_exp = U256::from(10).pow(U256::from(6));
+
+        let mut market = Market::default();
+        // 1 ETH = 2000 USDC, deep pool
+        market
+            .add_pool(MockUniswapV2Pool::new(
+                usdc,
+                weth,
+                Address::repeat_byte(1),
+                U256::from(200_000_000) * usdc_exp,
+                U256::from(100_000) * ONE_ETHER,
+            ))
+            .unwrap();
+        // dust pool with wrong price
+        market
+            .add_pool(MockUniswapV2Pool::new(
+                usdc,
+                weth,
+                Address::repeat_byte(2),
+                U256::from(30) * usdc_exp,
+                U256::from(1) * ONE_ETHER / U256::from(100),
+            ))
+            .unwrap();
+        // 1 USDC = 1 DAI
+        market
+            .add_pool(MockUniswapV2Pool::new(
+                usdc,
+                dai,
+                Address::repeat_byte(3),
+                U256::from(10_000_000) * usdc_exp,
+                U256::from(10_000_000) * ONE_ETHER,
+            ))
+            .unwrap();
+        // only dust pool for CRV
+        market.add_pool(MockUniswapV2Pool::new(crv, dai, Address::repeat_byte(4), ONE_ETHER, ONE_ETHER)).unwrap();
+
+        let prices = PriceGraph::new(PriceGraphConfig::default(), weth).calculate_eth_prices(
+            &TokenGraph::new(&market),
+            &EmptyDBTyped::<ErrReport>::new(),
+            Env::default(),
+        );
+
+        assert_eq!(prices.get(&weth), Some(&ONE_ETHER));
+
+        let usdc_price = prices.get(&usdc).unwrap().to::<u64>();
+        assert!(usdc_price > 1_990_000_000 && usdc_price < 2_000_000_000, "{usdc_price}");
+
+        let dai_price = *prices.get(&dai).unwrap() / ONE_ETHER;
+        assert!(dai_price > U256::from(1980) && dai_price < U256::from(2000), "{dai_price}");
+
+        assert!(!prices.contains_key(&crv));
+
+        let prices = PriceGraph::new(PriceGraphConfig { max_hops: 1, ..PriceGraphConfig::default() }, weth).calculate_eth_prices(
+            &TokenGraph::new(&market),
+            &EmptyDBTyped::<ErrReport>::new(),
+            Env::default(),
+        );
+        assert!(prices.contains_key(&usdc));
+        assert!(!prices.contains_key(&dai));
+    }
+
+    #[test]
+    fn test_wrapped_native_token_root() {
+        let wrapped_native_token = Address::repeat_byte(0x42);
+        let usdc = Address::repeat_byte(0x43);
+        let usdc_exp = U256::from(10).pow(U256::from(6));
+
+        let mut market = Market::default();
+        market
+            .add_pool(MockUniswapV2Pool::new(
+                usdc,
+                wrapped_native_token,
+                Address::repeat_byte(1),
+                U256::from(200_000_000) * usdc_exp,
+                U256::from(100_000) * ONE_ETHER,
+            ))
+            .unwrap();
+
+        let prices = PriceGraph::new(PriceGraphConfig::default(), wrapped_native_token).calculate_eth_prices(
+            &TokenGraph::new(&market),
+            &EmptyDBTyped::<ErrReport>::new(),
+            Env::default(),
+        );
+        assert_eq!(prices.get(&wrapped_native_token), Some(&ONE_ETHER));
+        assert!(prices.contains_key(&usdc));
+        assert!(!prices.contains_key(&TokenAddressEth::WETH));
+    }
+
+    #[test]
+    fn test_invalidate_pools() {
+        let weth = TokenAddressEth::WETH;
+        let usdc = TokenAddressEth::USDC;
+        let usdc_exp = U256::from(10).pow(U256::from(6));
+        let pool_address = Address::repeat_byte(1);
+        let db = EmptyDBTyped::<ErrReport>::new();
+
+        let mut market = Market::default();
+        market
+            .add_pool(MockUniswapV2Pool::new(usdc, weth, pool_address, U256::from(200_000_000) * usdc_exp, U256::from(100_000) * ONE_ETHER))
+            .unwrap();
+
+        let mut price_graph = PriceGraph::new(PriceGraphConfig::default(), weth);
+        let usdc_price = *price_graph.calculate_eth_prices(&TokenGraph::new(&market), &db, Env::default()).get(&usdc).unwrap();
+
+        // pool state changed, 1 ETH = 1000 USDC
+        let mut market = Market::default();
+        market
+            .add_pool(MockUniswapV2Pool::new(usdc, weth, pool_address, U256::from(100_000_000) * usdc_exp, U256::from(100_000) * ONE_ETHER))
+            .unwrap();
+        let token_graph = TokenGraph::new(&market);
+
+        let prices = price_graph.calculate_eth_prices(&token_graph, &db, Env::default());
+        assert_eq!(prices.get(&usdc), Some(&usdc_price));
+
+        price_graph.invalidate_pools(&HashSet::from([PoolId::Address(pool_address)]));
+        let prices = price_graph.calculate_eth_prices(&token_graph, &db, Env::default());
+        assert!(*prices.get(&usdc).unwrap() < usdc_price / U256::from(2) + usdc_exp);
+    }
+}
@@ -10,7 +10,7 @@ use alloy::{
 use eyre::eyre;
 use lazy_static::lazy_static;
 use loom_types_blockchain::GethStateUpdate;
-use revm::primitives::{Account, Env, ExecutionResult, HaltReason, Output, ResultAndState, TransactTo, CANCUN};
+use revm::primitives::{Account, Env, EvmState, ExecutionResult, HaltReason, Output, ResultAndState, TransactTo, CANCUN};
 use revm::{Database, DatabaseCommit, DatabaseRef, Evm};
 use std::collections::BTreeMap;
 use std::fmt::Debug;
@@ -169,6 +169,59 @@ pub fn convert_evm_result_to_rpc(
     Ok((logs, state_update))
 }
 
+/// Converts changed accounts of evm execution to pre and post state in format of geth prestate tracer in diff mode.
+/// Pre state is read from `state_db` that transaction was executed on, post state contains only changed fields.
+pub fn convert_evm_state_to_geth_diff<DB>(state_db: DB, state: EvmState) -> eyre::Result<(GethStateUpdate, GethStateUpdate)>
+where
+    DB: DatabaseRef,
+    <DB as DatabaseRef>::Error: Debug,
+{
+    let mut pre: GethStateUpdate = GethStateUpdate::default();
+    let mut post: GethStateUpdate = GethStateUpdate::default();
+
+    for (address, account) in state.into_iter() {
+        if !account.is_touched() {
+            continue;
+        }
+
+        let pre_info = state_db.basic_ref(address).map_err(|error| eyre!("BASIC_REF_ERROR : {error:?}"))?.unwrap_or_default();
+
+        let pre_storage: BTreeMap<B256, B256> =
+            account.storage.iter().filter(|(_, slot)| slot.is_changed()).map(|(k, v)| ((*k).into(), v.original_value.into())).collect();
+        let post_storage: BTreeMap<B256, B256> =
+            account.storage.iter().filter(|(_, slot)| slot.is_changed()).map(|(k, v)| ((*k).into(), v.present_value.into())).collect();
+
+        let balance_changed = pre_info.balance != account.info.balance;
+        let nonce_changed = pre_info.nonce != account.info.nonce;
+        let code_changed = pre_info.code_hash != account.info.code_hash;
+
+        if !balance_changed && !nonce_changed && !code_changed && post_storage.is_empty() {
+            continue;
+        }
+
+        pre.insert(
+            address,
+            AccountState {
+                balance: Some(pre_info.balance),
+                code: pre_info.code.filter(|code| !code.is_empty()).map(|code| code.original_bytes()),
+                nonce: Some(pre_info.nonce),
+                storage: pre_storage,
+            },
+        );
+        post.insert(
+            address,
+            AccountState {
+                balance: balance_changed.then_some(account.info.balance),
+                code: if code_changed { account.info.code.map(|code| code.original_bytes()) } else { None },
+                nonce: nonce_changed.then_some(account.info.nonce),
+                storage: post_storage,
+            },
+        );
+    }
+
+    Ok((pre, post))
+}
+
 pub fn revert_bytes_to_string(bytes: &Bytes) -> String {
     if bytes.len() < 4 {
         return format!("{:?}", bytes);
@@ -180,3 +233,49 @@ pub fn revert_bytes_to_string(bytes: &Bytes) -> String {
         Err(_) => format!("{:?}", bytes),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use loom_evm_db::LoomDB;
+    use revm::primitives::{AccountInfo, EvmStorageSlot};
+
+    #[test]
+    fn test_convert_evm_state_to_geth_diff() -> eyre::Result<()> {
+        let changed = Address::repeat_byte(1);
+        let untouched = Address::repeat_byte(2);
+
+        let mut db = LoomDB::new();
+        db.insert_account_info(changed, AccountInfo { balance: U256::from(100), nonce: 1, ..AccountInfo::default() });
+        db.insert_account_storage(changed, U256::from(1), U256::from(10))?;
+        db.insert_account_info(untouched, AccountInfo { balance: U256::from(5), ..AccountInfo::default() });
+
+        let mut account = Account::from(AccountInfo { balance: U256::from(90), nonce: 1, ..AccountInfo::default() });
+        account.storage.insert(U256::from(1), EvmStorageSlot::new_changed(U256::from(10), U256::from(20)));
+        account.storage.insert(U256::from(2), EvmStorageSlot::new(U256::from(7)));
+        account.mark_touch();
+
+        let mut state = EvmState::default();
+        state.insert(changed, account);
+        state.insert(untouched, Account::from(AccountInfo { balance: U256::from(5), ..AccountInfo::default() }));
+
+        let (pre, post) = convert_evm_state_to_geth_diff(&db, state)?;
+
+        assert_eq!(pre.len(), 1);
+        assert_eq!(post.len(), 1);
+
+        let pre_account = pre.get(&changed).unwrap();
+        assert_eq!(pre_account.balance, Some(U256::from(100)));
+        assert_eq!(pre_account.nonce, Some(1));
+        assert_eq!(pre_account.storage.len(), 1);
+        assert_eq!(pre_account.storage.get(&B256::from(U256::from(1))), Some(&B256::from(U256::from(10))));
+
+        let post_account = post.get(&changed).unwrap();
+        assert_eq!(post_account.balance, Some(U256::from(90)));
+        assert_eq!(post_account.nonce, None);
+        assert_eq!(post_account.code, None);
+        assert_eq!(post_account.storage.get(&B256::from(U256::from(1))), Some(&B256::from(U256::from(20))));
+
+        Ok(())
+    }
+}
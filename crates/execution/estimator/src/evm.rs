@@ -15,7 +15,8 @@ use loom_core_blockchain::{Blockchain, Strategy};
 use loom_evm_utils::NWETH;
 use loom_types_entities::{EstimationError, Swap, SwapEncoder};
 
-use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, Consumer, Producer, WorkerResult};
+use crate::exchange::set_exchange_out_balance;
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, WorkerResult};
 use loom_core_actors_macros::{Consumer, Producer};
 use loom_evm_db::{AlloyDB, DatabaseLoomExt};
 use loom_evm_utils::evm::evm_access_list;
@@ -26,7 +27,7 @@ use revm::DatabaseRef;
 async fn estimator_task<N, DB>(
     client: Option<impl Provider<N> + 'static>,
     swap_encoder: impl SwapEncoder,
-    estimate_request: SwapComposeData<DB>,
+    mut estimate_request: SwapComposeData<DB>,
     compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
     health_monitor_channel_tx: Option<Broadcaster<MessageHealthEvent>>,
     influxdb_write_channel_tx: Option<Broadcaster<WriteQuery>>,
@@ -48,9 +49,28 @@ where
     let tx_signer = estimate_request.tx_compose.signer.clone().ok_or(eyre!("NO_SIGNER"))?;
     let gas_price = estimate_request.tx_compose.priority_gas_fee + estimate_request.tx_compose.next_block_base_fee;
 
+    let Some(mut db) = estimate_request.poststate else {
+        error!("StateDB is None");
+        return Err(eyre!("STATE_DB_IS_NONE"));
+    };
+
+    if let Some(client) = client {
+        let ext_db = AlloyDB::new(client, BlockNumberOrTag::Latest.into());
+        if let Some(ext_db) = ext_db {
+            db.with_ext_db(ext_db)
+        } else {
+            error!("AlloyDB is None");
+        }
+    }
+
+    let evm_env = env_for_block(estimate_request.tx_compose.next_block_number, estimate_request.tx_compose.next_block_timestamp);
+
+    set_exchange_out_balance(&mut estimate_request.swap, Some(&db), evm_env.clone(), swap_encoder.address())?;
+
     let (to, call_value, call_data, _) = swap_encoder.encode(
         estimate_request.swap.clone(),
         estimate_request.tips_pct,
+        estimate_request.tips_policy.as_deref(),
         Some(estimate_request.tx_compose.next_block_number),
         None,
         Some(tx_signer.address()),
@@ -73,22 +93,6 @@ where
         ..TransactionRequest::default()
     };
 
-    let Some(mut db) = estimate_request.poststate else {
-        error!("StateDB is None");
-        return Err(eyre!("STATE_DB_IS_NONE"));
-    };
-
-    if let Some(client) = client {
-        let ext_db = AlloyDB::new(client, BlockNumberOrTag::Latest.into());
-        if let Some(ext_db) = ext_db {
-            db.with_ext_db(ext_db)
-        } else {
-            error!("AlloyDB is None");
-        }
-    }
-
-    let evm_env = env_for_block(estimate_request.tx_compose.next_block_number, estimate_request.tx_compose.next_block_timestamp);
-
     let (gas_used, access_list) = match evm_access_list(&db, &evm_env, &tx_request) {
         Ok((gas_used, access_list)) => {
             let pool_id_vec = estimate_request.swap.get_pool_id_vec();
@@ -121,7 +125,7 @@ where
             trace!("evm_access_list error calldata : {} {}", to, call_data);
 
             if let Some(health_monitor_channel_tx) = &health_monitor_channel_tx {
-                if let Swap::BackrunSwapLine(swap_line) = estimate_request.swap {
+                if let Swap::BackrunSwapLine(swap_line) | Swap::ExchangeSwapLine(swap_line) = estimate_request.swap {
                     if let Err(e) =
                         health_monitor_channel_tx.send(MessageHealthEvent::new(HealthEvent::SwapLineEstimationError(EstimationError {
                             swap_path: swap_line.path,
@@ -157,6 +161,7 @@ where
     let (to, call_value, call_data, tips_vec) = match swap_encoder.encode(
         estimate_request.swap.clone(),
         estimate_request.tips_pct,
+        estimate_request.tips_policy.as_deref(),
         Some(estimate_request.tx_compose.next_block_number),
         Some(gas_cost),
         Some(tx_signer.address()),
@@ -206,9 +211,9 @@ where
     };
 
     let sign_request = MessageSwapCompose::ready(SwapComposeData {
-        tx_compose: TxComposeData { tx_bundle: Some(tx_with_state), ..estimate_request.tx_compose },
+        tx_compose: TxComposeData { tx_bundle: Some(tx_with_state), gas_cost: Some(gas_cost), ..estimate_request.tx_compose },
         poststate: Some(db),
-        tips: Some(total_tips + gas_cost),
+        tips: Some(total_tips),
         ..estimate_request
     });
 
@@ -242,6 +247,7 @@ async fn estimator_worker<N, DB>(
     compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
     health_monitor_channel_tx: Option<Broadcaster<MessageHealthEvent>>,
     influxdb_write_channel_tx: Option<Broadcaster<WriteQuery>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -251,6 +257,9 @@ where
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = compose_channel_rx.recv() => {
                 let compose_request_msg : Result<MessageSwapCompose<DB>, RecvError> = msg;
                 match compose_request_msg {
@@ -282,6 +291,7 @@ where
             }
         }
     }
+    Ok("EvmEstimatorWorker stopped".to_string())
 }
 
 #[derive(Consumer, Producer)]
@@ -348,7 +358,7 @@ where
     E: SwapEncoder + Clone + Send + Sync + 'static,
     DB: DatabaseRef + DatabaseLoomExt + Send + Sync + Clone,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(estimator_worker(
             self.client.clone(),
             self.encoder.clone(),
@@ -356,6 +366,7 @@ where
             self.compose_channel_tx.clone().unwrap(),
             self.health_monitor_channel_tx.clone(),
             self.influxdb_write_channel_tx.clone(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
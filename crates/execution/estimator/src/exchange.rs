@@ -0,0 +1,16 @@
+use alloy_primitives::Address;
+use eyre::{OptionExt, Result};
+use loom_defi_pools::state_readers::ERC20StateReader;
+use loom_types_entities::Swap;
+use revm::primitives::Env;
+use revm::DatabaseRef;
+
+/// Reads output token balance of the swap executor before the exchange swap, the encoder guards the swapped amount against it
+pub(crate) fn set_exchange_out_balance<DB: DatabaseRef>(swap: &mut Swap, db: Option<&DB>, env: Env, executor: Address) -> Result<()> {
+    if let Swap::ExchangeSwapLine(swap_line) = swap {
+        let db = db.ok_or_eyre("STATE_DB_IS_NONE")?;
+        let token_to_address = swap_line.get_last_token().ok_or_eyre("NO_LAST_TOKEN")?.get_address();
+        swap_line.out_balance_before = Some(ERC20StateReader::balance_of(db, env, token_to_address, executor)?);
+    }
+    Ok(())
+}
@@ -12,17 +12,19 @@ use tokio::sync::broadcast::error::RecvError;
 use tracing::{debug, error, info};
 
 use loom_core_blockchain::Strategy;
+use loom_evm_utils::evm_env::env_for_block;
 use loom_evm_utils::NWETH;
 use loom_types_entities::{Swap, SwapEncoder};
 
+use crate::exchange::set_exchange_out_balance;
 use loom_broadcast_flashbots::Flashbots;
-use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, Consumer, Producer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, WorkerResult};
 use loom_core_actors_macros::{Consumer, Producer};
 use loom_types_blockchain::LoomTx;
 use loom_types_events::{MessageSwapCompose, SwapComposeData, SwapComposeMessage, TxComposeData, TxState};
 
 async fn estimator_task<P: Provider<Ethereum> + Send + Sync + Clone + 'static, DB: DatabaseRef + Send + Sync + Clone>(
-    estimate_request: SwapComposeData<DB>,
+    mut estimate_request: SwapComposeData<DB>,
     client: Arc<Flashbots<P>>,
     swap_encoder: impl SwapEncoder,
     compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
@@ -41,9 +43,13 @@ async fn estimator_task<P: Provider<Ethereum> + Send + Sync + Clone + 'static, D
     let gas_price = estimate_request.tx_compose.priority_gas_fee + estimate_request.tx_compose.next_block_base_fee;
     let gas_cost = U256::from(100_000 * gas_price);
 
+    let evm_env = env_for_block(estimate_request.tx_compose.next_block_number, estimate_request.tx_compose.next_block_timestamp);
+    set_exchange_out_balance(&mut estimate_request.swap, estimate_request.poststate.as_ref(), evm_env, swap_encoder.address())?;
+
     let (to, _, call_data, _) = swap_encoder.encode(
         estimate_request.swap.clone(),
         estimate_request.tips_pct,
+        estimate_request.tips_policy.as_deref(),
         Some(estimate_request.tx_compose.next_block_number),
         Some(gas_cost),
         Some(tx_signer.address()),
@@ -123,6 +129,7 @@ async fn estimator_task<P: Provider<Ethereum> + Send + Sync + Clone + 'static, D
                             _ => swap_encoder.encode(
                                 estimate_request.swap.clone(),
                                 estimate_request.tips_pct,
+                                estimate_request.tips_policy.as_deref(),
                                 Some(estimate_request.tx_compose.next_block_number),
                                 Some(gas_cost),
                                 Some(tx_signer.address()),
@@ -153,8 +160,8 @@ async fn estimator_task<P: Provider<Ethereum> + Send + Sync + Clone + 'static, D
                         let total_tips = tips_vec.into_iter().map(|v| v.tips).sum();
 
                         let sign_request = MessageSwapCompose::ready(SwapComposeData {
-                            tx_compose: TxComposeData { gas, ..estimate_request.tx_compose },
-                            tips: Some(total_tips + gas_cost),
+                            tx_compose: TxComposeData { gas, gas_cost: Some(gas_cost), ..estimate_request.tx_compose },
+                            tips: Some(total_tips),
                             ..estimate_request
                         });
 
@@ -202,11 +209,15 @@ async fn estimator_worker<P: Provider<Ethereum> + Send + Sync + Clone + 'static,
     encoder: impl SwapEncoder + Send + Sync + Clone + 'static,
     compose_channel_rx: Broadcaster<MessageSwapCompose<DB>>,
     compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(compose_channel_rx);
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = compose_channel_rx.recv() => {
                 let compose_request_msg : Result<MessageSwapCompose<DB>, RecvError> = msg;
                 match compose_request_msg {
@@ -233,6 +244,7 @@ async fn estimator_worker<P: Provider<Ethereum> + Send + Sync + Clone + 'static,
             }
         }
     }
+    Ok("GethEstimatorWorker stopped".to_string())
 }
 
 #[derive(Consumer, Producer)]
@@ -270,12 +282,13 @@ where
     E: SwapEncoder + Send + Sync + Clone + 'static,
     DB: DatabaseRef + Send + Sync + Clone,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(estimator_worker(
             self.client.clone(),
             self.encoder.clone(),
             self.compose_channel_rx.clone().unwrap(),
             self.compose_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -8,7 +8,7 @@ use revm::DatabaseRef;
 use tokio::sync::broadcast::error::RecvError;
 use tracing::{error, info};
 
-use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, Consumer, Producer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, WorkerResult};
 use loom_core_actors_macros::{Consumer, Producer};
 use loom_node_debug_provider::DebugProviderExt;
 use loom_types_entities::SwapEncoder;
@@ -18,11 +18,15 @@ async fn estimator_worker<DB: DatabaseRef + Send + Sync + Clone>(
     swap_encoder: impl SwapEncoder,
     compose_channel_rx: Broadcaster<MessageSwapCompose<DB>>,
     compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(compose_channel_rx);
 
     loop {
         tokio::select! {
+                    _ = cancel_token.cancelled() => {
+                        break;
+                    }
                     msg = compose_channel_rx.recv() => {
                         let compose_request_msg : Result<MessageSwapCompose<DB>, RecvError> = msg;
                         match compose_request_msg {
@@ -45,6 +49,7 @@ async fn estimator_worker<DB: DatabaseRef + Send + Sync + Clone>(
                                     let (to, _call_value, call_data, _) = swap_encoder.encode(
                                         estimate_request.swap.clone(),
                                         estimate_request.tips_pct,
+                                        estimate_request.tips_policy.as_deref(),
                                         Some(estimate_request.tx_compose.next_block_number),
                                         Some(gas_cost),
                                         Some(tx_signer.address()),
@@ -99,6 +104,7 @@ async fn estimator_worker<DB: DatabaseRef + Send + Sync + Clone>(
             }
         }
     }
+    Ok("HardhatEstimatorWorker stopped".to_string())
 }
 
 #[allow(dead_code)]
@@ -129,11 +135,12 @@ where
     E: SwapEncoder + Send + Sync + Clone + 'static,
     DB: DatabaseRef + Send + Sync + Clone,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(estimator_worker(
             self.encoder.clone(),
             self.compose_channel_rx.clone().unwrap(),
             self.compose_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -1,4 +1,5 @@
 mod evm;
+mod exchange;
 mod geth;
 mod hardhat;
 
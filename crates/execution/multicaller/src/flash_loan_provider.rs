@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use alloy_primitives::{Address, U256};
+use eyre::{eyre, Result};
+use tracing::trace;
+
+use crate::opcodes_encoder::{OpcodesEncoder, OpcodesEncoderV2};
+use loom_defi_abi::AbiEncoderHelper;
+use loom_defi_address_book::{FactoryAddress, PeripheryAddress, TokenAddressEth};
+use loom_types_blockchain::{MulticallerCall, MulticallerCalls};
+
+/// Aave V3 FLASHLOAN_PREMIUM_TOTAL on mainnet, in basis points
+pub const AAVE_V3_FLASH_LOAN_PREMIUM_BPS: u32 = 5;
+
+/// Source of flash loaned funds for swaps that cannot be started with a flash swap
+pub trait FlashLoanProvider: Send + Sync {
+    /// Contract that is called to take the loan
+    fn get_address(&self) -> Address;
+
+    /// Fee paid on top of amount, None if token is not supported
+    fn get_fee(&self, token: Address, amount: U256) -> Option<U256>;
+
+    /// Known amount of token available for loan, None if unknown
+    fn get_liquidity(&self, token: Address) -> Option<U256>;
+
+    /// Wrap inside opcodes in flash loan call. Inside opcodes must leave amount of token on multicaller.
+    fn encode_flash_loan(
+        &self,
+        token: Address,
+        amount: U256,
+        inside_opcodes: MulticallerCalls,
+        multicaller_address: Address,
+    ) -> Result<MulticallerCalls>;
+}
+
+pub struct BalancerFlashLoanProvider {
+    vault_address: Address,
+    liquidity: HashMap<Address, U256>,
+}
+
+impl BalancerFlashLoanProvider {
+    pub fn new(vault_address: Address) -> Self {
+        Self { vault_address, liquidity: HashMap::new() }
+    }
+
+    pub fn with_liquidity(self, token: Address, amount: U256) -> Self {
+        let mut liquidity = self.liquidity;
+        liquidity.insert(token, amount);
+        Self { liquidity, ..self }
+    }
+}
+
+impl Default for BalancerFlashLoanProvider {
+    fn default() -> Self {
+        Self::new(FactoryAddress::BALANCER_V2_VAULT)
+    }
+}
+
+impl FlashLoanProvider for BalancerFlashLoanProvider {
+    fn get_address(&self) -> Address {
+        self.vault_address
+    }
+
+    fn get_fee(&self, _token: Address, _amount: U256) -> Option<U256> {
+        Some(U256::ZERO)
+    }
+
+    fn get_liquidity(&self, token: Address) -> Option<U256> {
+        self.liquidity.get(&token).cloned()
+    }
+
+    fn encode_flash_loan(
+        &self,
+        token: Address,
+        amount: U256,
+        inside_opcodes: MulticallerCalls,
+        multicaller_address: Address,
+    ) -> Result<MulticallerCalls> {
+        // receiveFlashLoan handler of multicaller returns funds to the vault
+        let inside_call_bytes = OpcodesEncoderV2::pack_do_calls_data(&inside_opcodes)?;
+
+        let mut flash_opcodes = MulticallerCalls::new();
+        flash_opcodes.add(MulticallerCall::new_call(
+            self.vault_address,
+            &AbiEncoderHelper::encode_balancer_flashloan(token, amount, inside_call_bytes, multicaller_address),
+        ));
+        Ok(flash_opcodes)
+    }
+}
+
+pub struct AaveV3FlashLoanProvider {
+    pool_address: Address,
+    premium_bps: u32,
+    tokens: Vec<Address>,
+    liquidity: HashMap<Address, U256>,
+}
+
+impl AaveV3FlashLoanProvider {
+    pub fn new(pool_address: Address, premium_bps: u32, tokens: Vec<Address>) -> Self {
+        Self { pool_address, premium_bps, tokens, liquidity: HashMap::new() }
+    }
+
+    pub fn with_liquidity(self, token: Address, amount: U256) -> Self {
+        let mut liquidity = self.liquidity;
+        liquidity.insert(token, amount);
+        Self { liquidity, ..self }
+    }
+}
+
+impl Default for AaveV3FlashLoanProvider {
+    fn default() -> Self {
+        Self::new(
+            PeripheryAddress::AAVE_V3_POOL,
+            AAVE_V3_FLASH_LOAN_PREMIUM_BPS,
+            vec![TokenAddressEth::WETH, TokenAddressEth::USDC, TokenAddressEth::USDT, TokenAddressEth::DAI, TokenAddressEth::WBTC],
+        )
+    }
+}
+
+impl FlashLoanProvider for AaveV3FlashLoanProvider {
+    fn get_address(&self) -> Address {
+        self.pool_address
+    }
+
+    fn get_fee(&self, token: Address, amount: U256) -> Option<U256> {
+        if !self.tokens.contains(&token) {
+            return None;
+        }
+        // rounded up, pool takes premium with half up rounding
+        Some((amount * U256::from(self.premium_bps)).div_ceil(U256::from(10000)))
+    }
+
+    fn get_liquidity(&self, token: Address) -> Option<U256> {
+        self.liquidity.get(&token).cloned()
+    }
+
+    fn encode_flash_loan(
+        &self,
+        token: Address,
+        amount: U256,
+        inside_opcodes: MulticallerCalls,
+        multicaller_address: Address,
+    ) -> Result<MulticallerCalls> {
+        let fee = self.get_fee(token, amount).ok_or(eyre!("TOKEN_NOT_SUPPORTED"))?;
+
+        // pool pulls amount and premium after executeOperation, executeOperation handler of multicaller returns top of the stack
+        // that must be true, approve result is put there
+        let mut inside_opcodes = inside_opcodes;
+        let mut approve_opcode = MulticallerCall::new_call(token, &AbiEncoderHelper::encode_erc20_approve(self.pool_address, amount + fee));
+        approve_opcode.set_return_stack(true, 0, 0, 0x20);
+        inside_opcodes.add(approve_opcode);
+
+        let inside_call_bytes = OpcodesEncoderV2::pack_do_calls_data(&inside_opcodes)?;
+
+        let mut flash_opcodes = MulticallerCalls::new();
+        flash_opcodes.add(MulticallerCall::new_call(
+            self.pool_address,
+            &AbiEncoderHelper::encode_aave_flashloan_simple(multicaller_address, token, amount, inside_call_bytes),
+        ));
+        Ok(flash_opcodes)
+    }
+}
+
+pub struct UniswapV3FlashLoanProvider {
+    pool_address: Address,
+    token0: Address,
+    token1: Address,
+    /// Pool fee in hundredths of a bip
+    fee: u32,
+    liquidity: HashMap<Address, U256>,
+}
+
+impl UniswapV3FlashLoanProvider {
+    pub fn new(pool_address: Address, token0: Address, token1: Address, fee: u32) -> Self {
+        Self { pool_address, token0, token1, fee, liquidity: HashMap::new() }
+    }
+
+    pub fn with_liquidity(self, token: Address, amount: U256) -> Self {
+        let mut liquidity = self.liquidity;
+        liquidity.insert(token, amount);
+        Self { liquidity, ..self }
+    }
+}
+
+impl FlashLoanProvider for UniswapV3FlashLoanProvider {
+    fn get_address(&self) -> Address {
+        self.pool_address
+    }
+
+    fn get_fee(&self, token: Address, amount: U256) -> Option<U256> {
+        if token != self.token0 && token != self.token1 {
+            return None;
+        }
+        Some((amount * U256::from(self.fee)).div_ceil(U256::from(1_000_000)))
+    }
+
+    fn get_liquidity(&self, token: Address) -> Option<U256> {
+        self.liquidity.get(&token).cloned()
+    }
+
+    fn encode_flash_loan(
+        &self,
+        token: Address,
+        amount: U256,
+        inside_opcodes: MulticallerCalls,
+        multicaller_address: Address,
+    ) -> Result<MulticallerCalls> {
+        let fee = self.get_fee(token, amount).ok_or(eyre!("TOKEN_NOT_SUPPORTED"))?;
+
+        // pool checks its balance after uniswapV3FlashCallback handler of multicaller
+        let mut inside_opcodes = inside_opcodes;
+        inside_opcodes.add(MulticallerCall::new_call(token, &AbiEncoderHelper::encode_erc20_transfer(self.pool_address, amount + fee)));
+
+        let inside_call_bytes = OpcodesEncoderV2::pack_do_calls_data(&inside_opcodes)?;
+
+        let (amount0, amount1) = if token == self.token0 { (amount, U256::ZERO) } else { (U256::ZERO, amount) };
+
+        let mut flash_opcodes = MulticallerCalls::new();
+        flash_opcodes.add(MulticallerCall::new_call(
+            self.pool_address,
+            &AbiEncoderHelper::encode_uniswap3_flash(multicaller_address, amount0, amount1, inside_call_bytes),
+        ));
+        Ok(flash_opcodes)
+    }
+}
+
+/// Flash loan providers in order of preference for equal fees
+#[derive(Clone)]
+pub struct FlashLoanProviders {
+    providers: Vec<Arc<dyn FlashLoanProvider>>,
+}
+
+impl FlashLoanProviders {
+    pub fn new() -> Self {
+        Self { providers: Vec::new() }
+    }
+
+    pub fn with_provider(self, provider: Arc<dyn FlashLoanProvider>) -> Self {
+        let mut providers = self.providers;
+        providers.push(provider);
+        Self { providers }
+    }
+
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// Provider with the lowest fee for amount of token. Providers with known liquidity below amount and excluded addresses,
+    /// usually pools of the swap itself, are skipped.
+    pub fn select(&self, token: Address, amount: U256, exclude: &[Address]) -> Option<Arc<dyn FlashLoanProvider>> {
+        let mut best: Option<(U256, &Arc<dyn FlashLoanProvider>)> = None;
+
+        for provider in self.providers.iter() {
+            if exclude.contains(&provider.get_address()) {
+                continue;
+            }
+            let Some(fee) = provider.get_fee(token, amount) else {
+                continue;
+            };
+            if let Some(liquidity) = provider.get_liquidity(token) {
+                if liquidity < amount {
+                    trace!(provider=%provider.get_address(), %token, %liquidity, %amount, "Flash loan liquidity is not enough");
+                    continue;
+                }
+            }
+            if best.as_ref().is_none_or(|(best_fee, _)| fee < *best_fee) {
+                best = Some((fee, provider));
+            }
+        }
+
+        best.map(|(_, provider)| provider.clone())
+    }
+}
+
+impl Default for FlashLoanProviders {
+    fn default() -> Self {
+        Self::new()
+            .with_provider(Arc::new(BalancerFlashLoanProvider::default()))
+            .with_provider(Arc::new(AaveV3FlashLoanProvider::default()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{MulticallerDeployer, DEFAULT_VIRTUAL_ADDRESS};
+    use alloy_primitives::Bytes;
+    use alloy_sol_types::SolCall;
+    use loom_defi_abi::aave::IAavePool;
+    use loom_defi_abi::multicaller::IMultiCaller;
+    use loom_defi_abi::uniswap3::IUniswapV3Pool;
+    use loom_defi_address_book::UniswapV3PoolAddress;
+    use loom_evm_db::LoomDB;
+    use loom_evm_utils::evm::evm_call;
+    use revm::primitives::{AccountInfo, Bytecode, Env};
+
+    #[test]
+    fn test_select_provider() {
+        let weth = TokenAddressEth::WETH;
+        let usdc = TokenAddressEth::USDC;
+        let amount = U256::from(1_000_000u64);
+
+        let providers = FlashLoanProviders::new()
+            .with_provider(Arc::new(UniswapV3FlashLoanProvider::new(UniswapV3PoolAddress::USDC_WETH_500, usdc, weth, 3000)))
+            .with_provider(Arc::new(AaveV3FlashLoanProvider::default()))
+            .with_provider(Arc::new(BalancerFlashLoanProvider::default().with_liquidity(weth, amount)));
+
+        // balancer has no fee and enough liquidity
+        let provider = providers.select(weth, amount, &[]).unwrap();
+        assert_eq!(provider.get_address(), FactoryAddress::BALANCER_V2_VAULT);
+
+        // balancer has no enough liquidity, aave premium is lower than pool fee
+        let provider = providers.select(weth, amount + U256::from(1), &[]).unwrap();
+        assert_eq!(provider.get_address(), PeripheryAddress::AAVE_V3_POOL);
+        assert_eq!(provider.get_fee(weth, amount), Some(U256::from(500)));
+
+        // balancer liquidity is unknown for usdc
+        let provider = providers.select(usdc, amount, &[]).unwrap();
+        assert_eq!(provider.get_address(), FactoryAddress::BALANCER_V2_VAULT);
+
+        let provider = providers.select(weth, amount, &[FactoryAddress::BALANCER_V2_VAULT, PeripheryAddress::AAVE_V3_POOL]).unwrap();
+        assert_eq!(provider.get_address(), UniswapV3PoolAddress::USDC_WETH_500);
+
+        assert!(providers
+            .select(TokenAddressEth::CRV, amount, &[FactoryAddress::BALANCER_V2_VAULT, UniswapV3PoolAddress::USDC_WETH_500])
+            .is_none());
+    }
+
+    #[test]
+    fn test_encode_flash_loan() {
+        let weth = TokenAddressEth::WETH;
+        let amount = U256::from(1000);
+        let multicaller_address = Address::repeat_byte(1);
+        let provider = BalancerFlashLoanProvider::default();
+
+        let mut inside_opcodes = MulticallerCalls::new();
+        inside_opcodes.add(MulticallerCall::new_call(weth, &AbiEncoderHelper::encode_erc20_transfer(Address::repeat_byte(2), amount)));
+
+        let opcodes = provider.encode_flash_loan(weth, amount, inside_opcodes.clone(), multicaller_address).unwrap();
+        let flash_call = opcodes.opcodes_vec.first().unwrap();
+        assert_eq!(flash_call.to, FactoryAddress::BALANCER_V2_VAULT);
+        assert_eq!(
+            flash_call.call_data,
+            AbiEncoderHelper::encode_balancer_flashloan(
+                weth,
+                amount,
+                OpcodesEncoderV2::pack_do_calls_data(&inside_opcodes).unwrap(),
+                multicaller_address
+            )
+        );
+
+        let provider = UniswapV3FlashLoanProvider::new(UniswapV3PoolAddress::USDC_WETH_500, TokenAddressEth::USDC, weth, 500);
+        assert!(provider.encode_flash_loan(TokenAddressEth::DAI, amount, MulticallerCalls::new(), multicaller_address).is_err());
+    }
+
+    // token that returns true for any call
+    fn multicaller_db(token: Address) -> LoomDB {
+        let mut db = LoomDB::empty();
+        let code = MulticallerDeployer::new().account_info().code.unwrap();
+        db.insert_account_info(DEFAULT_VIRTUAL_ADDRESS, AccountInfo { code: Some(Bytecode::new_raw(code)), ..AccountInfo::default() });
+        // PUSH1 1 PUSH0 MSTORE PUSH1 0x20 PUSH0 RETURN
+        db.insert_account_info(
+            token,
+            AccountInfo {
+                code: Some(Bytecode::new_raw(Bytes::from(vec![0x60, 0x01, 0x5f, 0x52, 0x60, 0x20, 0x5f, 0xf3]))),
+                ..AccountInfo::default()
+            },
+        );
+        db
+    }
+
+    #[test]
+    fn test_aave_execute_operation_callback() {
+        let token = Address::repeat_byte(0x11);
+        // amount at the offset of params is misrouted by the calldata layout fallback of multicaller
+        let amount = U256::from(64);
+        let provider = AaveV3FlashLoanProvider::new(PeripheryAddress::AAVE_V3_POOL, AAVE_V3_FLASH_LOAN_PREMIUM_BPS, vec![token]);
+        let premium = provider.get_fee(token, amount).unwrap();
+
+        let opcodes = provider.encode_flash_loan(token, amount, MulticallerCalls::new(), DEFAULT_VIRTUAL_ADDRESS).unwrap();
+        let flash_call = IAavePool::flashLoanSimpleCall::abi_decode(&opcodes.opcodes_vec[0].call_data, true).unwrap();
+        assert_eq!(flash_call.receiverAddress, DEFAULT_VIRTUAL_ADDRESS);
+
+        // pool calls executeOperation on receiver and requires true
+        let call_data = IMultiCaller::executeOperationCall {
+            _0: token,
+            _1: amount,
+            _2: premium,
+            _3: DEFAULT_VIRTUAL_ADDRESS,
+            params: flash_call.params,
+        }
+        .abi_encode();
+        let (output, _) = evm_call(&multicaller_db(token), Env::default(), DEFAULT_VIRTUAL_ADDRESS, call_data).unwrap();
+        assert!(IMultiCaller::executeOperationCall::abi_decode_returns(&output, true).unwrap()._0);
+    }
+
+    #[test]
+    fn test_uniswap3_flash_callback() {
+        let token = Address::repeat_byte(0x11);
+        let provider = UniswapV3FlashLoanProvider::new(UniswapV3PoolAddress::USDC_WETH_500, token, TokenAddressEth::WETH, 500);
+
+        let opcodes = provider.encode_flash_loan(token, U256::from(1000), MulticallerCalls::new(), DEFAULT_VIRTUAL_ADDRESS).unwrap();
+        let flash_call = IUniswapV3Pool::flashCall::abi_decode(&opcodes.opcodes_vec[0].call_data, true).unwrap();
+        assert_eq!(flash_call.recipient, DEFAULT_VIRTUAL_ADDRESS);
+
+        // pool calls uniswapV3FlashCallback on recipient and checks balance after
+        let call_data = IMultiCaller::uniswapV3FlashCallbackCall { _0: U256::from(1), _1: U256::ZERO, data: flash_call.data }.abi_encode();
+        assert!(evm_call(&multicaller_db(token), Env::default(), DEFAULT_VIRTUAL_ADDRESS, call_data).is_ok());
+    }
+}
@@ -1,13 +1,18 @@
 #![allow(dead_code)]
 pub use deploy::{MulticallerDeployer, DEFAULT_VIRTUAL_ADDRESS};
+pub use flash_loan_provider::{
+    AaveV3FlashLoanProvider, BalancerFlashLoanProvider, FlashLoanProvider, FlashLoanProviders, UniswapV3FlashLoanProvider,
+    AAVE_V3_FLASH_LOAN_PREMIUM_BPS,
+};
 pub use multicaller_encoder::MulticallerEncoder;
-pub use multicaller_encoder::MulticallerSwapEncoder;
+pub use multicaller_encoder::{MulticallerSwapEncoder, DEFAULT_EXCHANGE_SLIPPAGE_BPS};
 pub use opcodes_encoder::{OpcodesEncoder, OpcodesEncoderV2};
 pub use pool_abi_encoder::ProtocolABIEncoderV2;
 pub use swapline_encoder::SwapLineEncoder;
 pub use swapstep_encoder::SwapStepEncoder;
 
 mod deploy;
+mod flash_loan_provider;
 mod multicaller_encoder;
 mod opcodes_encoder;
 mod opcodes_helpers;
@@ -1,4 +1,4 @@
-use alloy_primitives::{Address, Bytes};
+use alloy_primitives::{Address, Bytes, U256};
 use eyre::{eyre, OptionExt, Result};
 use std::sync::Arc;
 use tracing::error;
@@ -6,8 +6,11 @@ use tracing::error;
 use crate::pool_abi_encoder::ProtocolABIEncoderV2;
 use crate::pool_opcodes_encoder::ProtocolSwapOpcodesEncoderV2;
 use crate::{SwapLineEncoder, SwapStepEncoder, DEFAULT_VIRTUAL_ADDRESS};
-use loom_types_blockchain::MulticallerCalls;
-use loom_types_entities::Swap;
+use loom_types_blockchain::{LoomDataTypesEthereum, MulticallerCalls};
+use loom_types_entities::{Swap, SwapLine};
+
+/// Default allowed decrease of exchange swap output from the calculated amount, in basis points
+pub const DEFAULT_EXCHANGE_SLIPPAGE_BPS: u32 = 50;
 
 pub trait MulticallerEncoder {
     fn encode_calls(&self, calls: MulticallerCalls) -> Result<(Address, Bytes)>;
@@ -19,11 +22,13 @@ pub trait MulticallerEncoder {
 pub struct MulticallerSwapEncoder {
     pub multicaller_address: Address,
     pub swap_step_encoder: SwapStepEncoder,
+    /// Allowed decrease of exchange swap output from the calculated amount, in basis points
+    pub exchange_slippage_bps: u32,
 }
 
 impl MulticallerSwapEncoder {
     pub fn new(multicaller_address: Address, swap_step_encoder: SwapStepEncoder) -> Self {
-        Self { multicaller_address, swap_step_encoder }
+        Self { multicaller_address, swap_step_encoder, exchange_slippage_bps: DEFAULT_EXCHANGE_SLIPPAGE_BPS }
     }
 
     pub fn default_with_address(multicaller_address: Address) -> Self {
@@ -34,12 +39,28 @@ impl MulticallerSwapEncoder {
 
         let swap_step_encoder = SwapStepEncoder::new(multicaller_address, swap_line_encoder);
 
-        Self { multicaller_address, swap_step_encoder }
+        Self::new(multicaller_address, swap_step_encoder)
+    }
+
+    pub fn with_exchange_slippage_bps(self, exchange_slippage_bps: u32) -> Self {
+        Self { exchange_slippage_bps, ..self }
     }
 
     pub fn get_contract_address(&self) -> Address {
         self.multicaller_address
     }
+
+    /// Minimal output of exchange swap line, calculated amount out reduced by slippage
+    pub fn exchange_min_amount_out(&self, swap_line: &SwapLine<LoomDataTypesEthereum>) -> Result<U256> {
+        if self.exchange_slippage_bps > 10000 {
+            return Err(eyre!("INCORRECT_SLIPPAGE"));
+        }
+        let amount_out = swap_line.amount_out.unwrap_or_default();
+        if amount_out.is_zero() {
+            return Err(eyre!("AMOUNT_OUT_NOT_SET"));
+        }
+        Ok(amount_out * U256::from(10000 - self.exchange_slippage_bps) / U256::from(10000))
+    }
 }
 
 impl MulticallerEncoder for MulticallerSwapEncoder {
@@ -58,6 +79,11 @@ impl MulticallerEncoder for MulticallerSwapEncoder {
                 self.swap_step_encoder.encode_swap_steps(&swap_step_0, &swap_step_1)
             }
             Swap::BackrunSwapSteps((swap_step_0, swap_step_1)) => self.swap_step_encoder.encode_swap_steps(swap_step_0, swap_step_1),
+            Swap::ExchangeSwapLine(swap_line) => self.swap_step_encoder.swap_line_encoder.encode_exchange_swap_line(
+                swap_line,
+                swap_line.out_balance_before.ok_or_eyre("OUT_BALANCE_BEFORE_NOT_SET")?,
+                self.exchange_min_amount_out(swap_line)?,
+            ),
             Swap::Multiple(swap_vec) => {
                 if swap_vec.len() == 1 {
                     self.make_calls(&swap_vec[0])
@@ -82,3 +108,67 @@ impl Default for MulticallerSwapEncoder {
         MulticallerSwapEncoder::default_with_address(DEFAULT_VIRTUAL_ADDRESS)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use loom_defi_abi::AbiEncoderHelper;
+    use loom_defi_address_book::TokenAddressEth;
+    use loom_defi_pools::UniswapV2Pool;
+    use loom_types_entities::{SwapAmountType, SwapPath, Token};
+
+    #[test]
+    fn test_exchange_min_amount_out() {
+        let encoder = MulticallerSwapEncoder::default();
+        let mut swap_line = SwapLine::default();
+        assert!(encoder.exchange_min_amount_out(&swap_line).is_err());
+
+        swap_line.amount_out = SwapAmountType::Set(U256::from(10000));
+        assert_eq!(encoder.exchange_min_amount_out(&swap_line).unwrap(), U256::from(9950));
+
+        let encoder = encoder.with_exchange_slippage_bps(0);
+        assert_eq!(encoder.exchange_min_amount_out(&swap_line).unwrap(), U256::from(10000));
+
+        let encoder = encoder.with_exchange_slippage_bps(10001);
+        assert!(encoder.exchange_min_amount_out(&swap_line).is_err());
+    }
+
+    #[test]
+    fn test_make_calls_exchange_without_amount_out() {
+        let encoder = MulticallerSwapEncoder::default();
+        assert!(encoder.make_calls(&Swap::ExchangeSwapLine(SwapLine::default())).is_err());
+    }
+
+    #[test]
+    fn test_make_calls_exchange_guards_swapped_amount() {
+        let encoder = MulticallerSwapEncoder::default();
+        let pool = UniswapV2Pool::new_with_data(
+            Address::repeat_byte(1),
+            TokenAddressEth::USDC,
+            TokenAddressEth::WETH,
+            Address::ZERO,
+            U256::ZERO,
+            U256::ZERO,
+        );
+        let swap_path = SwapPath::new(vec![Token::new(TokenAddressEth::WETH), Token::new(TokenAddressEth::USDC)], vec![pool]);
+        let mut swap_line = SwapLine::from(swap_path);
+        swap_line.amount_in = SwapAmountType::Set(U256::from(1000));
+        swap_line.amount_out = SwapAmountType::Set(U256::from(10000));
+        assert!(encoder.make_calls(&Swap::ExchangeSwapLine(swap_line.clone())).is_err());
+
+        swap_line.out_balance_before = Some(U256::from(1000));
+        let calls = encoder.make_calls(&Swap::ExchangeSwapLine(swap_line.clone())).unwrap();
+        let guard = calls.opcodes_vec.last().unwrap();
+        assert_eq!(
+            guard.call_data,
+            AbiEncoderHelper::encode_multicaller_transfer_tips_no_payout(TokenAddressEth::USDC, U256::from(10950), U256::ZERO)
+        );
+
+        let recipient = Address::repeat_byte(2);
+        swap_line.swap_to = Some(recipient);
+        let calls = encoder.make_calls(&Swap::ExchangeSwapLine(swap_line)).unwrap();
+        let transfer = calls.opcodes_vec.last().unwrap();
+        assert_eq!(transfer.to, TokenAddressEth::USDC);
+        assert_eq!(transfer.call_data, AbiEncoderHelper::encode_erc20_transfer(recipient, U256::ZERO));
+    }
+}
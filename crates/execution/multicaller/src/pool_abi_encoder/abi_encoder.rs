@@ -1,6 +1,6 @@
 use crate::pool_abi_encoder::pools::{
-    CurveProtocolAbiEncoder, MaverickProtocolAbiEncoder, PancakeV3ProtocolAbiEncoder, UniswapV2ProtocolAbiEncoder,
-    UniswapV3ProtocolAbiEncoder,
+    CurveProtocolAbiEncoder, MaverickProtocolAbiEncoder, PancakeV3ProtocolAbiEncoder, PoolDelegateProtocolAbiEncoder,
+    UniswapV2ProtocolAbiEncoder, UniswapV3ProtocolAbiEncoder,
 };
 use crate::pool_abi_encoder::ProtocolAbiSwapEncoderTrait;
 use alloy_primitives::{Address, Bytes, U256};
@@ -22,6 +22,8 @@ impl Default for ProtocolABIEncoderV2 {
             (PoolClass::Maverick, Arc::new(MaverickProtocolAbiEncoder) as Arc<dyn ProtocolAbiSwapEncoderTrait>),
             (PoolClass::PancakeV3, Arc::new(PancakeV3ProtocolAbiEncoder) as Arc<dyn ProtocolAbiSwapEncoderTrait>),
             (PoolClass::Curve, Arc::new(CurveProtocolAbiEncoder) as Arc<dyn ProtocolAbiSwapEncoderTrait>),
+            (PoolClass::UniswapV4, Arc::new(PoolDelegateProtocolAbiEncoder) as Arc<dyn ProtocolAbiSwapEncoderTrait>),
+            (PoolClass::BalancerV2, Arc::new(PoolDelegateProtocolAbiEncoder) as Arc<dyn ProtocolAbiSwapEncoderTrait>),
         ]
         .into_iter()
         .collect();
@@ -115,7 +117,7 @@ mod tests {
     #[test]
     fn test_default() {
         let abi_encoder_v2 = ProtocolABIEncoderV2::default();
-        assert_eq!(abi_encoder_v2.pool_classes.len(), 5);
+        assert_eq!(abi_encoder_v2.pool_classes.len(), 7);
     }
 
     #[test]
@@ -1,10 +1,12 @@
 pub use curve::CurveProtocolAbiEncoder;
 pub use maverick::MaverickProtocolAbiEncoder;
 pub use pancake3::PancakeV3ProtocolAbiEncoder;
+pub use pool_delegate::PoolDelegateProtocolAbiEncoder;
 pub use uniswapv2::UniswapV2ProtocolAbiEncoder;
 pub use uniswapv3::UniswapV3ProtocolAbiEncoder;
 mod curve;
 mod maverick;
 mod pancake3;
+mod pool_delegate;
 mod uniswapv2;
 mod uniswapv3;
@@ -0,0 +1,103 @@
+use alloy_primitives::{Address, Bytes, U256};
+use eyre::{eyre, OptionExt, Result};
+use tracing::trace;
+
+use crate::opcodes_helpers::OpcodesHelpers;
+use crate::pool_abi_encoder::ProtocolAbiSwapEncoderTrait;
+use crate::pool_opcodes_encoder::swap_opcodes_encoders::MulticallerOpcodesPayload;
+use crate::pool_opcodes_encoder::SwapOpcodesEncoderTrait;
+use loom_defi_abi::AbiEncoderHelper;
+use loom_defi_address_book::FactoryAddress;
+use loom_types_blockchain::{MulticallerCall, MulticallerCalls};
+use loom_types_entities::{Pool, PreswapRequirement, SwapAmountType};
+
+/// Swaps through the Balancer Vault, the Vault pulls approved tokens from the multicaller
+pub struct BalancerV2SwapOpcodesEncoder;
+
+impl SwapOpcodesEncoderTrait for BalancerV2SwapOpcodesEncoder {
+    fn encode_swap_in_amount_provided(
+        &self,
+        swap_opcodes: &mut MulticallerCalls,
+        abi_encoder: &dyn ProtocolAbiSwapEncoderTrait,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount_in: SwapAmountType,
+        cur_pool: &dyn Pool,
+        next_pool: Option<&dyn Pool>,
+        _payload: MulticallerOpcodesPayload,
+        multicaller_address: Address,
+    ) -> Result<()> {
+        trace!(
+            "balancer v2 swap for pool={:?}, amount={:?} from {} to {}",
+            cur_pool.get_address(),
+            amount_in,
+            token_from_address,
+            token_to_address
+        );
+
+        let mut opcodes: Vec<(MulticallerCall, u32, usize)> = Vec::new();
+
+        // Approve
+        opcodes.push((
+            MulticallerCall::new_call(
+                token_from_address,
+                &AbiEncoderHelper::encode_erc20_approve(FactoryAddress::BALANCER_V2_VAULT, amount_in.unwrap_or_default()),
+            ),
+            0x24,
+            0x20,
+        ));
+
+        // Swap
+        let mut swap_opcode = MulticallerCall::new_call(
+            FactoryAddress::BALANCER_V2_VAULT,
+            &abi_encoder.encode_swap_in_amount_provided(
+                cur_pool,
+                token_from_address,
+                token_to_address,
+                amount_in.unwrap_or_default(),
+                multicaller_address,
+                Bytes::new(),
+            )?,
+        );
+        swap_opcode.set_return_stack(
+            true,
+            0,
+            abi_encoder.swap_in_amount_return_offset(cur_pool, token_from_address, token_to_address).ok_or_eyre("NO_OFFSET")?,
+            0x20,
+        );
+        opcodes.push((
+            swap_opcode,
+            abi_encoder.swap_in_amount_offset(cur_pool, token_from_address, token_to_address).ok_or_eyre("NO_OFFSET")?,
+            0x20,
+        ));
+
+        swap_opcodes.merge(OpcodesHelpers::build_multiple_stack(amount_in, opcodes, Some(token_from_address))?);
+
+        if let Some(next_pool) = next_pool {
+            if let PreswapRequirement::Transfer(addr) = next_pool.preswap_requirement() {
+                trace!("transfer token={:?}, to={:?}, amount=stack_rel_0", token_to_address, addr);
+
+                let mut transfer_opcode =
+                    MulticallerCall::new_call(token_to_address, &AbiEncoderHelper::encode_erc20_transfer(addr, U256::ZERO));
+                transfer_opcode.set_call_stack(true, 0, 0x24, 0x20);
+                swap_opcodes.add(transfer_opcode);
+            }
+        }
+        Ok(())
+    }
+
+    fn encode_swap_out_amount_provided(
+        &self,
+        _swap_opcodes: &mut MulticallerCalls,
+        _abi_encoder: &dyn ProtocolAbiSwapEncoderTrait,
+        _token_from_address: Address,
+        _token_to_address: Address,
+        _amount_out: SwapAmountType,
+        _cur_pool: &dyn Pool,
+        _next_pool: Option<&dyn Pool>,
+        _payload: MulticallerOpcodesPayload,
+        _multicaller_address: Address,
+    ) -> Result<()> {
+        Err(eyre!("NOT_IMPLEMENTED"))
+    }
+}
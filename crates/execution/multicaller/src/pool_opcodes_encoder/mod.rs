@@ -1,6 +1,7 @@
 use crate::pool_abi_encoder::ProtocolAbiSwapEncoderTrait;
 pub use crate::pool_opcodes_encoder::swap_opcodes_encoders::MulticallerOpcodesPayload;
 use alloy_primitives::Address;
+pub use balancer::BalancerV2SwapOpcodesEncoder;
 pub use curve::CurveSwapOpcodesEncoder;
 use eyre::{eyre, Result};
 use loom_types_blockchain::MulticallerCalls;
@@ -9,12 +10,15 @@ pub use steth::StEthSwapEncoder;
 pub use swap_opcodes_encoders::ProtocolSwapOpcodesEncoderV2;
 pub use uniswap2::UniswapV2SwapOpcodesEncoder;
 pub use uniswap3::UniswapV3SwapOpcodesEncoder;
+pub use uniswap4::UniswapV4SwapOpcodesEncoder;
 pub use wsteth::WstEthSwapEncoder;
 
+mod balancer;
 mod curve;
 mod steth;
 mod uniswap2;
 mod uniswap3;
+mod uniswap4;
 mod wsteth;
 
 mod swap_opcodes_encoders;
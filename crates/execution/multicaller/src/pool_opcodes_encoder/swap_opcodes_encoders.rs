@@ -1,6 +1,7 @@
 use crate::pool_abi_encoder::ProtocolAbiSwapEncoderTrait;
 use crate::pool_opcodes_encoder::{
-    CurveSwapOpcodesEncoder, SwapOpcodesEncoderTrait, UniswapV2SwapOpcodesEncoder, UniswapV3SwapOpcodesEncoder,
+    BalancerV2SwapOpcodesEncoder, CurveSwapOpcodesEncoder, SwapOpcodesEncoderTrait, UniswapV2SwapOpcodesEncoder,
+    UniswapV3SwapOpcodesEncoder, UniswapV4SwapOpcodesEncoder,
 };
 use crate::{OpcodesEncoder, OpcodesEncoderV2};
 use alloy_primitives::{Address, Bytes};
@@ -54,12 +55,16 @@ impl Default for ProtocolSwapOpcodesEncoderV2 {
         let uni2_opcodes_encoder = Arc::new(UniswapV2SwapOpcodesEncoder {});
         let uni3_opcodes_encoder = Arc::new(UniswapV3SwapOpcodesEncoder {});
         let curve_opcodes_encoder = Arc::new(CurveSwapOpcodesEncoder {});
+        let uni4_opcodes_encoder = Arc::new(UniswapV4SwapOpcodesEncoder {});
+        let balancer_opcodes_encoder = Arc::new(BalancerV2SwapOpcodesEncoder {});
 
         pool_classes.insert(PoolClass::UniswapV2, uni2_opcodes_encoder.clone());
         pool_classes.insert(PoolClass::Maverick, uni3_opcodes_encoder.clone());
         pool_classes.insert(PoolClass::UniswapV3, uni3_opcodes_encoder.clone());
         pool_classes.insert(PoolClass::PancakeV3, uni3_opcodes_encoder.clone());
         pool_classes.insert(PoolClass::Curve, curve_opcodes_encoder.clone());
+        pool_classes.insert(PoolClass::UniswapV4, uni4_opcodes_encoder.clone());
+        pool_classes.insert(PoolClass::BalancerV2, balancer_opcodes_encoder.clone());
 
         Self { pool_classes }
     }
@@ -0,0 +1,123 @@
+use crate::opcodes_helpers::OpcodesHelpers;
+use crate::pool_abi_encoder::ProtocolAbiSwapEncoderTrait;
+use crate::pool_opcodes_encoder::swap_opcodes_encoders::MulticallerOpcodesPayload;
+use crate::pool_opcodes_encoder::SwapOpcodesEncoderTrait;
+use crate::{OpcodesEncoder, OpcodesEncoderV2};
+use alloy_primitives::{Address, Bytes, U256};
+use alloy_sol_types::SolCall;
+use eyre::{eyre, OptionExt};
+use loom_defi_abi::uniswap4::IUniswapV4PoolManager;
+use loom_defi_abi::AbiEncoderHelper;
+use loom_types_blockchain::{MulticallerCall, MulticallerCalls};
+use loom_types_entities::{Pool, SwapAmountType};
+use tracing::trace;
+
+/// Swaps through PoolManager.unlock, the multicaller executes settlement opcodes in `unlockCallback`
+pub struct UniswapV4SwapOpcodesEncoder;
+
+impl SwapOpcodesEncoderTrait for UniswapV4SwapOpcodesEncoder {
+    fn encode_swap_in_amount_provided(
+        &self,
+        swap_opcodes: &mut MulticallerCalls,
+        abi_encoder: &dyn ProtocolAbiSwapEncoderTrait,
+        token_from_address: Address,
+        token_to_address: Address,
+        amount_in: SwapAmountType,
+        cur_pool: &dyn Pool,
+        next_pool: Option<&dyn Pool>,
+        _payload: MulticallerOpcodesPayload,
+        multicaller_address: Address,
+    ) -> eyre::Result<()> {
+        let pool_manager = cur_pool.get_address();
+        let swap_to = next_pool.and_then(|next_pool| next_pool.preswap_requirement().address()).unwrap_or(multicaller_address);
+
+        trace!(
+            "uniswap v4 swap in amount for pool={:?}, amount={:?} from {} to {}",
+            cur_pool.get_pool_id(),
+            amount_in,
+            token_from_address,
+            token_to_address
+        );
+
+        let mut unlock_opcodes = MulticallerCalls::new();
+
+        // syncing currency balance before payment
+        unlock_opcodes.add(MulticallerCall::new_call(
+            pool_manager,
+            &IUniswapV4PoolManager::syncCall { currency: token_from_address }.abi_encode().into(),
+        ));
+
+        // paying in amount to the pool manager
+        let transfer_opcode = MulticallerCall::new_call(
+            token_from_address,
+            &AbiEncoderHelper::encode_erc20_transfer(pool_manager, amount_in.unwrap_or_default()),
+        );
+        unlock_opcodes.merge(OpcodesHelpers::build_call_stack(amount_in, transfer_opcode, 0x24, 0x20, Some(token_from_address))?);
+
+        unlock_opcodes.add(MulticallerCall::new_call(pool_manager, &IUniswapV4PoolManager::settleCall {}.abi_encode().into()));
+
+        // exact input is negative amount specified
+        if amount_in.is_not_set() {
+            unlock_opcodes.add(MulticallerCall::new_calculation_call(&Bytes::from(vec![0x8, 0x2A, 0x00])));
+        }
+
+        let mut swap_opcode = MulticallerCall::new_call(
+            pool_manager,
+            &abi_encoder.encode_swap_in_amount_provided(
+                cur_pool,
+                token_from_address,
+                token_to_address,
+                amount_in.unwrap_or_default(),
+                swap_to,
+                Bytes::new(),
+            )?,
+        );
+        if amount_in.is_not_set() {
+            swap_opcode.set_call_stack(
+                true,
+                0,
+                abi_encoder.swap_in_amount_offset(cur_pool, token_from_address, token_to_address).ok_or_eyre("NO_OFFSET")?,
+                0x20,
+            );
+        }
+        // out amount is a half of returned BalanceDelta
+        swap_opcode.set_return_stack(
+            true,
+            0,
+            abi_encoder.swap_in_amount_return_offset(cur_pool, token_from_address, token_to_address).ok_or_eyre("NO_OFFSET")?,
+            0x10,
+        );
+        unlock_opcodes.add(swap_opcode);
+
+        // taking out amount to the next pool or multicaller
+        let mut take_opcode = MulticallerCall::new_call(
+            pool_manager,
+            &IUniswapV4PoolManager::takeCall { currency: token_to_address, to: swap_to, amount: U256::ZERO }.abi_encode().into(),
+        );
+        take_opcode.set_call_stack(true, 0, 0x44, 0x20);
+        unlock_opcodes.add(take_opcode);
+
+        let unlock_call_data = OpcodesEncoderV2::pack_do_calls_data(&unlock_opcodes)?;
+        swap_opcodes.add(MulticallerCall::new_call(
+            pool_manager,
+            &IUniswapV4PoolManager::unlockCall { data: unlock_call_data }.abi_encode().into(),
+        ));
+
+        Ok(())
+    }
+
+    fn encode_swap_out_amount_provided(
+        &self,
+        _swap_opcodes: &mut MulticallerCalls,
+        _abi_encoder: &dyn ProtocolAbiSwapEncoderTrait,
+        _token_from_address: Address,
+        _token_to_address: Address,
+        _amount_out: SwapAmountType,
+        _cur_pool: &dyn Pool,
+        _next_pool: Option<&dyn Pool>,
+        _payload: MulticallerOpcodesPayload,
+        _multicaller_address: Address,
+    ) -> eyre::Result<()> {
+        Err(eyre!("NOT_IMPLEMENTED"))
+    }
+}
@@ -1,9 +1,9 @@
-use crate::MulticallerSwapEncoder;
+use crate::{MulticallerEncoder, MulticallerSwapEncoder};
 use alloy_primitives::{Address, BlockNumber, Bytes, U256};
 use eyre::{eyre, OptionExt, Result};
 use loom_types_blockchain::LoomDataTypesEthereum;
 use loom_types_blockchain::MulticallerCalls;
-use loom_types_entities::tips::{tips_and_value_for_swap_type, Tips};
+use loom_types_entities::tips::{tips_and_value_for_swap_type, LegacyTipsPolicy, Tips, TipsPolicy};
 use loom_types_entities::{Swap, SwapEncoder, SwapStep};
 use tracing::{debug, error, trace};
 
@@ -22,6 +22,7 @@ impl SwapEncoder for MulticallerSwapEncoder {
         &self,
         swap: Swap,
         tips_pct: Option<u32>,
+        tips_policy: Option<&dyn TipsPolicy>,
         _next_block_number: Option<BlockNumber>,
         gas_cost: Option<U256>,
         sender_address: Option<Address>,
@@ -52,12 +53,12 @@ impl SwapEncoder for MulticallerSwapEncoder {
 
         let mut swap_opcodes = if swap_vec.is_empty() {
             match &swap {
-                Swap::ExchangeSwapLine(swap_line) => {
+                Swap::ExchangeSwapLine(_) => {
                     trace!("START: exchange swap line");
-                    match self.swap_step_encoder.swap_line_encoder.encode_swap_line_in_amount(swap_line, None) {
+                    match self.make_calls(&swap) {
                         Ok(calls) => calls,
                         Err(e) => {
-                            error!("swap_line_encoder.encode_swap_line_in_amount : {}", e);
+                            error!("swap_line_encoder.encode_exchange_swap_line : {}", e);
                             return Err(eyre!("ENCODING_FAILED"));
                         }
                     }
@@ -79,22 +80,26 @@ impl SwapEncoder for MulticallerSwapEncoder {
         };
         trace!("END: swap_opcodes");
 
-        let tips_vec =
-            if let (Some(tips_pct), Some(sender_address), Some(sender_eth_balance)) = (tips_pct, sender_address, sender_eth_balance) {
-                let (tips_vec, _call_value) = tips_and_value_for_swap_type(&swap, Some(tips_pct), gas_cost, sender_eth_balance)?;
-                for tips in &tips_vec {
-                    swap_opcodes = self.swap_step_encoder.encode_tips(
-                        swap_opcodes,
-                        tips.token_in.get_address(),
-                        tips.min_change,
-                        tips.tips,
-                        sender_address,
-                    )?;
-                }
-                tips_vec
-            } else {
-                vec![]
-            };
+        // exchange swaps have no profit to pay tips from
+        let tips_vec = if let (Some(tips_pct), Some(sender_address), Some(sender_eth_balance), false) =
+            (tips_pct, sender_address, sender_eth_balance, matches!(swap, Swap::ExchangeSwapLine(_)))
+        {
+            let default_tips_policy = LegacyTipsPolicy::default();
+            let tips_policy = tips_policy.unwrap_or(&default_tips_policy);
+            let (tips_vec, _call_value) = tips_and_value_for_swap_type(&swap, Some(tips_pct), tips_policy, gas_cost, sender_eth_balance)?;
+            for tips in &tips_vec {
+                swap_opcodes = self.swap_step_encoder.encode_tips(
+                    swap_opcodes,
+                    tips.token_in.get_address(),
+                    tips.min_change,
+                    tips.tips,
+                    sender_address,
+                )?;
+            }
+            tips_vec
+        } else {
+            vec![]
+        };
 
         let (to, call_data) = self.swap_step_encoder.to_call_data(&swap_opcodes)?;
 
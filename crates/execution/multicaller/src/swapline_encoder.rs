@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use alloy_primitives::{Address, U256};
-use eyre::{eyre, Result};
+use eyre::{eyre, OptionExt, Result};
 use tracing::trace;
 
 use crate::pool_abi_encoder::ProtocolAbiSwapEncoderTrait;
@@ -12,7 +12,7 @@ use loom_defi_address_book::TokenAddressEth;
 use loom_types_blockchain::LoomDataTypesEthereum;
 use loom_types_blockchain::{MulticallerCall, MulticallerCalls};
 use loom_types_entities::SwapAmountType::RelativeStack;
-use loom_types_entities::{PoolWrapper, SwapAmountType, SwapLine, Token};
+use loom_types_entities::{PoolWrapper, PreswapRequirement, SwapAmountType, SwapLine, Token};
 
 #[derive(Clone)]
 pub struct SwapLineEncoder {
@@ -391,6 +391,52 @@ impl SwapLineEncoder {
         Ok(swap_opcodes)
     }
 
+    /// Exchange of multicaller funds along the swap line. Reverts if multicaller balance of the last token after the swap is below
+    /// balance_before + min_amount_out, then transfers swapped amount to the recipient if it is set.
+    pub fn encode_exchange_swap_line(
+        &self,
+        swap_path: &SwapLine<LoomDataTypesEthereum>,
+        balance_before: U256,
+        min_amount_out: U256,
+    ) -> Result<MulticallerCalls> {
+        let first_pool = swap_path.pools().first().ok_or_eyre("NO_POOLS")?;
+        let token_from_address = swap_path.get_first_token().ok_or_eyre("NO_FIRST_TOKEN")?.get_address();
+        let token_to_address = swap_path.get_last_token().ok_or_eyre("NO_LAST_TOKEN")?.get_address();
+
+        let mut swap_opcodes = MulticallerCalls::new();
+
+        // pools without callback expect funds before the swap
+        if let (PreswapRequirement::Transfer(funds_to), SwapAmountType::Set(amount_in)) =
+            (first_pool.preswap_requirement(), swap_path.amount_in)
+        {
+            trace!("exchange transfer token={:?}, to={:?}, amount={}", token_from_address, funds_to, amount_in);
+            swap_opcodes.add(MulticallerCall::new_call(token_from_address, &AbiEncoderHelper::encode_erc20_transfer(funds_to, amount_in)));
+        }
+
+        swap_opcodes.merge(self.encode_swap_line_in_amount(swap_path, None)?);
+
+        // guard the swapped amount, not the total balance that may already cover min_amount_out
+        let min_balance = balance_before.checked_add(min_amount_out).ok_or_eyre("MIN_BALANCE_OVERFLOW")?;
+        trace!("exchange min balance token={:?}, min_balance={}", token_to_address, min_balance);
+        swap_opcodes.add(MulticallerCall::new_internal_call(&AbiEncoderHelper::encode_multicaller_transfer_tips_no_payout(
+            token_to_address,
+            min_balance,
+            U256::ZERO,
+        )));
+
+        // output stays on multicaller if swap line has no receiver
+        if let Some(recipient) = swap_path.swap_to.filter(|recipient| *recipient != self.multicaller_address) {
+            trace!("exchange transfer token={:?}, to={:?}, amount=stack_rel_0", token_to_address, recipient);
+            // out amount of the last swap is on top of the stack
+            let mut transfer_opcode =
+                MulticallerCall::new_call(token_to_address, &AbiEncoderHelper::encode_erc20_transfer(recipient, U256::ZERO));
+            transfer_opcode.set_call_stack(true, 0, 0x24, 0x20);
+            swap_opcodes.add(transfer_opcode);
+        }
+
+        Ok(swap_opcodes)
+    }
+
     pub fn encode_tips(
         &self,
         swap_opcodes: MulticallerCalls,
@@ -1,33 +1,34 @@
 use alloy_primitives::{Address, Bytes, U256};
-use eyre::Result;
-use lazy_static::lazy_static;
+use eyre::{eyre, OptionExt, Result};
 use tracing::trace;
 
+use crate::flash_loan_provider::{BalancerFlashLoanProvider, FlashLoanProvider, FlashLoanProviders};
 use crate::opcodes_encoder::{OpcodesEncoder, OpcodesEncoderV2};
 use crate::SwapLineEncoder;
-use loom_defi_abi::AbiEncoderHelper;
+use loom_defi_address_book::FactoryAddress;
 use loom_types_blockchain::LoomDataTypesEthereum;
 use loom_types_blockchain::{MulticallerCall, MulticallerCalls};
-use loom_types_entities::{SwapAmountType, SwapStep};
-
-lazy_static! {
-    static ref BALANCER_VAULT_ADDRESS: Address = "0xBA12222222228d8Ba445958a75a0704d566BF2C8".parse().unwrap();
-}
+use loom_types_entities::{PoolClass, SwapAmountType, SwapStep};
 
 #[derive(Clone)]
 pub struct SwapStepEncoder {
     pub multicaller_address: Address,
     pub swap_line_encoder: SwapLineEncoder,
+    pub flash_loan_providers: FlashLoanProviders,
 }
 
 impl SwapStepEncoder {
     pub fn new(multicaller_address: Address, swap_line_encoder: SwapLineEncoder) -> Self {
-        Self { multicaller_address, swap_line_encoder }
+        Self { multicaller_address, swap_line_encoder, flash_loan_providers: FlashLoanProviders::default() }
     }
 
     pub fn default_with_address(multicaller_address: Address) -> Self {
         let swap_line_encoder = SwapLineEncoder::default_with_address(multicaller_address);
-        Self { multicaller_address, swap_line_encoder }
+        Self::new(multicaller_address, swap_line_encoder)
+    }
+
+    pub fn with_flash_loan_providers(self, flash_loan_providers: FlashLoanProviders) -> Self {
+        Self { flash_loan_providers, ..self }
     }
 
     pub fn get_contract_address(&self) -> Address {
@@ -53,6 +54,14 @@ impl SwapStepEncoder {
     }
 
     pub fn encode_balancer_flash_loan(&self, steps: Vec<SwapStep<LoomDataTypesEthereum>>) -> Result<MulticallerCalls> {
+        self.encode_flash_loan(steps, &BalancerFlashLoanProvider::default())
+    }
+
+    pub fn encode_flash_loan(
+        &self,
+        steps: Vec<SwapStep<LoomDataTypesEthereum>>,
+        flash_loan_provider: &dyn FlashLoanProvider,
+    ) -> Result<MulticallerCalls> {
         let flash_funds_to = self.multicaller_address;
 
         let mut swap_opcodes = MulticallerCalls::new();
@@ -61,8 +70,8 @@ impl SwapStepEncoder {
 
         let mut steps = steps.clone();
 
-        let token = first_swap.first_token().unwrap();
-        let in_amount = first_swap.get_in_amount().unwrap();
+        let token = first_swap.first_token().ok_or_eyre("NO_FIRST_TOKEN")?;
+        let in_amount = first_swap.get_in_amount()?;
 
         for (swap_idx, swap) in steps.iter_mut().enumerate() {
             if swap_idx > 0 {
@@ -80,16 +89,9 @@ impl SwapStepEncoder {
             }
         }
 
-        let inside_call_bytes = OpcodesEncoderV2::pack_do_calls_data(&swap_opcodes)?;
-
-        let mut flash_opcodes = MulticallerCalls::new();
-
-        let flash_call_data =
-            AbiEncoderHelper::encode_balancer_flashloan(token.get_address(), in_amount, inside_call_bytes, self.multicaller_address);
+        trace!(provider = %flash_loan_provider.get_address(), token = %token.get_address(), %in_amount, "encode_flash_loan");
 
-        flash_opcodes.add(MulticallerCall::new_call(*BALANCER_VAULT_ADDRESS, &flash_call_data));
-
-        Ok(flash_opcodes)
+        flash_loan_provider.encode_flash_loan(token.get_address(), in_amount, swap_opcodes, self.multicaller_address)
     }
 
     pub fn encode_in_amount(
@@ -186,8 +188,21 @@ impl SwapStepEncoder {
             trace!("encode_swap_steps -> sp1.can_flash_swap()");
             self.encode_out_amount(sp0.clone(), sp1.clone())
         } else {
-            trace!("encode_swap_steps -> encode_balancer_flash_loan");
-            self.encode_balancer_flash_loan(vec![sp0.clone(), sp1.clone()])
+            let token = sp0.first_token().ok_or_eyre("NO_FIRST_TOKEN")?.get_address();
+            let in_amount = sp0.get_in_amount()?;
+            // pools of the swap are locked during the flash loan and cannot lend
+            let mut swap_pools: Vec<Address> = Vec::new();
+            for pool in [sp0, sp1].iter().flat_map(|step| step.swap_line_vec().iter().flat_map(|swap_line| swap_line.pools().iter())) {
+                swap_pools.push(pool.get_address());
+                // balancer pools swap through the vault, vault is locked as well
+                if pool.get_class() == PoolClass::BalancerV2 && !swap_pools.contains(&FactoryAddress::BALANCER_V2_VAULT) {
+                    swap_pools.push(FactoryAddress::BALANCER_V2_VAULT);
+                }
+            }
+            let flash_loan_provider =
+                self.flash_loan_providers.select(token, in_amount, &swap_pools).ok_or_else(|| eyre!("NO_FLASH_LOAN_PROVIDER"))?;
+            trace!("encode_swap_steps -> encode_flash_loan provider={}", flash_loan_provider.get_address());
+            self.encode_flash_loan(vec![sp0.clone(), sp1.clone()], flash_loan_provider.as_ref())
         }
     }
 
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use eyre::eyre;
 use influxdb::{Client, ReadQuery, WriteQuery};
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Consumer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, WorkerResult};
 use loom_core_actors_macros::Consumer;
 use loom_core_blockchain::Blockchain;
 use std::collections::HashMap;
@@ -14,6 +14,7 @@ pub async fn start_influxdb_worker(
     database: String,
     tags: HashMap<String, String>,
     event_receiver: Broadcaster<WriteQuery>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     let client = Client::new(url, database.clone());
     let create_db_stmt = format!("CREATE DATABASE {}", database);
@@ -22,9 +23,12 @@ pub async fn start_influxdb_worker(
         Ok(_) => info!("Database created with name: {}", database),
         Err(e) => info!("Database creation failed or already exists: {:?}", e),
     }
-    let mut event_receiver = event_receiver.subscribe();
+    subscribe!(event_receiver);
     loop {
-        let event_result = event_receiver.recv().await;
+        let event_result = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            event_result = event_receiver.recv() => event_result,
+        };
         match event_result {
             Ok(mut event) => {
                 for (key, value) in tags.iter() {
@@ -56,6 +60,7 @@ pub async fn start_influxdb_worker(
             },
         }
     }
+    Ok("InfluxDB writer stopped".to_string())
 }
 
 #[derive(Consumer)]
@@ -79,7 +84,7 @@ impl InfluxDbWriterActor {
 
 #[async_trait]
 impl Actor for InfluxDbWriterActor {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let influxdb_write_channel_rx = match &self.influxdb_write_channel_rx {
             Some(rx) => rx.clone(),
             None => {
@@ -92,6 +97,7 @@ impl Actor for InfluxDbWriterActor {
             self.database.clone(),
             self.tags.clone(),
             influxdb_write_channel_rx.clone(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -1,3 +1,7 @@
 mod influxdb_actor;
+mod prometheus_actor;
 
 pub use influxdb_actor::InfluxDbWriterActor;
+pub use prometheus_actor::{
+    parse_line_protocol, LinePoint, PrometheusExporterActor, PrometheusMetrics, DEFAULT_BUCKETS, DEFAULT_IGNORED_TAGS, DEFAULT_MAX_SERIES,
+};
@@ -0,0 +1,520 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use eyre::eyre;
+use influxdb::{Query, WriteQuery};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, SharedState, WorkerResult};
+use loom_core_actors_macros::Consumer;
+use loom_core_blockchain::Blockchain;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use tracing::{debug, error, info, warn};
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Default histogram buckets, covers both millisecond latencies and small counts
+pub const DEFAULT_BUCKETS: [f64; 14] = [1.0, 2.0, 5.0, 10.0, 20.0, 50.0, 100.0, 200.0, 500.0, 1000.0, 2000.0, 5000.0, 10000.0, 50000.0];
+
+/// Tags that are dropped by default because every value creates a new time series
+pub const DEFAULT_IGNORED_TAGS: [&str; 9] =
+    ["block", "tx_block", "block_idx", "target_block", "tx_hash", "stuffing", "stuffing_tx", "other_tx", "pool"];
+
+/// Default limit of time series, new label sets above it are dropped
+pub const DEFAULT_MAX_SERIES: usize = 10_000;
+
+/// A single point of the influxdb line protocol with numeric fields only
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinePoint {
+    pub measurement: String,
+    pub tags: Vec<(String, String)>,
+    pub fields: Vec<(String, f64)>,
+}
+
+#[derive(Clone, Debug)]
+enum Series {
+    Counter(f64),
+    Gauge(f64),
+    Histogram { buckets: Vec<u64>, sum: f64, count: u64 },
+}
+
+type Labels = Vec<(String, String)>;
+
+/// Prometheus metrics built from influxdb write queries.
+///
+/// Every measurement is counted in `<namespace>_<measurement>_total`. Every numeric field is exported as a gauge
+/// `<namespace>_<measurement>_<field>` with the last value, or as a histogram if the measurement is listed in `histograms`.
+/// Field `value` is exported as `<namespace>_<measurement>`, field `total` as `<namespace>_<measurement>_total_value` not to
+/// collide with the counter. Influxdb tags become labels, no more than `max_series` series are kept.
+pub struct PrometheusMetrics {
+    namespace: String,
+    labels: Labels,
+    ignored_tags: Vec<String>,
+    histograms: Vec<String>,
+    buckets: Vec<f64>,
+    max_series: usize,
+    series_count: usize,
+    dropped_series: u64,
+    families: BTreeMap<String, BTreeMap<Labels, Series>>,
+}
+
+impl Default for PrometheusMetrics {
+    fn default() -> Self {
+        Self::new("loom".to_string(), HashMap::new(), DEFAULT_IGNORED_TAGS.iter().map(|t| t.to_string()).collect(), vec![], vec![])
+    }
+}
+
+impl PrometheusMetrics {
+    pub fn new(
+        namespace: String,
+        labels: HashMap<String, String>,
+        ignored_tags: Vec<String>,
+        histograms: Vec<String>,
+        buckets: Vec<f64>,
+    ) -> Self {
+        let mut labels: Labels = labels.into_iter().map(|(k, v)| (sanitize_name(&k), v)).collect();
+        labels.sort();
+        let mut buckets: Vec<f64> = if buckets.is_empty() { DEFAULT_BUCKETS.to_vec() } else { buckets };
+        buckets.retain(|b| b.is_finite());
+        buckets.sort_by(|a, b| a.total_cmp(b));
+        buckets.dedup();
+
+        Self {
+            namespace: sanitize_name(&namespace),
+            labels,
+            ignored_tags,
+            histograms,
+            buckets,
+            max_series: DEFAULT_MAX_SERIES,
+            series_count: 0,
+            dropped_series: 0,
+            families: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_max_series(self, max_series: usize) -> Self {
+        Self { max_series, ..self }
+    }
+
+    /// Series of metric `name` with `labels`, created with `init` if the series limit is not reached
+    fn series_mut(&mut self, name: String, labels: &Labels, init: impl FnOnce() -> Series) -> Option<&mut Series> {
+        let exists = self.families.get(&name).is_some_and(|series_map| series_map.contains_key(labels));
+        if !exists {
+            if self.series_count >= self.max_series {
+                if self.dropped_series == 0 {
+                    warn!("Prometheus series limit {} reached, new series are dropped", self.max_series);
+                }
+                self.dropped_series += 1;
+                return None;
+            }
+            self.series_count += 1;
+        }
+        Some(self.families.entry(name).or_default().entry(labels.clone()).or_insert_with(init))
+    }
+
+    fn metric_name(&self, measurement: &str, field: Option<&str>) -> String {
+        let mut name = String::new();
+        if !self.namespace.is_empty() {
+            name.push_str(&self.namespace);
+            name.push('_');
+        }
+        name.push_str(&sanitize_name(measurement));
+        match field {
+            Some("value") | None => {}
+            Some("total") => name.push_str("_total_value"),
+            Some(field) => {
+                name.push('_');
+                name.push_str(&sanitize_name(field));
+            }
+        }
+        name
+    }
+
+    /// Update metrics with a parsed line protocol point
+    pub fn observe(&mut self, point: &LinePoint) {
+        let mut labels: Labels = self.labels.clone();
+        for (key, value) in point.tags.iter() {
+            if self.ignored_tags.contains(key) {
+                continue;
+            }
+            let key = sanitize_name(key);
+            labels.retain(|(k, _)| k != &key);
+            labels.push((key, value.clone()));
+        }
+        labels.sort();
+
+        let counter_name = format!("{}_total", self.metric_name(&point.measurement, None));
+        match self.series_mut(counter_name, &labels, || Series::Counter(0.0)) {
+            Some(Series::Counter(counter)) => *counter += 1.0,
+            Some(_) => warn!("Prometheus metric type mismatch for {}", point.measurement),
+            None => {}
+        }
+
+        let is_histogram = self.histograms.contains(&point.measurement);
+        for (field, value) in point.fields.iter() {
+            let name = self.metric_name(&point.measurement, Some(field));
+            let buckets_len = self.buckets.len();
+            let bounds = if is_histogram { self.buckets.clone() } else { vec![] };
+            let Some(series) = self.series_mut(name, &labels, || {
+                if is_histogram {
+                    Series::Histogram { buckets: vec![0; buckets_len], sum: 0.0, count: 0 }
+                } else {
+                    Series::Gauge(0.0)
+                }
+            }) else {
+                continue;
+            };
+            match series {
+                Series::Gauge(gauge) => *gauge = *value,
+                Series::Histogram { buckets, sum, count } => {
+                    for (bucket, bound) in buckets.iter_mut().zip(bounds.iter()) {
+                        if value <= bound {
+                            *bucket += 1;
+                        }
+                    }
+                    *sum += value;
+                    *count += 1;
+                }
+                Series::Counter(_) => warn!("Prometheus metric type mismatch for {}.{}", point.measurement, field),
+            }
+        }
+    }
+
+    pub fn dropped_series(&self) -> u64 {
+        self.dropped_series
+    }
+
+    /// Render all metrics in the Prometheus text exposition format
+    pub fn render(&self) -> String {
+        let mut ret = String::new();
+        for (name, series_map) in self.families.iter() {
+            let Some(first) = series_map.values().next() else {
+                continue;
+            };
+            let kind = match first {
+                Series::Counter(_) => "counter",
+                Series::Gauge(_) => "gauge",
+                Series::Histogram { .. } => "histogram",
+            };
+            let _ = writeln!(ret, "# TYPE {name} {kind}");
+
+            for (labels, series) in series_map.iter() {
+                match series {
+                    Series::Counter(value) | Series::Gauge(value) => {
+                        let _ = writeln!(ret, "{name}{} {}", format_labels(labels, None), format_value(*value));
+                    }
+                    Series::Histogram { buckets, sum, count } => {
+                        for (bucket, bound) in buckets.iter().zip(self.buckets.iter()) {
+                            let _ = writeln!(ret, "{name}_bucket{} {bucket}", format_labels(labels, Some(&format_value(*bound))));
+                        }
+                        let _ = writeln!(ret, "{name}_bucket{} {count}", format_labels(labels, Some("+Inf")));
+                        let _ = writeln!(ret, "{name}_sum{} {}", format_labels(labels, None), format_value(*sum));
+                        let _ = writeln!(ret, "{name}_count{} {count}", format_labels(labels, None));
+                    }
+                }
+            }
+        }
+        if self.dropped_series > 0 {
+            let name = format!("{}_total", self.metric_name("prometheus_dropped_series", None));
+            let _ = writeln!(ret, "# TYPE {name} counter");
+            let _ = writeln!(ret, "{name}{} {}", format_labels(&self.labels, None), self.dropped_series);
+        }
+        ret
+    }
+}
+
+fn sanitize_name(name: &str) -> String {
+    let mut ret: String = name.chars().map(|c| if c.is_ascii_alphanumeric() || c == '_' || c == ':' { c } else { '_' }).collect();
+    if ret.starts_with(|c: char| c.is_ascii_digit()) {
+        ret.insert(0, '_');
+    }
+    ret
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value == f64::INFINITY {
+        "+Inf".to_string()
+    } else if value == f64::NEG_INFINITY {
+        "-Inf".to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+fn format_labels(labels: &Labels, le: Option<&str>) -> String {
+    if labels.is_empty() && le.is_none() {
+        return String::new();
+    }
+    let mut parts: Vec<String> =
+        labels.iter().map(|(k, v)| format!("{k}=\"{}\"", v.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))).collect();
+    if let Some(le) = le {
+        parts.push(format!("le=\"{le}\""));
+    }
+    format!("{{{}}}", parts.join(","))
+}
+
+/// Split at unescaped `separator` outside of double quotes
+fn split_unescaped(s: &str, separator: char) -> Vec<&str> {
+    let mut ret = vec![];
+    let mut start = 0;
+    let mut escaped = false;
+    let mut quoted = false;
+    for (i, c) in s.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' => escaped = true,
+            '"' => quoted = !quoted,
+            c if c == separator && !quoted => {
+                ret.push(&s[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    ret.push(&s[start..]);
+    ret
+}
+
+fn unescape(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                ret.push(next);
+            }
+        } else {
+            ret.push(c);
+        }
+    }
+    ret
+}
+
+fn parse_field_value(value: &str) -> Option<f64> {
+    match value {
+        "t" | "T" | "true" | "True" | "TRUE" => Some(1.0),
+        "f" | "F" | "false" | "False" | "FALSE" => Some(0.0),
+        _ if value.starts_with('"') => None,
+        _ => match value.strip_suffix('i').or_else(|| value.strip_suffix('u')) {
+            Some(int_value) => int_value.parse::<i128>().ok().map(|v| v as f64),
+            None => value.parse::<f64>().ok(),
+        },
+    }
+}
+
+/// Parse an influxdb line protocol line, string fields are skipped
+pub fn parse_line_protocol(line: &str) -> Option<LinePoint> {
+    let sections = split_unescaped(line.trim(), ' ');
+    let (series, fields) = match sections.as_slice() {
+        [series, fields] | [series, fields, _] => (*series, *fields),
+        _ => return None,
+    };
+
+    let mut series = split_unescaped(series, ',').into_iter();
+    let measurement = unescape(series.next()?);
+    if measurement.is_empty() {
+        return None;
+    }
+    let mut tags = vec![];
+    for tag in series {
+        let parts = split_unescaped(tag, '=');
+        if parts.len() != 2 {
+            return None;
+        }
+        tags.push((unescape(parts[0]), unescape(parts[1])));
+    }
+
+    let mut ret_fields = vec![];
+    for field in split_unescaped(fields, ',') {
+        let parts = split_unescaped(field, '=');
+        if parts.len() != 2 {
+            return None;
+        }
+        if let Some(value) = parse_field_value(parts[1]) {
+            ret_fields.push((unescape(parts[0]), value));
+        }
+    }
+
+    Some(LinePoint { measurement, tags, fields: ret_fields })
+}
+
+pub async fn start_prometheus_worker(
+    metrics: SharedState<PrometheusMetrics>,
+    event_receiver: Broadcaster<WriteQuery>,
+    cancel_token: CancellationToken,
+) -> WorkerResult {
+    subscribe!(event_receiver);
+    loop {
+        let event_result = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            event_result = event_receiver.recv() => event_result,
+        };
+        match event_result {
+            Ok(event) => {
+                let line = match event.build() {
+                    Ok(query) => query.get(),
+                    Err(e) => {
+                        debug!("Cannot build influxdb query: {:?}", e);
+                        continue;
+                    }
+                };
+                match parse_line_protocol(&line) {
+                    Some(point) => metrics.write().await.observe(&point),
+                    None => debug!("Cannot parse line protocol: {}", line),
+                }
+            }
+            Err(e) => match e {
+                tokio::sync::broadcast::error::RecvError::Closed => {
+                    error!("Prometheus channel closed");
+                    return Err(eyre!("PROMETHEUS_CHANNEL_CLOSED"));
+                }
+                tokio::sync::broadcast::error::RecvError::Lagged(lagged) => {
+                    warn!("Prometheus lagged: {:?}", lagged);
+                    continue;
+                }
+            },
+        }
+    }
+    Ok("Prometheus worker stopped".to_string())
+}
+
+pub async fn start_prometheus_server(host: String, router: Router, cancel_token: CancellationToken) -> WorkerResult {
+    info!("Prometheus metrics server listening on {}", &host);
+    let listener = tokio::net::TcpListener::bind(host).await?;
+    axum::serve(listener, router).with_graceful_shutdown(async move { cancel_token.cancelled().await }).await?;
+    Ok("Prometheus metrics server shutdown".to_string())
+}
+
+/// Consumes influxdb write queries and keeps Prometheus metrics, that are served with [`PrometheusExporterActor::router`]
+#[derive(Consumer)]
+pub struct PrometheusExporterActor {
+    metrics: SharedState<PrometheusMetrics>,
+    host: Option<String>,
+    #[consumer]
+    influxdb_write_channel_rx: Option<Broadcaster<WriteQuery>>,
+}
+
+impl Default for PrometheusExporterActor {
+    fn default() -> Self {
+        Self::new(PrometheusMetrics::default())
+    }
+}
+
+impl PrometheusExporterActor {
+    pub fn new(metrics: PrometheusMetrics) -> Self {
+        Self { metrics: SharedState::new(metrics), host: None, influxdb_write_channel_rx: None }
+    }
+
+    /// Serves `/metrics` on its own listener at `host`, for binaries that do not run the web server
+    pub fn with_host(self, host: String) -> Self {
+        Self { host: Some(host), ..self }
+    }
+
+    pub fn on_bc(self, bc: &Blockchain) -> Self {
+        Self { influxdb_write_channel_rx: Some(bc.influxdb_write_channel()), ..self }
+    }
+
+    pub fn metrics(&self) -> SharedState<PrometheusMetrics> {
+        self.metrics.clone()
+    }
+
+    /// Router with `GET /metrics` to be merged into the web server
+    pub fn router(&self) -> Router {
+        let metrics = self.metrics.clone();
+        Router::new().route(
+            "/metrics",
+            get(move || {
+                let metrics = metrics.clone();
+                async move { ([(CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)], metrics.read().await.render()).into_response() }
+            }),
+        )
+    }
+}
+
+impl Actor for PrometheusExporterActor {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let influxdb_write_channel_rx = match &self.influxdb_write_channel_rx {
+            Some(rx) => rx.clone(),
+            None => {
+                error!("InfluxDB write channel is not set.");
+                return Err(eyre!("INFLUXDB_WRITE_CHANNEL_NOT_SET"));
+            }
+        };
+        let mut tasks =
+            vec![tokio::task::spawn(start_prometheus_worker(self.metrics.clone(), influxdb_write_channel_rx, cancel_token.clone()))];
+        if let Some(host) = &self.host {
+            tasks.push(tokio::task::spawn(start_prometheus_server(host.clone(), self.router(), cancel_token)));
+        }
+        Ok(tasks)
+    }
+
+    fn name(&self) -> &'static str {
+        "PrometheusExporterActor"
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_protocol() {
+        let point = parse_line_protocol(
+            r#"channel_stats,channel=market\ events,block=10 sent=5i,capacity=1.5,message="a b,c=d",ok=true 1700000000"#,
+        )
+        .unwrap();
+        assert_eq!(point.measurement, "channel_stats");
+        assert_eq!(point.tags, vec![("channel".to_string(), "market events".to_string()), ("block".to_string(), "10".to_string())]);
+        assert_eq!(point.fields, vec![("sent".to_string(), 5.0), ("capacity".to_string(), 1.5), ("ok".to_string(), 1.0)]);
+
+        assert!(parse_line_protocol("measurement_only").is_none());
+    }
+
+    #[test]
+    fn test_render() {
+        let mut metrics = PrometheusMetrics::new(
+            "loom".to_string(),
+            HashMap::from([("bot_name".to_string(), "loom".to_string())]),
+            vec!["block".to_string()],
+            vec!["block_latency".to_string()],
+            vec![10.0, 100.0],
+        );
+        metrics.observe(&parse_line_protocol("block_latency,block=1 value=50 1").unwrap());
+        metrics.observe(&parse_line_protocol("block_latency,block=2 value=150 1").unwrap());
+        metrics.observe(&parse_line_protocol("mempool tx_mempool_size=7i 1").unwrap());
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("# TYPE loom_block_latency histogram"));
+        assert!(rendered.contains("loom_block_latency_bucket{bot_name=\"loom\",le=\"10\"} 0"));
+        assert!(rendered.contains("loom_block_latency_bucket{bot_name=\"loom\",le=\"100\"} 1"));
+        assert!(rendered.contains("loom_block_latency_bucket{bot_name=\"loom\",le=\"+Inf\"} 2"));
+        assert!(rendered.contains("loom_block_latency_sum{bot_name=\"loom\"} 200"));
+        assert!(rendered.contains("loom_block_latency_total{bot_name=\"loom\"} 2"));
+        assert!(rendered.contains("# TYPE loom_mempool_tx_mempool_size gauge"));
+        assert!(rendered.contains("loom_mempool_tx_mempool_size{bot_name=\"loom\"} 7"));
+    }
+
+    #[test]
+    fn test_series_limit() {
+        let mut metrics = PrometheusMetrics::default().with_max_series(4);
+        metrics.observe(&parse_line_protocol("arb,tx_hash=0x01,pool=0x02,protocol=uni2 total=3i,value=1 1").unwrap());
+        metrics.observe(&parse_line_protocol("arb,tx_hash=0x03,pool=0x04,protocol=uni2 total=5i,value=2 1").unwrap());
+        metrics.observe(&parse_line_protocol("arb,protocol=uni3 total=7i,value=3 1").unwrap());
+
+        let rendered = metrics.render();
+        assert!(rendered.contains("# TYPE loom_arb_total counter"));
+        assert!(rendered.contains("loom_arb_total{protocol=\"uni2\"} 2"));
+        assert!(rendered.contains("loom_arb_total_value{protocol=\"uni2\"} 5"));
+        assert!(rendered.contains("loom_arb{protocol=\"uni2\"} 2"));
+        assert!(!rendered.contains("0x01"));
+        assert!(rendered.contains("loom_arb_total{protocol=\"uni3\"} 1"));
+        assert!(!rendered.contains("protocol=\"uni3\"} 7"));
+        assert_eq!(metrics.dropped_series(), 2);
+        assert!(rendered.contains("loom_prometheus_dropped_series_total 2"));
+    }
+}
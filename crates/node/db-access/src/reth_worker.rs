@@ -20,7 +20,7 @@ use std::path::Path;
 use std::sync::Arc;
 use tracing::{debug, error, info, trace};
 
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Producer, WorkerResult};
+use loom_core_actors::{Actor, ActorResult, Broadcaster, CancellationToken, Producer, WorkerResult};
 use loom_core_actors_macros::Producer;
 use loom_core_blockchain::Blockchain;
 use loom_evm_utils::reth_types::append_all_matching_block_logs;
@@ -38,6 +38,7 @@ pub async fn reth_node_worker<P>(
     new_block_with_tx_channel: Option<Broadcaster<MessageBlock>>,
     new_block_logs_channel: Option<Broadcaster<MessageBlockLogs>>,
     new_block_state_update_channel: Option<Broadcaster<MessageBlockStateUpdate>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
@@ -60,6 +61,9 @@ where
 
     loop {
         tokio::select! {
+        _ = cancel_token.cancelled() => {
+            break;
+        }
         block_msg = stream.next() => {
             let Some(block_header) = block_msg else {
                     continue
@@ -240,6 +244,7 @@ where
             }
         }
     }
+    Ok("Reth node worker stopped".to_string())
 }
 
 pub fn reth_node_worker_starter<P>(
@@ -249,6 +254,7 @@ pub fn reth_node_worker_starter<P>(
     new_block_with_tx_channel: Option<Broadcaster<MessageBlock>>,
     new_block_logs_channel: Option<Broadcaster<MessageBlockLogs>>,
     new_block_state_update_channel: Option<Broadcaster<MessageBlockStateUpdate>>,
+    cancel_token: CancellationToken,
 ) -> ActorResult
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
@@ -260,6 +266,7 @@ where
         new_block_with_tx_channel,
         new_block_logs_channel,
         new_block_state_update_channel,
+        cancel_token,
     ));
     Ok(vec![handler])
 }
@@ -315,7 +322,7 @@ impl<P> Actor for RethDbAccessBlockActor<P>
 where
     P: Provider<Ethereum> + DebugProviderExt<Ethereum> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         reth_node_worker_starter(
             self.client.clone(),
             self.reth_db_path.clone(),
@@ -323,6 +330,7 @@ where
             self.block_with_tx_channel.clone(),
             self.block_logs_channel.clone(),
             self.block_state_update_channel.clone(),
+            cancel_token,
         )
     }
     fn name(&self) -> &'static str {
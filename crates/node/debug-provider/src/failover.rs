@@ -0,0 +1,360 @@
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use alloy::primitives::{BlockNumber, U64};
+use alloy::rpc::json_rpc::{ErrorPayload, Id, Request, RequestPacket, ResponsePacket, ResponsePayload, SerializedRequest};
+use alloy::transports::{BoxTransport, TransportError, TransportErrorKind, TransportFut};
+use futures::future::join_all;
+use tokio::task::JoinHandle;
+use tower::Service;
+use tracing::{error, trace, warn};
+
+/// Weight of the last sample in average latency
+const LATENCY_EWMA_WEIGHT: f64 = 0.2;
+/// Endpoint with this number of errors in a row is used only when all other endpoints fail
+const MAX_CONSECUTIVE_ERRORS: u64 = 3;
+/// Time to wait for endpoint response before the request is counted as failed
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+/// JSON-RPC error codes of endpoint side failures: internal error, limit exceeded and rate limit
+const SERVER_ERROR_CODES: [i64; 3] = [-32603, -32005, 429];
+
+/// Request statistics of a failover endpoint
+#[derive(Clone, Debug, Default)]
+pub struct EndpointStats {
+    /// Exponentially weighted average of request latency, None until first successful request
+    pub latency: Option<Duration>,
+    /// Last block number reported by endpoint
+    pub head_block: BlockNumber,
+    /// Errors since last successful request
+    pub consecutive_errors: u64,
+    pub requests: u64,
+    pub errors: u64,
+}
+
+impl EndpointStats {
+    fn record_success(&mut self, latency: Duration) {
+        self.requests += 1;
+        self.consecutive_errors = 0;
+        self.latency = Some(match self.latency {
+            Some(avg) => {
+                Duration::from_secs_f64(avg.as_secs_f64() * (1.0 - LATENCY_EWMA_WEIGHT) + latency.as_secs_f64() * LATENCY_EWMA_WEIGHT)
+            }
+            None => latency,
+        });
+    }
+
+    fn record_error(&mut self) {
+        self.requests += 1;
+        self.errors += 1;
+        self.consecutive_errors += 1;
+    }
+}
+
+struct FailoverEndpoint {
+    name: String,
+    transport: BoxTransport,
+    stats: RwLock<EndpointStats>,
+}
+
+impl FailoverEndpoint {
+    fn stats(&self) -> EndpointStats {
+        self.stats.read().map(|stats| stats.clone()).unwrap_or_default()
+    }
+
+    fn update_stats(&self, f: impl FnOnce(&mut EndpointStats)) {
+        if let Ok(mut stats) = self.stats.write() {
+            f(&mut stats)
+        }
+    }
+
+    /// Sends request to the endpoint, request that is not answered in `timeout` fails with transport error
+    async fn call(&self, req: RequestPacket, timeout: Duration) -> Result<ResponsePacket, TransportError> {
+        match tokio::time::timeout(timeout, self.transport.clone().call(req)).await {
+            Ok(response) => response,
+            Err(_) => Err(TransportErrorKind::custom_str("REQUEST_TIMEOUT")),
+        }
+    }
+}
+
+fn is_server_error(payload: &ResponsePayload) -> bool {
+    matches!(payload, ResponsePayload::Failure(ErrorPayload { code, .. }) if SERVER_ERROR_CODES.contains(code))
+}
+
+/// Response contains JSON-RPC error caused by the endpoint. Other errors like reverts or invalid params
+/// are the same on every endpoint and are returned to the caller
+fn is_server_failure(response: &ResponsePacket) -> bool {
+    match response {
+        ResponsePacket::Single(response) => is_server_error(&response.payload),
+        ResponsePacket::Batch(responses) => responses.iter().any(|response| is_server_error(&response.payload)),
+    }
+}
+
+/// Transport that sends each request to the fastest endpoint that is not behind the best known head block.
+/// Requests are retried on the next endpoint on transport errors, timeouts and server side JSON-RPC errors. Subscriptions are not supported.
+#[derive(Clone)]
+pub struct FailoverTransport {
+    endpoints: Arc<Vec<FailoverEndpoint>>,
+    max_block_lag: u64,
+    request_timeout: Duration,
+}
+
+impl FailoverTransport {
+    pub fn new(endpoints: Vec<(String, BoxTransport)>) -> Self {
+        let endpoints = endpoints.into_iter().map(|(name, transport)| FailoverEndpoint {
+            name,
+            transport,
+            stats: RwLock::new(EndpointStats::default()),
+        });
+        Self { endpoints: Arc::new(endpoints.collect()), max_block_lag: 1, request_timeout: DEFAULT_REQUEST_TIMEOUT }
+    }
+
+    /// Number of blocks endpoint can be behind the best head before it is deprioritized
+    pub fn with_max_block_lag(self, max_block_lag: u64) -> Self {
+        Self { max_block_lag, ..self }
+    }
+
+    /// Time to wait for endpoint response before the request is sent to the next endpoint
+    pub fn with_request_timeout(self, request_timeout: Duration) -> Self {
+        Self { request_timeout, ..self }
+    }
+
+    /// Statistics of endpoints in configuration order
+    pub fn stats(&self) -> Vec<(String, EndpointStats)> {
+        self.endpoints.iter().map(|endpoint| (endpoint.name.clone(), endpoint.stats())).collect()
+    }
+
+    /// Best head block among endpoints
+    pub fn head_block(&self) -> BlockNumber {
+        self.endpoints.iter().map(|endpoint| endpoint.stats().head_block).max().unwrap_or_default()
+    }
+
+    /// Endpoint indexes, healthy endpoints ordered by latency go first
+    fn ranked(&self) -> Vec<usize> {
+        let stats: Vec<EndpointStats> = self.endpoints.iter().map(|endpoint| endpoint.stats()).collect();
+        let best_head = stats.iter().map(|s| s.head_block).max().unwrap_or_default();
+
+        let mut ranked: Vec<usize> = (0..stats.len()).collect();
+        ranked.sort_by_key(|&idx| {
+            let s = &stats[idx];
+            let healthy = s.consecutive_errors < MAX_CONSECUTIVE_ERRORS && s.head_block + self.max_block_lag >= best_head;
+            (!healthy, s.latency.unwrap_or(Duration::MAX))
+        });
+        ranked
+    }
+
+    async fn request(self, req: RequestPacket) -> Result<ResponsePacket, TransportError> {
+        let mut last_result = Err(TransportErrorKind::custom_str("NO_ENDPOINTS"));
+
+        for idx in self.ranked() {
+            let endpoint = &self.endpoints[idx];
+            let start = Instant::now();
+            match endpoint.call(req.clone(), self.request_timeout).await {
+                Ok(response) if !is_server_failure(&response) => {
+                    let latency = start.elapsed();
+                    endpoint.update_stats(|stats| stats.record_success(latency));
+                    trace!(endpoint = %endpoint.name, ?latency, "Failover request");
+                    return Ok(response);
+                }
+                Ok(response) => {
+                    warn!(endpoint = %endpoint.name, "Failover endpoint returned server JSON-RPC error");
+                    endpoint.update_stats(|stats| stats.record_error());
+                    last_result = Ok(response);
+                }
+                Err(error) => {
+                    warn!(endpoint = %endpoint.name, %error, "Failover endpoint request failed");
+                    endpoint.update_stats(|stats| stats.record_error());
+                    last_result = Err(error);
+                }
+            }
+        }
+
+        // JSON-RPC error or transport error of the last endpoint is returned if all endpoints fail
+        last_result
+    }
+
+    /// Requests block number from all endpoints to update their head blocks and latencies
+    pub async fn poll_heads(&self) {
+        let req: SerializedRequest = match Request::new("eth_blockNumber", Id::Number(0), ()).try_into() {
+            Ok(req) => req,
+            Err(error) => {
+                error!(%error, "eth_blockNumber request serialization failed");
+                return;
+            }
+        };
+
+        let futures = self.endpoints.iter().map(|endpoint| {
+            let req = RequestPacket::Single(req.clone());
+            async move {
+                let start = Instant::now();
+                let head_block = match endpoint.call(req, self.request_timeout).await {
+                    Ok(ResponsePacket::Single(response)) => match response.payload {
+                        ResponsePayload::Success(value) => serde_json::from_str::<U64>(value.get()).ok(),
+                        ResponsePayload::Failure(_) => None,
+                    },
+                    _ => None,
+                };
+                match head_block {
+                    Some(head_block) => {
+                        let latency = start.elapsed();
+                        endpoint.update_stats(|stats| {
+                            stats.record_success(latency);
+                            stats.head_block = head_block.to();
+                        });
+                    }
+                    None => {
+                        warn!(endpoint = %endpoint.name, "Failover endpoint head block request failed");
+                        endpoint.update_stats(|stats| stats.record_error());
+                    }
+                }
+            }
+        });
+        join_all(futures).await;
+    }
+
+    /// Spawns task that polls head blocks of endpoints with `interval`
+    pub fn spawn_head_monitor(&self, interval: Duration) -> JoinHandle<()> {
+        let transport = self.clone();
+        tokio::task::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            loop {
+                interval.tick().await;
+                transport.poll_heads().await;
+            }
+        })
+    }
+}
+
+impl Service<RequestPacket> for FailoverTransport {
+    type Response = ResponsePacket;
+    type Error = TransportError;
+    type Future = TransportFut<'static>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: RequestPacket) -> Self::Future {
+        Box::pin(self.clone().request(req))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy::providers::{Provider, ProviderBuilder};
+    use alloy::rpc::client::RpcClient;
+    use alloy::rpc::json_rpc::Response;
+    use eyre::Result;
+    use serde_json::value::RawValue;
+
+    /// Answers every request with block number, transport error, JSON-RPC error with code or does not answer at all
+    #[derive(Clone)]
+    enum MockTransport {
+        Block(u64),
+        TransportError,
+        RpcError(i64),
+        Hang,
+    }
+
+    impl Service<RequestPacket> for MockTransport {
+        type Response = ResponsePacket;
+        type Error = TransportError;
+        type Future = TransportFut<'static>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: RequestPacket) -> Self::Future {
+            let mock = self.clone();
+            Box::pin(async move {
+                let RequestPacket::Single(req) = req else {
+                    return Err(TransportErrorKind::custom_str("BATCH_NOT_SUPPORTED"));
+                };
+                let payload = match mock {
+                    MockTransport::Block(block) => {
+                        ResponsePayload::Success(RawValue::from_string(format!("\"0x{block:x}\"")).map_err(TransportErrorKind::custom)?)
+                    }
+                    MockTransport::TransportError => return Err(TransportErrorKind::custom_str("MOCK_ERROR")),
+                    MockTransport::RpcError(code) => {
+                        ResponsePayload::Failure(ErrorPayload { code, message: "MOCK_ERROR".into(), data: None })
+                    }
+                    MockTransport::Hang => {
+                        tokio::time::sleep(Duration::from_secs(3600)).await;
+                        return Err(TransportErrorKind::custom_str("MOCK_ERROR"));
+                    }
+                };
+                Ok(ResponsePacket::Single(Response { id: req.id().clone(), payload }))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failover() -> Result<()> {
+        let transport = FailoverTransport::new(vec![
+            ("failing".to_string(), BoxTransport::new(MockTransport::TransportError)),
+            ("lagging".to_string(), BoxTransport::new(MockTransport::Block(90))),
+            ("synced".to_string(), BoxTransport::new(MockTransport::Block(100))),
+        ]);
+        let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(RpcClient::new(transport.clone(), false));
+
+        // endpoints without latency keep configuration order, failed request is retried on the next endpoint
+        assert_eq!(provider.get_block_number().await?, 90);
+        assert_eq!(transport.stats()[0].1.errors, 1);
+        assert_eq!(transport.stats()[1].1.requests, 1);
+
+        transport.poll_heads().await;
+        assert_eq!(transport.head_block(), 100);
+        // lagging endpoint is deprioritized even if it is faster
+        assert_eq!(transport.ranked()[0], 2);
+        assert_eq!(provider.get_block_number().await?, 100);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failover_timeout_and_rpc_error() -> Result<()> {
+        let transport = FailoverTransport::new(vec![
+            ("hanging".to_string(), BoxTransport::new(MockTransport::Hang)),
+            ("rpc_error".to_string(), BoxTransport::new(MockTransport::RpcError(-32603))),
+            ("synced".to_string(), BoxTransport::new(MockTransport::Block(100))),
+        ])
+        .with_request_timeout(Duration::from_millis(100));
+        let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(RpcClient::new(transport.clone(), false));
+
+        // timed out request and server JSON-RPC error are retried on the next endpoint
+        assert_eq!(provider.get_block_number().await?, 100);
+        assert_eq!(transport.stats()[0].1.errors, 1);
+        assert_eq!(transport.stats()[1].1.errors, 1);
+
+        transport.poll_heads().await;
+        assert_eq!(transport.stats()[0].1.errors, 2);
+        assert_eq!(transport.stats()[1].1.errors, 2);
+        assert_eq!(transport.head_block(), 100);
+
+        // JSON-RPC error is returned when no endpoint succeeds
+        let transport = FailoverTransport::new(vec![("rpc_error".to_string(), BoxTransport::new(MockTransport::RpcError(-32603)))]);
+        let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(RpcClient::new(transport, false));
+        assert!(provider.get_block_number().await.unwrap_err().as_error_resp().is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_failover_revert_not_retried() -> Result<()> {
+        let transport = FailoverTransport::new(vec![
+            ("revert".to_string(), BoxTransport::new(MockTransport::RpcError(3))),
+            ("synced".to_string(), BoxTransport::new(MockTransport::Block(100))),
+        ]);
+        let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(RpcClient::new(transport.clone(), false));
+
+        // revert is returned to the caller as is, endpoint is not penalised and request is not sent to the next endpoint
+        assert_eq!(provider.get_block_number().await.unwrap_err().as_error_resp().unwrap().code, 3);
+        assert_eq!(transport.stats()[0].1.requests, 1);
+        assert_eq!(transport.stats()[0].1.errors, 0);
+        assert_eq!(transport.stats()[1].1.requests, 0);
+
+        Ok(())
+    }
+}
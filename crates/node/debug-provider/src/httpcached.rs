@@ -9,7 +9,7 @@ use alloy::rpc::types::trace::geth::GethDebugTracingCallOptions;
 use alloy::{
     primitives::{BlockHash, BlockNumber, B128, B256, U128},
     rpc::{
-        json_rpc::{Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest},
+        json_rpc::{ErrorPayload, Id, Request, RequestPacket, Response, ResponsePacket, ResponsePayload, SerializedRequest},
         types::{trace::geth::GethDebugTracingOptions, Block, BlockNumberOrTag, TransactionRequest},
     },
     transports::{
@@ -27,13 +27,16 @@ use url::Url;
 
 use crate::cachefolder::CacheFolder;
 
+/// Transport that replays node responses from a cache folder and records missing ones.
+/// Without a client (offline mode) every request must be served from the cache, misses are returned as errors.
 #[derive(Clone)]
 pub struct HttpCachedTransport {
-    client: Http<Client>,
+    client: Option<Http<Client>>,
     block_number: Arc<AtomicU64>,
     block_filters: Arc<RwLock<HashMap<U128, BlockNumber>>>,
     block_hashes: Arc<RwLock<HashMap<BlockNumber, B256>>>,
     cache_folder: Option<CacheFolder>,
+    cache_misses: Arc<AtomicU64>,
 }
 
 impl HttpCachedTransport {
@@ -44,14 +47,39 @@ impl HttpCachedTransport {
             None => None,
         };
         Self {
-            client,
+            client: Some(client),
             block_number: Arc::new(AtomicU64::new(0)),
             block_filters: Arc::new(RwLock::new(HashMap::new())),
             block_hashes: Arc::new(RwLock::new(HashMap::new())),
             cache_folder,
+            cache_misses: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Create transport that works only with recorded cache and never hits the network
+    pub async fn new_offline(cache_path: &str) -> Result<Self> {
+        if !std::path::Path::new(cache_path).is_dir() {
+            return Err(eyre!("CACHE_FOLDER_NOT_FOUND"));
+        }
+        Ok(Self {
+            client: None,
+            block_number: Arc::new(AtomicU64::new(0)),
+            block_filters: Arc::new(RwLock::new(HashMap::new())),
+            block_hashes: Arc::new(RwLock::new(HashMap::new())),
+            cache_folder: Some(CacheFolder::new(cache_path).await),
+            cache_misses: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.client.is_none()
+    }
+
+    /// Number of requests that were not found in cache in offline mode
+    pub fn cache_misses(&self) -> u64 {
+        self.cache_misses.load(Ordering::Relaxed)
+    }
+
     pub fn set_block_number(&self, block_number: u64) -> u64 {
         self.block_number.swap(block_number, Ordering::Relaxed)
     }
@@ -184,31 +212,56 @@ impl HttpCachedTransport {
         Ok(ResponsePacket::Single(body))
     }
 
+    fn error_method(method: &str) -> String {
+        format!("{}_error", method)
+    }
+
     pub async fn cached_or_execute(&self, req: SerializedRequest) -> Result<ResponsePacket, TransportError> {
         let req_hash = req.params_hash();
         let method = req.method().to_string();
-        match self.read_cached(method.clone(), req_hash).await {
-            Ok(cached) => {
-                let value = RawValue::from_string(cached).unwrap();
-                let body = Response { id: Id::None, payload: ResponsePayload::Success(value) };
-                Ok(ResponsePacket::Single(body))
-            }
-            Err(_) => {
-                let mut client = self.client.clone();
-                match client.call(RequestPacket::Single(req)).await {
-                    Ok(resp) => {
-                        if let ResponsePacket::Single(resp) = resp.clone() {
-                            if let Err(e) = self.write_cached(method, req_hash, resp.payload.as_success().unwrap().to_string()).await {
-                                error!("{}", e);
-                            }
-                        }
-                        Ok(resp)
-                    }
-                    Err(e) => {
-                        error!("client.call error {e}");
-                        Err(e)
+        if let Ok(cached) = self.read_cached(method.clone(), req_hash).await {
+            let value = RawValue::from_string(cached).map_err(|e| TransportError::DeserError { err: e, text: "err".to_string() })?;
+            let body = Response { id: Id::None, payload: ResponsePayload::Success(value) };
+            return Ok(ResponsePacket::Single(body));
+        }
+        if let Ok(cached) = self.read_cached(Self::error_method(&method), req_hash).await {
+            let error_payload: ErrorPayload =
+                serde_json::from_str(&cached).map_err(|e| TransportError::DeserError { err: e, text: cached.clone() })?;
+            let body = Response { id: Id::None, payload: ResponsePayload::Failure(error_payload) };
+            return Ok(ResponsePacket::Single(body));
+        }
+        self.execute_and_record(req).await
+    }
+
+    /// Execute request with the node and write the response to cache. Fails in offline mode.
+    pub async fn execute_and_record(&self, req: SerializedRequest) -> Result<ResponsePacket, TransportError> {
+        let req_hash = req.params_hash();
+        let method = req.method().to_string();
+        let Some(mut client) = self.client.clone() else {
+            self.cache_misses.fetch_add(1, Ordering::Relaxed);
+            error!(method, params = ?req.params(), "Cache miss in offline mode");
+            return Err(TransportErrorKind::custom_str(format!("CACHE_MISS {} {}", method, req_hash).as_str()));
+        };
+
+        match client.call(RequestPacket::Single(req)).await {
+            Ok(resp) => {
+                if let ResponsePacket::Single(single_resp) = &resp {
+                    let write_result = match &single_resp.payload {
+                        ResponsePayload::Success(value) => self.write_cached(method, req_hash, value.to_string()).await,
+                        ResponsePayload::Failure(error_payload) => match serde_json::to_string(error_payload) {
+                            Ok(data) => self.write_cached(Self::error_method(&method), req_hash, data).await,
+                            Err(e) => Err(e.into()),
+                        },
+                    };
+                    if let Err(e) = write_result {
+                        error!("{}", e);
                     }
                 }
+                Ok(resp)
+            }
+            Err(e) => {
+                error!("client.call error {e}");
+                Err(e)
             }
         }
     }
@@ -225,10 +278,9 @@ impl HttpCachedTransport {
         );
         let new_req: SerializedRequest = new_req.try_into().unwrap();
 
-        //let resp = self.cached_or_execute(new_req.clone()).await;
-        //trace!("eth_call resp : {:?}", resp);
-        //resp
-        self.client.clone().call(RequestPacket::Single(new_req)).await
+        let resp = self.cached_or_execute(new_req).await;
+        trace!("eth_call resp : {:?}", resp);
+        resp
     }
 
     pub async fn eth_get_storage_at(self, req: SerializedRequest) -> Result<ResponsePacket, TransportError> {
@@ -249,7 +301,7 @@ impl HttpCachedTransport {
 
         let new_req: SerializedRequest = new_req.try_into().unwrap();
 
-        let resp = self.client.clone().call(RequestPacket::Single(new_req)).await;
+        let resp = self.cached_or_execute(new_req).await;
         trace!("eth_get_storage_at resp : {:?}", resp);
         resp
     }
@@ -308,9 +360,7 @@ impl HttpCachedTransport {
         );
         let new_req: SerializedRequest = new_req.try_into().unwrap();
 
-        // let resp = self.cached_or_execute(new_req.clone()).await;
-        // trace!("eth_call resp : {:?}", resp);
-        self.client.clone().call(RequestPacket::Single(new_req)).await
+        self.cached_or_execute(new_req).await
     }
 
     pub async fn debug_trace_block_by_hash(self, req: SerializedRequest) -> Result<ResponsePacket, TransportError> {
@@ -330,7 +380,10 @@ impl Service<RequestPacket> for HttpCachedTransport {
     type Future = TransportFut<'static>;
 
     fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
-        self.client.poll_ready(cx)
+        match &mut self.client {
+            Some(client) => client.poll_ready(cx),
+            None => Poll::Ready(Ok(())),
+        }
     }
 
     fn call(&mut self, req: RequestPacket) -> Self::Future {
@@ -344,7 +397,7 @@ impl Service<RequestPacket> for HttpCachedTransport {
                     single_req.params()
                 );
 
-                let mut self_clone = self.clone();
+                let self_clone = self.clone();
                 match single_req.method() {
                     "eth_blockNumber" | "get_block_number" => Box::pin(self_clone.get_block_number()),
                     "eth_newBlockFilter" => Box::pin(self_clone.new_block_filter()),
@@ -357,30 +410,20 @@ impl Service<RequestPacket> for HttpCachedTransport {
                     "debug_traceBlockByHash" => Box::pin(self_clone.debug_trace_block_by_hash(single_req)),
                     "debug_traceBlockByNumber" => Box::pin(self_clone.debug_trace_block_by_number(single_req)),
                     "debug_traceCall" => Box::pin(self_clone.debug_trace_call(single_req)),
-                    _ => Box::pin(async move {
-                        match self_clone.client.call(RequestPacket::Single(single_req)).await {
-                            Ok(response) => {
-                                match &response {
-                                    ResponsePacket::Single(single_resp) => {
-                                        trace!("responsepacket response : {:?} ", single_resp);
-                                        trace!(
-                                            "responsepacket payload id : {} len {}",
-                                            single_resp.id,
-                                            single_resp.payload.as_success().unwrap().get().len()
-                                        );
-                                    }
-                                    ResponsePacket::Batch(batch_resp) => {
-                                        error!("Batch response received {}", batch_resp.len())
-                                    }
-                                }
-                                Ok(response)
-                            }
-                            Err(e) => Err(e),
+                    // requests not bound to a block are recorded but always executed while online
+                    _ => {
+                        if self_clone.is_offline() {
+                            Box::pin(async move { self_clone.cached_or_execute(single_req).await })
+                        } else {
+                            Box::pin(async move { self_clone.execute_and_record(single_req).await })
                         }
-                    }),
+                    }
                 }
             }
-            _ => self.client.call(req),
+            _ => match &mut self.client {
+                Some(client) => client.call(req),
+                None => Box::pin(async move { Err(TransportErrorKind::custom_str("BATCH_NOT_SUPPORTED_OFFLINE")) }),
+            },
         }
     }
 }
@@ -407,7 +450,33 @@ mod test {
     use tracing::debug;
     use url::Url;
 
+    use crate::cachefolder::CacheFolder;
     use crate::httpcached::HttpCachedTransport;
+    use alloy::rpc::json_rpc::{Id, Request, SerializedRequest};
+
+    #[tokio::test]
+    async fn test_offline_cache() -> Result<()> {
+        let cache_path = env::temp_dir().join("loom_httpcached_offline_test");
+        let cache_path = cache_path.to_str().unwrap();
+        let cache_folder = CacheFolder::new(cache_path).await;
+
+        let req: SerializedRequest =
+            Request::new("eth_getBlockByNumber", Id::Number(1), (BlockNumberOrTag::Number(100), false)).try_into()?;
+        cache_folder.write("eth_getBlockByNumber".to_string(), req.params_hash(), "null".to_string()).await?;
+
+        let transport = HttpCachedTransport::new_offline(cache_path).await?;
+        transport.set_block_number(100);
+        let provider = ProviderBuilder::new().disable_recommended_fillers().on_client(RpcClient::new(transport.clone(), true));
+
+        let block = provider.get_block_by_number(BlockNumberOrTag::Number(100), BlockTransactionsKind::Hashes).await?;
+        assert!(block.is_none());
+        assert_eq!(transport.cache_misses(), 0);
+
+        assert!(provider.get_block_by_number(BlockNumberOrTag::Number(99), BlockTransactionsKind::Hashes).await.is_err());
+        assert_eq!(transport.cache_misses(), 1);
+
+        Ok(())
+    }
 
     #[tokio::test]
     async fn test_create_service() -> Result<()> {
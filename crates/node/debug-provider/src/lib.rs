@@ -1,9 +1,11 @@
 pub use anvilprovider::AnvilProviderExt;
 pub use debugprovider::{AnvilDebugProvider, AnvilDebugProviderFactory, AnvilDebugProviderType, DebugProviderExt};
+pub use failover::{EndpointStats, FailoverTransport};
 pub use httpcached::HttpCachedTransport;
 
 mod anvilprovider;
 mod archiveprovider;
 mod cachefolder;
 mod debugprovider;
+mod failover;
 mod httpcached;
@@ -1,5 +1,5 @@
 use crate::node_exex_worker::node_exex_grpc_worker;
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Producer};
+use loom_core_actors::{Actor, ActorResult, Broadcaster, CancellationToken, Producer};
 use loom_core_actors_macros::Producer;
 use loom_core_blockchain::Blockchain;
 use loom_types_events::{MessageBlock, MessageBlockHeader, MessageBlockLogs, MessageBlockStateUpdate, MessageMempoolDataUpdate};
@@ -45,7 +45,7 @@ impl NodeExExGrpcActor {
 }
 
 impl Actor for NodeExExGrpcActor {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let handler = tokio::task::spawn(node_exex_grpc_worker(
             Some(self.url.clone()),
             self.block_header_channel.clone().unwrap(),
@@ -53,6 +53,7 @@ impl Actor for NodeExExGrpcActor {
             self.block_logs_channel.clone().unwrap(),
             self.block_state_update_channel.clone().unwrap(),
             self.mempool_update_channel.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![handler])
     }
@@ -13,7 +13,7 @@ use revm::db::{BundleAccount, StorageWithOriginalValues};
 use tokio::select;
 use tracing::{error, info};
 
-use loom_core_actors::{Broadcaster, WorkerResult};
+use loom_core_actors::{Broadcaster, CancellationToken, WorkerResult};
 use loom_evm_utils::reth_types::append_all_matching_block_logs_sealed;
 use loom_node_grpc_exex_proto::ExExClient;
 use loom_types_blockchain::{GethStateUpdate, MempoolTx};
@@ -145,6 +145,7 @@ pub async fn node_exex_grpc_worker(
     logs_channel: Broadcaster<MessageBlockLogs>,
     state_update_channel: Broadcaster<MessageBlockStateUpdate>,
     mempool_channel: Broadcaster<MessageMempoolDataUpdate>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     let client = ExExClient::connect(url.unwrap_or("http://[::1]:10000".to_string())).await?;
 
@@ -165,6 +166,9 @@ pub async fn node_exex_grpc_worker(
 
     loop {
         select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
 
             header = stream_header.next() => {
                 if let Some(header) = header {
@@ -235,4 +239,5 @@ pub async fn node_exex_grpc_worker(
             }
         }
     }
+    Ok("ExEx grpc worker stopped".to_string())
 }
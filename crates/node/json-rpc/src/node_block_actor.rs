@@ -6,7 +6,7 @@ use crate::node_block_hash_worker::new_node_block_header_worker;
 use crate::node_block_logs_worker::new_node_block_logs_worker;
 use crate::node_block_state_worker::new_node_block_state_worker;
 use crate::node_block_with_tx_worker::new_block_with_tx_worker;
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Producer, WorkerResult};
+use loom_core_actors::{Actor, ActorResult, Broadcaster, CancellationToken, Producer, WorkerResult};
 use loom_core_actors_macros::Producer;
 use loom_core_blockchain::Blockchain;
 use loom_node_actor_config::NodeBlockActorConfig;
@@ -20,6 +20,7 @@ pub fn new_node_block_workers_starter<P>(
     new_block_with_tx_channel: Option<Broadcaster<MessageBlock>>,
     new_block_logs_channel: Option<Broadcaster<MessageBlockLogs>>,
     new_block_state_update_channel: Option<Broadcaster<MessageBlockStateUpdate>>,
+    cancel_token: CancellationToken,
 ) -> ActorResult
 where
     P: Provider<Ethereum> + DebugProviderExt + Send + Sync + Clone + 'static,
@@ -28,19 +29,39 @@ where
     let mut tasks: Vec<JoinHandle<WorkerResult>> = Vec::new();
 
     if let Some(channel) = new_block_with_tx_channel {
-        tasks.push(tokio::task::spawn(new_block_with_tx_worker(client.clone(), new_header_internal_channel.clone(), channel)));
+        tasks.push(tokio::task::spawn(new_block_with_tx_worker(
+            client.clone(),
+            new_header_internal_channel.clone(),
+            channel,
+            cancel_token.clone(),
+        )));
     }
 
     if let Some(channel) = new_block_headers_channel {
-        tasks.push(tokio::task::spawn(new_node_block_header_worker(client.clone(), new_header_internal_channel.clone(), channel)));
+        tasks.push(tokio::task::spawn(new_node_block_header_worker(
+            client.clone(),
+            new_header_internal_channel.clone(),
+            channel,
+            cancel_token.clone(),
+        )));
     }
 
     if let Some(channel) = new_block_logs_channel {
-        tasks.push(tokio::task::spawn(new_node_block_logs_worker(client.clone(), new_header_internal_channel.clone(), channel)));
+        tasks.push(tokio::task::spawn(new_node_block_logs_worker(
+            client.clone(),
+            new_header_internal_channel.clone(),
+            channel,
+            cancel_token.clone(),
+        )));
     }
 
     if let Some(channel) = new_block_state_update_channel {
-        tasks.push(tokio::task::spawn(new_node_block_state_worker(client.clone(), new_header_internal_channel.clone(), channel)));
+        tasks.push(tokio::task::spawn(new_node_block_state_worker(
+            client.clone(),
+            new_header_internal_channel.clone(),
+            channel,
+            cancel_token.clone(),
+        )));
     }
 
     Ok(tasks)
@@ -94,13 +115,14 @@ impl<P> Actor for NodeBlockActor<P>
 where
     P: Provider<Ethereum> + DebugProviderExt + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         new_node_block_workers_starter(
             self.client.clone(),
             self.block_header_channel.clone(),
             self.block_with_tx_channel.clone(),
             self.block_logs_channel.clone(),
             self.block_state_update_channel.clone(),
+            cancel_token,
         )
     }
     fn name(&self) -> &'static str {
@@ -8,7 +8,7 @@ use alloy_rpc_types::Header;
 use chrono::Utc;
 use eyre::Result;
 use futures::StreamExt;
-use loom_core_actors::{run_sync, Broadcaster, WorkerResult};
+use loom_core_actors::{run_sync, Broadcaster, CancellationToken, WorkerResult};
 use loom_types_events::{BlockHeader, MessageBlockHeader};
 use tracing::{error, info};
 
@@ -42,6 +42,7 @@ pub async fn new_node_block_header_worker<P>(
     client: P,
     new_block_header_channel: Broadcaster<Header>,
     block_header_channel: Broadcaster<MessageBlockHeader>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
@@ -54,6 +55,9 @@ where
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             block_msg = stream.next() => {
                 if let Some(block_header) = block_msg {
                     let block_hash = block_header.hash;
@@ -71,4 +75,5 @@ where
             }
         }
     }
+    Ok("Block header worker stopped".to_string())
 }
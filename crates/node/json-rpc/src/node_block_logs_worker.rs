@@ -6,18 +6,23 @@ use alloy_rpc_types::{Filter, Header};
 use tokio::sync::broadcast::Receiver;
 use tracing::{debug, error};
 
-use loom_core_actors::{subscribe, Broadcaster, WorkerResult};
+use loom_core_actors::{subscribe, Broadcaster, CancellationToken, WorkerResult};
 use loom_types_events::{BlockLogs, Message, MessageBlockLogs};
 
 pub async fn new_node_block_logs_worker<N: Network, P: Provider<N> + Send + Sync + 'static>(
     client: P,
     block_header_receiver: Broadcaster<Header>,
     sender: Broadcaster<MessageBlockLogs>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(block_header_receiver);
 
     loop {
-        if let Ok(block_header) = block_header_receiver.recv().await {
+        let block_header = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            block_header = block_header_receiver.recv() => block_header,
+        };
+        if let Ok(block_header) = block_header {
             let (block_number, block_hash) = (block_header.number, block_header.hash);
             debug!("BlockLogs header received {} {}", block_number, block_hash);
             let filter = Filter::new().at_block_hash(block_header.hash());
@@ -43,6 +48,7 @@ pub async fn new_node_block_logs_worker<N: Network, P: Provider<N> + Send + Sync
             debug!("BlockLogs processing finished {} {}", block_number, block_hash);
         }
     }
+    Ok("BlockLogs worker stopped".to_string())
 }
 
 #[allow(dead_code)]
@@ -4,7 +4,7 @@ use alloy_provider::Provider;
 use alloy_rpc_types::{BlockId, Header};
 use tracing::{debug, error};
 
-use loom_core_actors::{subscribe, Broadcaster, WorkerResult};
+use loom_core_actors::{subscribe, Broadcaster, CancellationToken, WorkerResult};
 use loom_node_debug_provider::DebugProviderExt;
 use loom_types_blockchain::debug_trace_block;
 use loom_types_events::{BlockStateUpdate, Message, MessageBlockStateUpdate};
@@ -13,6 +13,7 @@ pub async fn new_node_block_state_worker<P, N>(
     client: P,
     block_header_receiver: Broadcaster<Header>,
     sender: Broadcaster<MessageBlockStateUpdate>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
@@ -21,7 +22,11 @@ where
     subscribe!(block_header_receiver);
 
     loop {
-        if let Ok(block_header) = block_header_receiver.recv().await {
+        let block_header = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            block_header = block_header_receiver.recv() => block_header,
+        };
+        if let Ok(block_header) = block_header {
             let (block_number, block_hash) = (block_header.number, block_header.hash);
             debug!("BlockState header received {} {}", block_number, block_hash);
 
@@ -38,4 +43,5 @@ where
             debug!("BlockState processing finished {} {}", block_number, block_hash);
         }
     }
+    Ok("BlockState worker stopped".to_string())
 }
@@ -1,7 +1,7 @@
 use alloy_network::{primitives::HeaderResponse, Ethereum};
 use alloy_provider::Provider;
 use alloy_rpc_types::{BlockTransactionsKind, Header};
-use loom_core_actors::{subscribe, Broadcaster, WorkerResult};
+use loom_core_actors::{subscribe, Broadcaster, CancellationToken, WorkerResult};
 use loom_types_events::{BlockUpdate, Message, MessageBlock};
 use tracing::{debug, error};
 
@@ -9,6 +9,7 @@ pub async fn new_block_with_tx_worker<P>(
     client: P,
     block_header_receiver: Broadcaster<Header>,
     sender: Broadcaster<MessageBlock>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     P: Provider<Ethereum> + Send + Sync + 'static,
@@ -16,7 +17,11 @@ where
     subscribe!(block_header_receiver);
 
     loop {
-        if let Ok(block_header) = block_header_receiver.recv().await {
+        let block_header = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            block_header = block_header_receiver.recv() => block_header,
+        };
+        if let Ok(block_header) = block_header {
             let (block_number, block_hash) = (block_header.number, block_header.hash);
             debug!("BlockWithTx header received {} {}", block_number, block_hash);
 
@@ -44,4 +49,5 @@ where
             debug!("BlockWithTx processing finished {} {}", block_number, block_hash);
         }
     }
+    Ok("BlockWithTx worker stopped".to_string())
 }
@@ -4,7 +4,7 @@ use alloy_provider::Provider;
 use futures::StreamExt;
 use tracing::error;
 
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Producer, WorkerResult};
+use loom_core_actors::{Actor, ActorResult, Broadcaster, CancellationToken, Producer, WorkerResult};
 use loom_core_actors_macros::*;
 use loom_core_blockchain::Blockchain;
 use loom_types_blockchain::LoomDataTypesEthereum;
@@ -12,14 +12,26 @@ use loom_types_blockchain::MempoolTx;
 use loom_types_events::{MessageMempoolDataUpdate, NodeMempoolDataUpdate};
 
 /// Worker listens for new transactions in the node mempool and broadcasts [`MessageMempoolDataUpdate`].
-pub async fn new_node_mempool_worker<P>(client: P, name: String, mempool_tx: Broadcaster<MessageMempoolDataUpdate>) -> WorkerResult
+pub async fn new_node_mempool_worker<P>(
+    client: P,
+    name: String,
+    mempool_tx: Broadcaster<MessageMempoolDataUpdate>,
+    cancel_token: CancellationToken,
+) -> WorkerResult
 where
     P: Provider<Ethereum> + Send + Sync + 'static,
 {
     let mempool_subscription = client.subscribe_full_pending_transactions().await?;
     let mut stream = mempool_subscription.into_stream();
 
-    while let Some(tx) = stream.next().await {
+    loop {
+        let tx = tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            tx = stream.next() => match tx {
+                Some(tx) => tx,
+                None => break,
+            },
+        };
         let tx_hash: TxHash = tx.tx_hash();
         let update_msg: MessageMempoolDataUpdate = MessageMempoolDataUpdate::new_with_source(
             NodeMempoolDataUpdate { tx_hash, mempool_tx: MempoolTx { tx: Some(tx), ..MempoolTx::default() } },
@@ -66,9 +78,13 @@ impl<P> Actor for NodeMempoolActor<P>
 where
     P: Provider<Ethereum> + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
-        let task =
-            tokio::task::spawn(new_node_mempool_worker(self.client.clone(), self.name.to_string(), self.mempool_tx.clone().unwrap()));
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(new_node_mempool_worker(
+            self.client.clone(),
+            self.name.to_string(),
+            self.mempool_tx.clone().unwrap(),
+            cancel_token,
+        ));
         Ok(vec![task])
     }
 
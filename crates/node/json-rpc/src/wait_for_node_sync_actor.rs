@@ -2,7 +2,7 @@ use alloy_network::Ethereum;
 use alloy_provider::Provider;
 use alloy_rpc_types::SyncStatus;
 use eyre::eyre;
-use loom_core_actors::{Actor, ActorResult, WorkerResult};
+use loom_core_actors::{Actor, ActorResult, CancellationToken, WorkerResult};
 use loom_node_debug_provider::DebugProviderExt;
 use std::time::Duration;
 use tokio::time::timeout;
@@ -75,7 +75,7 @@ where
         Ok(())
     }
 
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, _cancel_token: CancellationToken) -> ActorResult {
         Err(eyre!("NEED_TO_BE_WAITED"))
     }
 
@@ -1,6 +1,7 @@
 use revm::{Database, DatabaseCommit, DatabaseRef};
 use std::any::type_name;
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use crate::compose::replayer_compose_worker;
 use crate::worker::node_player_worker;
@@ -8,11 +9,11 @@ use alloy_network::{Ethereum, Network};
 use alloy_primitives::BlockNumber;
 use alloy_provider::Provider;
 use eyre::ErrReport;
-use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, BlockchainState};
 use loom_evm_db::DatabaseLoomExt;
-use loom_node_debug_provider::DebugProviderExt;
+use loom_node_debug_provider::{DebugProviderExt, HttpCachedTransport};
 use loom_types_blockchain::LoomDataTypesEthereum;
 use loom_types_blockchain::Mempool;
 use loom_types_entities::MarketState;
@@ -24,6 +25,8 @@ pub struct NodeBlockPlayerActor<P, N, DB: Send + Sync + Clone + 'static> {
     client: P,
     start_block: BlockNumber,
     end_block: BlockNumber,
+    block_interval: Duration,
+    transport: Option<HttpCachedTransport>,
     #[accessor]
     mempool: Option<SharedState<Mempool>>,
     #[accessor]
@@ -52,6 +55,8 @@ where
             client,
             start_block,
             end_block,
+            block_interval: Duration::from_millis(1000),
+            transport: None,
             mempool: None,
             market_state: None,
             compose_channel: None,
@@ -63,6 +68,16 @@ where
         }
     }
 
+    /// Delay between blocks, gives actors time to process the previous block
+    pub fn with_block_interval(self, block_interval: Duration) -> Self {
+        Self { block_interval, ..self }
+    }
+
+    /// Move block number of the cached transport with every played block, so `latest` requests are served for the current block
+    pub fn with_transport(self, transport: HttpCachedTransport) -> Self {
+        Self { transport: Some(transport), ..self }
+    }
+
     pub fn on_bc(self, bc: &Blockchain, state: &BlockchainState<DB>) -> Self {
         Self {
             mempool: Some(bc.mempool()),
@@ -83,11 +98,11 @@ where
     N: Send + Sync,
     DB: Database<Error = ErrReport> + DatabaseRef<Error = ErrReport> + DatabaseCommit + DatabaseLoomExt + Send + Sync + Clone + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let mut handles: Vec<JoinHandle<WorkerResult>> = Vec::new();
         if let Some(mempool) = self.mempool.clone() {
             if let Some(compose_channel) = self.compose_channel.clone() {
-                let handle = tokio::task::spawn(replayer_compose_worker(mempool, compose_channel));
+                let handle = tokio::task::spawn(replayer_compose_worker(mempool, compose_channel, cancel_token.clone()));
                 handles.push(handle);
             }
         }
@@ -96,12 +111,15 @@ where
             self.client.clone(),
             self.start_block,
             self.end_block,
+            self.block_interval,
+            self.transport.clone(),
             self.mempool.clone(),
             self.market_state.clone(),
             self.block_header_channel.clone(),
             self.block_with_tx_channel.clone(),
             self.block_logs_channel.clone(),
             self.block_state_update_channel.clone(),
+            cancel_token,
         ));
         handles.push(handle);
         Ok(handles)
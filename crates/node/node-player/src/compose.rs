@@ -1,16 +1,23 @@
-use loom_core_actors::{Broadcaster, SharedState, WorkerResult};
+use loom_core_actors::{subscribe, Broadcaster, CancellationToken, SharedState, WorkerResult};
 use loom_evm_utils::reth_types::decode_into_transaction;
 use loom_types_blockchain::Mempool;
 use loom_types_events::{MessageTxCompose, RlpState, TxComposeMessageType};
 use tokio::select;
 use tracing::{error, info};
 
-pub(crate) async fn replayer_compose_worker(mempool: SharedState<Mempool>, compose_channel: Broadcaster<MessageTxCompose>) -> WorkerResult {
-    let mut compose_channel_rx = compose_channel.subscribe();
+pub(crate) async fn replayer_compose_worker(
+    mempool: SharedState<Mempool>,
+    compose_channel: Broadcaster<MessageTxCompose>,
+    cancel_token: CancellationToken,
+) -> WorkerResult {
+    subscribe!(compose_channel);
 
     loop {
         select! {
-            msg = compose_channel_rx.recv() => {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
+            msg = compose_channel.recv() => {
                 if let Ok(msg) = msg {
                     if let TxComposeMessageType::Broadcast(broadcast_msg) = msg.inner {
                         info!("Broadcast compose message received. {:?}", broadcast_msg.tx_bundle);
@@ -37,4 +44,5 @@ pub(crate) async fn replayer_compose_worker(mempool: SharedState<Mempool>, compo
             }
         }
     }
+    Ok("Replayer compose worker stopped".to_string())
 }
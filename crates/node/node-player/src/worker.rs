@@ -4,9 +4,9 @@ use alloy_network::Ethereum;
 use alloy_primitives::BlockNumber;
 use alloy_provider::Provider;
 use alloy_rpc_types::{BlockTransactions, BlockTransactionsKind, Filter};
-use loom_core_actors::{Broadcaster, SharedState, WorkerResult};
+use loom_core_actors::{Broadcaster, CancellationToken, SharedState, WorkerResult};
 use loom_evm_db::DatabaseLoomExt;
-use loom_node_debug_provider::DebugProviderExt;
+use loom_node_debug_provider::{DebugProviderExt, HttpCachedTransport};
 use loom_types_blockchain::{debug_trace_block, Mempool};
 use loom_types_entities::MarketState;
 use loom_types_events::{
@@ -24,12 +24,15 @@ pub async fn node_player_worker<P, DB>(
     provider: P,
     start_block: BlockNumber,
     end_block: BlockNumber,
+    block_interval: Duration,
+    transport: Option<HttpCachedTransport>,
     mempool: Option<SharedState<Mempool>>,
     market_state: Option<SharedState<MarketState<DB>>>,
     new_block_headers_channel: Option<Broadcaster<MessageBlockHeader>>,
     new_block_with_tx_channel: Option<Broadcaster<MessageBlock>>,
     new_block_logs_channel: Option<Broadcaster<MessageBlockLogs>>,
     new_block_state_update_channel: Option<Broadcaster<MessageBlockStateUpdate>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     P: Provider<Ethereum> + DebugProviderExt<Ethereum> + Send + Sync + Clone + 'static,
@@ -37,7 +40,9 @@ where
     <DB as DatabaseRef>::Error: Debug,
 {
     for curblock_number in RangeInclusive::new(start_block, end_block) {
-        //let curblock_number = provider.client().transport().fetch_next_block().await?;
+        if let Some(transport) = &transport {
+            transport.set_block_number(curblock_number);
+        }
         let block = provider.get_block_by_number(curblock_number.into(), BlockTransactionsKind::Hashes).await?;
 
         if let Some(block) = block {
@@ -176,7 +181,10 @@ where
             }
         }
 
-        tokio::time::sleep(Duration::from_millis(1000)).await;
+        tokio::select! {
+            _ = cancel_token.cancelled() => break,
+            _ = tokio::time::sleep(block_interval) => {}
+        }
     }
 
     Ok("Node block player worker finished".to_string())
@@ -0,0 +1,120 @@
+use alloy_primitives::{TxHash, I256, U256};
+use loom_evm_utils::NWETH;
+use loom_types_entities::{BundleInclusion, BundleInclusionStats, BundleInclusionStatus};
+use serde::Serialize;
+use utoipa::PartialSchema;
+use utoipa::ToSchema;
+
+fn signed_eth(value: I256) -> f64 {
+    let float_value = NWETH::to_float(value.unsigned_abs());
+    if value.is_negative() {
+        -float_value
+    } else {
+        float_value
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleStatus {
+    Pending,
+    Included,
+    Reverted,
+    Missed,
+}
+
+impl From<BundleInclusionStatus> for BundleStatus {
+    fn from(status: BundleInclusionStatus) -> Self {
+        match status {
+            BundleInclusionStatus::Pending => BundleStatus::Pending,
+            BundleInclusionStatus::Included => BundleStatus::Included,
+            BundleInclusionStatus::Reverted => BundleStatus::Reverted,
+            BundleInclusionStatus::Missed => BundleStatus::Missed,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct Bundle {
+    #[schema(schema_with = String::schema)]
+    pub tx_hash: TxHash,
+    pub target_block: u64,
+    pub status: BundleStatus,
+    pub relays: Vec<String>,
+    pub origin: Option<String>,
+    pub swap: String,
+    /// Expected profit in ETH after tips
+    pub expected_profit: f64,
+    #[schema(schema_with = String::schema)]
+    pub tips: U256,
+    /// Estimated gas cost in ETH
+    pub expected_gas_cost: f64,
+    pub included_block: Option<u64>,
+    pub tx_index: Option<usize>,
+    pub builder: Option<String>,
+    pub landed_relay: Option<String>,
+    pub gas_used: Option<u64>,
+    /// Gas cost in ETH
+    pub gas_cost: Option<f64>,
+    /// Realized profit in ETH after tips
+    pub realized_profit: Option<f64>,
+}
+
+impl From<&BundleInclusion> for Bundle {
+    fn from(record: &BundleInclusion) -> Self {
+        Self {
+            tx_hash: record.tx_hash,
+            target_block: record.target_block,
+            status: record.status.into(),
+            relays: record.relays.clone(),
+            origin: record.origin.clone(),
+            swap: record.swap.clone(),
+            expected_profit: signed_eth(record.expected_net_profit_eth()),
+            tips: record.tips,
+            expected_gas_cost: NWETH::to_float(record.expected_gas_cost),
+            included_block: record.included_block,
+            tx_index: record.tx_index,
+            builder: record.builder.clone(),
+            landed_relay: record.landed_relay.clone(),
+            gas_used: record.gas_used,
+            gas_cost: record.gas_cost.map(NWETH::to_float),
+            realized_profit: record.realized_profit_eth.map(signed_eth),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BundleStats {
+    pub pending: usize,
+    pub included: usize,
+    pub reverted: usize,
+    pub missed: usize,
+    /// Sum of expected profits in ETH of landed bundles
+    pub expected_profit: f64,
+    /// Sum of realized profits in ETH of landed bundles
+    pub realized_profit: f64,
+    pub gas_cost: f64,
+}
+
+impl From<BundleInclusionStats> for BundleStats {
+    fn from(stats: BundleInclusionStats) -> Self {
+        Self {
+            pending: stats.pending,
+            included: stats.included,
+            reverted: stats.reverted,
+            missed: stats.missed,
+            expected_profit: signed_eth(stats.expected_profit_eth),
+            realized_profit: signed_eth(stats.realized_profit_eth),
+            gas_cost: NWETH::to_float(stats.gas_cost),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct BundlesResponse {
+    pub stats: BundleStats,
+    pub pending: Vec<Bundle>,
+    /// Resolved bundles, most recent first
+    pub bundles: Vec<Bundle>,
+    pub total: usize,
+}
@@ -0,0 +1,36 @@
+use loom_core_actors::ChannelStats;
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubscriberLag {
+    pub subscriber: String,
+    pub lag: u64,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ChannelStatsResponse {
+    pub name: String,
+    pub capacity: usize,
+    pub sent: u64,
+    pub dropped: u64,
+    pub subscribers: usize,
+    pub queued: usize,
+    pub lagged: u64,
+    pub subscribers_lag: Vec<SubscriberLag>,
+}
+
+impl From<ChannelStats> for ChannelStatsResponse {
+    fn from(stats: ChannelStats) -> Self {
+        Self {
+            name: stats.name,
+            capacity: stats.capacity,
+            sent: stats.sent,
+            dropped: stats.dropped,
+            subscribers: stats.subscribers,
+            queued: stats.queued,
+            lagged: stats.lagged,
+            subscribers_lag: stats.subscribers_lag.into_iter().map(|(subscriber, lag)| SubscriberLag { subscriber, lag }).collect(),
+        }
+    }
+}
@@ -1,5 +1,6 @@
-use alloy_primitives::{Bytes, B256, U64};
-use serde::{Deserialize, Serialize};
+use alloy_primitives::{Address, Bytes, B256, U256, U64};
+use alloy_rpc_types::Log;
+use serde::{Deserialize, Serialize, Serializer};
 use utoipa::PartialSchema;
 use utoipa::ToSchema;
 
@@ -13,6 +14,16 @@ pub struct BundleParam {
     #[serde(rename = "blockNumber")]
     #[schema(schema_with = String::schema)]
     pub target_block: Option<U64>,
+
+    #[serde(rename = "stateBlockNumber")]
+    #[schema(schema_with = String::schema)]
+    pub state_block: Option<U64>,
+
+    /// Timestamp of simulated block, calculated from the target block if not set
+    pub timestamp: Option<u64>,
+
+    /// Base fee of simulated block, calculated from the latest block if not set
+    pub base_fee: Option<u64>,
     // dropped the rest of the fields
 }
 
@@ -20,9 +31,7 @@ pub struct BundleParam {
 pub struct BundleRequest {
     #[allow(dead_code)]
     pub jsonrpc: String,
-    #[allow(dead_code)]
     pub id: u64,
-    #[allow(dead_code)]
     pub method: String,
     pub params: Vec<BundleParam>,
 }
@@ -40,3 +49,82 @@ pub struct SendBundleResponse {
     pub id: u64,
     pub result: BundleResponse,
 }
+
+// Contract creation is returned as "0x" address as flashbots relays do
+fn serialize_optional_address<S: Serializer>(address: &Option<Address>, s: S) -> Result<S::Ok, S::Error> {
+    match address {
+        Some(address) => address.serialize(s),
+        None => s.serialize_str("0x"),
+    }
+}
+
+// Amounts are returned as decimal strings as flashbots relays do
+fn serialize_u256_decimal<S: Serializer>(value: &U256, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&value.to_string())
+}
+
+/// Result of a transaction simulated in a bundle, same as `eth_callBundle` result of flashbots relays
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleTransactionResult {
+    #[schema(schema_with = String::schema)]
+    pub tx_hash: B256,
+    /// Difference of coinbase balance, includes priority fees and direct payments
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub coinbase_diff: U256,
+    /// Value sent to coinbase directly
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub eth_sent_to_coinbase: U256,
+    /// Effective priority fee per gas
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub gas_price: U256,
+    pub gas_used: u64,
+    /// Priority fees paid to coinbase
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub gas_fees: U256,
+    #[schema(schema_with = String::schema)]
+    pub from_address: Address,
+    #[serde(serialize_with = "serialize_optional_address")]
+    #[schema(schema_with = String::schema)]
+    pub to_address: Option<Address>,
+    #[schema(schema_with = String::schema)]
+    pub value: Option<Bytes>,
+    pub error: Option<String>,
+    pub revert: Option<String>,
+    #[schema(value_type = Option<Vec<Object>>)]
+    pub logs: Option<Vec<Log>>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct CallBundleResult {
+    #[schema(schema_with = String::schema)]
+    pub bundle_hash: B256,
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub coinbase_diff: U256,
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub eth_sent_to_coinbase: U256,
+    /// Coinbase difference per gas
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub bundle_gas_price: U256,
+    pub total_gas_used: u64,
+    #[serde(serialize_with = "serialize_u256_decimal")]
+    #[schema(schema_with = String::schema)]
+    pub gas_fees: U256,
+    pub state_block_number: u64,
+    pub results: Vec<CallBundleTransactionResult>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CallBundleResponse {
+    pub jsonrpc: String,
+    pub id: u64,
+    pub result: CallBundleResult,
+}
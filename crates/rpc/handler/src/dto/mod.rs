@@ -1,4 +1,6 @@
 pub mod block;
+pub mod bundle;
+pub mod channel;
 pub mod flashbots;
 pub mod pagination;
 pub mod pool;
@@ -25,3 +25,46 @@ pub struct QuoteResponse {
     pub out_amount: U256,
     pub gas_used: u64,
 }
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct MarketQuoteRequest {
+    #[schema(schema_with = String::schema)]
+    pub token_address_from: Address,
+    #[schema(schema_with = String::schema)]
+    pub token_address_to: Address,
+    #[schema(schema_with = String::schema)]
+    pub amount_in: U256,
+    /// Max number of routes to return, defaults to 5
+    pub routes: Option<usize>,
+    /// Max number of pools in a route, defaults to 3
+    pub max_hops: Option<usize>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct MarketQuoteResponse {
+    pub block_number: u64,
+    pub routes: Vec<QuoteRoute>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct QuoteRoute {
+    #[schema(schema_with = String::schema)]
+    pub out_amount: U256,
+    pub gas_used: u64,
+    pub hops: Vec<QuoteHop>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct QuoteHop {
+    pub pool_id: String,
+    pub protocol: PoolProtocol,
+    #[schema(schema_with = String::schema)]
+    pub token_address_from: Address,
+    #[schema(schema_with = String::schema)]
+    pub token_address_to: Address,
+    #[schema(schema_with = String::schema)]
+    pub amount_in: U256,
+    #[schema(schema_with = String::schema)]
+    pub amount_out: U256,
+    pub gas_used: u64,
+}
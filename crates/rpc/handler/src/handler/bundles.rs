@@ -0,0 +1,36 @@
+use crate::dto::bundle::{Bundle, BundlesResponse};
+use crate::dto::pagination::Pagination;
+use axum::extract::{Query, State};
+use axum::Json;
+use loom_rpc_state::AppState;
+use revm::{DatabaseCommit, DatabaseRef};
+
+/// Get bundles
+///
+/// Get inclusion results and realized profit of broadcast bundles
+#[utoipa::path(
+    get,
+    path = "/bundles",
+    tag = "node",
+    tags = [],
+    params(
+        Pagination
+    ),
+    responses(
+    (status = 200, description = "Pending and resolved bundles", body = BundlesResponse),
+    )
+)]
+pub async fn bundles<DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static>(
+    State(app_state): State<AppState<DB>>,
+    pagination: Query<Pagination>,
+) -> Json<BundlesResponse> {
+    let bundle_inclusions = app_state.bc.bundle_inclusions();
+    let bundle_inclusions_guard = bundle_inclusions.read().await;
+
+    Json(BundlesResponse {
+        stats: bundle_inclusions_guard.stats().into(),
+        pending: bundle_inclusions_guard.pending().iter().map(Bundle::from).collect(),
+        bundles: bundle_inclusions_guard.resolved().skip(pagination.start()).take(pagination.limit).map(Bundle::from).collect(),
+        total: bundle_inclusions_guard.resolved().count(),
+    })
+}
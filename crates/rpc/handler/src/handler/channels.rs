@@ -0,0 +1,23 @@
+use crate::dto::channel::ChannelStatsResponse;
+use axum::extract::State;
+use axum::Json;
+use loom_rpc_state::AppState;
+use revm::{DatabaseCommit, DatabaseRef};
+
+/// Get channel stats
+///
+/// Get send counts, subscribers and lag of blockchain and strategy channels
+#[utoipa::path(
+    get,
+    path = "/channels",
+    tag = "node",
+    tags = [],
+    responses(
+    (status = 200, description = "Stats of all blockchain and strategy channels", body = [ChannelStatsResponse]),
+    )
+)]
+pub async fn channels<DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static>(
+    State(app_state): State<AppState<DB>>,
+) -> Json<Vec<ChannelStatsResponse>> {
+    Json(app_state.bc.channels_stats().into_iter().chain(app_state.strategy.channels_stats()).map(ChannelStatsResponse::from).collect())
+}
@@ -1,72 +1,318 @@
-use crate::dto::flashbots::{BundleRequest, BundleResponse, SendBundleResponse};
-use alloy_primitives::{hex, keccak256, U256};
+use crate::dto::flashbots::{
+    BundleParam, BundleRequest, BundleResponse, CallBundleResponse, CallBundleResult, CallBundleTransactionResult, SendBundleResponse,
+};
+use alloy_primitives::{hex, keccak256, Address, Bytes, U256};
+use alloy_rpc_types::Log;
 use axum::extract::State;
 use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
 use axum::Json;
-use loom_evm_utils::evm::evm_transact;
+use loom_evm_utils::evm::revert_bytes_to_string;
 use loom_evm_utils::evm_tx_env::env_from_signed_tx;
 use loom_rpc_state::AppState;
-use loom_types_blockchain::ChainParameters;
-use revm::primitives::{BlockEnv, Env, CANCUN};
-use revm::{DatabaseCommit, DatabaseRef, Evm};
+use revm::primitives::{BlockEnv, CfgEnv, Env, ExecutionResult, CANCUN};
+use revm::{Database, DatabaseCommit, DatabaseRef, Evm};
 use std::fmt::Debug;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub async fn flashbots<DB>(
     State(app_state): State<AppState<DB>>,
     Json(bundle_request): Json<BundleRequest>,
-) -> Result<Json<SendBundleResponse>, (StatusCode, String)>
+) -> Result<Response, (StatusCode, String)>
 where
     DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
     <DB as DatabaseRef>::Error: Debug,
 {
+    match bundle_request.method.as_str() {
+        "eth_callBundle" => call_bundle(app_state, bundle_request).await.map(|response| Json(response).into_response()),
+        _ => send_bundle(app_state, bundle_request).await.map(|response| Json(response).into_response()),
+    }
+}
+
+async fn send_bundle<DB>(app_state: AppState<DB>, bundle_request: BundleRequest) -> Result<SendBundleResponse, (StatusCode, String)>
+where
+    DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
+    <DB as DatabaseRef>::Error: Debug,
+{
+    let mut bundle_hash = None;
     for (bundle_idx, bundle_param) in bundle_request.params.iter().enumerate() {
         info!(
             "Flashbots bundle({bundle_idx}): target_block={:?}, transactions_len={:?}",
             bundle_param.target_block,
             bundle_param.transactions.len()
         );
-        let last_block_header = app_state.bc.latest_block().read().await.block_header.clone().unwrap_or_default();
-        let target_block = bundle_param.target_block.unwrap_or_default().to::<u64>();
-        if target_block <= last_block_header.number {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                format!("Target block is target_block={} <= last_block={}", target_block, last_block_header.number),
-            ));
+        let result = simulate_bundle(&app_state, bundle_param).await?;
+        for tx_result in result.results.iter() {
+            info!(
+                "Flashbots bundle({bundle_idx}) -> tx={}: gas_used={}, coinbase_diff={}, error={:?}, revert={:?}",
+                tx_result.tx_hash, tx_result.gas_used, tx_result.coinbase_diff, tx_result.error, tx_result.revert
+            );
         }
-        let next_block_timestamp = last_block_header.timestamp + 12 * (target_block - last_block_header.number);
-        let next_block_base_fee = ChainParameters::ethereum().calc_next_block_base_fee(
-            last_block_header.gas_used,
-            last_block_header.gas_limit,
-            last_block_header.base_fee_per_gas.unwrap_or_default(),
+        bundle_hash = Some(result.bundle_hash);
+    }
+
+    Ok(SendBundleResponse { jsonrpc: "2.0".to_string(), id: bundle_request.id, result: BundleResponse { bundle_hash } })
+}
+
+async fn call_bundle<DB>(app_state: AppState<DB>, bundle_request: BundleRequest) -> Result<CallBundleResponse, (StatusCode, String)>
+where
+    DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
+    <DB as DatabaseRef>::Error: Debug,
+{
+    let Some(bundle_param) = bundle_request.params.first() else {
+        return Err((StatusCode::BAD_REQUEST, "Bundle is missing in params".to_string()));
+    };
+    let result = simulate_bundle(&app_state, bundle_param).await?;
+
+    Ok(CallBundleResponse { jsonrpc: "2.0".to_string(), id: bundle_request.id, result })
+}
+
+/// Simulates bundle transactions one after another on top of the latest market state in the env of the target block
+async fn simulate_bundle<DB>(app_state: &AppState<DB>, bundle_param: &BundleParam) -> Result<CallBundleResult, (StatusCode, String)>
+where
+    DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'static,
+    <DB as DatabaseRef>::Error: Debug,
+{
+    let chain_parameters = app_state.bc.chain_parameters();
+    let last_block_header = app_state.bc.latest_block().read().await.block_header.clone().unwrap_or_default();
+    let target_block = bundle_param.target_block.map(|x| x.to::<u64>()).unwrap_or(last_block_header.number + 1);
+    if target_block <= last_block_header.number {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Target block is target_block={} <= last_block={}", target_block, last_block_header.number),
+        ));
+    }
+    if let Some(state_block) = bundle_param.state_block {
+        if state_block.to::<u64>() != last_block_header.number {
+            warn!("Simulating bundle on state of last_block={} instead of state_block={}", last_block_header.number, state_block);
+        }
+    }
+
+    let next_block_timestamp = bundle_param
+        .timestamp
+        .unwrap_or(chain_parameters.calc_block_timestamp(last_block_header.timestamp, target_block - last_block_header.number));
+    let next_block_base_fee = bundle_param.base_fee.unwrap_or(chain_parameters.calc_next_block_base_fee(
+        last_block_header.gas_used,
+        last_block_header.gas_limit,
+        last_block_header.base_fee_per_gas.unwrap_or_default(),
+    ));
+    let coinbase = last_block_header.beneficiary;
+    let basefee = U256::from(next_block_base_fee);
+
+    let evm_env = Env {
+        block: BlockEnv {
+            number: U256::from(target_block),
+            coinbase,
+            timestamp: U256::from(next_block_timestamp),
+            gas_limit: U256::from(last_block_header.gas_limit),
+            basefee,
+            prevrandao: Some(last_block_header.mix_hash),
+            ..BlockEnv::default()
+        },
+        cfg: CfgEnv::default().with_chain_id(chain_parameters.chain_id),
+        ..Env::default()
+    };
+    let db = app_state.state.market_state().read().await.state_db.clone();
+    let mut evm = Evm::builder().with_spec_id(CANCUN).with_ref_db(db).with_env(Box::new(evm_env)).build();
+
+    let mut results = Vec::new();
+    let mut log_index = 0u64;
+    for (tx_idx, tx) in bundle_param.transactions.iter().enumerate() {
+        let tx_hash = keccak256(tx);
+
+        let tx_env = env_from_signed_tx(tx.clone()).map_err(|e| (StatusCode::BAD_REQUEST, format!("Error: {}", e)))?;
+        info!("Flashbots bundle tx({tx_idx}): caller={:?}, transact_to={:?}, data={:?}, value={:?}, gas_price={:?}, gas_limit={:?}, nonce={:?}, chain_id={:?}, access_list_len={}",
+               tx_env.caller, tx_env.transact_to, tx_env.data, tx_env.value, tx_env.gas_price, tx_env.gas_limit, tx_env.nonce, tx_env.chain_id, tx_env.access_list.len());
+
+        let from_address = tx_env.caller;
+        let to_address = tx_env.transact_to.to().cloned();
+        evm.context.evm.env.tx = tx_env;
+        let gas_price = evm.context.evm.env.effective_gas_price().saturating_sub(basefee);
+
+        let coinbase_before = coinbase_balance(&mut evm, coinbase)?;
+        let execution_result = evm.transact_commit().map_err(|e| {
+            error!("Flashbots tx error latest_block={}, tx_hash={}, err={:?}", last_block_header.number, tx_hash, e);
+            (StatusCode::BAD_REQUEST, format!("Error: {:?}", e))
+        })?;
+        let coinbase_after = coinbase_balance(&mut evm, coinbase)?;
+
+        let gas_used = execution_result.gas_used();
+        let gas_fees = U256::from(gas_used) * gas_price;
+        let coinbase_diff = coinbase_after.saturating_sub(coinbase_before);
+
+        let logs: Vec<Log> = execution_result
+            .logs()
+            .iter()
+            .map(|log| {
+                let log = Log {
+                    inner: log.clone(),
+                    block_number: Some(target_block),
+                    transaction_hash: Some(tx_hash),
+                    transaction_index: Some(tx_idx as u64),
+                    log_index: Some(log_index),
+                    ..Log::default()
+                };
+                log_index += 1;
+                log
+            })
+            .collect();
+
+        let (error, revert) = match &execution_result {
+            ExecutionResult::Success { .. } => (None, None),
+            ExecutionResult::Revert { output, .. } => (Some("execution reverted".to_string()), Some(revert_bytes_to_string(output))),
+            ExecutionResult::Halt { reason, .. } => (Some(format!("{:?}", reason)), None),
+        };
+        info!("Flashbots bundle tx({tx_idx}) result={:?}, gas_used={}", execution_result.output().map(hex::encode_prefixed), gas_used);
+
+        results.push(CallBundleTransactionResult {
+            tx_hash,
+            coinbase_diff,
+            eth_sent_to_coinbase: coinbase_diff.saturating_sub(gas_fees),
+            gas_price,
+            gas_used,
+            gas_fees,
+            from_address,
+            to_address,
+            value: execution_result.output().cloned(),
+            error,
+            revert,
+            logs: Some(logs),
+        });
+    }
+
+    let bundle_hash = keccak256(results.iter().flat_map(|tx_result| tx_result.tx_hash.to_vec()).collect::<Bytes>());
+    let coinbase_diff: U256 = results.iter().map(|tx_result| tx_result.coinbase_diff).sum();
+    let total_gas_used: u64 = results.iter().map(|tx_result| tx_result.gas_used).sum();
+
+    Ok(CallBundleResult {
+        bundle_hash,
+        coinbase_diff,
+        eth_sent_to_coinbase: results.iter().map(|tx_result| tx_result.eth_sent_to_coinbase).sum(),
+        bundle_gas_price: if total_gas_used == 0 { U256::ZERO } else { coinbase_diff / U256::from(total_gas_used) },
+        total_gas_used,
+        gas_fees: results.iter().map(|tx_result| tx_result.gas_fees).sum(),
+        state_block_number: last_block_header.number,
+        results,
+    })
+}
+
+fn coinbase_balance<DB>(evm: &mut Evm<'_, (), DB>, coinbase: Address) -> Result<U256, (StatusCode, String)>
+where
+    DB: Database,
+    <DB as Database>::Error: Debug,
+{
+    let account = evm.context.evm.db.basic(coinbase).map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Error: {:?}", e)))?;
+    Ok(account.map(|account| account.balance).unwrap_or_default())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_consensus::{SignableTransaction, TxEnvelope, TxLegacy};
+    use alloy_eips::eip2718::Encodable2718;
+    use alloy_network::TxSignerSync;
+    use alloy_primitives::{TxKind, U64};
+    use alloy_rpc_types::Header;
+    use alloy_signer_local::PrivateKeySigner;
+    use diesel_async::pooled_connection::AsyncDieselConnectionManager;
+    use diesel_async::AsyncPgConnection;
+    use loom_broadcast_flashbots::client::SimulatedBundle;
+    use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
+    use loom_evm_db::LoomDB;
+    use loom_types_entities::MarketState;
+    use revm::primitives::{AccountInfo, Bytecode};
+
+    const GWEI: u128 = 1_000_000_000;
+
+    fn sign_tx(signer: &PrivateKeySigner, nonce: u64, to: Address, value: U256) -> Bytes {
+        let mut tx = TxLegacy {
+            chain_id: Some(1),
+            nonce,
+            gas_price: 2 * GWEI,
+            gas_limit: 100_000,
+            to: TxKind::Call(to),
+            value,
+            input: Bytes::new(),
+        };
+        let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+        Bytes::from(TxEnvelope::from(tx.into_signed(signature)).encoded_2718())
+    }
+
+    #[tokio::test]
+    async fn test_call_bundle() {
+        let signer = PrivateKeySigner::random();
+        let coinbase = Address::repeat_byte(0xcb);
+        let reverting_contract = Address::repeat_byte(0xee);
+        let tip = U256::from(10_000_000 * GWEI);
+
+        let mut db = LoomDB::empty();
+        db.insert_account_info(signer.address(), AccountInfo { balance: U256::from(GWEI * GWEI), ..AccountInfo::default() });
+        // PUSH1 0 PUSH1 0 REVERT
+        db.insert_account_info(
+            reverting_contract,
+            AccountInfo { code: Some(Bytecode::new_raw(Bytes::from(vec![0x60, 0x00, 0x60, 0x00, 0xfd]))), ..AccountInfo::default() },
         );
-        let evm_env = Env {
-            block: BlockEnv {
-                number: U256::from(target_block),
-                timestamp: U256::from(next_block_timestamp),
-                basefee: U256::from(next_block_base_fee),
-                ..BlockEnv::default()
+
+        let bc = Blockchain::new(1);
+        bc.latest_block().write().await.block_header = Some(Header {
+            inner: alloy_consensus::Header {
+                number: 100,
+                timestamp: 1_000_000,
+                gas_limit: 30_000_000,
+                base_fee_per_gas: Some(GWEI as u64),
+                beneficiary: coinbase,
+                ..alloy_consensus::Header::default()
             },
-            ..Env::default()
+            ..Header::default()
+        });
+
+        let app_state = AppState {
+            db: bb8::Pool::builder()
+                .build_unchecked(AsyncDieselConnectionManager::<AsyncPgConnection>::new("postgres://localhost/loom".to_string())),
+            bc,
+            state: BlockchainState::new_with_market_state(MarketState::new(db)),
+            strategy: Strategy::<LoomDB>::new(),
         };
-        let db = app_state.state.market_state().read().await.state_db.clone();
-        let mut evm = Evm::builder().with_spec_id(CANCUN).with_ref_db(db).with_env(Box::new(evm_env)).build();
-        for (tx_idx, tx) in bundle_param.transactions.iter().enumerate() {
-            let tx_hash = keccak256(tx);
 
-            let tx_env = env_from_signed_tx(tx.clone()).map_err(|e| (StatusCode::BAD_REQUEST, format!("Error: {}", e)))?;
-            info!("Flashbots bundle({bundle_idx}) -> tx({tx_idx}): caller={:?}, transact_to={:?}, data={:?}, value={:?}, gas_price={:?}, gas_limit={:?}, nonce={:?}, chain_id={:?}, access_list_len={}",
-               tx_env.caller, tx_env.transact_to, tx_env.data, tx_env.value, tx_env.gas_price, tx_env.gas_limit, tx_env.nonce, tx_env.chain_id, tx_env.access_list.len());
+        let txs = vec![sign_tx(&signer, 0, reverting_contract, U256::ZERO), sign_tx(&signer, 1, coinbase, tip)];
+        let bundle_request = BundleRequest {
+            jsonrpc: "2.0".to_string(),
+            id: 1,
+            method: "eth_callBundle".to_string(),
+            params: vec![BundleParam {
+                transactions: txs.clone(),
+                target_block: Some(U64::from(101)),
+                state_block: None,
+                timestamp: None,
+                base_fee: Some(GWEI as u64),
+            }],
+        };
 
-            evm.context.evm.env.tx = tx_env;
+        let response = flashbots(State(app_state), Json(bundle_request)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        let bundle: SimulatedBundle = serde_json::from_value(response["result"].clone()).unwrap();
 
-            let (result, gas_used) = evm_transact(&mut evm).map_err(|e| {
-                error!("Flashbot tx error latest_block={}, tx_hash={}, err={}/{:?}", last_block_header.number, tx_hash, e, e);
-                (StatusCode::BAD_REQUEST, format!("Error: {}", e))
-            })?;
-            info!("result={}, gas_used={}", hex::encode_prefixed(result), gas_used);
-        }
-    }
+        assert_eq!(bundle.simulation_block, U64::from(100));
+        assert_eq!(bundle.transactions.len(), 2);
 
-    Ok(Json(SendBundleResponse { jsonrpc: "2.0".to_string(), id: 1, result: BundleResponse { bundle_hash: None } }))
+        let reverted = bundle.find_tx(keccak256(&txs[0])).unwrap();
+        assert_eq!(reverted.error, Some("execution reverted".to_string()));
+        assert_eq!(reverted.to, Some(reverting_contract));
+        assert_eq!(reverted.gas_price, U256::from(GWEI));
+        assert_eq!(reverted.coinbase_tip, U256::ZERO);
+        assert_eq!(reverted.coinbase_diff, reverted.gas_fees);
+
+        let payment = bundle.find_tx(keccak256(&txs[1])).unwrap();
+        assert_eq!(payment.error, None);
+        assert_eq!(payment.gas_used, U256::from(21_000));
+        assert_eq!(payment.gas_fees, U256::from(21_000 * GWEI));
+        assert_eq!(payment.coinbase_tip, tip);
+        assert_eq!(payment.coinbase_diff, tip + payment.gas_fees);
+
+        assert_eq!(bundle.coinbase_tip, tip);
+        assert_eq!(bundle.coinbase_diff, tip + reverted.gas_fees + payment.gas_fees);
+        assert_eq!(bundle.gas_used, reverted.gas_used + payment.gas_used);
+    }
 }
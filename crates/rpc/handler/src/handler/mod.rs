@@ -1,4 +1,6 @@
 pub mod blocks;
+pub mod bundles;
+pub mod channels;
 pub mod flashbots;
 pub mod pools;
 pub mod ws;
@@ -1,18 +1,27 @@
 use crate::dto::pagination::Pagination;
 use crate::dto::pool::{MarketStats, Pool, PoolClass, PoolDetailsResponse, PoolProtocol, PoolResponse};
-use crate::dto::quote::{Filter, QuoteRequest, QuoteResponse};
-use alloy_primitives::Address;
+use crate::dto::quote::{Filter, MarketQuoteRequest, MarketQuoteResponse, QuoteHop, QuoteRequest, QuoteResponse, QuoteRoute};
+use alloy_primitives::{Address, U256};
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 use eyre::ErrReport;
 use loom_evm_utils::error_handler::internal_error;
+use loom_evm_utils::evm_env::env_for_block;
 use loom_rpc_state::AppState;
-use loom_types_entities::{PoolId, PoolWrapper};
+use loom_types_entities::{Market, PoolId, PoolWrapper};
 use revm::primitives::Env;
 use revm::{DatabaseCommit, DatabaseRef};
+use std::collections::HashMap;
 use std::str::FromStr;
 
+const DEFAULT_QUOTE_ROUTES: usize = 5;
+const DEFAULT_QUOTE_MAX_HOPS: usize = 3;
+const MAX_QUOTE_HOPS: usize = 4;
+/// Candidates with the largest amount of a token kept for the next hop
+const QUOTE_CANDIDATES_PER_TOKEN: usize = 8;
+const MAX_QUOTE_CALCULATIONS: usize = 20_000;
+
 /// Get latest block
 ///
 /// Get the latest block header
@@ -171,3 +180,270 @@ pub async fn pool_quote<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Se
         }
     }
 }
+
+/// Get a multi-hop quote
+///
+/// Get the best routes over all market pools for a pair of tokens, calculated against the latest block
+#[utoipa::path(
+    post,
+    path = "/quote",
+    tag = "market",
+    tags = [],
+    request_body = MarketQuoteRequest,
+    responses(
+        (status = 200, description = "Best routes sorted by out amount", body = MarketQuoteResponse),
+        (status = 503, description = "Latest block header is not received yet"),
+    )
+)]
+pub async fn market_quote<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + 'static>(
+    State(app_state): State<AppState<DB>>,
+    Json(quote_request): Json<MarketQuoteRequest>,
+) -> Result<Json<MarketQuoteResponse>, (StatusCode, String)> {
+    if quote_request.token_address_from == quote_request.token_address_to {
+        return Err((StatusCode::BAD_REQUEST, "Tokens must be different".to_string()));
+    }
+    let max_hops = quote_request.max_hops.unwrap_or(DEFAULT_QUOTE_MAX_HOPS).clamp(1, MAX_QUOTE_HOPS);
+    let routes_limit = quote_request.routes.unwrap_or(DEFAULT_QUOTE_ROUTES).max(1);
+
+    let (block_number, evm_env) = {
+        let latest_block = app_state.bc.latest_block().read().await;
+        match &latest_block.block_header {
+            Some(header) => {
+                let mut evm_env = env_for_block(header.number, header.timestamp);
+                evm_env.block.basefee = U256::from(header.base_fee_per_gas.unwrap_or_default());
+                (header.number, evm_env)
+            }
+            None => return Err((StatusCode::SERVICE_UNAVAILABLE, "No block header found".to_string())),
+        }
+    };
+
+    let token_from = quote_request.token_address_from;
+    let token_to = quote_request.token_address_to;
+    let amount_in = quote_request.amount_in;
+
+    // market and state locks are released before the search, pools are calculated in a blocking task
+    let graph = QuoteGraph::from_market(&*app_state.bc.market().read().await, &token_from, &token_to, max_hops);
+    let state_db = app_state.state.market_state().read().await.state_db.clone();
+
+    let mut routes =
+        tokio::task::spawn_blocking(move || graph.find_routes(&state_db, evm_env, &token_from, &token_to, amount_in, max_hops))
+            .await
+            .map_err(internal_error)?;
+    if routes.is_empty() {
+        return Err((StatusCode::NOT_FOUND, "No route found".to_string()));
+    }
+    routes.truncate(routes_limit);
+
+    Ok(Json(MarketQuoteResponse { block_number, routes }))
+}
+
+#[derive(Clone)]
+struct QuoteCandidate {
+    tokens: Vec<Address>,
+    pools: Vec<PoolId>,
+    hops: Vec<QuoteHop>,
+    amount: U256,
+    gas_used: u64,
+}
+
+/// Enabled pools by token of the market part reachable from `token_from` in `max_hops`, copied so the search runs without the market lock
+struct QuoteGraph {
+    token_pools: HashMap<Address, Vec<(Address, PoolWrapper)>>,
+}
+
+impl QuoteGraph {
+    fn from_market(market: &Market, token_from: &Address, token_to: &Address, max_hops: usize) -> Self {
+        let mut token_pools: HashMap<Address, Vec<(Address, PoolWrapper)>> = HashMap::new();
+        let mut layer: Vec<Address> = vec![*token_from];
+
+        for hop in 0..max_hops {
+            let is_last_hop = hop + 1 == max_hops;
+            let mut next_layer: Vec<Address> = vec![];
+
+            for current_token in layer.iter() {
+                let Some(next_tokens) = market.get_token_tokens(current_token) else {
+                    continue;
+                };
+                for next_token in next_tokens.iter() {
+                    if (is_last_hop && next_token != token_to) || next_token == token_from {
+                        continue;
+                    }
+                    let Some(pool_ids) = market.get_token_token_pools(current_token, next_token) else {
+                        continue;
+                    };
+                    let pools: Vec<(Address, PoolWrapper)> = pool_ids
+                        .iter()
+                        .filter(|pool_id| !market.is_pool_disabled(pool_id))
+                        .filter_map(|pool_id| market.get_pool(pool_id))
+                        .map(|pool| (*next_token, pool.clone()))
+                        .collect();
+                    if pools.is_empty() {
+                        continue;
+                    }
+                    token_pools.entry(*current_token).or_default().extend(pools);
+                    if next_token != token_to && !token_pools.contains_key(next_token) && !next_layer.contains(next_token) {
+                        next_layer.push(*next_token);
+                    }
+                }
+            }
+            layer = next_layer;
+        }
+
+        Self { token_pools }
+    }
+
+    /// Beam search from `token_from` to `token_to` calculating every hop for the quoted amount. At each hop only the
+    /// `QUOTE_CANDIDATES_PER_TOKEN` best candidates of a token are extended, so shallow pools are dropped before they multiply
+    /// the number of paths, and no more than `MAX_QUOTE_CALCULATIONS` pool calculations are done.
+    /// Returns routes sorted by out amount.
+    fn find_routes<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        state_db: &DB,
+        env: Env,
+        token_from: &Address,
+        token_to: &Address,
+        amount_in: U256,
+        max_hops: usize,
+    ) -> Vec<QuoteRoute> {
+        let mut routes: Vec<QuoteRoute> = vec![];
+        let mut layer: Vec<QuoteCandidate> =
+            vec![QuoteCandidate { tokens: vec![*token_from], pools: vec![], hops: vec![], amount: amount_in, gas_used: 0 }];
+        let mut calculations = 0;
+
+        'search: for hop in 0..max_hops {
+            let is_last_hop = hop + 1 == max_hops;
+            let mut next_layer: HashMap<Address, Vec<QuoteCandidate>> = HashMap::new();
+
+            for candidate in layer.iter() {
+                let current_token = *candidate.tokens.last().unwrap();
+                let Some(pools) = self.token_pools.get(&current_token) else {
+                    continue;
+                };
+                // pools to the destination token are calculated first so direct routes are not lost to the budget
+                let pools = pools
+                    .iter()
+                    .filter(|(next_token, _)| next_token == token_to)
+                    .chain(pools.iter().filter(|(next_token, _)| next_token != token_to));
+
+                for (next_token, pool) in pools {
+                    if (is_last_hop && next_token != token_to)
+                        || candidate.tokens.contains(next_token)
+                        || candidate.pools.contains(&pool.get_pool_id())
+                    {
+                        continue;
+                    }
+                    if calculations >= MAX_QUOTE_CALCULATIONS {
+                        break 'search;
+                    }
+                    calculations += 1;
+
+                    let Ok((amount_out, gas_used)) =
+                        pool.calculate_out_amount(state_db, env.clone(), &current_token, next_token, candidate.amount)
+                    else {
+                        continue;
+                    };
+                    if amount_out.is_zero() {
+                        continue;
+                    }
+
+                    let mut next_candidate = candidate.clone();
+                    next_candidate.tokens.push(*next_token);
+                    next_candidate.pools.push(pool.get_pool_id());
+                    next_candidate.hops.push(QuoteHop {
+                        pool_id: pool.get_pool_id().to_string(),
+                        protocol: PoolProtocol::from(pool.get_protocol()),
+                        token_address_from: current_token,
+                        token_address_to: *next_token,
+                        amount_in: candidate.amount,
+                        amount_out,
+                        gas_used,
+                    });
+                    next_candidate.amount = amount_out;
+                    next_candidate.gas_used += gas_used;
+
+                    if next_token == token_to {
+                        routes.push(QuoteRoute {
+                            out_amount: next_candidate.amount,
+                            gas_used: next_candidate.gas_used,
+                            hops: next_candidate.hops,
+                        });
+                    } else {
+                        next_layer.entry(*next_token).or_default().push(next_candidate);
+                    }
+                }
+            }
+
+            layer = next_layer
+                .into_values()
+                .flat_map(|mut candidates| {
+                    candidates.sort_by(|a, b| b.amount.cmp(&a.amount));
+                    candidates.truncate(QUOTE_CANDIDATES_PER_TOKEN);
+                    candidates
+                })
+                .collect();
+            if layer.is_empty() {
+                break;
+            }
+        }
+
+        routes.sort_by(|a, b| b.out_amount.cmp(&a.out_amount).then(a.gas_used.cmp(&b.gas_used)));
+        routes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use loom_types_entities::MockUniswapV2Pool;
+    use revm::db::EmptyDBTyped;
+
+    #[test]
+    fn test_find_routes() {
+        let token_a = Address::repeat_byte(0x0a);
+        let token_b = Address::repeat_byte(0x0b);
+        let token_c = Address::repeat_byte(0x0c);
+        let token_d = Address::repeat_byte(0x0d);
+        let reserve = U256::from(10).pow(U256::from(24));
+
+        let mut market = Market::default();
+        // shallow direct pool
+        market
+            .add_pool(MockUniswapV2Pool::new(
+                token_a,
+                token_b,
+                Address::repeat_byte(1),
+                reserve / U256::from(1000),
+                reserve / U256::from(1000),
+            ))
+            .unwrap();
+        // deep pools through C
+        market.add_pool(MockUniswapV2Pool::new(token_a, token_c, Address::repeat_byte(2), reserve, reserve)).unwrap();
+        market.add_pool(MockUniswapV2Pool::new(token_c, token_b, Address::repeat_byte(3), reserve, reserve)).unwrap();
+        // D is reachable in two hops only, but is not a destination
+        market.add_pool(MockUniswapV2Pool::new(token_c, token_d, Address::repeat_byte(4), reserve, reserve)).unwrap();
+
+        let state_db = EmptyDBTyped::<ErrReport>::new();
+        let amount_in = reserve / U256::from(10_000);
+
+        let graph = QuoteGraph::from_market(&market, &token_a, &token_b, 3);
+        let routes = graph.find_routes(&state_db, Env::default(), &token_a, &token_b, amount_in, 3);
+        assert_eq!(routes.len(), 2);
+        assert_eq!(routes[0].hops.len(), 2);
+        assert_eq!(routes[1].hops.len(), 1);
+        assert!(routes[0].out_amount > routes[1].out_amount);
+
+        let hops = &routes[0].hops;
+        assert_eq!((hops[0].token_address_from, hops[0].token_address_to), (token_a, token_c));
+        assert_eq!((hops[1].token_address_from, hops[1].token_address_to), (token_c, token_b));
+        assert_eq!(hops[0].amount_in, amount_in);
+        assert_eq!(hops[0].amount_out, hops[1].amount_in);
+        assert_eq!(hops[1].amount_out, routes[0].out_amount);
+        assert_eq!(hops.iter().map(|hop| hop.gas_used).sum::<u64>(), routes[0].gas_used);
+
+        let graph = QuoteGraph::from_market(&market, &token_a, &token_b, 1);
+        assert!(graph.token_pools[&token_a].iter().all(|(next_token, _)| *next_token == token_b));
+        let routes = graph.find_routes(&state_db, Env::default(), &token_a, &token_b, amount_in, 1);
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].hops[0].pool_id, PoolId::Address(Address::repeat_byte(1)).to_string());
+    }
+}
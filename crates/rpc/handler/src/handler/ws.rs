@@ -7,7 +7,6 @@ use axum::{
 use crate::dto::block::{BlockHeader, WebSocketMessage};
 use eyre::ErrReport;
 use loom_rpc_state::AppState;
-use loom_types_blockchain::ChainParameters;
 use revm::{DatabaseCommit, DatabaseRef};
 use std::net::SocketAddr;
 use tracing::{error, warn};
@@ -30,14 +29,15 @@ async fn on_upgrade<DB: DatabaseRef + DatabaseCommit + Send + Sync + Clone + 'st
     _who: SocketAddr,
     app_state: AppState<DB>,
 ) {
-    let mut receiver = app_state.bc.new_block_headers_channel().subscribe();
+    let mut receiver = app_state.bc.new_block_headers_channel().subscribe_metered(module_path!());
+    let chain_parameters = app_state.bc.chain_parameters();
 
     while let Ok(header) = receiver.recv().await {
         let ws_msg = WebSocketMessage::BlockHeader(BlockHeader {
             number: header.inner.header.number,
             timestamp: header.inner.header.timestamp,
             base_fee_per_gas: header.inner.header.base_fee_per_gas,
-            next_block_base_fee: ChainParameters::ethereum().calc_next_block_base_fee_from_header(&header.inner.header),
+            next_block_base_fee: chain_parameters.calc_next_block_base_fee_from_header(&header.inner.header),
         });
         match serde_json::to_string(&ws_msg) {
             Ok(json) => {
@@ -1,13 +1,22 @@
 use crate::dto::block::BlockHeader;
+use crate::dto::bundle::{Bundle, BundleStats, BundleStatus, BundlesResponse};
+use crate::dto::channel::{ChannelStatsResponse, SubscriberLag};
 use crate::dto::pool::MarketStats;
 use crate::dto::pool::Pool;
 use crate::dto::pool::PoolClass;
 use crate::dto::pool::PoolDetailsResponse;
 use crate::dto::pool::PoolProtocol;
 use crate::dto::pool::PoolResponse;
+use crate::dto::quote::MarketQuoteRequest;
+use crate::dto::quote::MarketQuoteResponse;
+use crate::dto::quote::QuoteHop;
 use crate::dto::quote::QuoteRequest;
 use crate::dto::quote::QuoteResponse;
+use crate::dto::quote::QuoteRoute;
 use crate::handler::blocks::__path_latest_block;
+use crate::handler::bundles::__path_bundles;
+use crate::handler::channels::__path_channels;
+use crate::handler::pools::__path_market_quote;
 use crate::handler::pools::__path_market_stats;
 use crate::handler::pools::__path_pool;
 use crate::handler::pools::__path_pool_quote;
@@ -26,11 +35,21 @@ pub struct BlockApi;
 
 #[derive(OpenApi)]
 #[openapi(
-    paths(pool, pools, pool_quote, market_stats),
+    paths(channels, bundles),
+    tags(
+        (name = "node", description = "Node")
+    ),
+    components(schemas(ChannelStatsResponse, SubscriberLag, BundlesResponse, Bundle, BundleStats, BundleStatus))
+)]
+pub struct NodeApi;
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(pool, pools, pool_quote, market_quote, market_stats),
     tags(
         (name = "market", description = "Market")
     ),
-    components(schemas(PoolResponse, PoolDetailsResponse, Pool, PoolClass, PoolProtocol, MarketStats, QuoteRequest, QuoteResponse))
+    components(schemas(PoolResponse, PoolDetailsResponse, Pool, PoolClass, PoolProtocol, MarketStats, QuoteRequest, QuoteResponse, MarketQuoteRequest, MarketQuoteResponse, QuoteRoute, QuoteHop))
 )]
 pub struct MarketApi;
 
@@ -39,6 +58,7 @@ pub struct MarketApi;
 #[openapi(
     nest(
         (path = "/api/v1/block/", api = BlockApi),
+        (path = "/api/v1", api = NodeApi),
         (path = "/api/v1/markets", api = MarketApi)
     )
 )]
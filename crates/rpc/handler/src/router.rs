@@ -1,6 +1,8 @@
 use crate::handler::blocks::latest_block;
+use crate::handler::bundles::bundles;
+use crate::handler::channels::channels;
 use crate::handler::flashbots::flashbots;
-use crate::handler::pools::{market_stats, pool, pool_quote, pools};
+use crate::handler::pools::{market_quote, market_stats, pool, pool_quote, pools};
 use crate::handler::ws::ws_handler;
 //use crate::openapi::ApiDoc;
 use axum::routing::{get, post};
@@ -17,6 +19,8 @@ pub fn router<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Sync + Send
             "/api/v1",
             Router::new()
                 .nest("/block", router_block()) // rename to node
+                .route("/channels", get(channels))
+                .route("/bundles", get(bundles))
                 .nest("/markets", router_market())
                 .nest("/flashbots", Router::new().route("/", post(flashbots))),
         )
@@ -34,5 +38,6 @@ pub fn router_market<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Sync
         .route("/pools/:address", get(pool))
         .route("/pools/:address/quote", post(pool_quote))
         .route("/pools", get(pools))
+        .route("/quote", post(market_quote))
         .route("/", get(market_stats))
 }
@@ -3,7 +3,7 @@ use axum::Router;
 use eyre::ErrReport;
 use loom_core_actors::{Actor, ActorResult, WorkerResult};
 use loom_core_actors_macros::Consumer;
-use loom_core_blockchain::{Blockchain, BlockchainState};
+use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
 use loom_rpc_state::AppState;
 use loom_storage_db::DbPool;
 use revm::{DatabaseCommit, DatabaseRef};
@@ -18,15 +18,17 @@ pub async fn start_web_server_worker<S, DB>(
     extra_router: Router<S>,
     bc: Blockchain,
     state: BlockchainState<DB>,
+    strategy: Strategy<DB>,
     db_pool: DbPool,
     shutdown_token: CancellationToken,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + Default + 'static,
     S: Clone + Send + Sync + 'static,
     Router: From<Router<S>>,
 {
-    let app_state = AppState { db: db_pool, bc, state };
+    let app_state = AppState { db: db_pool, bc, state, strategy };
     let router = router(app_state);
     let router = router.merge(extra_router);
 
@@ -37,7 +39,10 @@ where
     let listener = TcpListener::bind(host).await?;
     axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>())
         .with_graceful_shutdown(async move {
-            shutdown_token.cancelled().await;
+            tokio::select! {
+                _ = shutdown_token.cancelled() => {}
+                _ = cancel_token.cancelled() => {}
+            }
             info!("Shutting down webserver...");
         })
         .await?;
@@ -53,6 +58,7 @@ pub struct WebServerActor<S, DB: Clone + Send + Sync + 'static> {
     db_pool: DbPool,
     bc: Option<Blockchain>,
     state: Option<BlockchainState<DB>>,
+    strategy: Option<Strategy<DB>>,
 }
 
 impl<S, DB> WebServerActor<S, DB>
@@ -62,11 +68,11 @@ where
     Router: From<Router<S>>,
 {
     pub fn new(host: String, extra_router: Router<S>, db_pool: DbPool, shutdown_token: CancellationToken) -> Self {
-        Self { host, extra_router, shutdown_token, db_pool, bc: None, state: None }
+        Self { host, extra_router, shutdown_token, db_pool, bc: None, state: None, strategy: None }
     }
 
-    pub fn on_bc(self, bc: &Blockchain, state: &BlockchainState<DB>) -> Self {
-        Self { bc: Some(bc.clone()), state: Some(state.clone()), ..self }
+    pub fn on_bc(self, bc: &Blockchain, state: &BlockchainState<DB>, strategy: &Strategy<DB>) -> Self {
+        Self { bc: Some(bc.clone()), state: Some(state.clone()), strategy: Some(strategy.clone()), ..self }
     }
 }
 
@@ -76,14 +82,16 @@ where
     Router: From<Router<S>>,
     DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + Default + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::spawn(start_web_server_worker(
             self.host.clone(),
             self.extra_router.clone(),
             self.bc.clone().unwrap(),
             self.state.clone().unwrap(),
+            self.strategy.clone().unwrap(),
             self.db_pool.clone(),
             self.shutdown_token.clone(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
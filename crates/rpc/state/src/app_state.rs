@@ -1,4 +1,4 @@
-use loom_core_blockchain::{Blockchain, BlockchainState};
+use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
 use loom_storage_db::DbPool;
 use revm::{DatabaseCommit, DatabaseRef};
 
@@ -7,4 +7,5 @@ pub struct AppState<DB: DatabaseRef + DatabaseCommit + Clone + Send + Sync + 'st
     pub db: DbPool,
     pub bc: Blockchain,
     pub state: BlockchainState<DB>,
+    pub strategy: Strategy<DB>,
 }
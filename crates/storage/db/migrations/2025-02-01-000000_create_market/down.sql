@@ -0,0 +1,3 @@
+DROP TABLE swaps;
+DROP TABLE pools;
+DROP TABLE tokens;
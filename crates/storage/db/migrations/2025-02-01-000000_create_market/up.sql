@@ -0,0 +1,49 @@
+CREATE TABLE tokens
+(
+    chain_id   BIGINT    NOT NULL,
+    address    BYTEA     NOT NULL,
+    symbol     VARCHAR,
+    name       VARCHAR,
+    decimals   SMALLINT  NOT NULL,
+    basic      BOOLEAN   NOT NULL DEFAULT FALSE,
+    middle     BOOLEAN   NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT now(),
+    PRIMARY KEY (chain_id, address)
+);
+
+CREATE TABLE pools
+(
+    chain_id   BIGINT         NOT NULL,
+    pool_id    BYTEA          NOT NULL,
+    address    BYTEA          NOT NULL,
+    class      VARCHAR        NOT NULL,
+    protocol   VARCHAR        NOT NULL,
+    tokens     BYTEA[]        NOT NULL,
+    fee        NUMERIC(78, 0) NOT NULL,
+    created_at TIMESTAMP      NOT NULL DEFAULT now(),
+    PRIMARY KEY (chain_id, pool_id)
+);
+
+CREATE TABLE swaps
+(
+    id                  BIGSERIAL PRIMARY KEY,
+    chain_id            BIGINT         NOT NULL,
+    block_number        BIGINT         NOT NULL,
+    swap_type           VARCHAR        NOT NULL,
+    origin              VARCHAR,
+    pools               BYTEA[]        NOT NULL,
+    tokens              BYTEA[]        NOT NULL,
+    amount_in           NUMERIC(78, 0),
+    amount_out          NUMERIC(78, 0),
+    profit_eth          NUMERIC(78, 0) NOT NULL,
+    gas                 BIGINT         NOT NULL,
+    next_block_base_fee BIGINT         NOT NULL,
+    priority_gas_fee    BIGINT         NOT NULL,
+    tips                NUMERIC(78, 0),
+    tips_pct            INTEGER,
+    stuffing_tx_hashes  BYTEA[]        NOT NULL,
+    description         TEXT           NOT NULL,
+    created_at          TIMESTAMP      NOT NULL DEFAULT now()
+);
+
+CREATE INDEX swaps_chain_id_block_number_idx ON swaps (chain_id, block_number);
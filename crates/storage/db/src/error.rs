@@ -0,0 +1,13 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DbError {
+    #[error("Failed to get connection from pool: {0}")]
+    Connection(#[from] bb8::RunError<diesel_async::pooled_connection::PoolError>),
+    #[error("Query failed: {0}")]
+    Query(#[from] diesel::result::Error),
+    #[error("Migration failed: {0}")]
+    Migration(String),
+    #[error("Conversion failed: {0}")]
+    Conversion(String),
+}
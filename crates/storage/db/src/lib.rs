@@ -1,3 +1,20 @@
+pub use error::DbError;
+pub use market_db_loader_actor::MarketDbLoaderOneShotActor;
+pub use market_db_writer_actor::MarketDbWriterActor;
+pub use migrations::{run_migrations, MIGRATIONS};
+pub use models::{NewPoolRecord, NewSwapRecord, NewTokenRecord, PoolRecord, SwapRecord, TokenRecord};
 pub use pool::{init_db_pool, DbPool};
+pub use pool_repository::PoolRepository;
+pub use swap_repository::SwapRepository;
+pub use token_repository::TokenRepository;
 
+mod error;
+mod market_db_loader_actor;
+mod market_db_writer_actor;
+mod migrations;
+mod models;
 mod pool;
+mod pool_repository;
+pub mod schema;
+mod swap_repository;
+mod token_repository;
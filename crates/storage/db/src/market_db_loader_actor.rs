@@ -0,0 +1,106 @@
+use crate::{DbPool, PoolRepository, TokenRepository};
+use eyre::eyre;
+use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Producer, SharedState, WorkerResult};
+use loom_core_actors_macros::{Accessor, Producer};
+use loom_core_blockchain::Blockchain;
+use loom_types_entities::pool_config::PoolsLoadingConfig;
+use loom_types_entities::Market;
+use loom_types_events::LoomTask;
+use tracing::{error, info};
+
+const POOLS_PER_TASK: usize = 1000;
+
+pub async fn market_db_loader_worker(
+    chain_id: u64,
+    db_pool: DbPool,
+    pools_config: PoolsLoadingConfig,
+    market: SharedState<Market>,
+    tasks_tx: Broadcaster<LoomTask>,
+    cancel_token: CancellationToken,
+) -> WorkerResult {
+    let token_records = TokenRepository::new(db_pool.clone()).load_all(chain_id).await?;
+    let mut tokens_loaded = 0usize;
+    {
+        let mut market_guard = market.write().await;
+        for token_record in token_records {
+            match token_record.to_token() {
+                Ok(token) => {
+                    // default tokens keep their basic and middle flags
+                    if market_guard.get_token(&token.get_address()).is_none() {
+                        market_guard.add_token(token);
+                        tokens_loaded += 1;
+                    }
+                }
+                Err(error) => error!(%error, "Failed to convert token record"),
+            }
+        }
+    }
+
+    let pool_records = PoolRepository::new(db_pool).load_all(chain_id).await?;
+    let mut pools = Vec::with_capacity(pool_records.len());
+    for pool_record in pool_records {
+        match (pool_record.pool_id(), pool_record.pool_class()) {
+            (Ok(pool_id), Ok(pool_class)) => {
+                if pools_config.is_enabled(pool_class) {
+                    pools.push((pool_id, pool_class));
+                }
+            }
+            (Err(error), _) | (_, Err(error)) => error!(%error, "Failed to convert pool record"),
+        }
+    }
+    let pools_loaded = pools.len();
+
+    for chunk in pools.chunks(POOLS_PER_TASK) {
+        if cancel_token.is_cancelled() {
+            return Ok("Market db loader cancelled".to_string());
+        }
+        if let Err(error) = tasks_tx.send(LoomTask::FetchAndAddPools(chunk.to_vec())) {
+            error!(%error, "Failed to send pools to loader");
+            return Err(eyre!("POOL_LOADER_NOT_RUNNING"));
+        }
+    }
+
+    info!(tokens_loaded, pools_loaded, "Market loaded from database");
+    Ok("Market db loader finished".to_string())
+}
+
+/// Re-hydrates the market from the database. Tokens are added directly, pools are sent to `PoolLoaderActor`
+/// with `LoomTask::FetchAndAddPools`, so the pool loader must already be running.
+#[derive(Accessor, Producer)]
+pub struct MarketDbLoaderOneShotActor {
+    chain_id: u64,
+    db_pool: DbPool,
+    pools_config: PoolsLoadingConfig,
+    #[accessor]
+    market: Option<SharedState<Market>>,
+    #[producer]
+    tasks_tx: Option<Broadcaster<LoomTask>>,
+}
+
+impl MarketDbLoaderOneShotActor {
+    pub fn new(db_pool: DbPool, pools_config: PoolsLoadingConfig) -> Self {
+        Self { chain_id: 1, db_pool, pools_config, market: None, tasks_tx: None }
+    }
+
+    pub fn on_bc(self, bc: &Blockchain) -> Self {
+        Self { chain_id: bc.chain_id(), market: Some(bc.market()), tasks_tx: Some(bc.tasks_channel()), ..self }
+    }
+}
+
+impl Actor for MarketDbLoaderOneShotActor {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(market_db_loader_worker(
+            self.chain_id,
+            self.db_pool.clone(),
+            self.pools_config.clone(),
+            self.market.clone().unwrap(),
+            self.tasks_tx.clone().unwrap(),
+            cancel_token,
+        ));
+        Ok(vec![task])
+    }
+
+    fn name(&self) -> &'static str {
+        "MarketDbLoaderOneShotActor"
+    }
+}
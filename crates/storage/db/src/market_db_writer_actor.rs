@@ -0,0 +1,163 @@
+use crate::models::{NewPoolRecord, NewSwapRecord, NewTokenRecord};
+use crate::{DbPool, PoolRepository, SwapRepository, TokenRepository};
+use eyre::eyre;
+use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, SharedState, WorkerResult};
+use loom_core_actors_macros::{Accessor, Consumer};
+use loom_core_blockchain::{Blockchain, Strategy};
+use loom_types_entities::{Market, PoolId};
+use loom_types_events::{MarketEvents, MessageSwapCompose, SwapComposeMessage};
+use revm::DatabaseRef;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, error, info, warn};
+
+async fn persist_pool(
+    db_writes: &mut JoinSet<()>,
+    chain_id: u64,
+    market: &SharedState<Market>,
+    pool_repository: PoolRepository,
+    token_repository: TokenRepository,
+    pool_id: PoolId,
+) {
+    let (pool_record, token_records) = {
+        let market_guard = market.read().await;
+        let Some(pool) = market_guard.get_pool(&pool_id) else {
+            warn!(%pool_id, "Pool not found in market");
+            return;
+        };
+        // tokens that are not loaded to market have no metadata, writing placeholders would overwrite stored data
+        let token_records: Vec<NewTokenRecord> = pool
+            .get_tokens()
+            .iter()
+            .filter_map(|address| market_guard.get_token(address).map(|token| NewTokenRecord::from_token(chain_id, &token)))
+            .collect();
+        (NewPoolRecord::from_pool(chain_id, pool), token_records)
+    };
+
+    db_writes.spawn(async move {
+        if let Err(error) = token_repository.upsert(&token_records).await {
+            error!(%error, "Failed to store tokens");
+        }
+        match pool_repository.insert(&[pool_record]).await {
+            Ok(_) => debug!(%pool_id, "Pool stored"),
+            Err(error) => error!(%error, %pool_id, "Failed to store pool"),
+        }
+    });
+}
+
+pub async fn market_db_writer_worker<DB: Clone + Send + Sync + 'static>(
+    chain_id: u64,
+    db_pool: DbPool,
+    market: SharedState<Market>,
+    market_events_rx: Broadcaster<MarketEvents>,
+    swap_compose_channel_rx: Broadcaster<MessageSwapCompose<DB>>,
+    cancel_token: CancellationToken,
+) -> WorkerResult {
+    let pool_repository = PoolRepository::new(db_pool.clone());
+    let token_repository = TokenRepository::new(db_pool.clone());
+    let swap_repository = SwapRepository::new(db_pool);
+
+    subscribe!(market_events_rx);
+    subscribe!(swap_compose_channel_rx);
+
+    // pending writes are awaited on shutdown
+    let mut db_writes: JoinSet<()> = JoinSet::new();
+
+    loop {
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                info!("Waiting for {} pending db writes", db_writes.len());
+                while db_writes.join_next().await.is_some() {}
+                return Ok("Market db writer stopped".to_string());
+            }
+            Some(_) = db_writes.join_next(), if !db_writes.is_empty() => {}
+            msg = market_events_rx.recv() => {
+                match msg {
+                    Ok(MarketEvents::NewPoolLoaded { pool_id, .. }) => {
+                        persist_pool(&mut db_writes, chain_id, &market, pool_repository.clone(), token_repository.clone(), pool_id).await;
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(lag)) => warn!("Market events lagged: {}", lag),
+                    Err(RecvError::Closed) => {
+                        error!("Market events channel closed");
+                        return Err(eyre!("MARKET_EVENTS_CHANNEL_CLOSED"));
+                    }
+                }
+            }
+            msg = swap_compose_channel_rx.recv() => {
+                match msg {
+                    Ok(msg) => {
+                        if let SwapComposeMessage::Ready(swap_compose_data) = msg.inner() {
+                            let record = NewSwapRecord::from_swap_compose(chain_id, swap_compose_data);
+                            let swap_repository = swap_repository.clone();
+                            db_writes.spawn(async move {
+                                if let Err(error) = swap_repository.insert(&record).await {
+                                    error!(%error, "Failed to store swap");
+                                }
+                            });
+                        }
+                    }
+                    Err(RecvError::Lagged(lag)) => warn!("Swap compose channel lagged: {}", lag),
+                    Err(RecvError::Closed) => {
+                        error!("Swap compose channel closed");
+                        return Err(eyre!("SWAP_COMPOSE_CHANNEL_CLOSED"));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Persists every loaded pool with its tokens and every swap that reaches `Ready` state
+#[derive(Accessor, Consumer)]
+pub struct MarketDbWriterActor<DB: Clone + Send + Sync + 'static> {
+    chain_id: u64,
+    db_pool: DbPool,
+    #[accessor]
+    market: Option<SharedState<Market>>,
+    #[consumer]
+    market_events_rx: Option<Broadcaster<MarketEvents>>,
+    #[consumer]
+    swap_compose_channel_rx: Option<Broadcaster<MessageSwapCompose<DB>>>,
+}
+
+impl<DB> MarketDbWriterActor<DB>
+where
+    DB: DatabaseRef + Send + Sync + Clone + 'static,
+{
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { chain_id: 1, db_pool, market: None, market_events_rx: None, swap_compose_channel_rx: None }
+    }
+
+    pub fn on_bc(self, bc: &Blockchain, strategy: &Strategy<DB>) -> Self {
+        Self {
+            chain_id: bc.chain_id(),
+            market: Some(bc.market()),
+            market_events_rx: Some(bc.market_events_channel()),
+            swap_compose_channel_rx: Some(strategy.swap_compose_channel()),
+            ..self
+        }
+    }
+}
+
+impl<DB> Actor for MarketDbWriterActor<DB>
+where
+    DB: DatabaseRef + Send + Sync + Clone + 'static,
+{
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
+        let task = tokio::task::spawn(market_db_writer_worker(
+            self.chain_id,
+            self.db_pool.clone(),
+            self.market.clone().unwrap(),
+            self.market_events_rx.clone().unwrap(),
+            self.swap_compose_channel_rx.clone().unwrap(),
+            cancel_token,
+        ));
+        Ok(vec![task])
+    }
+
+    fn name(&self) -> &'static str {
+        "MarketDbWriterActor"
+    }
+}
@@ -0,0 +1,22 @@
+use crate::DbError;
+use diesel::Connection;
+use diesel_async::async_connection_wrapper::AsyncConnectionWrapper;
+use diesel_async::AsyncPgConnection;
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+use tracing::info;
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Applies pending migrations. Migrations are run with a blocking connection wrapper on a separate thread.
+pub async fn run_migrations(db_url: String) -> Result<(), DbError> {
+    tokio::task::spawn_blocking(move || {
+        let mut conn = AsyncConnectionWrapper::<AsyncPgConnection>::establish(&db_url).map_err(|e| DbError::Migration(e.to_string()))?;
+        let applied = conn.run_pending_migrations(MIGRATIONS).map_err(|e| DbError::Migration(e.to_string()))?;
+        for version in applied {
+            info!(%version, "Migration applied");
+        }
+        Ok(())
+    })
+    .await
+    .map_err(|e| DbError::Migration(e.to_string()))?
+}
@@ -0,0 +1,275 @@
+use crate::schema::{pools, swaps, tokens};
+use crate::DbError;
+use alloy_primitives::{Address, B256, U256};
+use bigdecimal::BigDecimal;
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use loom_types_entities::{PoolClass, PoolId, PoolWrapper, Swap, SwapAmountType, Token};
+use loom_types_events::SwapComposeData;
+use std::str::FromStr;
+
+pub(crate) fn u256_to_numeric(value: U256) -> BigDecimal {
+    BigDecimal::from_str(&value.to_string()).unwrap_or_default()
+}
+
+pub(crate) fn numeric_to_u256(value: &BigDecimal) -> Result<U256, DbError> {
+    let (digits, _) = value.with_scale(0).into_bigint_and_exponent();
+    U256::from_str(&digits.to_string()).map_err(|e| DbError::Conversion(e.to_string()))
+}
+
+pub(crate) fn bytes_to_address(bytes: &[u8]) -> Result<Address, DbError> {
+    Address::try_from(bytes).map_err(|e| DbError::Conversion(e.to_string()))
+}
+
+pub(crate) fn pool_id_to_bytes(pool_id: &PoolId) -> Vec<u8> {
+    match pool_id {
+        PoolId::Address(address) => address.to_vec(),
+        PoolId::Bytes32(bytes) => bytes.to_vec(),
+    }
+}
+
+pub(crate) fn bytes_to_pool_id(bytes: &[u8]) -> Result<PoolId, DbError> {
+    match bytes.len() {
+        20 => Ok(PoolId::Address(Address::from_slice(bytes))),
+        32 => Ok(PoolId::Bytes32(B256::from_slice(bytes))),
+        len => Err(DbError::Conversion(format!("invalid pool id length {len}"))),
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = tokens, check_for_backend(diesel::pg::Pg))]
+pub struct TokenRecord {
+    pub chain_id: i64,
+    pub address: Vec<u8>,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: i16,
+    pub basic: bool,
+    pub middle: bool,
+    pub created_at: NaiveDateTime,
+}
+
+impl TokenRecord {
+    pub fn to_token(&self) -> Result<Token, DbError> {
+        Ok(Token::new_with_data(
+            bytes_to_address(&self.address)?,
+            self.symbol.clone(),
+            self.name.clone(),
+            Some(self.decimals as u8),
+            self.basic,
+            self.middle,
+        ))
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = tokens)]
+pub struct NewTokenRecord {
+    pub chain_id: i64,
+    pub address: Vec<u8>,
+    pub symbol: Option<String>,
+    pub name: Option<String>,
+    pub decimals: i16,
+    pub basic: bool,
+    pub middle: bool,
+}
+
+impl NewTokenRecord {
+    pub fn from_token(chain_id: u64, token: &Token) -> Self {
+        // symbol and name fall back to address when they are not fetched
+        let address = token.get_address().to_string();
+        NewTokenRecord {
+            chain_id: chain_id as i64,
+            address: token.get_address().to_vec(),
+            symbol: Some(token.get_symbol()).filter(|symbol| *symbol != address),
+            name: Some(token.get_name()).filter(|name| *name != address),
+            decimals: token.get_decimals() as i16,
+            basic: token.is_basic(),
+            middle: token.is_middle(),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = pools, check_for_backend(diesel::pg::Pg))]
+pub struct PoolRecord {
+    pub chain_id: i64,
+    pub pool_id: Vec<u8>,
+    pub address: Vec<u8>,
+    pub class: String,
+    pub protocol: String,
+    pub tokens: Vec<Vec<u8>>,
+    pub fee: BigDecimal,
+    pub created_at: NaiveDateTime,
+}
+
+impl PoolRecord {
+    pub fn pool_id(&self) -> Result<PoolId, DbError> {
+        bytes_to_pool_id(&self.pool_id)
+    }
+
+    pub fn pool_class(&self) -> Result<PoolClass, DbError> {
+        PoolClass::from_str(&self.class).map_err(|e| DbError::Conversion(e.to_string()))
+    }
+
+    pub fn tokens(&self) -> Result<Vec<Address>, DbError> {
+        self.tokens.iter().map(|token| bytes_to_address(token)).collect()
+    }
+
+    pub fn fee(&self) -> Result<U256, DbError> {
+        numeric_to_u256(&self.fee)
+    }
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = pools)]
+pub struct NewPoolRecord {
+    pub chain_id: i64,
+    pub pool_id: Vec<u8>,
+    pub address: Vec<u8>,
+    pub class: String,
+    pub protocol: String,
+    pub tokens: Vec<Vec<u8>>,
+    pub fee: BigDecimal,
+}
+
+impl NewPoolRecord {
+    pub fn from_pool(chain_id: u64, pool: &PoolWrapper) -> Self {
+        NewPoolRecord {
+            chain_id: chain_id as i64,
+            pool_id: pool_id_to_bytes(&pool.get_pool_id()),
+            address: pool.get_address().to_vec(),
+            class: pool.get_class().to_string(),
+            protocol: pool.get_protocol().to_string(),
+            tokens: pool.get_tokens().iter().map(|token| token.to_vec()).collect(),
+            fee: u256_to_numeric(pool.get_fee()),
+        }
+    }
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = swaps, check_for_backend(diesel::pg::Pg))]
+pub struct SwapRecord {
+    pub id: i64,
+    pub chain_id: i64,
+    pub block_number: i64,
+    pub swap_type: String,
+    pub origin: Option<String>,
+    pub pools: Vec<Vec<u8>>,
+    pub tokens: Vec<Vec<u8>>,
+    pub amount_in: Option<BigDecimal>,
+    pub amount_out: Option<BigDecimal>,
+    pub profit_eth: BigDecimal,
+    pub gas: i64,
+    pub next_block_base_fee: i64,
+    pub priority_gas_fee: i64,
+    pub tips: Option<BigDecimal>,
+    pub tips_pct: Option<i32>,
+    pub stuffing_tx_hashes: Vec<Vec<u8>>,
+    pub description: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Insertable, Debug, Clone)]
+#[diesel(table_name = swaps)]
+pub struct NewSwapRecord {
+    pub chain_id: i64,
+    pub block_number: i64,
+    pub swap_type: String,
+    pub origin: Option<String>,
+    pub pools: Vec<Vec<u8>>,
+    pub tokens: Vec<Vec<u8>>,
+    pub amount_in: Option<BigDecimal>,
+    pub amount_out: Option<BigDecimal>,
+    pub profit_eth: BigDecimal,
+    pub gas: i64,
+    pub next_block_base_fee: i64,
+    pub priority_gas_fee: i64,
+    pub tips: Option<BigDecimal>,
+    pub tips_pct: Option<i32>,
+    pub stuffing_tx_hashes: Vec<Vec<u8>>,
+    pub description: String,
+}
+
+fn swap_type_name(swap: &Swap) -> &'static str {
+    match swap {
+        Swap::None => "None",
+        Swap::ExchangeSwapLine(_) => "ExchangeSwapLine",
+        Swap::BackrunSwapSteps(_) => "BackrunSwapSteps",
+        Swap::BackrunSwapLine(_) => "BackrunSwapLine",
+        Swap::Multiple(_) => "Multiple",
+    }
+}
+
+fn set_amount_to_numeric(amount: &SwapAmountType) -> Option<BigDecimal> {
+    match amount {
+        SwapAmountType::Set(value) => Some(u256_to_numeric(*value)),
+        _ => None,
+    }
+}
+
+impl NewSwapRecord {
+    pub fn from_swap_compose<DB>(chain_id: u64, swap_compose: &SwapComposeData<DB>) -> Self {
+        let swap = &swap_compose.swap;
+        let (tokens, amount_in, amount_out) = match swap {
+            Swap::ExchangeSwapLine(swap_line) | Swap::BackrunSwapLine(swap_line) => (
+                swap_line.tokens().iter().map(|token| token.get_address().to_vec()).collect(),
+                set_amount_to_numeric(&swap_line.amount_in),
+                set_amount_to_numeric(&swap_line.amount_out),
+            ),
+            _ => (swap.get_first_token().map(|token| vec![token.get_address().to_vec()]).unwrap_or_default(), None, None),
+        };
+
+        NewSwapRecord {
+            chain_id: chain_id as i64,
+            block_number: swap_compose.tx_compose.next_block_number as i64,
+            swap_type: swap_type_name(swap).to_string(),
+            origin: swap_compose.origin.clone(),
+            pools: swap.get_pool_id_vec().iter().map(pool_id_to_bytes).collect(),
+            tokens,
+            amount_in,
+            amount_out,
+            profit_eth: u256_to_numeric(swap.abs_profit_eth()),
+            gas: swap_compose.tx_compose.gas as i64,
+            next_block_base_fee: swap_compose.tx_compose.next_block_base_fee as i64,
+            priority_gas_fee: swap_compose.tx_compose.priority_gas_fee as i64,
+            tips: swap_compose.tips.map(u256_to_numeric),
+            tips_pct: swap_compose.tips_pct.map(|tips_pct| tips_pct as i32),
+            stuffing_tx_hashes: swap_compose.tx_compose.stuffing_txs_hashes.iter().map(|hash| hash.to_vec()).collect(),
+            description: swap.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_numeric_roundtrip() {
+        for value in [U256::ZERO, U256::from(1000), U256::from(10).pow(U256::from(18)), U256::MAX] {
+            assert_eq!(numeric_to_u256(&u256_to_numeric(value)).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn test_pool_id_roundtrip() {
+        let address_pool_id = PoolId::Address(Address::repeat_byte(1));
+        let bytes_pool_id = PoolId::Bytes32(B256::repeat_byte(2));
+        assert_eq!(bytes_to_pool_id(&pool_id_to_bytes(&address_pool_id)).unwrap(), address_pool_id);
+        assert_eq!(bytes_to_pool_id(&pool_id_to_bytes(&bytes_pool_id)).unwrap(), bytes_pool_id);
+        assert!(bytes_to_pool_id(&[0u8; 4]).is_err());
+    }
+
+    #[test]
+    fn test_token_record_without_metadata() {
+        let record = NewTokenRecord::from_token(1, &Token::new(Address::repeat_byte(1)));
+        assert_eq!(record.symbol, None);
+        assert_eq!(record.name, None);
+
+        let token = Token::new_with_data(Address::repeat_byte(1), Some("WETH".to_string()), None, Some(18), true, false);
+        let record = NewTokenRecord::from_token(1, &token);
+        assert_eq!(record.symbol, Some("WETH".to_string()));
+        assert_eq!(record.name, None);
+    }
+}
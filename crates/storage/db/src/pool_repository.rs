@@ -0,0 +1,50 @@
+use crate::models::{NewPoolRecord, PoolRecord};
+use crate::schema::pools;
+use crate::{DbError, DbPool};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+#[derive(Clone)]
+pub struct PoolRepository {
+    db_pool: DbPool,
+}
+
+impl PoolRepository {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Inserts pools, pools that are already stored are skipped
+    pub async fn insert(&self, records: &[NewPoolRecord]) -> Result<usize, DbError> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+        let mut conn = self.db_pool.get().await?;
+        let rows = diesel::insert_into(pools::table)
+            .values(records)
+            .on_conflict((pools::chain_id, pools::pool_id))
+            .do_nothing()
+            .execute(&mut conn)
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn load_all(&self, chain_id: u64) -> Result<Vec<PoolRecord>, DbError> {
+        let mut conn = self.db_pool.get().await?;
+        let records = pools::table
+            .filter(pools::chain_id.eq(chain_id as i64))
+            .order(pools::created_at.asc())
+            .select(PoolRecord::as_select())
+            .load(&mut conn)
+            .await?;
+        Ok(records)
+    }
+
+    pub async fn delete(&self, chain_id: u64, pool_id: Vec<u8>) -> Result<usize, DbError> {
+        let mut conn = self.db_pool.get().await?;
+        let rows = diesel::delete(pools::table.filter(pools::chain_id.eq(chain_id as i64)).filter(pools::pool_id.eq(pool_id)))
+            .execute(&mut conn)
+            .await?;
+        Ok(rows)
+    }
+}
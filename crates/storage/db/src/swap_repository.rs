@@ -0,0 +1,47 @@
+use crate::models::{NewSwapRecord, SwapRecord};
+use crate::schema::swaps;
+use crate::{DbError, DbPool};
+use diesel::prelude::*;
+use diesel_async::RunQueryDsl;
+
+#[derive(Clone)]
+pub struct SwapRepository {
+    db_pool: DbPool,
+}
+
+impl SwapRepository {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Stores swap and returns its id
+    pub async fn insert(&self, record: &NewSwapRecord) -> Result<i64, DbError> {
+        let mut conn = self.db_pool.get().await?;
+        let id = diesel::insert_into(swaps::table).values(record).returning(swaps::id).get_result(&mut conn).await?;
+        Ok(id)
+    }
+
+    pub async fn load_by_block(&self, chain_id: u64, block_number: u64) -> Result<Vec<SwapRecord>, DbError> {
+        let mut conn = self.db_pool.get().await?;
+        let records = swaps::table
+            .filter(swaps::chain_id.eq(chain_id as i64))
+            .filter(swaps::block_number.eq(block_number as i64))
+            .order(swaps::id.asc())
+            .select(SwapRecord::as_select())
+            .load(&mut conn)
+            .await?;
+        Ok(records)
+    }
+
+    pub async fn load_latest(&self, chain_id: u64, limit: i64) -> Result<Vec<SwapRecord>, DbError> {
+        let mut conn = self.db_pool.get().await?;
+        let records = swaps::table
+            .filter(swaps::chain_id.eq(chain_id as i64))
+            .order(swaps::id.desc())
+            .limit(limit)
+            .select(SwapRecord::as_select())
+            .load(&mut conn)
+            .await?;
+        Ok(records)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::models::{NewTokenRecord, TokenRecord};
+use crate::schema::tokens;
+use crate::{DbError, DbPool};
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Varchar};
+use diesel::upsert::excluded;
+use diesel_async::RunQueryDsl;
+
+define_sql_function! {
+    fn coalesce(x: Nullable<Varchar>, y: Nullable<Varchar>) -> Nullable<Varchar>;
+}
+
+#[derive(Clone)]
+pub struct TokenRepository {
+    db_pool: DbPool,
+}
+
+impl TokenRepository {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Inserts tokens or refreshes metadata of already stored ones, missing symbol and name keep stored values
+    pub async fn upsert(&self, records: &[NewTokenRecord]) -> Result<usize, DbError> {
+        if records.is_empty() {
+            return Ok(0);
+        }
+        let mut conn = self.db_pool.get().await?;
+        let rows = diesel::insert_into(tokens::table)
+            .values(records)
+            .on_conflict((tokens::chain_id, tokens::address))
+            .do_update()
+            .set((
+                tokens::symbol.eq(coalesce(excluded(tokens::symbol), tokens::symbol)),
+                tokens::name.eq(coalesce(excluded(tokens::name), tokens::name)),
+                tokens::decimals.eq(excluded(tokens::decimals)),
+                tokens::basic.eq(excluded(tokens::basic)),
+                tokens::middle.eq(excluded(tokens::middle)),
+            ))
+            .execute(&mut conn)
+            .await?;
+        Ok(rows)
+    }
+
+    pub async fn load_all(&self, chain_id: u64) -> Result<Vec<TokenRecord>, DbError> {
+        let mut conn = self.db_pool.get().await?;
+        let records = tokens::table.filter(tokens::chain_id.eq(chain_id as i64)).select(TokenRecord::as_select()).load(&mut conn).await?;
+        Ok(records)
+    }
+}
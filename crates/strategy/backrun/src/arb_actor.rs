@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::marker::PhantomData;
 
 use alloy_network::Network;
@@ -8,8 +9,9 @@ use revm::{Database, DatabaseCommit, DatabaseRef};
 use tokio::task::JoinHandle;
 use tracing::info;
 
-use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
+use loom_evm_db::DatabaseLoomExt;
 use loom_node_debug_provider::DebugProviderExt;
 use loom_types_blockchain::Mempool;
 use loom_types_entities::{BlockHistory, LatestBlock, Market, MarketState};
@@ -17,7 +19,7 @@ use loom_types_events::{MarketEvents, MempoolEvents, MessageHealthEvent, Message
 
 use super::{PendingTxStateChangeProcessorActor, StateChangeArbSearcherActor};
 use crate::block_state_change_processor::BlockStateChangeProcessorActor;
-use crate::BackrunConfig;
+use crate::{BackrunConfig, PendingTxSimulation};
 
 #[derive(Accessor, Consumer, Producer)]
 pub struct StateChangeArbActor<P, N, DB: Clone + Send + Sync + 'static> {
@@ -25,6 +27,7 @@ pub struct StateChangeArbActor<P, N, DB: Clone + Send + Sync + 'static> {
     client: P,
     use_blocks: bool,
     use_mempool: bool,
+    mempool_simulation: HashMap<String, PendingTxSimulation>,
     #[accessor]
     market: Option<SharedState<Market>>,
     #[accessor]
@@ -61,6 +64,7 @@ where
             client,
             use_blocks,
             use_mempool,
+            mempool_simulation: HashMap::new(),
             market: None,
             mempool: None,
             latest_block: None,
@@ -74,15 +78,28 @@ where
             _n: PhantomData,
         }
     }
+
+    /// Simulation backend for pending txs of each mempool source
+    pub fn with_mempool_simulation(self, mempool_simulation: HashMap<String, PendingTxSimulation>) -> Self {
+        Self { mempool_simulation, ..self }
+    }
 }
 
 impl<P, N, DB> Actor for StateChangeArbActor<P, N, DB>
 where
     N: Network,
     P: Provider<N> + DebugProviderExt<N> + Send + Sync + Clone + 'static,
-    DB: DatabaseRef<Error = ErrReport> + Database<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + Default + 'static,
+    DB: DatabaseRef<Error = ErrReport>
+        + Database<Error = ErrReport>
+        + DatabaseCommit
+        + DatabaseLoomExt
+        + Send
+        + Sync
+        + Clone
+        + Default
+        + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let searcher_pool_update_channel = Broadcaster::new(100);
         let mut tasks: Vec<JoinHandle<WorkerResult>> = Vec::new();
 
@@ -93,7 +110,7 @@ where
             .produce(self.compose_channel_tx.clone().unwrap())
             .produce(self.pool_health_monitor_tx.clone().unwrap())
             .produce(self.influxdb_write_channel_tx.clone().unwrap())
-            .start()
+            .start_with_cancel(cancel_token.clone())
         {
             Err(e) => {
                 panic!("{}", e)
@@ -105,7 +122,8 @@ where
         }
 
         if self.mempool_events_tx.is_some() && self.use_mempool {
-            let mut pending_tx_state_processor = PendingTxStateChangeProcessorActor::new(self.client.clone());
+            let mut pending_tx_state_processor =
+                PendingTxStateChangeProcessorActor::new(self.client.clone()).with_simulation(self.mempool_simulation.clone());
             match pending_tx_state_processor
                 .access(self.mempool.clone().unwrap())
                 .access(self.latest_block.clone().unwrap())
@@ -114,7 +132,7 @@ where
                 .consume(self.mempool_events_tx.clone().unwrap())
                 .consume(self.market_events_tx.clone().unwrap())
                 .produce(searcher_pool_update_channel.clone())
-                .start()
+                .start_with_cancel(cancel_token.clone())
             {
                 Err(e) => {
                     panic!("{}", e)
@@ -133,7 +151,7 @@ where
                 .access(self.block_history.clone().unwrap())
                 .consume(self.market_events_tx.clone().unwrap())
                 .produce(searcher_pool_update_channel.clone())
-                .start()
+                .start_with_cancel(cancel_token.clone())
             {
                 Err(e) => {
                     panic!("{}", e)
@@ -1,6 +1,10 @@
 use alloy_primitives::Address;
+use eyre::Result;
 use loom_types_entities::strategy_config::StrategyConfig;
+use loom_types_entities::tips::{TipsPolicy, TipsPolicyConfig};
+use loom_types_entities::SwapOptimizerKind;
 use serde::Deserialize;
+use std::sync::{Arc, OnceLock};
 
 #[derive(Clone, Deserialize, Debug)]
 pub struct BackrunConfigSection {
@@ -11,6 +15,13 @@ pub struct BackrunConfigSection {
 pub struct BackrunConfig {
     eoa: Option<Address>,
     smart: bool,
+    #[serde(default)]
+    optimizer: SwapOptimizerKind,
+    #[serde(default)]
+    tips: Option<TipsPolicyConfig>,
+    /// Policy built from `tips`, shared by clones so observed bids reach every user
+    #[serde(skip)]
+    tips_policy: Arc<OnceLock<Option<Arc<dyn TipsPolicy>>>>,
 }
 
 impl StrategyConfig for BackrunConfig {
@@ -24,13 +35,27 @@ impl BackrunConfig {
         self.smart
     }
 
+    pub fn optimizer(&self) -> SwapOptimizerKind {
+        self.optimizer
+    }
+
+    /// Tips policy for composed swaps, None keeps the encoder default.
+    /// Policy is built once, all clones of the config return the same instance.
+    pub fn tips_policy(&self) -> Result<Option<Arc<dyn TipsPolicy>>> {
+        if let Some(tips_policy) = self.tips_policy.get() {
+            return Ok(tips_policy.clone());
+        }
+        let tips_policy = self.tips.as_ref().map(|tips| tips.build()).transpose()?;
+        Ok(self.tips_policy.get_or_init(|| tips_policy).clone())
+    }
+
     pub fn new_dumb() -> Self {
-        Self { eoa: None, smart: false }
+        Self { eoa: None, smart: false, optimizer: SwapOptimizerKind::default(), tips: None, tips_policy: Default::default() }
     }
 }
 
 impl Default for BackrunConfig {
     fn default() -> Self {
-        Self { eoa: None, smart: true }
+        Self { eoa: None, smart: true, optimizer: SwapOptimizerKind::default(), tips: None, tips_policy: Default::default() }
     }
 }
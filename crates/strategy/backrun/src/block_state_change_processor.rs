@@ -1,6 +1,8 @@
 use super::affected_pools_state::get_affected_pools_from_state_update;
 use eyre::eyre;
-use loom_core_actors::{run_sync, subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    run_sync, subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
 use loom_types_blockchain::ChainParameters;
@@ -17,11 +19,16 @@ pub async fn block_state_change_worker<DB: DatabaseRef + Send + Sync + Clone + '
     block_history: SharedState<BlockHistory<DB>>,
     market_events_rx: Broadcaster<MarketEvents>,
     state_updates_broadcaster: Broadcaster<StateUpdateEvent<DB, LoomDataTypesEthereum>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(market_events_rx);
 
     loop {
-        let market_event = match market_events_rx.recv().await {
+        let market_event = tokio::select! {
+            _ = cancel_token.cancelled() => break Ok("BlockStateChangeWorker stopped".to_string()),
+            market_event = market_events_rx.recv() => market_event,
+        };
+        let market_event = match market_event {
             Ok(market_event) => market_event,
             Err(e) => match e {
                 RecvError::Closed => {
@@ -77,7 +84,8 @@ pub async fn block_state_change_worker<DB: DatabaseRef + Send + Sync + Clone + '
             Vec::new(),
             "block_searcher".to_string(),
             90_00,
-        );
+        )
+        .with_parent_block_hash(block_hash);
         run_sync!(state_updates_broadcaster.send(request));
     }
 }
@@ -124,13 +132,14 @@ impl<DB: DatabaseRef + Send + Sync + Clone + 'static> Default for BlockStateChan
 }
 
 impl<DB: DatabaseRef + Send + Sync + Clone + 'static> Actor for BlockStateChangeProcessorActor<DB> {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(block_state_change_worker(
             self.chain_parameters.clone(),
             self.market.clone().unwrap(),
             self.block_history.clone().unwrap(),
             self.market_events_rx.clone().unwrap(),
             self.state_updates_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -1,11 +1,13 @@
 pub use arb_actor::StateChangeArbActor;
 pub use backrun_config::{BackrunConfig, BackrunConfigSection};
 pub use block_state_change_processor::BlockStateChangeProcessorActor;
+pub use pending_tx_simulation::PendingTxSimulation;
 pub use pending_tx_state_change_processor::PendingTxStateChangeProcessorActor;
 pub use state_change_arb_searcher::StateChangeArbSearcherActor;
 pub use swap_calculator::SwapCalculator;
 
 mod block_state_change_processor;
+mod pending_tx_simulation;
 mod pending_tx_state_change_processor;
 mod state_change_arb_searcher;
 
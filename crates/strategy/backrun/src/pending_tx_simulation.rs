@@ -0,0 +1,142 @@
+use alloy_eips::BlockNumberOrTag;
+use alloy_network::Network;
+use alloy_primitives::{Address, BlockNumber, U256};
+use alloy_provider::Provider;
+use alloy_rpc_types::Transaction;
+use eyre::{eyre, Result};
+use revm::primitives::{BlockEnv, Env, CANCUN};
+use revm::{DatabaseRef, Evm};
+use serde::Deserialize;
+use tracing::error;
+
+use loom_evm_db::{AlloyDB, DatabaseLoomExt};
+use loom_evm_utils::evm::convert_evm_state_to_geth_diff;
+use loom_evm_utils::evm_tx_env::tx_to_evm_tx;
+use loom_types_blockchain::GethStateUpdate;
+
+/// Backend used to get state changes of pending transactions
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PendingTxSimulation {
+    /// `debug_traceCall` with prestate tracer in diff mode on the node
+    #[default]
+    Trace,
+    /// Execution in revm on top of market state, missing state is fetched from the node
+    Local,
+}
+
+/// Executes pending tx on top of market state and returns pre and post state like prestate tracer in diff mode.
+/// Accounts and slots that are not in the market state are fetched from latest block of the node.
+/// Missing state is fetched with `block_in_place`, so it must be called on multi-thread tokio runtime.
+/// On current-thread runtime it returns `EXT_DB_NOT_CREATED` error instead of panicking.
+#[allow(clippy::too_many_arguments)]
+pub fn local_call_diff<P, N, DB>(
+    client: P,
+    state_db: DB,
+    tx: &Transaction,
+    coinbase: Address,
+    block_number: BlockNumber,
+    block_time: u64,
+    next_base_fee: u64,
+) -> Result<(GethStateUpdate, GethStateUpdate)>
+where
+    N: Network,
+    P: Provider<N> + Send + Sync + Clone + 'static,
+    DB: DatabaseRef + DatabaseLoomExt,
+    <DB as DatabaseRef>::Error: std::fmt::Debug,
+{
+    let mut state_db = state_db;
+    // AlloyDB is not created on current-thread runtime where block_in_place panics
+    let ext_db = AlloyDB::new(client, BlockNumberOrTag::Latest.into()).ok_or_else(|| eyre!("EXT_DB_NOT_CREATED"))?;
+    state_db.with_ext_db(ext_db);
+
+    let mut tx_env = tx_to_evm_tx(tx);
+    // same as debug_traceCall, do not check nonce and keep underpriced txs executable in the next block
+    tx_env.nonce = None;
+    if tx_env.gas_price < U256::from(next_base_fee) {
+        tx_env.gas_price = U256::from(next_base_fee);
+    }
+
+    let mut env = Env {
+        block: BlockEnv {
+            number: U256::from(block_number),
+            coinbase,
+            timestamp: U256::from(block_time),
+            basefee: U256::from(next_base_fee),
+            ..BlockEnv::default()
+        },
+        tx: tx_env,
+        ..Env::default()
+    };
+    if let Some(chain_id) = env.tx.chain_id {
+        env.cfg.chain_id = chain_id;
+    }
+
+    let result_and_state = {
+        let mut evm = Evm::builder().with_spec_id(CANCUN).with_ref_db(&state_db).with_env(Box::new(env)).build();
+        evm.transact().map_err(|error| {
+            error!(?error, "local_call_diff evm.transact");
+            eyre!("TRANSACT_ERROR")
+        })?
+    };
+
+    convert_evm_state_to_geth_diff(&state_db, result_and_state.state)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_rpc_types::{BlockOverrides, BlockTransactionsKind};
+    use alloy_rpc_types_trace::geth::GethDebugTracingCallOptions;
+    use loom_evm_db::LoomDBType;
+    use loom_node_debug_provider::AnvilDebugProviderFactory;
+    use loom_types_blockchain::{debug_trace_call_diff, TRACING_CALL_OPTS};
+    use std::env;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_local_call_diff() -> Result<()> {
+        let node_url = env::var("MAINNET_WS")?;
+
+        let client = AnvilDebugProviderFactory::from_node_on_block(node_url, 20045799).await?;
+
+        // first tx of the next block is executed on top of the forked block state
+        let block = client.node().get_block_by_number(BlockNumberOrTag::Number(20045800), BlockTransactionsKind::Full).await?.unwrap();
+        let tx = block.transactions.as_transactions().unwrap()[0].clone();
+        let next_base_fee = block.header.base_fee_per_gas.unwrap_or_default();
+
+        let (local_pre, local_post) = local_call_diff(
+            client.clone(),
+            LoomDBType::default(),
+            &tx,
+            block.header.beneficiary,
+            block.header.number,
+            block.header.timestamp,
+            next_base_fee,
+        )?;
+
+        let call_opts = GethDebugTracingCallOptions {
+            block_overrides: Some(BlockOverrides {
+                number: Some(U256::from(block.header.number)),
+                time: Some(block.header.timestamp),
+                coinbase: Some(block.header.beneficiary),
+                base_fee: Some(U256::from(next_base_fee)),
+                ..Default::default()
+            }),
+            ..TRACING_CALL_OPTS.clone()
+        };
+        let (trace_pre, trace_post) =
+            debug_trace_call_diff(client.clone(), tx.clone().into_request(), BlockNumberOrTag::Latest.into(), Some(call_opts)).await?;
+
+        // storage changes are compared, prestate tracer does not keep slots cleared to zero in post state
+        for (address, trace_account) in trace_pre.iter() {
+            assert_eq!(local_pre.get(address).map(|account| &account.storage), Some(&trace_account.storage), "pre {address}");
+        }
+        for (address, local_account) in local_post.iter() {
+            let local_storage: Vec<_> = local_account.storage.iter().filter(|(_, value)| !value.is_zero()).collect();
+            let trace_storage: Vec<_> = trace_post.get(address).map(|account| account.storage.iter().collect()).unwrap_or_default();
+            assert_eq!(local_storage, trace_storage, "post {address}");
+        }
+
+        Ok(())
+    }
+}
@@ -17,9 +17,12 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, warn};
 
-use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, BlockchainState, Strategy};
+use loom_evm_db::DatabaseLoomExt;
 use loom_node_debug_provider::DebugProviderExt;
 use loom_types_blockchain::{debug_trace_call_diff, GethStateUpdateVec, Mempool, TRACING_CALL_OPTS};
 use loom_types_entities::required_state::{accounts_vec_len, storage_vec_len};
@@ -28,6 +31,7 @@ use loom_types_events::{MarketEvents, MempoolEvents, StateUpdateEvent};
 
 use super::affected_pools_code::{get_affected_pools_from_code, is_pool_code};
 use super::affected_pools_state::get_affected_pools_from_state_update;
+use super::pending_tx_simulation::{local_call_diff, PendingTxSimulation};
 
 lazy_static! {
     static ref COINBASE: Address = "0x1f9090aaE28b8a3dCeaDf281B0F12828e676c326".parse().unwrap();
@@ -48,11 +52,13 @@ pub async fn pending_tx_state_change_task<P, N, DB>(
     cur_next_base_fee: u64,
     cur_state_override: StateOverride,
     state_updates_broadcaster: Broadcaster<StateUpdateEvent<DB>>,
+    simulation: Arc<HashMap<String, PendingTxSimulation>>,
 ) -> Result<()>
 where
     N: Network,
     P: Provider<N> + DebugProviderExt<N> + Send + Sync + Clone + 'static,
-    DB: DatabaseRef + Database + DatabaseCommit + Clone + Send + Sync + 'static,
+    DB: DatabaseRef + Database + DatabaseCommit + DatabaseLoomExt + Clone + Send + Sync + 'static,
+    <DB as DatabaseRef>::Error: std::fmt::Debug,
 {
     let mut state_update_vec: GethStateUpdateVec = Vec::new();
     let mut state_required_vec: GethStateUpdateVec = Vec::new();
@@ -135,8 +141,15 @@ where
         return Err(eyre!("NON_AFFECTING_TX"));
     }
 
-    let diff_trace_result =
-        debug_trace_call_diff(client.clone(), transaction_request, BlockNumberOrTag::Latest.into(), Some(call_opts)).await;
+    let diff_trace_result = match simulation.get(&source).copied().unwrap_or_default() {
+        PendingTxSimulation::Trace => {
+            debug_trace_call_diff(client.clone(), transaction_request, BlockNumberOrTag::Latest.into(), Some(call_opts)).await
+        }
+        PendingTxSimulation::Local => {
+            let cur_state_db = market_state.read().await.state_db.clone();
+            local_call_diff(client.clone(), cur_state_db, &tx, *COINBASE, cur_block_number, cur_block_time, cur_next_base_fee)
+        }
+    };
     match diff_trace_result {
         Ok((pre, post)) => {
             state_required_vec.push(pre.clone());
@@ -148,7 +161,7 @@ where
         Err(error) => {
             let tx_hash = tx.tx_hash();
             mempool.write().await.set_failed(tx_hash);
-            debug!(block=cur_block_number, %tx_hash, %error, "debug_trace_call error for");
+            debug!(block=cur_block_number, %tx_hash, %error, %source, "pending tx simulation error for");
         }
     }
 
@@ -180,7 +193,8 @@ where
                 vec![mempool_tx.tx.clone().unwrap()],
                 "pending_tx_searcher".to_string(),
                 9000,
-            );
+            )
+            .with_parent_block_hash(latest_header.hash);
             if let Err(e) = state_updates_broadcaster.send(request) {
                 error!("state_updates_broadcaster : {}", e)
             }
@@ -224,7 +238,8 @@ where
                             vec![mempool_tx.tx.unwrap()],
                             "poolcode_searcher".to_string(),
                             3000,
-                        );
+                        )
+                        .with_parent_block_hash(latest_header.hash);
                         if let Err(e) = state_updates_broadcaster.send(request) {
                             error!("state_updates_broadcaster : {}", e)
                         }
@@ -251,16 +266,20 @@ pub async fn pending_tx_state_change_worker<P, N, DB>(
     mempool_events_rx: Broadcaster<MempoolEvents>,
     market_events_rx: Broadcaster<MarketEvents>,
     state_updates_broadcaster: Broadcaster<StateUpdateEvent<DB>>,
+    simulation: HashMap<String, PendingTxSimulation>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     N: Network,
     P: Provider<N> + DebugProviderExt<N> + Send + Sync + Clone + 'static,
-    DB: DatabaseRef + Database + DatabaseCommit + Clone + Send + Sync + 'static,
+    DB: DatabaseRef + Database + DatabaseCommit + DatabaseLoomExt + Clone + Send + Sync + 'static,
+    <DB as DatabaseRef>::Error: std::fmt::Debug,
 {
     subscribe!(mempool_events_rx);
     subscribe!(market_events_rx);
 
     let affecting_tx: Arc<RwLock<HashMap<TxHash, bool>>> = Arc::new(RwLock::new(HashMap::new()));
+    let simulation = Arc::new(simulation);
     let mut cur_next_base_fee = 0;
     let mut cur_block_number: Option<BlockNumber> = None;
     let mut cur_block_time: Option<u64> = None;
@@ -268,6 +287,9 @@ where
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = market_events_rx.recv() => {
                 if let Ok(msg) = msg {
                     let market_event_msg : MarketEvents = msg;
@@ -312,6 +334,7 @@ where
                                 cur_next_base_fee,
                                 cur_state_override.clone(),
                                 state_updates_broadcaster.clone(),
+                                simulation.clone(),
                             )
                         );
                     }
@@ -319,11 +342,13 @@ where
             }
         }
     }
+    Ok("PendingTxStateChangeWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer)]
 pub struct PendingTxStateChangeProcessorActor<P, N, DB: Clone + Send + Sync + 'static> {
     client: P,
+    simulation: HashMap<String, PendingTxSimulation>,
     #[accessor]
     market: Option<SharedState<Market>>,
     #[accessor]
@@ -350,6 +375,7 @@ where
     pub fn new(client: P) -> PendingTxStateChangeProcessorActor<P, N, DB> {
         PendingTxStateChangeProcessorActor {
             client,
+            simulation: HashMap::new(),
             market: None,
             mempool: None,
             market_state: None,
@@ -361,6 +387,11 @@ where
         }
     }
 
+    /// Simulation backend for each mempool source, sources that are not set use `debug_traceCall`
+    pub fn with_simulation(self, simulation: HashMap<String, PendingTxSimulation>) -> Self {
+        Self { simulation, ..self }
+    }
+
     pub fn on_bc(self, bc: &Blockchain, state: &BlockchainState<DB>, strategy: &Strategy<DB>) -> Self {
         Self {
             market: Some(bc.market()),
@@ -379,9 +410,10 @@ impl<P, N, DB> Actor for PendingTxStateChangeProcessorActor<P, N, DB>
 where
     N: Network,
     P: Provider<N> + DebugProviderExt<N> + Send + Sync + Clone + 'static,
-    DB: DatabaseRef + Database + DatabaseCommit + Send + Sync + Clone + 'static,
+    DB: DatabaseRef + Database + DatabaseCommit + DatabaseLoomExt + Send + Sync + Clone + 'static,
+    <DB as DatabaseRef>::Error: std::fmt::Debug,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(pending_tx_state_change_worker(
             self.client.clone(),
             self.market.clone().unwrap(),
@@ -391,6 +423,8 @@ where
             self.mempool_events_rx.clone().unwrap(),
             self.market_events_rx.clone().unwrap(),
             self.state_updates_tx.clone().unwrap(),
+            self.simulation.clone(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
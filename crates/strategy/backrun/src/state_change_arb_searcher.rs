@@ -16,20 +16,25 @@ use tracing::{debug, error, info, trace};
 
 use crate::BackrunConfig;
 use crate::SwapCalculator;
-use loom_core_actors::{subscribe, Accessor, Actor, ActorResult, Broadcaster, Consumer, Producer, SharedState, WorkerResult};
+use loom_core_actors::{
+    subscribe, Accessor, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, SharedState, WorkerResult,
+};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, Strategy};
 use loom_evm_db::DatabaseHelpers;
 use loom_types_entities::strategy_config::StrategyConfig;
-use loom_types_entities::{Market, PoolWrapper, Swap, SwapDirection, SwapError, SwapLine, SwapPath};
+use loom_types_entities::tips::TipsPolicy;
+use loom_types_entities::{Market, PoolWrapper, Swap, SwapDirection, SwapError, SwapLine, SwapOptimizer, SwapPath};
 use loom_types_events::{
     BestTxSwapCompose, HealthEvent, Message, MessageHealthEvent, MessageSwapCompose, StateUpdateEvent, SwapComposeData, SwapComposeMessage,
     TxComposeData,
 };
 
+#[allow(clippy::too_many_arguments)]
 async fn state_change_arb_searcher_task<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + Default + 'static>(
     thread_pool: Arc<ThreadPool>,
     backrun_config: BackrunConfig,
+    tips_policy: Option<Arc<dyn TipsPolicy>>,
     state_update_event: StateUpdateEvent<DB>,
     market: SharedState<Market>,
     swap_request_tx: Broadcaster<MessageSwapCompose<DB>>,
@@ -100,6 +105,7 @@ async fn state_change_arb_searcher_task<DB: DatabaseRef<Error = ErrReport> + Dat
     info!("Calculation started: swap_path_vec_len={} elapsed={}", swap_path_vec.len(), start_time.elapsed().as_micros());
 
     let env = state_update_event.evm_env();
+    let optimizer = SwapOptimizer::new(backrun_config.optimizer(), state_update_event.next_base_fee);
 
     let channel_len = swap_path_vec.len();
     let (swap_path_tx, mut swap_line_rx) = tokio::sync::mpsc::channel(channel_len);
@@ -109,11 +115,11 @@ async fn state_change_arb_searcher_task<DB: DatabaseRef<Error = ErrReport> + Dat
 
     tokio::task::spawn(async move {
         thread_pool.install(|| {
-            swap_path_vec.into_par_iter().for_each_with((&swap_path_tx, &market_state_clone, &env), |req, item| {
+            swap_path_vec.into_par_iter().for_each_with((&swap_path_tx, &market_state_clone, &env, &optimizer), |req, item| {
                 let mut mut_item: SwapLine = SwapLine { path: item, ..Default::default() };
                 //#[cfg(not(debug_assertions))]
                 //let start_time = chrono::Local::now();
-                let calc_result = SwapCalculator::calculate(&mut mut_item, req.1, req.2.clone());
+                let calc_result = SwapCalculator::calculate_with_optimizer(&mut mut_item, req.1, req.2.clone(), req.3);
                 //#[cfg(not(debug_assertions))]
                 //let took_time = chrono::Local::now() - start_time;
 
@@ -174,6 +180,7 @@ async fn state_change_arb_searcher_task<DB: DatabaseRef<Error = ErrReport> + Dat
                     tx_compose: TxComposeData {
                         eoa: backrun_config.eoa(),
                         next_block_number: state_update_event.next_block_number,
+                        parent_block_hash: state_update_event.parent_block_hash,
                         next_block_timestamp: state_update_event.next_block_timestamp,
                         next_block_base_fee: state_update_event.next_base_fee,
                         gas: swap_line.gas_used.unwrap_or(300000),
@@ -184,6 +191,7 @@ async fn state_change_arb_searcher_task<DB: DatabaseRef<Error = ErrReport> + Dat
                     swap: Swap::BackrunSwapLine(swap_line),
                     origin: Some(state_update_event.origin.clone()),
                     tips_pct: Some(state_update_event.tips_pct),
+                    tips_policy: tips_policy.clone(),
                     poststate: Some(db.clone()),
                     poststate_update: Some(state_update_event.state_update().clone()),
                     ..SwapComposeData::default()
@@ -241,6 +249,7 @@ pub async fn state_change_arb_searcher_worker<
     swap_request_tx: Broadcaster<MessageSwapCompose<DB>>,
     pool_health_monitor_tx: Broadcaster<MessageHealthEvent>,
     influxdb_write_channel_tx: Broadcaster<WriteQuery>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult {
     subscribe!(search_request_rx);
 
@@ -248,9 +257,13 @@ pub async fn state_change_arb_searcher_worker<
     let tasks = (cpus * 5) / 10;
     info!("Starting state arb searcher cpus={cpus}, tasks={tasks}");
     let thread_pool = Arc::new(ThreadPoolBuilder::new().num_threads(tasks).build()?);
+    let tips_policy = backrun_config.tips_policy()?;
 
     loop {
         tokio::select! {
+                _ = cancel_token.cancelled() => {
+                    break;
+                }
                 msg = search_request_rx.recv() => {
                 let pool_update_msg : Result<StateUpdateEvent<DB>, RecvError> = msg;
                 if let Ok(msg) = pool_update_msg {
@@ -258,6 +271,7 @@ pub async fn state_change_arb_searcher_worker<
                         state_change_arb_searcher_task(
                             thread_pool.clone(),
                             backrun_config.clone(),
+                            tips_policy.clone(),
                             msg,
                             market.clone(),
                             swap_request_tx.clone(),
@@ -269,6 +283,7 @@ pub async fn state_change_arb_searcher_worker<
             }
         }
     }
+    Ok("StateChangeArbSearcherWorker stopped".to_string())
 }
 
 #[derive(Accessor, Consumer, Producer)]
@@ -313,7 +328,7 @@ impl<DB: DatabaseRef<Error = ErrReport> + Send + Sync + Clone + 'static> StateCh
 impl<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone + Default + 'static> Actor
     for StateChangeArbSearcherActor<DB>
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(state_change_arb_searcher_worker(
             self.backrun_config.clone(),
             self.market.clone().unwrap(),
@@ -321,6 +336,7 @@ impl<DB: DatabaseRef<Error = ErrReport> + DatabaseCommit + Send + Sync + Clone +
             self.compose_tx.clone().unwrap(),
             self.pool_health_monitor_tx.clone().unwrap(),
             self.influxdb_write_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -2,8 +2,9 @@ use alloy_primitives::utils::parse_units;
 use alloy_primitives::U256;
 use eyre::ErrReport;
 use lazy_static::lazy_static;
+use loom_defi_pools::UniswapV2Pool;
 use loom_types_blockchain::LoomDataTypes;
-use loom_types_entities::{SwapError, SwapLine};
+use loom_types_entities::{PoolClass, SwapAmountType, SwapError, SwapLine, SwapLineOptimizer};
 use revm::primitives::Env;
 use revm::DatabaseRef;
 
@@ -11,6 +12,8 @@ lazy_static! {
     static ref START_OPTIMIZE_INPUT: U256 = parse_units("0.01", "ether").unwrap().get_absolute();
 }
 
+const FEE_DENOMINATOR: u64 = 10000;
+
 pub struct SwapCalculator {}
 
 impl SwapCalculator {
@@ -23,9 +26,192 @@ impl SwapCalculator {
         let first_token = path.get_first_token().unwrap();
         if let Some(amount_in) = first_token.calc_token_value_from_eth(*START_OPTIMIZE_INPUT) {
             //trace!("calculate : {} amount in : {}",first_token.get_symbol(), first_token.to_float(amount_in) );
+            if Self::calculate_uniswap2_cycle(path, state, env.clone(), amount_in) {
+                return Ok(path);
+            }
             path.optimize_with_in_amount(state, env, amount_in)
         } else {
             Err(path.to_error("PRICE_NOT_SET".to_string()))
         }
     }
+
+    #[inline]
+    pub fn calculate_with_optimizer<'a, O: SwapLineOptimizer<LDT>, DB: DatabaseRef<Error = ErrReport>, LDT: LoomDataTypes>(
+        path: &'a mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        optimizer: &O,
+    ) -> eyre::Result<&'a mut SwapLine<LDT>, SwapError<LDT>> {
+        let first_token = path.get_first_token().unwrap();
+        if let Some(amount_in) = first_token.calc_token_value_from_eth(*START_OPTIMIZE_INPUT) {
+            if Self::calculate_uniswap2_cycle(path, state, env.clone(), amount_in) {
+                return Ok(path);
+            }
+            path.optimize_with(optimizer, state, env, amount_in)
+        } else {
+            Err(path.to_error("PRICE_NOT_SET".to_string()))
+        }
+    }
+
+    /// Fast path for cycles of UniswapV2 pools. Sets amounts for the optimal in amount, or for the start amount if the cycle is not
+    /// profitable. Returns false if the path is not a UniswapV2 cycle or calculation failed, so iterative optimizer should be used.
+    fn calculate_uniswap2_cycle<DB: DatabaseRef<Error = ErrReport>, LDT: LoomDataTypes>(
+        path: &mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        start_amount: U256,
+    ) -> bool {
+        let Some(optimal_amount) = Self::uniswap2_cycle_optimal_in_amount(path, state, env.clone()) else {
+            return false;
+        };
+        let amount_in = if optimal_amount.is_zero() { start_amount } else { optimal_amount };
+
+        match path.calculate_with_in_amount(state, env, amount_in) {
+            Ok((amount_out, gas_used, calculation_results)) => {
+                path.amount_in = SwapAmountType::Set(amount_in);
+                path.amount_out = SwapAmountType::Set(amount_out);
+                path.gas_used = Some(gas_used);
+                path.calculation_results = calculation_results;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// Optimal in amount for a cycle of UniswapV2 pools, zero if the cycle is not profitable.
+    ///
+    /// Each hop is `out = fee * r_out * x / (r_in + fee * x)`, a composition of hops keeps the form `Eb * x / (Ea + x)`
+    /// with virtual reserves `Ea`, `Eb`. Profit `Eb * x / (Ea + x) - x` is maximal at `x = sqrt(Ea * Eb) - Ea`.
+    pub fn uniswap2_cycle_optimal_in_amount<DB: DatabaseRef<Error = ErrReport>, LDT: LoomDataTypes>(
+        path: &SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+    ) -> Option<U256> {
+        if path.pools().is_empty() || path.get_first_token()? != path.get_last_token()? {
+            return None;
+        }
+
+        let mut virtual_reserves: Option<(U256, U256)> = None;
+
+        for (i, pool) in path.pools().iter().enumerate() {
+            if pool.get_class() != PoolClass::UniswapV2 {
+                return None;
+            }
+            let uni2_pool = pool.as_any().downcast_ref::<UniswapV2Pool>()?;
+            let (reserve_0, reserve_1) = uni2_pool.fetch_reserves(state, env.clone()).ok()?;
+
+            let token_from = path.tokens()[i].get_address();
+            let token_to = path.tokens()[i + 1].get_address();
+            let (reserve_in, reserve_out) = if token_from < token_to { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+            if reserve_in.is_zero() || reserve_out.is_zero() {
+                return None;
+            }
+
+            virtual_reserves = Some(match virtual_reserves {
+                None => (reserve_in.checked_mul(U256::from(FEE_DENOMINATOR))?.checked_div(uni2_pool.get_fee())?, reserve_out),
+                Some(reserves) => Self::uniswap2_virtual_reserves(reserves, reserve_in, reserve_out, uni2_pool.get_fee())?,
+            });
+        }
+
+        let (ea, eb) = virtual_reserves?;
+        if eb <= ea {
+            return Some(U256::ZERO);
+        }
+        Some(Self::sqrt(ea.checked_mul(eb)?).saturating_sub(ea))
+    }
+
+    // Appends pool with reserves and fee to the virtual pool (ea, eb)
+    fn uniswap2_virtual_reserves((ea, eb): (U256, U256), reserve_in: U256, reserve_out: U256, fee: U256) -> Option<(U256, U256)> {
+        let reserve_in_scaled = reserve_in.checked_mul(U256::from(FEE_DENOMINATOR))?;
+        let denominator = reserve_in_scaled.checked_add(fee.checked_mul(eb)?)?;
+        let ea_next = reserve_in_scaled.checked_mul(ea)?.checked_div(denominator)?;
+        let eb_next = fee.checked_mul(reserve_out)?.checked_mul(eb)?.checked_div(denominator)?;
+        Some((ea_next, eb_next))
+    }
+
+    fn sqrt(value: U256) -> U256 {
+        if value < U256::from(2) {
+            return value;
+        }
+        let mut ret = value;
+        let mut next = (value >> 1) + (value & U256::from(1));
+        while next < ret {
+            ret = next;
+            next = (value / next + next) >> 1;
+        }
+        ret
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloy_primitives::{Address, I256};
+    use loom_defi_address_book::TokenAddressEth;
+    use loom_evm_db::LoomDBType;
+    use loom_types_entities::{BrentOptimizer, SwapPath, Token};
+    use std::sync::Arc;
+
+    const RESERVES_CELL: u64 = 8;
+
+    fn add_uniswap2_pool(state: &mut LoomDBType, address: Address, reserve_weth: U256, reserve_usdt: U256) -> UniswapV2Pool {
+        // WETH address is less than USDT, so WETH is token0
+        state.insert_account_storage(address, U256::from(RESERVES_CELL), reserve_weth | (reserve_usdt << 112)).unwrap();
+        UniswapV2Pool::new_with_data(address, TokenAddressEth::WETH, TokenAddressEth::USDT, Address::ZERO, reserve_weth, reserve_usdt)
+            .set_reserves_cell(Some(U256::from(RESERVES_CELL)))
+    }
+
+    #[test]
+    fn test_sqrt() {
+        for value in [0u64, 1, 2, 3, 4, 15, 16, 17, 1_000_000, u64::MAX] {
+            let root = SwapCalculator::sqrt(U256::from(value));
+            assert!(root * root <= U256::from(value));
+            assert!((root + U256::from(1)) * (root + U256::from(1)) > U256::from(value));
+        }
+    }
+
+    #[test]
+    fn test_uniswap2_cycle_matches_numeric_optimizer() {
+        let mut state = LoomDBType::default();
+        let weth_reserve = U256::from(1000) * U256::from(10).pow(U256::from(18));
+        let pool_sell = add_uniswap2_pool(&mut state, Address::repeat_byte(1), weth_reserve, U256::from(2_100_000_000_000u64));
+        let pool_buy = add_uniswap2_pool(&mut state, Address::repeat_byte(2), weth_reserve, U256::from(2_000_000_000_000u64));
+
+        let weth = Arc::new(Token::new_with_data(TokenAddressEth::WETH, Some("WETH".to_string()), None, Some(18), true, false));
+        let usdt = Arc::new(Token::new_with_data(TokenAddressEth::USDT, Some("USDT".to_string()), None, Some(6), true, false));
+        let swap_path = SwapPath::new(vec![weth.clone(), usdt, weth], vec![pool_sell, pool_buy]);
+
+        let optimal_amount =
+            SwapCalculator::uniswap2_cycle_optimal_in_amount(&SwapLine::from(swap_path.clone()), &state, Env::default()).unwrap();
+        assert!(!optimal_amount.is_zero());
+
+        let mut closed_form_line = SwapLine::from(swap_path.clone());
+        SwapCalculator::calculate(&mut closed_form_line, &state, Env::default()).unwrap();
+        assert_eq!(closed_form_line.amount_in.unwrap(), optimal_amount);
+
+        let mut numeric_line = SwapLine::from(swap_path);
+        numeric_line.optimize_with(&BrentOptimizer::new(), &state, Env::default(), *START_OPTIMIZE_INPUT).unwrap();
+
+        // in amounts agree within 0.1% and closed form profit is not worse than numeric one
+        let amount_diff = closed_form_line.amount_in.unwrap().abs_diff(numeric_line.amount_in.unwrap());
+        assert!(amount_diff < optimal_amount / U256::from(1000));
+        let closed_form_profit = closed_form_line.profit().unwrap();
+        let numeric_profit = numeric_line.profit().unwrap();
+        assert!(closed_form_profit.is_positive());
+        assert!(closed_form_profit >= numeric_profit - I256::from_raw(U256::from(10u64.pow(12))));
+    }
+
+    #[test]
+    fn test_uniswap2_cycle_not_profitable() {
+        let mut state = LoomDBType::default();
+        let weth_reserve = U256::from(1000) * U256::from(10).pow(U256::from(18));
+        let pool_sell = add_uniswap2_pool(&mut state, Address::repeat_byte(1), weth_reserve, U256::from(2_000_000_000_000u64));
+        let pool_buy = add_uniswap2_pool(&mut state, Address::repeat_byte(2), weth_reserve, U256::from(2_001_000_000_000u64));
+
+        let weth = Arc::new(Token::new_with_data(TokenAddressEth::WETH, Some("WETH".to_string()), None, Some(18), true, false));
+        let usdt = Arc::new(Token::new_with_data(TokenAddressEth::USDT, Some("USDT".to_string()), None, Some(6), true, false));
+        let swap_line = SwapLine::from(SwapPath::new(vec![weth.clone(), usdt, weth], vec![pool_sell, pool_buy]));
+
+        assert_eq!(SwapCalculator::uniswap2_cycle_optimal_in_amount(&swap_line, &state, Env::default()), Some(U256::ZERO));
+    }
 }
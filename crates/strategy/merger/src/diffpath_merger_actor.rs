@@ -5,10 +5,9 @@ use eyre::{OptionExt, Result};
 use lazy_static::lazy_static;
 use revm::{Database, DatabaseCommit, DatabaseRef};
 use tokio::sync::broadcast::error::RecvError;
-use tokio::sync::broadcast::Receiver;
 use tracing::{debug, error, info};
 
-use loom_core_actors::{Actor, ActorResult, Broadcaster, Consumer, Producer, WorkerResult};
+use loom_core_actors::{subscribe, Actor, ActorResult, Broadcaster, CancellationToken, Consumer, Producer, WorkerResult};
 use loom_core_actors_macros::{Accessor, Consumer, Producer};
 use loom_core_blockchain::{Blockchain, Strategy};
 use loom_evm_utils::NWETH;
@@ -38,18 +37,22 @@ async fn diff_path_merger_worker<DB>(
     market_events_rx: Broadcaster<MarketEvents>,
     compose_channel_rx: Broadcaster<MessageSwapCompose<DB>>,
     compose_channel_tx: Broadcaster<MessageSwapCompose<DB>>,
+    cancel_token: CancellationToken,
 ) -> WorkerResult
 where
     DB: DatabaseRef + Database + DatabaseCommit + Send + Sync + Clone + 'static,
 {
-    let mut market_events_rx: Receiver<MarketEvents> = market_events_rx.subscribe();
+    subscribe!(market_events_rx);
 
-    let mut compose_channel_rx: Receiver<MessageSwapCompose<DB>> = compose_channel_rx.subscribe();
+    subscribe!(compose_channel_rx);
 
     let mut swap_paths: Vec<SwapComposeData<DB>> = Vec::new();
 
     loop {
         tokio::select! {
+            _ = cancel_token.cancelled() => {
+                break;
+            }
             msg = market_events_rx.recv() => {
                 if let Ok(msg) = msg {
                     let market_event_msg : MarketEvents = msg;
@@ -141,6 +144,7 @@ where
 
         }
     }
+    Ok("DiffPathMergerWorker stopped".to_string())
 }
 
 #[derive(Consumer, Producer, Accessor, Default)]
@@ -178,11 +182,12 @@ impl<DB> Actor for DiffPathMergerActor<DB>
 where
     DB: DatabaseRef + Database + DatabaseCommit + Send + Sync + Clone + Default + 'static,
 {
-    fn start(&self) -> ActorResult {
+    fn start_with_cancel(&self, cancel_token: CancellationToken) -> ActorResult {
         let task = tokio::task::spawn(diff_path_merger_worker(
             self.market_events.clone().unwrap(),
             self.compose_channel_rx.clone().unwrap(),
             self.compose_channel_tx.clone().unwrap(),
+            cancel_token,
         ));
         Ok(vec![task])
     }
@@ -1,15 +1,142 @@
+use alloy_chains::NamedChain;
 use alloy_eips::eip1559::BaseFeeParams;
+use alloy_primitives::Address;
 use alloy_rpc_types_eth::Header;
+use eyre::{eyre, Result};
+use loom_defi_address_book::{TokenAddressArbitrum, TokenAddressBase, TokenAddressBsc, TokenAddressEth, TokenAddressOptimism};
+use std::time::Duration;
+
+/// Arbitrum does not adjust base fee per block like EIP-1559 does, it stays at the minimum most of the time.
+/// A huge denominator with elasticity of one keeps the predicted base fee flat.
+const ARBITRUM_BASE_FEE_MAX_CHANGE_DENOMINATOR: u128 = u64::MAX as u128;
+const ARBITRUM_ELASTICITY_MULTIPLIER: u128 = 1;
+
+/// Token that is added to the market when blockchain is created
+#[derive(Clone, Debug)]
+pub struct ChainToken {
+    pub address: Address,
+    pub symbol: &'static str,
+    pub decimals: u8,
+    pub basic: bool,
+    pub middle: bool,
+}
+
+impl ChainToken {
+    pub const fn basic(address: Address, symbol: &'static str, decimals: u8) -> Self {
+        ChainToken { address, symbol, decimals, basic: true, middle: false }
+    }
+
+    pub const fn middle(address: Address, symbol: &'static str, decimals: u8) -> Self {
+        ChainToken { address, symbol, decimals, basic: false, middle: true }
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct ChainParameters {
     pub chain_id: u64,
     pub base_fee_params: BaseFeeParams,
+    pub block_time: Duration,
+    pub wrapped_native_token: Address,
+    pub default_tokens: Vec<ChainToken>,
 }
 
 impl ChainParameters {
     pub fn ethereum() -> ChainParameters {
-        ChainParameters { chain_id: 1, base_fee_params: BaseFeeParams::ethereum() }
+        ChainParameters {
+            chain_id: NamedChain::Mainnet as u64,
+            base_fee_params: BaseFeeParams::ethereum(),
+            block_time: Duration::from_secs(12),
+            wrapped_native_token: TokenAddressEth::WETH,
+            default_tokens: vec![
+                ChainToken::basic(TokenAddressEth::WETH, "WETH", 18),
+                ChainToken::basic(TokenAddressEth::USDC, "USDC", 6),
+                ChainToken::basic(TokenAddressEth::USDT, "USDT", 6),
+                ChainToken::basic(TokenAddressEth::DAI, "DAI", 18),
+                ChainToken::basic(TokenAddressEth::WBTC, "WBTC", 8),
+                ChainToken::middle(TokenAddressEth::THREECRV, "3Crv", 18),
+            ],
+        }
+    }
+
+    pub fn optimism() -> ChainParameters {
+        ChainParameters {
+            chain_id: NamedChain::Optimism as u64,
+            base_fee_params: BaseFeeParams::optimism_canyon(),
+            block_time: Duration::from_secs(2),
+            wrapped_native_token: TokenAddressOptimism::WETH,
+            default_tokens: vec![
+                ChainToken::basic(TokenAddressOptimism::WETH, "WETH", 18),
+                ChainToken::basic(TokenAddressOptimism::USDC, "USDC", 6),
+                ChainToken::basic(TokenAddressOptimism::USDT, "USDT", 6),
+                ChainToken::basic(TokenAddressOptimism::DAI, "DAI", 18),
+                ChainToken::basic(TokenAddressOptimism::WBTC, "WBTC", 8),
+            ],
+        }
+    }
+
+    pub fn base() -> ChainParameters {
+        ChainParameters {
+            chain_id: NamedChain::Base as u64,
+            base_fee_params: BaseFeeParams::optimism_canyon(),
+            block_time: Duration::from_secs(2),
+            wrapped_native_token: TokenAddressBase::WETH,
+            default_tokens: vec![
+                ChainToken::basic(TokenAddressBase::WETH, "WETH", 18),
+                ChainToken::basic(TokenAddressBase::USDC, "USDC", 6),
+                ChainToken::basic(TokenAddressBase::DAI, "DAI", 18),
+            ],
+        }
+    }
+
+    pub fn arbitrum() -> ChainParameters {
+        ChainParameters {
+            chain_id: NamedChain::Arbitrum as u64,
+            base_fee_params: BaseFeeParams::new(ARBITRUM_BASE_FEE_MAX_CHANGE_DENOMINATOR, ARBITRUM_ELASTICITY_MULTIPLIER),
+            block_time: Duration::from_millis(250),
+            wrapped_native_token: TokenAddressArbitrum::WETH,
+            default_tokens: vec![
+                ChainToken::basic(TokenAddressArbitrum::WETH, "WETH", 18),
+                ChainToken::basic(TokenAddressArbitrum::WBTC, "WBTC", 8),
+                ChainToken::basic(TokenAddressArbitrum::USDC, "USDC", 6),
+                ChainToken::basic(TokenAddressArbitrum::USDT, "USDT", 6),
+                ChainToken::basic(TokenAddressArbitrum::DAI, "DAI", 18),
+            ],
+        }
+    }
+
+    pub fn bsc() -> ChainParameters {
+        ChainParameters {
+            chain_id: NamedChain::BinanceSmartChain as u64,
+            base_fee_params: BaseFeeParams::ethereum(),
+            block_time: Duration::from_secs(3),
+            wrapped_native_token: TokenAddressBsc::WBNB,
+            default_tokens: vec![
+                ChainToken::basic(TokenAddressBsc::WBNB, "WBNB", 18),
+                ChainToken::basic(TokenAddressBsc::ETH, "ETH", 18),
+                ChainToken::basic(TokenAddressBsc::USDT, "USDT", 18),
+                ChainToken::basic(TokenAddressBsc::USDC, "USDC", 18),
+                ChainToken::basic(TokenAddressBsc::BTCB, "BTCB", 18),
+            ],
+        }
+    }
+
+    /// Parameters for a chain that is not in the registry. Ethereum base fee rules, no wrapped native token and no default tokens.
+    pub fn custom(chain_id: u64) -> ChainParameters {
+        ChainParameters {
+            chain_id,
+            base_fee_params: BaseFeeParams::ethereum(),
+            block_time: Duration::from_secs(12),
+            wrapped_native_token: Address::ZERO,
+            default_tokens: Vec::new(),
+        }
+    }
+
+    pub fn with_base_fee_params(self, base_fee_params: BaseFeeParams) -> Self {
+        Self { base_fee_params, ..self }
+    }
+
+    pub fn with_block_time(self, block_time: Duration) -> Self {
+        Self { block_time, ..self }
     }
 
     pub fn calc_next_block_base_fee(&self, gas_used: u64, gas_limit: u64, base_fee: u64) -> u64 {
@@ -19,6 +146,15 @@ impl ChainParameters {
     pub fn calc_next_block_base_fee_from_header(&self, header: &Header) -> u64 {
         self.base_fee_params.next_block_base_fee(header.gas_used, header.gas_limit, header.base_fee_per_gas.unwrap_or_default())
     }
+
+    /// Expected timestamp of a block `blocks_ahead` blocks after the block with `timestamp`
+    pub fn calc_block_timestamp(&self, timestamp: u64, blocks_ahead: u64) -> u64 {
+        timestamp + (self.block_time.as_millis() as u64 * blocks_ahead) / 1000
+    }
+
+    pub fn is_wrapped_native_token(&self, address: &Address) -> bool {
+        self.wrapped_native_token.eq(address)
+    }
 }
 
 impl Default for ChainParameters {
@@ -26,11 +162,49 @@ impl Default for ChainParameters {
         Self::ethereum()
     }
 }
-impl From<u64> for ChainParameters {
-    fn from(chain_id: u64) -> Self {
-        match chain_id {
-            1 => ChainParameters::ethereum(),
-            _ => unimplemented!(),
+
+impl TryFrom<u64> for ChainParameters {
+    type Error = eyre::Report;
+
+    fn try_from(chain_id: u64) -> Result<Self> {
+        match NamedChain::try_from(chain_id) {
+            Ok(NamedChain::Mainnet) => Ok(ChainParameters::ethereum()),
+            Ok(NamedChain::Optimism) => Ok(ChainParameters::optimism()),
+            Ok(NamedChain::Base) => Ok(ChainParameters::base()),
+            Ok(NamedChain::Arbitrum) => Ok(ChainParameters::arbitrum()),
+            Ok(NamedChain::BinanceSmartChain) => Ok(ChainParameters::bsc()),
+            _ => Err(eyre!("CHAIN_PARAMETERS_NOT_FOUND")),
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        for chain_id in [1u64, 10, 56, 8453, 42161] {
+            let chain_parameters = ChainParameters::try_from(chain_id).unwrap();
+            assert_eq!(chain_parameters.chain_id, chain_id);
+            assert!(chain_parameters.default_tokens.iter().any(|token| token.address == chain_parameters.wrapped_native_token));
+        }
+        assert!(ChainParameters::try_from(12345u64).is_err());
+    }
+
+    #[test]
+    fn test_next_base_fee() {
+        // full block increases base fee by 1/8 on ethereum
+        assert_eq!(ChainParameters::ethereum().calc_next_block_base_fee(30_000_000, 30_000_000, 8_000_000_000), 9_000_000_000);
+        // elasticity 6 and denominator 250 on optimism stack
+        assert_eq!(ChainParameters::base().calc_next_block_base_fee(60_000_000, 60_000_000, 1_000_000), 1_020_000);
+        // arbitrum base fee stays flat
+        assert_eq!(ChainParameters::arbitrum().calc_next_block_base_fee(1_000_000, 32_000_000, 10_000_000), 10_000_000);
+    }
+
+    #[test]
+    fn test_block_timestamp() {
+        assert_eq!(ChainParameters::ethereum().calc_block_timestamp(1000, 2), 1024);
+        assert_eq!(ChainParameters::arbitrum().calc_block_timestamp(1000, 8), 1002);
+    }
+}
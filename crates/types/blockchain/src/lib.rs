@@ -1,5 +1,5 @@
 pub use accountnoncetx::AccountNonceAndTransactions;
-pub use chain_parameters::ChainParameters;
+pub use chain_parameters::{ChainParameters, ChainToken};
 pub use fetchstate::FetchState;
 pub use loom_data_types::{LoomBlock, LoomDataTypes, LoomHeader, LoomTx};
 pub use loom_data_types_ethereum::LoomDataTypesEthereum;
@@ -0,0 +1,66 @@
+use alloy_primitives::utils::parse_units;
+use alloy_primitives::{Address, U256};
+use criterion::{criterion_group, criterion_main, Criterion};
+use loom_defi_address_book::TokenAddressEth;
+use loom_evm_db::LoomDBType;
+use loom_types_entities::{
+    BrentOptimizer, GoldenSectionOptimizer, MockUniswapV2Pool, StepOptimizer, SwapLine, SwapLineOptimizer, SwapPath, Token,
+};
+use revm::primitives::Env;
+use std::sync::Arc;
+
+fn create_swap_line(sell_reserve: u64, buy_reserve: u64) -> SwapLine {
+    let weth = Arc::new(Token::new_with_data(TokenAddressEth::WETH, Some("WETH".to_string()), None, Some(18), true, false));
+    let usdt = Arc::new(Token::new_with_data(TokenAddressEth::USDT, Some("USDT".to_string()), None, Some(6), true, false));
+    let weth_reserve = parse_units("1000", "ether").unwrap().get_absolute();
+
+    let pool_sell =
+        MockUniswapV2Pool::new(TokenAddressEth::WETH, TokenAddressEth::USDT, Address::random(), weth_reserve, U256::from(sell_reserve));
+    let pool_buy =
+        MockUniswapV2Pool::new(TokenAddressEth::WETH, TokenAddressEth::USDT, Address::random(), weth_reserve, U256::from(buy_reserve));
+
+    SwapLine::from(SwapPath::new(vec![weth.clone(), usdt, weth], vec![pool_sell, pool_buy]))
+}
+
+fn optimize<O: SwapLineOptimizer>(optimizer: &O, swap_line: &SwapLine) -> (usize, f64) {
+    let state = LoomDBType::default();
+    let start_amount = parse_units("0.01", "ether").unwrap().get_absolute();
+    let mut swap_line = swap_line.clone();
+    let result = optimizer.optimize(&mut swap_line, &state, Env::default(), start_amount).unwrap();
+    let profit = swap_line.get_first_token().unwrap().to_float_sign(result.profit);
+    (result.iterations, profit)
+}
+
+fn benchmark_optimizers(c: &mut Criterion) {
+    let base_fee = 10_000_000_000u64;
+    // price difference of 1%, 5% and 50% between pools
+    let swap_lines = [
+        ("small", create_swap_line(2_020_000_000_000, 2_000_000_000_000)),
+        ("medium", create_swap_line(2_100_000_000_000, 2_000_000_000_000)),
+        ("large", create_swap_line(3_000_000_000_000, 2_000_000_000_000)),
+    ];
+
+    let step = StepOptimizer::new();
+    let golden_section = GoldenSectionOptimizer::new().with_base_fee(base_fee);
+    let brent = BrentOptimizer::new().with_base_fee(base_fee);
+
+    for (name, swap_line) in swap_lines.iter() {
+        let (iterations, profit) = optimize(&step, swap_line);
+        println!("{name} step : iterations {iterations} profit {profit}");
+        let (iterations, profit) = optimize(&golden_section, swap_line);
+        println!("{name} golden_section : iterations {iterations} profit {profit}");
+        let (iterations, profit) = optimize(&brent, swap_line);
+        println!("{name} brent : iterations {iterations} profit {profit}");
+    }
+
+    let mut group = c.benchmark_group("optimizer");
+    for (name, swap_line) in swap_lines.iter() {
+        group.bench_function(format!("step_{name}"), |b| b.iter(|| optimize(&step, swap_line)));
+        group.bench_function(format!("golden_section_{name}"), |b| b.iter(|| optimize(&golden_section, swap_line)));
+        group.bench_function(format!("brent_{name}"), |b| b.iter(|| optimize(&brent, swap_line)));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_optimizers);
+criterion_main!(benches);
@@ -94,7 +94,7 @@ impl<S> BlockHistory<S> {
         let block_number = header.number;
         let block_hash = header.hash;
 
-        //todo: process reorg
+        // numbers of reorged blocks are updated in BlockHistoryManager::set_chain_head
         if self.latest_block_number <= block_number {
             self.latest_block_number = block_number;
             self.block_numbers.insert(block_number, block_hash);
@@ -244,6 +244,17 @@ impl<S> BlockHistory<S> {
     pub fn contains_block(&self, block_hash: &BlockHash) -> bool {
         self.block_entries.contains_key(block_hash)
     }
+
+    /// Walks parents of block_hash back to the first block of the current chain. Returns block_hash entry if it is in the current chain.
+    pub fn find_common_ancestor(&self, block_hash: &BlockHash) -> Option<&BlockHistoryEntry> {
+        let mut entry = self.block_entries.get(block_hash)?;
+        loop {
+            if self.block_numbers.get(&entry.number()) == Some(&entry.hash()) {
+                return Some(entry);
+            }
+            entry = self.block_entries.get(&entry.parent_hash())?;
+        }
+    }
 }
 
 pub struct BlockHistoryManager<P, D> {
@@ -549,6 +560,31 @@ mod test {
         assert_eq!(block_history.block_numbers[&4], header_4_1.hash);
     }
 
+    #[test]
+    fn test_find_common_ancestor() {
+        let mut block_history = BlockHistory::<LoomDBType>::new(10);
+
+        let header_1_0 = create_header(1, U256::from(1).into());
+        let header_2_0 = create_next_header(&header_1_0, 0);
+        let header_3_0 = create_next_header(&header_2_0, 0);
+        let header_2_1 = create_next_header(&header_1_0, 1);
+        let header_3_1 = create_next_header(&header_2_1, 0);
+
+        block_history.add_block_header(header_1_0.clone()).unwrap();
+        block_history.add_block_header(header_2_0.clone()).unwrap();
+        block_history.add_block_header(header_3_0.clone()).unwrap();
+
+        assert_eq!(block_history.find_common_ancestor(&header_3_0.hash).unwrap().hash(), header_3_0.hash);
+
+        block_history.add_block_header(header_2_1.clone()).unwrap();
+        block_history.add_block_header(header_3_1.clone()).unwrap();
+        block_history.set_entry(block_history.get_block_history_entry(&header_2_1.hash).cloned().unwrap());
+
+        assert_eq!(block_history.find_common_ancestor(&header_3_0.hash).unwrap().hash(), header_1_0.hash);
+        assert_eq!(block_history.find_common_ancestor(&header_3_1.hash).unwrap().hash(), header_3_1.hash);
+        assert!(block_history.find_common_ancestor(&BlockHash::repeat_byte(0xff)).is_none());
+    }
+
     #[tokio::test]
     async fn test_with_anvil() -> Result<()> {
         let anvil = Anvil::new().try_spawn()?;
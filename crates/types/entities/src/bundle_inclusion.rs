@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use alloy_primitives::{BlockNumber, TxHash, I256, U256};
+use serde::Serialize;
+
+/// Broadcast bundles are kept for this number of blocks after target block before marked as missed
+pub const BUNDLE_INCLUSION_BLOCKS: u64 = 2;
+/// Number of resolved records kept in history
+pub const BUNDLE_INCLUSION_HISTORY_LEN: usize = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleInclusionStatus {
+    Pending,
+    Included,
+    Reverted,
+    Missed,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct BundleInclusion {
+    pub tx_hash: TxHash,
+    pub target_block: BlockNumber,
+    /// Relays that accepted the bundle
+    pub relays: Vec<String>,
+    /// Expected profit in ETH before tips
+    pub expected_profit_eth: U256,
+    /// Tips paid to block builder, gas cost excluded
+    pub tips: U256,
+    /// Gas cost estimated before broadcast
+    pub expected_gas_cost: U256,
+    pub origin: Option<String>,
+    pub swap: String,
+    pub status: BundleInclusionStatus,
+    pub included_block: Option<BlockNumber>,
+    pub tx_index: Option<usize>,
+    /// Extra data of the block the bundle was included in, usually identifies builder
+    pub builder: Option<String>,
+    /// Relay the bundle was sent to that built the block, matched by block extra data
+    pub landed_relay: Option<String>,
+    pub gas_used: Option<u64>,
+    pub gas_cost: Option<U256>,
+    /// Realized profit in ETH after tips and before gas cost
+    pub realized_profit_eth: Option<I256>,
+}
+
+impl BundleInclusion {
+    pub fn new(tx_hash: TxHash, target_block: BlockNumber, expected_profit_eth: U256, tips: U256) -> Self {
+        Self {
+            tx_hash,
+            target_block,
+            relays: Vec::new(),
+            expected_profit_eth,
+            tips,
+            expected_gas_cost: U256::ZERO,
+            origin: None,
+            swap: String::new(),
+            status: BundleInclusionStatus::Pending,
+            included_block: None,
+            tx_index: None,
+            builder: None,
+            landed_relay: None,
+            gas_used: None,
+            gas_cost: None,
+            realized_profit_eth: None,
+        }
+    }
+
+    /// Expected profit in ETH after tips
+    pub fn expected_net_profit_eth(&self) -> I256 {
+        I256::from_raw(self.expected_profit_eth) - I256::from_raw(self.tips)
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct BundleInclusionStats {
+    pub pending: usize,
+    pub included: usize,
+    pub reverted: usize,
+    pub missed: usize,
+    pub expected_profit_eth: I256,
+    pub realized_profit_eth: I256,
+    pub gas_cost: U256,
+}
+
+/// Broadcast bundles waiting for inclusion and results of resolved ones
+#[derive(Clone, Debug, Default)]
+pub struct BundleInclusionHistory {
+    pending: Vec<BundleInclusion>,
+    resolved: VecDeque<BundleInclusion>,
+}
+
+impl BundleInclusionHistory {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds broadcast bundle, rebroadcast of the same transaction only adds relays
+    pub fn add_pending(&mut self, record: BundleInclusion) {
+        if self.is_pending(&record.tx_hash) {
+            self.add_relays(&record.tx_hash, record.relays);
+            return;
+        }
+        self.pending.push(record);
+    }
+
+    /// Adds relays that accepted bundle with pending transaction
+    pub fn add_relays(&mut self, tx_hash: &TxHash, relays: impl IntoIterator<Item = String>) {
+        if let Some(pending) = self.pending.iter_mut().find(|pending| pending.tx_hash == *tx_hash) {
+            for relay in relays {
+                if !pending.relays.contains(&relay) {
+                    pending.relays.push(relay);
+                }
+            }
+        }
+    }
+
+    pub fn is_pending(&self, tx_hash: &TxHash) -> bool {
+        self.pending.iter().any(|pending| pending.tx_hash == *tx_hash)
+    }
+
+    pub fn pending(&self) -> &Vec<BundleInclusion> {
+        &self.pending
+    }
+
+    /// Resolved records, most recent first
+    pub fn resolved(&self) -> impl Iterator<Item = &BundleInclusion> {
+        self.resolved.iter().rev()
+    }
+
+    /// Moves pending record to resolved history after it was found in a block
+    pub fn resolve(&mut self, tx_hash: &TxHash, update: impl FnOnce(&mut BundleInclusion)) -> Option<BundleInclusion> {
+        let idx = self.pending.iter().position(|pending| pending.tx_hash == *tx_hash)?;
+        let mut record = self.pending.remove(idx);
+        update(&mut record);
+        self.push_resolved(record.clone());
+        Some(record)
+    }
+
+    /// Marks pending records which target block passed as missed
+    pub fn expire(&mut self, block_number: BlockNumber) -> Vec<BundleInclusion> {
+        let (expired, pending): (Vec<BundleInclusion>, Vec<BundleInclusion>) =
+            self.pending.drain(..).partition(|pending| pending.target_block + BUNDLE_INCLUSION_BLOCKS <= block_number);
+        self.pending = pending;
+
+        let mut missed = Vec::new();
+        for mut record in expired {
+            record.status = BundleInclusionStatus::Missed;
+            missed.push(record.clone());
+            self.push_resolved(record);
+        }
+        missed
+    }
+
+    pub fn stats(&self) -> BundleInclusionStats {
+        let mut stats = BundleInclusionStats { pending: self.pending.len(), ..BundleInclusionStats::default() };
+        for record in self.resolved.iter() {
+            match record.status {
+                BundleInclusionStatus::Included => stats.included += 1,
+                BundleInclusionStatus::Reverted => stats.reverted += 1,
+                BundleInclusionStatus::Missed => stats.missed += 1,
+                BundleInclusionStatus::Pending => {}
+            }
+            if record.status != BundleInclusionStatus::Missed {
+                stats.expected_profit_eth += record.expected_net_profit_eth();
+                stats.realized_profit_eth += record.realized_profit_eth.unwrap_or_default();
+                stats.gas_cost += record.gas_cost.unwrap_or_default();
+            }
+        }
+        stats
+    }
+
+    fn push_resolved(&mut self, record: BundleInclusion) {
+        if self.resolved.len() >= BUNDLE_INCLUSION_HISTORY_LEN {
+            self.resolved.pop_front();
+        }
+        self.resolved.push_back(record);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bundle_inclusion_history() {
+        let mut history = BundleInclusionHistory::new();
+
+        let mut record = BundleInclusion::new(TxHash::repeat_byte(1), 10, U256::from(100), U256::from(60));
+        record.relays = vec!["relay_a".to_string()];
+        history.add_pending(record.clone());
+        record.relays = vec!["relay_a".to_string(), "relay_b".to_string()];
+        history.add_pending(record);
+        history.add_pending(BundleInclusion::new(TxHash::repeat_byte(2), 10, U256::from(50), U256::from(30)));
+        history.add_relays(&TxHash::repeat_byte(2), vec!["relay_c".to_string()]);
+        history.add_relays(&TxHash::repeat_byte(3), vec!["relay_c".to_string()]);
+
+        assert_eq!(history.pending().len(), 2);
+        assert_eq!(history.pending()[0].relays, vec!["relay_a".to_string(), "relay_b".to_string()]);
+        assert_eq!(history.pending()[1].relays, vec!["relay_c".to_string()]);
+
+        let included = history
+            .resolve(&TxHash::repeat_byte(1), |record| {
+                record.status = BundleInclusionStatus::Included;
+                record.included_block = Some(10);
+                record.tx_index = Some(3);
+                record.realized_profit_eth = Some(I256::try_from(35).unwrap());
+                record.gas_cost = Some(U256::from(5));
+            })
+            .unwrap();
+        assert_eq!(included.tx_index, Some(3));
+        assert!(history.resolve(&TxHash::repeat_byte(1), |_| {}).is_none());
+
+        assert!(history.expire(11).is_empty());
+        let missed = history.expire(12);
+        assert_eq!(missed.len(), 1);
+        assert_eq!(missed[0].tx_hash, TxHash::repeat_byte(2));
+        assert!(!history.is_pending(&TxHash::repeat_byte(2)));
+
+        let stats = history.stats();
+        assert_eq!(stats.pending, 0);
+        assert_eq!(stats.included, 1);
+        assert_eq!(stats.missed, 1);
+        assert_eq!(stats.expected_profit_eth, I256::try_from(40).unwrap());
+        assert_eq!(stats.realized_profit_eth, I256::try_from(35).unwrap());
+        assert_eq!(stats.gas_cost, U256::from(5));
+        assert_eq!(history.resolved().next().unwrap().status, BundleInclusionStatus::Missed);
+    }
+}
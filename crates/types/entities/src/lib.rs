@@ -4,33 +4,44 @@ extern crate core;
 
 pub use account_nonce_balance::{AccountNonceAndBalanceState, AccountNonceAndBalances};
 pub use block_history::{BlockHistory, BlockHistoryEntry, BlockHistoryManager, BlockHistoryState};
+pub use bundle_inclusion::{
+    BundleInclusion, BundleInclusionHistory, BundleInclusionStats, BundleInclusionStatus, BUNDLE_INCLUSION_BLOCKS,
+    BUNDLE_INCLUSION_HISTORY_LEN,
+};
 pub use calculation_result::CalculationResult;
 pub use datafetcher::{DataFetcher, FetchState};
 pub use keystore::KeyStore;
 pub use latest_block::LatestBlock;
 pub use market::Market;
 pub use market_state::MarketState;
-pub use mock_pool::MockPool;
+pub use mock_pool::{MockPool, MockUniswapV2Pool};
 pub use pool::{get_protocol_by_factory, Pool, PoolAbiEncoder, PoolClass, PoolProtocol, PoolWrapper, PreswapRequirement};
 pub use pool_id::PoolId;
 pub use pool_loader::{PoolLoader, PoolLoaders};
+pub use remote_signer::TxSignerRemote;
 pub use signers::{LoomTxSigner, TxSignerEth, TxSigners};
 pub use swap::Swap;
 pub use swap_direction::SwapDirection;
 pub use swap_encoder::SwapEncoder;
 pub use swap_error::{EstimationError, SwapError};
 pub use swap_line::{SwapAmountType, SwapLine};
+pub use swap_optimizer::{
+    BrentOptimizer, GoldenSectionOptimizer, OptimizerResult, StepOptimizer, SwapLineOptimizer, SwapOptimizer, SwapOptimizerKind,
+    DEFAULT_OPTIMIZER_MAX_ITERATIONS, DEFAULT_OPTIMIZER_TOLERANCE,
+};
 pub use swap_path::{SwapPath, SwapPaths};
 pub use swap_path_builder::build_swap_path_vec;
 pub use swap_step::SwapStep;
 pub use token::{Token, TokenWrapper};
 
 mod block_history;
+mod bundle_inclusion;
 mod latest_block;
 mod market;
 mod market_state;
 mod pool;
 mod swap_line;
+mod swap_optimizer;
 mod swap_path;
 mod token;
 
@@ -39,6 +50,7 @@ pub mod required_state;
 mod swap_path_builder;
 mod swap_step;
 
+mod remote_signer;
 mod signers;
 
 mod keystore;
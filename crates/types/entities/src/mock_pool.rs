@@ -2,8 +2,8 @@ use crate::pool_id::PoolId;
 use crate::required_state::RequiredState;
 use crate::{Pool, PoolAbiEncoder, PoolClass, PoolProtocol, PreswapRequirement, SwapDirection};
 use alloy_primitives::{Address, U256};
-use eyre::ErrReport;
 use eyre::Result;
+use eyre::{eyre, ErrReport};
 use revm::primitives::Env;
 use revm::DatabaseRef;
 use std::any::Any;
@@ -104,3 +104,123 @@ impl Pool for MockPool {
         PreswapRequirement::Base
     }
 }
+
+/// Constant product pool with fixed reserves and 0.3% fee, calculates amounts without state
+#[derive(Clone)]
+pub struct MockUniswapV2Pool {
+    pub(crate) token0: Address,
+    pub(crate) token1: Address,
+    pub(crate) address: Address,
+    pub(crate) reserve0: U256,
+    pub(crate) reserve1: U256,
+}
+
+impl MockUniswapV2Pool {
+    const GAS_USED: u64 = 100_000;
+
+    pub fn new(token0: Address, token1: Address, address: Address, reserve0: U256, reserve1: U256) -> Self {
+        Self { token0, token1, address, reserve0, reserve1 }
+    }
+
+    fn reserves(&self, token_address_from: &Address, token_address_to: &Address) -> Result<(U256, U256)> {
+        if *token_address_from == self.token0 && *token_address_to == self.token1 {
+            Ok((self.reserve0, self.reserve1))
+        } else if *token_address_from == self.token1 && *token_address_to == self.token0 {
+            Ok((self.reserve1, self.reserve0))
+        } else {
+            Err(eyre!("TOKEN_NOT_FOUND"))
+        }
+    }
+}
+
+impl Pool for MockUniswapV2Pool {
+    fn as_any<'a>(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_class(&self) -> PoolClass {
+        PoolClass::UniswapV2
+    }
+
+    fn get_protocol(&self) -> PoolProtocol {
+        PoolProtocol::UniswapV2
+    }
+
+    fn get_address(&self) -> Address {
+        self.address
+    }
+
+    fn get_pool_id(&self) -> PoolId {
+        PoolId::Address(self.address)
+    }
+
+    fn get_fee(&self) -> U256 {
+        U256::from(9970)
+    }
+
+    fn get_tokens(&self) -> Vec<Address> {
+        vec![self.token0, self.token1]
+    }
+
+    fn get_swap_directions(&self) -> Vec<SwapDirection> {
+        vec![(self.token0, self.token1).into(), (self.token1, self.token0).into()]
+    }
+
+    fn calculate_out_amount(
+        &self,
+        state: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        in_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let (reserve_in, reserve_out) = self.reserves(token_address_from, token_address_to)?;
+        let amount_in_with_fee = in_amount * U256::from(997);
+        let out_amount = amount_in_with_fee * reserve_out / (reserve_in * U256::from(1000) + amount_in_with_fee);
+        Ok((out_amount, Self::GAS_USED))
+    }
+
+    fn calculate_in_amount(
+        &self,
+        state: &dyn DatabaseRef<Error = ErrReport>,
+        env: Env,
+        token_address_from: &Address,
+        token_address_to: &Address,
+        out_amount: U256,
+    ) -> Result<(U256, u64), ErrReport> {
+        let (reserve_in, reserve_out) = self.reserves(token_address_from, token_address_to)?;
+        if out_amount >= reserve_out {
+            return Err(eyre!("RESERVE_EXCEEDED"));
+        }
+        let in_amount = reserve_in * out_amount * U256::from(1000) / ((reserve_out - out_amount) * U256::from(997)) + U256::from(1);
+        Ok((in_amount, Self::GAS_USED))
+    }
+
+    fn can_flash_swap(&self) -> bool {
+        true
+    }
+
+    fn can_calculate_in_amount(&self) -> bool {
+        true
+    }
+
+    fn get_abi_encoder(&self) -> Option<&dyn PoolAbiEncoder> {
+        None
+    }
+
+    fn get_read_only_cell_vec(&self) -> Vec<U256> {
+        Vec::new()
+    }
+
+    fn get_state_required(&self) -> Result<RequiredState> {
+        Ok(RequiredState::new())
+    }
+
+    fn is_native(&self) -> bool {
+        false
+    }
+
+    fn preswap_requirement(&self) -> PreswapRequirement {
+        PreswapRequirement::Base
+    }
+}
@@ -179,7 +179,7 @@ impl<LDT: LoomDataTypes> Eq for PoolWrapper<LDT> {}
 
 impl<LDT: LoomDataTypes> Ord for PoolWrapper<LDT> {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.get_address().cmp(&other.get_address())
+        self.get_pool_id().cmp(&other.get_pool_id())
     }
 }
 
@@ -197,13 +197,13 @@ impl<LDT: LoomDataTypes> Debug for PoolWrapper<LDT> {
 
 impl<LDT: LoomDataTypes> Hash for PoolWrapper<LDT> {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        self.get_address().hash(state)
+        self.get_pool_id().hash(state)
     }
 }
 
 impl<LDT: LoomDataTypes> PartialEq for PoolWrapper<LDT> {
     fn eq(&self, other: &Self) -> bool {
-        self.pool.get_address() == other.pool.get_address()
+        self.pool.get_pool_id() == other.pool.get_pool_id()
     }
 }
 
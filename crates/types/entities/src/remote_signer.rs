@@ -0,0 +1,183 @@
+use alloy_consensus::TxEnvelope;
+use alloy_network::eip2718::Decodable2718;
+use alloy_primitives::{Address, Bytes};
+use alloy_provider::{Provider, RootProvider};
+use alloy_rpc_types::{Transaction, TransactionInput};
+use eyre::{eyre, Result};
+use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use tracing::error;
+
+use crate::LoomTxSigner;
+
+/// Signer delegating signing to a remote service with web3signer compatible `eth_signTransaction` api, keys never leave the service
+#[derive(Clone)]
+pub struct TxSignerRemote {
+    address: Address,
+    url: String,
+    client: RootProvider,
+}
+
+impl fmt::Debug for TxSignerRemote {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TxSignerRemote").field("address", &self.address.to_string()).field("url", &self.url).finish()
+    }
+}
+
+impl TxSignerRemote {
+    pub fn new(url: &str, address: Address) -> Result<TxSignerRemote> {
+        let client = RootProvider::new_http(url.parse()?);
+        Ok(TxSignerRemote { address, url: url.to_string(), client })
+    }
+
+    /// Creates signers for all accounts returned by `eth_accounts` of the remote service
+    pub async fn from_accounts(url: &str) -> Result<Vec<TxSignerRemote>> {
+        let client = RootProvider::new_http(url.parse()?);
+        let accounts = client.get_accounts().await?;
+        Ok(accounts.into_iter().map(|address| TxSignerRemote { address, url: url.to_string(), client: client.clone() }).collect())
+    }
+}
+
+impl LoomTxSigner<LoomDataTypesEthereum> for TxSignerRemote {
+    fn address(&self) -> <LoomDataTypesEthereum as LoomDataTypes>::Address {
+        self.address
+    }
+
+    fn sign<'a>(
+        &'a self,
+        tx_req: <LoomDataTypesEthereum as LoomDataTypes>::TransactionRequest,
+    ) -> Pin<Box<dyn Future<Output = Result<<LoomDataTypesEthereum as LoomDataTypes>::Transaction>> + Send + 'a>> {
+        let fut = async move {
+            let mut tx_req = tx_req;
+            tx_req.from = Some(self.address);
+            // web3signer expects call data in data field
+            tx_req.input = TransactionInput::both(tx_req.input.into_input().unwrap_or_default());
+
+            let signed_tx_bytes: Bytes = self.client.client().request("eth_signTransaction", (tx_req,)).await.map_err(|e| {
+                error!(url = self.url, address = %self.address, error = %e, "Remote signer request failed");
+                eyre!("REMOTE_SIGNER_REQUEST_FAILED")
+            })?;
+
+            let tx_env = TxEnvelope::decode_2718(&mut signed_tx_bytes.as_ref()).map_err(|_| eyre!("REMOTE_SIGNER_INVALID_TRANSACTION"))?;
+            let from = tx_env.recover_signer().map_err(|_| eyre!("REMOTE_SIGNER_INVALID_SIGNATURE"))?;
+            if from != self.address {
+                error!(url = self.url, address = %self.address, %from, "Remote signer signed with wrong key");
+                return Err(eyre!("REMOTE_SIGNER_ADDRESS_MISMATCH"));
+            }
+
+            Ok(Transaction {
+                inner: tx_env,
+                block_hash: None,
+                block_number: None,
+                transaction_index: None,
+                effective_gas_price: None,
+                from,
+            })
+        };
+        Box::pin(fut)
+    }
+
+    fn sign_sync(
+        &self,
+        _tx_req: <LoomDataTypesEthereum as LoomDataTypes>::TransactionRequest,
+    ) -> Result<<LoomDataTypesEthereum as LoomDataTypes>::Transaction> {
+        Err(eyre!("REMOTE_SIGNER_SYNC_NOT_SUPPORTED"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::TxSignerEth;
+    use alloy_network::eip2718::Encodable2718;
+    use alloy_network::TransactionBuilder;
+    use alloy_primitives::{hex, TxHash, B256};
+    use alloy_rpc_types::TransactionRequest;
+    use alloy_signer_local::PrivateKeySigner;
+    use loom_types_blockchain::LoomTx;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    async fn handle_request(mut stream: TcpStream, signer: TxSignerEth) -> Result<()> {
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 4096];
+        let (header_len, content_len) = loop {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                return Ok(());
+            }
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+                let headers = String::from_utf8_lossy(&buf[..pos]).to_lowercase();
+                let content_len = headers
+                    .lines()
+                    .find_map(|line| line.strip_prefix("content-length:").map(|v| v.trim().parse::<usize>()))
+                    .transpose()?
+                    .unwrap_or_default();
+                break (pos + 4, content_len);
+            }
+        };
+        while buf.len() < header_len + content_len {
+            let n = stream.read(&mut chunk).await?;
+            buf.extend_from_slice(&chunk[..n]);
+        }
+
+        let request: serde_json::Value = serde_json::from_slice(&buf[header_len..header_len + content_len])?;
+        let result = match request["method"].as_str() {
+            Some("eth_accounts") => serde_json::json!([signer.address()]),
+            Some("eth_signTransaction") => {
+                let tx_req: TransactionRequest = serde_json::from_value(request["params"][0].clone())?;
+                let tx = signer.sign(tx_req).await?;
+                serde_json::json!(Bytes::from(tx.inner.encoded_2718()))
+            }
+            _ => serde_json::Value::Null,
+        };
+        let body = serde_json::json!({"jsonrpc": "2.0", "id": request["id"], "result": result}).to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        stream.write_all(response.as_bytes()).await?;
+        Ok(())
+    }
+
+    // Stub of remote signing service holding the key
+    async fn start_stub_signer(wallet: PrivateKeySigner) -> Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}", listener.local_addr()?);
+        let signer = TxSignerEth::new(wallet);
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_request(stream, signer.clone()));
+            }
+        });
+        Ok(url)
+    }
+
+    #[tokio::test]
+    async fn test_remote_sign() -> Result<()> {
+        let wallet = PrivateKeySigner::from_bytes(&B256::repeat_byte(1))?;
+        let url = start_stub_signer(wallet.clone()).await?;
+
+        let signers = TxSignerRemote::from_accounts(&url).await?;
+        assert_eq!(signers.len(), 1);
+        assert_eq!(signers[0].address(), wallet.address());
+
+        let tx_req = TransactionRequest::default()
+            .with_to(Address::ZERO)
+            .with_nonce(1)
+            .with_gas_limit(1)
+            .with_max_fee_per_gas(1)
+            .with_max_priority_fee_per_gas(1);
+        let tx = signers[0].sign(tx_req.clone()).await?;
+        assert_eq!(tx.tx_hash(), TxHash::from(hex!("a43d09cb299eb6269f5a63fb10ea078c649cbf6a5f159cfd5b6f4be7ad0dfcfd")));
+        assert!(signers[0].sign_sync(tx_req.clone()).is_err());
+
+        let wrong_signer = TxSignerRemote::new(&url, Address::repeat_byte(2))?;
+        assert!(wrong_signer.sign(tx_req).await.is_err());
+        Ok(())
+    }
+}
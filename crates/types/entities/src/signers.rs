@@ -130,6 +130,10 @@ impl<LDT: LoomDataTypes> TxSigners<LDT> {
         TxSigners { signers: IndexMap::new() }
     }
 
+    pub fn add_signer(&mut self, signer: Arc<dyn LoomTxSigner<LDT>>) {
+        self.signers.insert(signer.address(), signer);
+    }
+
     pub fn len(&self) -> usize {
         self.signers.len()
     }
@@ -1,4 +1,4 @@
-use crate::tips::Tips;
+use crate::tips::{Tips, TipsPolicy};
 use crate::Swap;
 use alloy_primitives::{Address, BlockNumber, Bytes, U256};
 use eyre::Result;
@@ -8,6 +8,8 @@ use std::sync::Arc;
 pub trait SwapEncoder {
     /// Encodes Swap
     ///
+    /// - tips_pct - share of profit requested by the strategy
+    /// - tips_policy - policy of the strategy to calculate share of profit paid as tips
     /// - next_block_number - number of the next block
     /// - next_block_gas_price - base_fee + priority fee for transaction
     /// - sender_address - EOA of of the transaction
@@ -19,6 +21,7 @@ pub trait SwapEncoder {
         &self,
         swap: Swap,
         tips_pct: Option<u32>,
+        tips_policy: Option<&dyn TipsPolicy>,
         next_block_number: Option<BlockNumber>,
         gas_cost: Option<U256>,
         sender_address: Option<Address>,
@@ -2,15 +2,14 @@ use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
+use crate::swap_optimizer::{StepOptimizer, SwapLineOptimizer};
+use crate::swap_path::SwapPath;
+use crate::{CalculationResult, PoolId, PoolWrapper, SwapError, SwapStep, Token};
 use alloy_primitives::{I256, U256};
 use eyre::{eyre, ErrReport, Report, Result};
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
 use revm::primitives::Env;
 use revm::DatabaseRef;
-use tracing::debug;
-
-use crate::swap_path::SwapPath;
-use crate::{CalculationResult, PoolId, PoolWrapper, SwapError, SwapStep, Token};
 
 #[derive(Debug, Clone, Default)]
 pub enum SwapAmountType<LDT: LoomDataTypes = LoomDataTypesEthereum> {
@@ -59,10 +58,12 @@ pub struct SwapLine<LDT: LoomDataTypes = LoomDataTypesEthereum> {
     pub amount_out: SwapAmountType<LDT>,
     /// The in and out amounts for each swap step
     pub calculation_results: Vec<CalculationResult>,
-    /// Output token of the swap
+    /// Receiver of the swap output, output stays on the swap executor if not set
     pub swap_to: Option<LDT::Address>,
     /// Gas used for the swap
     pub gas_used: Option<u64>,
+    /// Output token balance of the swap executor before the swap, exchange output is checked against it
+    pub out_balance_before: Option<U256>,
 }
 
 impl<LDT: LoomDataTypes> Default for SwapLine<LDT> {
@@ -74,6 +75,7 @@ impl<LDT: LoomDataTypes> Default for SwapLine<LDT> {
             calculation_results: Vec::default(),
             swap_to: None,
             gas_used: None,
+            out_balance_before: None,
         }
     }
 }
@@ -237,6 +239,7 @@ impl<LDT: LoomDataTypes> SwapLine<LDT> {
             calculation_results: vec![],
             swap_to: None,
             gas_used: None,
+            out_balance_before: None,
         };
         let second = SwapLine::<LDT> {
             path: SwapPath::new(self.tokens()[pool_index..].to_vec(), self.pools()[pool_index..].to_vec()),
@@ -245,6 +248,7 @@ impl<LDT: LoomDataTypes> SwapLine<LDT> {
             calculation_results: vec![],
             swap_to: None,
             gas_used: None,
+            out_balance_before: None,
         };
         Ok((first, second))
     }
@@ -436,96 +440,18 @@ impl<LDT: LoomDataTypes> SwapLine<LDT> {
         env: Env,
         in_amount: U256,
     ) -> Result<&mut Self, SwapError<LDT>> {
-        let mut current_in_amount = in_amount;
-        let mut best_profit: Option<I256> = None;
-        let mut current_step = U256::from(10000);
-        let mut inc_direction = true;
-        let mut first_step_change = false;
-        let mut next_amount = current_in_amount;
-        let mut prev_in_amount = U256::ZERO;
-        let mut counter = 0;
-        let denominator = U256::from(1000);
-
-        loop {
-            counter += 1;
-            //let next_amount  = current_in_amount + (current_in_amount * current_step / 10000);
-
-            if counter > 30 {
-                debug!("optimize_swap_path_in_amount iterations exceeded : {self} {current_in_amount} {current_step}");
-                return Ok(self);
-            }
-
-            let (current_out_amount, current_gas_used, calculation_results) =
-                match self.calculate_with_in_amount(state, env.clone(), next_amount) {
-                    Ok(ret) => ret,
-                    Err(e) => {
-                        if counter == 1 {
-                            // break if first swap already fails
-                            return Err(e);
-                        }
-                        (U256::ZERO, 0, vec![])
-                    }
-                };
-
-            let current_profit = I256::from_raw(current_out_amount) - I256::from_raw(next_amount);
-
-            if best_profit.is_none() {
-                best_profit = Some(current_profit);
-                self.amount_in = SwapAmountType::Set(next_amount);
-                self.amount_out = SwapAmountType::Set(current_out_amount);
-                self.gas_used = Some(current_gas_used);
-                self.calculation_results = calculation_results;
-                current_in_amount = next_amount;
-                if current_out_amount.is_zero() || current_profit.is_negative() {
-                    return Ok(self);
-                }
-            } else if best_profit.unwrap() > current_profit || current_out_amount.is_zero()
-            /*|| next_profit < current_profit*/
-            {
-                if first_step_change && inc_direction && current_step < denominator {
-                    inc_direction = false;
-                    //TODO : Check why not used
-                    next_amount = prev_in_amount;
-                    current_in_amount = prev_in_amount;
-                    first_step_change = true;
-                    //debug!("inc direction changed {} {} {}", next_amount, current_profit, bestprofit.unwrap());
-                } else if first_step_change && !inc_direction {
-                    //TODO : Check why is self aligned
-                    inc_direction = true;
-                    current_step /= U256::from(10);
-                    best_profit = Some(current_profit);
-                    first_step_change = true;
-                    //debug!("dec direction changed  {} {} {}", next_amount, current_profit, bestprofit.unwrap());
-
-                    if current_step == U256::from(1) {
-                        break;
-                    }
-                } else {
-                    current_step /= U256::from(10);
-                    first_step_change = true;
-                    if current_step == U256::from(1) {
-                        break;
-                    }
-                }
-            } else {
-                best_profit = Some(current_profit);
-                self.amount_in = SwapAmountType::Set(next_amount);
-                self.amount_out = SwapAmountType::Set(current_out_amount);
-                self.gas_used = Some(current_gas_used);
-                self.calculation_results = calculation_results;
-                current_in_amount = next_amount;
-                first_step_change = false;
-            }
-
-            prev_in_amount = current_in_amount;
-            if inc_direction {
-                next_amount = current_in_amount + (current_in_amount * current_step / denominator);
-            } else {
-                next_amount = current_in_amount - (current_in_amount * current_step / denominator);
-            }
-            //trace!("opt step : {} direction {} first_step {} step : {} current_in_amount : {} next_amount: {} profit : {} {}", counter, inc_direction, first_step_change,  current_step, current_in_amount , next_amount, current_profit, bestprofit.unwrap());
-        }
+        self.optimize_with(&StepOptimizer::new(), state, env, in_amount)
+    }
 
+    /// Optimize the swap line starting from a given in amount with the optimizer provided
+    pub fn optimize_with<O: SwapLineOptimizer<LDT>, DB: DatabaseRef<Error = ErrReport>>(
+        &mut self,
+        optimizer: &O,
+        state: &DB,
+        env: Env,
+        in_amount: U256,
+    ) -> Result<&mut Self, SwapError<LDT>> {
+        optimizer.optimize(self, state, env, in_amount)?;
         Ok(self)
     }
 }
@@ -557,6 +483,7 @@ mod tests {
             calculation_results: vec![],
             swap_to: Some(Address::default()),
             gas_used: Some(10000),
+            out_balance_before: None,
         };
 
         (pool1, pool2, swap_line)
@@ -0,0 +1,645 @@
+use alloy_primitives::{I256, U256};
+use eyre::ErrReport;
+use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
+use revm::primitives::Env;
+use revm::DatabaseRef;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::{CalculationResult, SwapAmountType, SwapError, SwapLine};
+
+/// Default convergence tolerance, in units of the input token
+pub const DEFAULT_OPTIMIZER_TOLERANCE: f64 = 0.0001;
+/// Default limit of swap line evaluations
+pub const DEFAULT_OPTIMIZER_MAX_ITERATIONS: usize = 64;
+
+// 1/phi scaled by 10^12
+const INV_PHI_NUMERATOR: u64 = 618_033_988_750;
+const INV_PHI_DENOMINATOR: u64 = 1_000_000_000_000;
+// 1 - 1/phi
+const CGOLD: f64 = 0.381_966_011_250_105;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SwapOptimizerKind {
+    #[default]
+    Step,
+    GoldenSection,
+    Brent,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct OptimizerResult {
+    /// Number of swap line evaluations
+    pub iterations: usize,
+    /// Best profit found, in input token. Gas cost is subtracted for gas aware optimizers
+    pub profit: I256,
+}
+
+/// Searches for the in amount that maximizes the profit of a swap line.
+/// On success the swap line holds amounts, gas and calculation results of the best point found.
+pub trait SwapLineOptimizer<LDT: LoomDataTypes = LoomDataTypesEthereum> {
+    fn optimize<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        swap_line: &mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        in_amount: U256,
+    ) -> Result<OptimizerResult, SwapError<LDT>>;
+}
+
+/// Optimizer selected at runtime, e.g. from strategy config
+#[derive(Clone, Debug)]
+pub enum SwapOptimizer {
+    Step(StepOptimizer),
+    GoldenSection(GoldenSectionOptimizer),
+    Brent(BrentOptimizer),
+}
+
+impl SwapOptimizer {
+    pub fn new(kind: SwapOptimizerKind, base_fee: u64) -> Self {
+        match kind {
+            SwapOptimizerKind::Step => SwapOptimizer::Step(StepOptimizer::new()),
+            SwapOptimizerKind::GoldenSection => SwapOptimizer::GoldenSection(GoldenSectionOptimizer::new().with_base_fee(base_fee)),
+            SwapOptimizerKind::Brent => SwapOptimizer::Brent(BrentOptimizer::new().with_base_fee(base_fee)),
+        }
+    }
+}
+
+impl Default for SwapOptimizer {
+    fn default() -> Self {
+        SwapOptimizer::Step(StepOptimizer::new())
+    }
+}
+
+impl<LDT: LoomDataTypes> SwapLineOptimizer<LDT> for SwapOptimizer {
+    fn optimize<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        swap_line: &mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        in_amount: U256,
+    ) -> Result<OptimizerResult, SwapError<LDT>> {
+        match self {
+            SwapOptimizer::Step(optimizer) => optimizer.optimize(swap_line, state, env, in_amount),
+            SwapOptimizer::GoldenSection(optimizer) => optimizer.optimize(swap_line, state, env, in_amount),
+            SwapOptimizer::Brent(optimizer) => optimizer.optimize(swap_line, state, env, in_amount),
+        }
+    }
+}
+
+/// Step search that multiplies the in amount by a decreasing step. Profit is not gas aware.
+#[derive(Clone, Debug)]
+pub struct StepOptimizer {
+    max_iterations: usize,
+}
+
+impl StepOptimizer {
+    pub fn new() -> Self {
+        Self { max_iterations: 30 }
+    }
+
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self { max_iterations }
+    }
+}
+
+impl Default for StepOptimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<LDT: LoomDataTypes> SwapLineOptimizer<LDT> for StepOptimizer {
+    fn optimize<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        swap_line: &mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        in_amount: U256,
+    ) -> Result<OptimizerResult, SwapError<LDT>> {
+        let mut current_in_amount = in_amount;
+        let mut best_profit: Option<I256> = None;
+        let mut current_step = U256::from(10000);
+        let mut inc_direction = true;
+        let mut first_step_change = false;
+        let mut next_amount = current_in_amount;
+        let mut prev_in_amount = U256::ZERO;
+        let mut counter = 0;
+        let denominator = U256::from(1000);
+
+        loop {
+            counter += 1;
+
+            if counter > self.max_iterations {
+                debug!("optimize_swap_path_in_amount iterations exceeded : {swap_line} {current_in_amount} {current_step}");
+                break;
+            }
+
+            let (current_out_amount, current_gas_used, calculation_results) =
+                match swap_line.calculate_with_in_amount(state, env.clone(), next_amount) {
+                    Ok(ret) => ret,
+                    Err(e) => {
+                        if counter == 1 {
+                            // break if first swap already fails
+                            return Err(e);
+                        }
+                        (U256::ZERO, 0, vec![])
+                    }
+                };
+
+            let current_profit = I256::from_raw(current_out_amount) - I256::from_raw(next_amount);
+
+            if best_profit.is_none() {
+                best_profit = Some(current_profit);
+                swap_line.amount_in = SwapAmountType::Set(next_amount);
+                swap_line.amount_out = SwapAmountType::Set(current_out_amount);
+                swap_line.gas_used = Some(current_gas_used);
+                swap_line.calculation_results = calculation_results;
+                current_in_amount = next_amount;
+                if current_out_amount.is_zero() || current_profit.is_negative() {
+                    break;
+                }
+            } else if best_profit.unwrap() > current_profit || current_out_amount.is_zero() {
+                if first_step_change && inc_direction && current_step < denominator {
+                    // increasing overshot right after a step change, go back and search below the best amount
+                    inc_direction = false;
+                    next_amount = prev_in_amount;
+                    current_in_amount = prev_in_amount;
+                    first_step_change = true;
+                } else if first_step_change && !inc_direction {
+                    inc_direction = true;
+                    current_step /= U256::from(10);
+                    best_profit = Some(current_profit);
+                    first_step_change = true;
+
+                    if current_step == U256::from(1) {
+                        break;
+                    }
+                } else {
+                    current_step /= U256::from(10);
+                    first_step_change = true;
+                    if current_step == U256::from(1) {
+                        break;
+                    }
+                }
+            } else {
+                best_profit = Some(current_profit);
+                swap_line.amount_in = SwapAmountType::Set(next_amount);
+                swap_line.amount_out = SwapAmountType::Set(current_out_amount);
+                swap_line.gas_used = Some(current_gas_used);
+                swap_line.calculation_results = calculation_results;
+                current_in_amount = next_amount;
+                first_step_change = false;
+            }
+
+            prev_in_amount = current_in_amount;
+            if inc_direction {
+                next_amount = current_in_amount + (current_in_amount * current_step / denominator);
+            } else {
+                next_amount = current_in_amount - (current_in_amount * current_step / denominator);
+            }
+        }
+
+        let profit = match (swap_line.amount_in, swap_line.amount_out) {
+            (SwapAmountType::Set(amount_in), SwapAmountType::Set(amount_out)) => I256::from_raw(amount_out) - I256::from_raw(amount_in),
+            _ => I256::ZERO,
+        };
+
+        Ok(OptimizerResult { iterations: counter.min(self.max_iterations), profit })
+    }
+}
+
+/// Golden-section search over a bracket around the maximum of gas aware profit
+#[derive(Clone, Debug)]
+pub struct GoldenSectionOptimizer {
+    base_fee: u64,
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl GoldenSectionOptimizer {
+    pub fn new() -> Self {
+        Self { base_fee: 0, tolerance: DEFAULT_OPTIMIZER_TOLERANCE, max_iterations: DEFAULT_OPTIMIZER_MAX_ITERATIONS }
+    }
+
+    /// Gas price used to subtract gas cost from profit
+    pub fn with_base_fee(self, base_fee: u64) -> Self {
+        Self { base_fee, ..self }
+    }
+
+    /// Width of the final bracket, in units of the input token
+    pub fn with_tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self { max_iterations, ..self }
+    }
+}
+
+impl Default for GoldenSectionOptimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<LDT: LoomDataTypes> SwapLineOptimizer<LDT> for GoldenSectionOptimizer {
+    fn optimize<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        swap_line: &mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        in_amount: U256,
+    ) -> Result<OptimizerResult, SwapError<LDT>> {
+        let mut evaluator = ProfitEvaluator::new(swap_line, state, env, self.base_fee);
+        let tolerance = evaluator.tolerance_amount(self.tolerance);
+
+        if let Some((mut a, _, mut b)) = evaluator.bracket(in_amount, self.max_iterations)? {
+            let inv_phi = |width: U256| width * U256::from(INV_PHI_NUMERATOR) / U256::from(INV_PHI_DENOMINATOR);
+
+            let mut c = b - inv_phi(b - a);
+            let mut d = a + inv_phi(b - a);
+            let mut fc = evaluator.profit(c);
+            let mut fd = evaluator.profit(d);
+
+            while b - a > tolerance && c < d && evaluator.iterations < self.max_iterations {
+                if fc > fd {
+                    b = d;
+                    d = c;
+                    fd = fc;
+                    c = b - inv_phi(b - a);
+                    fc = evaluator.profit(c);
+                } else {
+                    a = c;
+                    c = d;
+                    fc = fd;
+                    d = a + inv_phi(b - a);
+                    fd = evaluator.profit(d);
+                }
+            }
+        }
+
+        evaluator.finish(swap_line)
+    }
+}
+
+/// Brent's method: parabolic interpolation with golden-section fallback over a bracket of gas aware profit
+#[derive(Clone, Debug)]
+pub struct BrentOptimizer {
+    base_fee: u64,
+    tolerance: f64,
+    max_iterations: usize,
+}
+
+impl BrentOptimizer {
+    pub fn new() -> Self {
+        Self { base_fee: 0, tolerance: DEFAULT_OPTIMIZER_TOLERANCE, max_iterations: DEFAULT_OPTIMIZER_MAX_ITERATIONS }
+    }
+
+    /// Gas price used to subtract gas cost from profit
+    pub fn with_base_fee(self, base_fee: u64) -> Self {
+        Self { base_fee, ..self }
+    }
+
+    /// Convergence tolerance of the in amount, in units of the input token
+    pub fn with_tolerance(self, tolerance: f64) -> Self {
+        Self { tolerance, ..self }
+    }
+
+    pub fn with_max_iterations(self, max_iterations: usize) -> Self {
+        Self { max_iterations, ..self }
+    }
+}
+
+impl Default for BrentOptimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<LDT: LoomDataTypes> SwapLineOptimizer<LDT> for BrentOptimizer {
+    fn optimize<DB: DatabaseRef<Error = ErrReport>>(
+        &self,
+        swap_line: &mut SwapLine<LDT>,
+        state: &DB,
+        env: Env,
+        in_amount: U256,
+    ) -> Result<OptimizerResult, SwapError<LDT>> {
+        let mut evaluator = ProfitEvaluator::new(swap_line, state, env, self.base_fee);
+        let tol1 = u256_to_f64(evaluator.tolerance_amount(self.tolerance));
+        let tol2 = 2.0 * tol1;
+
+        if let Some((lo, mid, hi)) = evaluator.bracket(in_amount, self.max_iterations)? {
+            let (mut a, mut b) = (u256_to_f64(lo), u256_to_f64(hi));
+            let mut x = u256_to_f64(mid);
+            let (mut w, mut v) = (x, x);
+            let mut fx = evaluator.cost(x);
+            let (mut fw, mut fv) = (fx, fx);
+            let mut d: f64 = 0.0;
+            let mut e: f64 = 0.0;
+
+            while evaluator.iterations < self.max_iterations {
+                let xm = 0.5 * (a + b);
+                if (x - xm).abs() <= tol2 - 0.5 * (b - a) {
+                    break;
+                }
+
+                let mut golden = true;
+                if e.abs() > tol1 {
+                    let r = (x - w) * (fx - fv);
+                    let mut q = (x - v) * (fx - fw);
+                    let mut p = (x - v) * q - (x - w) * r;
+                    q = 2.0 * (q - r);
+                    if q > 0.0 {
+                        p = -p;
+                    }
+                    q = q.abs();
+                    let e_prev = e;
+                    e = d;
+                    // written so that NaN from failed calculations falls back to golden section
+                    if p.abs() < (0.5 * q * e_prev).abs() && p > q * (a - x) && p < q * (b - x) {
+                        d = p / q;
+                        let u = x + d;
+                        if u - a < tol2 || b - u < tol2 {
+                            d = tol1.copysign(xm - x);
+                        }
+                        golden = false;
+                    }
+                }
+                if golden {
+                    e = if x >= xm { a - x } else { b - x };
+                    d = CGOLD * e;
+                }
+
+                let u = if d.abs() >= tol1 { x + d } else { x + tol1.copysign(d) };
+                let fu = evaluator.cost(u);
+
+                if fu <= fx {
+                    if u >= x {
+                        a = x;
+                    } else {
+                        b = x;
+                    }
+                    (v, fv) = (w, fw);
+                    (w, fw) = (x, fx);
+                    (x, fx) = (u, fu);
+                } else {
+                    if u < x {
+                        a = u;
+                    } else {
+                        b = u;
+                    }
+                    if fu <= fw || w == x {
+                        (v, fv) = (w, fw);
+                        (w, fw) = (u, fu);
+                    } else if fu <= fv || v == x || v == w {
+                        (v, fv) = (u, fu);
+                    }
+                }
+            }
+        }
+
+        evaluator.finish(swap_line)
+    }
+}
+
+struct Evaluation {
+    amount_in: U256,
+    amount_out: U256,
+    gas_used: u64,
+    calculation_results: Vec<CalculationResult>,
+    profit: I256,
+}
+
+/// Calculates gas aware profit of a swap line and keeps the best evaluated point
+struct ProfitEvaluator<'a, LDT: LoomDataTypes, DB> {
+    swap_line: SwapLine<LDT>,
+    state: &'a DB,
+    env: Env,
+    base_fee: u64,
+    iterations: usize,
+    best: Option<Evaluation>,
+}
+
+impl<'a, LDT: LoomDataTypes, DB: DatabaseRef<Error = ErrReport>> ProfitEvaluator<'a, LDT, DB> {
+    fn new(swap_line: &SwapLine<LDT>, state: &'a DB, env: Env, base_fee: u64) -> Self {
+        Self { swap_line: swap_line.clone(), state, env, base_fee, iterations: 0, best: None }
+    }
+
+    fn tolerance_amount(&self, tolerance: f64) -> U256 {
+        let decimals = self.swap_line.get_first_token().map_or(18, |token| token.get_decimals());
+        f64_to_u256(tolerance * 10f64.powi(decimals as i32)).max(U256::from(1))
+    }
+
+    /// Gas cost in input token, fails if the input token has no ETH price so gas negative trades are not reported as profitable
+    fn gas_cost(&self, gas_used: u64) -> Result<U256, SwapError<LDT>> {
+        if self.base_fee == 0 {
+            return Ok(U256::ZERO);
+        }
+        let gas_cost_eth = U256::from(gas_used) * U256::from(self.base_fee);
+        self.swap_line
+            .get_first_token()
+            .and_then(|token| token.calc_token_value_from_eth(gas_cost_eth))
+            .ok_or_else(|| self.swap_line.to_error("TOKEN_PRICE_NOT_SET".to_string()))
+    }
+
+    fn evaluate(&mut self, amount_in: U256) -> Result<I256, SwapError<LDT>> {
+        self.iterations += 1;
+        let (amount_out, gas_used, calculation_results) =
+            self.swap_line.calculate_with_in_amount(self.state, self.env.clone(), amount_in)?;
+        let profit = I256::from_raw(amount_out) - I256::from_raw(amount_in) - I256::from_raw(self.gas_cost(gas_used)?);
+
+        if self.best.as_ref().is_none_or(|best| profit > best.profit) {
+            self.best = Some(Evaluation { amount_in, amount_out, gas_used, calculation_results, profit });
+        }
+        Ok(profit)
+    }
+
+    /// Profit for the in amount, None if calculation failed. None compares less than any profit.
+    fn profit(&mut self, amount_in: U256) -> Option<I256> {
+        if amount_in.is_zero() {
+            return None;
+        }
+        self.evaluate(amount_in).ok()
+    }
+
+    /// Negated profit for minimization, failed calculations are treated as the worst value
+    fn cost(&mut self, amount_in: f64) -> f64 {
+        self.profit(f64_to_u256(amount_in)).map_or(f64::MAX, |profit| -i256_to_f64(profit))
+    }
+
+    /// Finds (lo, mid, hi) with profit(mid) not less than profit at both ends by doubling or halving the in amount.
+    /// Returns None without searching if out amount at the start amount does not exceed the in amount, gas cost is not considered.
+    fn bracket(&mut self, in_amount: U256, max_iterations: usize) -> Result<Option<(U256, U256, U256)>, SwapError<LDT>> {
+        // break if first swap already fails
+        let start_profit = Some(self.evaluate(in_amount)?);
+        if self.best.as_ref().is_some_and(|best| best.amount_out <= best.amount_in) {
+            return Ok(None);
+        }
+
+        let two = U256::from(2);
+        let mut lo = in_amount / two;
+        let mut mid = in_amount;
+        let mut f_mid = start_profit;
+        let mut hi = in_amount.saturating_mul(two);
+        let mut f_hi = self.profit(hi);
+
+        if f_hi > f_mid {
+            while self.iterations < max_iterations {
+                lo = mid;
+                (mid, f_mid) = (hi, f_hi);
+                hi = mid.saturating_mul(two);
+                f_hi = self.profit(hi);
+                if f_hi <= f_mid {
+                    break;
+                }
+            }
+        } else {
+            while self.iterations < max_iterations {
+                lo = mid / two;
+                let f_lo = self.profit(lo);
+                if f_lo <= f_mid || lo.is_zero() {
+                    break;
+                }
+                hi = mid;
+                (mid, f_mid) = (lo, f_lo);
+            }
+        }
+
+        Ok(Some((lo, mid, hi)))
+    }
+
+    fn finish(self, swap_line: &mut SwapLine<LDT>) -> Result<OptimizerResult, SwapError<LDT>> {
+        let Some(best) = self.best else {
+            return Err(swap_line.to_error("NOT_OPTIMIZED".to_string()));
+        };
+        swap_line.amount_in = SwapAmountType::Set(best.amount_in);
+        swap_line.amount_out = SwapAmountType::Set(best.amount_out);
+        swap_line.gas_used = Some(best.gas_used);
+        swap_line.calculation_results = best.calculation_results;
+
+        Ok(OptimizerResult { iterations: self.iterations, profit: best.profit })
+    }
+}
+
+fn u256_to_f64(value: U256) -> f64 {
+    value.as_limbs().iter().rev().fold(0f64, |acc, limb| acc * 18_446_744_073_709_551_616f64 + *limb as f64)
+}
+
+fn i256_to_f64(value: I256) -> f64 {
+    if value.is_negative() {
+        -u256_to_f64(value.unsigned_abs())
+    } else {
+        u256_to_f64(value.into_raw())
+    }
+}
+
+fn f64_to_u256(value: f64) -> U256 {
+    if value.is_nan() || value <= 0.0 {
+        U256::ZERO
+    } else {
+        // saturating cast, amounts above u128::MAX do not appear in swaps
+        U256::from(value as u128)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::mock_pool::MockUniswapV2Pool;
+    use crate::{SwapPath, Token};
+    use alloy_primitives::utils::parse_units;
+    use alloy_primitives::Address;
+    use loom_defi_address_book::TokenAddressEth;
+    use loom_evm_db::LoomDBType;
+    use std::sync::Arc;
+
+    fn arb_swap_line() -> SwapLine {
+        let weth = Arc::new(Token::new_with_data(TokenAddressEth::WETH, Some("WETH".to_string()), None, Some(18), true, false));
+        let usdt = Arc::new(Token::new_with_data(TokenAddressEth::USDT, Some("USDT".to_string()), None, Some(6), true, false));
+        let weth_reserve = parse_units("1000", "ether").unwrap().get_absolute();
+        // WETH is sold at 2100 USDT in the first pool and bought at 2000 USDT in the second one
+        let pool_sell = MockUniswapV2Pool::new(
+            TokenAddressEth::WETH,
+            TokenAddressEth::USDT,
+            Address::repeat_byte(1),
+            weth_reserve,
+            U256::from(2_100_000_000_000u64),
+        );
+        let pool_buy = MockUniswapV2Pool::new(
+            TokenAddressEth::WETH,
+            TokenAddressEth::USDT,
+            Address::repeat_byte(2),
+            weth_reserve,
+            U256::from(2_000_000_000_000u64),
+        );
+
+        SwapLine::from(SwapPath::new(vec![weth.clone(), usdt, weth], vec![pool_sell, pool_buy]))
+    }
+
+    #[test]
+    fn test_optimizers_find_same_maximum() {
+        let state = LoomDBType::default();
+        let start_amount = parse_units("0.01", "ether").unwrap().get_absolute();
+
+        let mut golden_line = arb_swap_line();
+        let golden = GoldenSectionOptimizer::new().optimize(&mut golden_line, &state, Env::default(), start_amount).unwrap();
+        let mut brent_line = arb_swap_line();
+        let brent = BrentOptimizer::new().optimize(&mut brent_line, &state, Env::default(), start_amount).unwrap();
+        let mut step_line = arb_swap_line();
+        let step = StepOptimizer::new().optimize(&mut step_line, &state, Env::default(), start_amount).unwrap();
+
+        assert!(golden.profit.is_positive());
+        assert!(brent.profit.is_positive());
+        assert!(golden.profit >= step.profit);
+        assert!(brent.profit >= step.profit);
+        assert!(brent.iterations <= DEFAULT_OPTIMIZER_MAX_ITERATIONS);
+
+        let tolerance = parse_units("0.001", "ether").unwrap().get_absolute();
+        assert!(golden_line.abs_profit().abs_diff(brent_line.abs_profit()) < tolerance);
+    }
+
+    #[test]
+    fn test_gas_aware_profit() {
+        let state = LoomDBType::default();
+        let start_amount = parse_units("0.01", "ether").unwrap().get_absolute();
+        let base_fee = 100_000_000_000u64;
+
+        let mut swap_line = arb_swap_line();
+        let result = BrentOptimizer::new().with_base_fee(base_fee).optimize(&mut swap_line, &state, Env::default(), start_amount).unwrap();
+
+        let gas_cost = U256::from(swap_line.gas_used.unwrap()) * U256::from(base_fee);
+        assert_eq!(result.profit, I256::from_raw(swap_line.abs_profit()) - I256::from_raw(gas_cost));
+    }
+
+    #[test]
+    fn test_gas_aware_profit_requires_token_price() {
+        let state = LoomDBType::default();
+        let mut swap_line = arb_swap_line();
+        let (weth, usdt) = (swap_line.path.tokens[0].clone(), swap_line.path.tokens[1].clone());
+        swap_line.path.tokens = vec![usdt.clone(), weth, usdt];
+        swap_line.path.pools.reverse();
+
+        let result = BrentOptimizer::new().with_base_fee(100_000_000_000u64).optimize(
+            &mut swap_line,
+            &state,
+            Env::default(),
+            U256::from(10_000_000u64),
+        );
+        assert_eq!(result.unwrap_err().msg, "TOKEN_PRICE_NOT_SET");
+    }
+
+    #[test]
+    fn test_unprofitable_start() {
+        let state = LoomDBType::default();
+        let mut swap_line = arb_swap_line();
+        swap_line.path.pools.reverse();
+
+        let result = GoldenSectionOptimizer::new()
+            .optimize(&mut swap_line, &state, Env::default(), parse_units("0.01", "ether").unwrap().get_absolute())
+            .unwrap();
+        assert!(result.profit.is_negative());
+        assert_eq!(result.iterations, 1);
+    }
+}
@@ -1,14 +1,14 @@
-use std::collections::HashMap;
-use std::fmt::{Display, Formatter};
-use std::sync::Arc;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Arc, RwLock};
 
 use crate::{Swap, Token};
 use alloy_primitives::utils::format_units;
 use alloy_primitives::{Address, U256};
 use eyre::{eyre, OptionExt, Result};
-use lazy_static::lazy_static;
 use loom_evm_utils::NWETH;
 use rand::random;
+use serde::Deserialize;
 use tracing::{error, info};
 
 #[derive(Clone, Debug)]
@@ -34,42 +34,243 @@ impl Display for Tips {
     }
 }
 
-lazy_static! {
-    static ref START_PCT : U256 = U256::from(9900);
+/// Share of profit paid to the block builder
+pub trait TipsPolicy: Debug + Send + Sync {
+    /// Returns share of profit after gas cost in basis points for swap with profit_eth.
+    /// requested_pct is the share set by the strategy that composed the swap, it caps the share of the policy.
+    fn tips_pct(&self, profit_eth: &U256, requested_pct: Option<u32>) -> u32;
 
-    static ref SLOPES : Vec<(U256,U256)> = vec![
-//x        (U256::from(0), U256::from(10000)),
-        (U256::from(10).pow(U256::from(19)), U256::from(7000)),
-        (U256::from(10).pow(U256::from(19))*U256::from(5), U256::from(5000))
-    ];
+    /// Records a bid that won a block, either of a competitor or of our own included bundle
+    fn observe_winning_bid(&self, _profit_eth: U256, _tips_eth: U256) {}
 }
-pub fn tips_pct_advanced(profit: &U256) -> u32 {
-    let mut start_point = U256::ZERO;
-    let mut start_pct = *START_PCT;
-    for (x, y) in SLOPES.iter() {
-        if x > profit {
-            return (start_pct - ((start_pct - y) * (profit - start_point) / (x - start_point))).to::<u32>();
+
+fn cap_pct(tips_pct: u32, requested_pct: Option<u32>) -> u32 {
+    requested_pct.map_or(tips_pct, |requested_pct| tips_pct.min(requested_pct))
+}
+
+const PCT_DENOMINATOR: u32 = 10000;
+
+fn randomize_pct(tips_pct: u32, randomize_bps: u32) -> u32 {
+    if randomize_bps == 0 {
+        return tips_pct;
+    }
+    tips_pct.saturating_sub(random::<u32>() % randomize_bps)
+}
+
+/// Linear interpolation between (profit_eth, pct) points starting from start_pct at zero profit.
+/// Requested share caps the table.
+#[derive(Clone, Debug)]
+pub struct PiecewiseLinearTipsPolicy {
+    start_pct: u32,
+    slopes: Vec<(U256, u32)>,
+    randomize_bps: u32,
+}
+
+impl PiecewiseLinearTipsPolicy {
+    pub fn new(start_pct: u32, slopes: Vec<(U256, u32)>, randomize_bps: u32) -> Self {
+        Self { start_pct, slopes, randomize_bps }
+    }
+
+    pub fn interpolate(&self, profit_eth: &U256) -> u32 {
+        let mut start_point = U256::ZERO;
+        let mut start_pct = U256::from(self.start_pct);
+        for (x, y) in self.slopes.iter() {
+            let y = U256::from(*y);
+            if x > profit_eth {
+                return if start_pct >= y {
+                    (start_pct - ((start_pct - y) * (profit_eth - start_point) / (x - start_point))).to::<u32>()
+                } else {
+                    (start_pct + ((y - start_pct) * (profit_eth - start_point) / (x - start_point))).to::<u32>()
+                };
+            }
+            start_point = *x;
+            start_pct = y;
+        }
+        start_pct.to()
+    }
+}
+
+impl Default for PiecewiseLinearTipsPolicy {
+    fn default() -> Self {
+        Self {
+            start_pct: 9900,
+            slopes: vec![(U256::from(10).pow(U256::from(19)), 7000), (U256::from(10).pow(U256::from(19)) * U256::from(5), 5000)],
+            randomize_bps: 50,
+        }
+    }
+}
+
+impl TipsPolicy for PiecewiseLinearTipsPolicy {
+    fn tips_pct(&self, profit_eth: &U256, requested_pct: Option<u32>) -> u32 {
+        cap_pct(randomize_pct(self.interpolate(profit_eth), self.randomize_bps), requested_pct)
+    }
+}
+
+/// Behaviour used before tips policies were configurable: requested share is paid as is, the default piecewise linear
+/// table is used only when no share is requested. Used when strategy has no tips policy configured.
+#[derive(Clone, Debug, Default)]
+pub struct LegacyTipsPolicy {
+    table: PiecewiseLinearTipsPolicy,
+}
+
+impl TipsPolicy for LegacyTipsPolicy {
+    fn tips_pct(&self, profit_eth: &U256, requested_pct: Option<u32>) -> u32 {
+        randomize_pct(requested_pct.unwrap_or_else(|| self.table.interpolate(profit_eth)), self.table.randomize_bps)
+    }
+}
+
+/// Same share for every swap, capped by requested share
+#[derive(Clone, Debug)]
+pub struct FixedTipsPolicy {
+    pct: u32,
+    randomize_bps: u32,
+}
+
+impl FixedTipsPolicy {
+    pub fn new(pct: u32, randomize_bps: u32) -> Self {
+        Self { pct, randomize_bps }
+    }
+}
+
+impl TipsPolicy for FixedTipsPolicy {
+    fn tips_pct(&self, _profit_eth: &U256, requested_pct: Option<u32>) -> u32 {
+        cap_pct(randomize_pct(self.pct, self.randomize_bps), requested_pct)
+    }
+}
+
+/// Overbids a percentile of recently observed winning bids, bounded by min_pct and max_pct and capped by requested share.
+/// Clones share observed bids.
+#[derive(Clone, Debug)]
+pub struct CompetitionTipsPolicy {
+    min_pct: u32,
+    max_pct: u32,
+    overbid_bps: u32,
+    percentile: u32,
+    window: usize,
+    winning_bids: Arc<RwLock<VecDeque<u32>>>,
+}
+
+impl CompetitionTipsPolicy {
+    pub fn new(min_pct: u32, max_pct: u32, overbid_bps: u32, percentile: u32, window: usize) -> Self {
+        Self { min_pct, max_pct, overbid_bps, percentile, window, winning_bids: Arc::new(RwLock::new(VecDeque::with_capacity(window))) }
+    }
+
+    fn winning_bid_percentile(&self) -> Option<u32> {
+        let winning_bids = self.winning_bids.read().ok()?;
+        if winning_bids.is_empty() {
+            return None;
+        }
+        let mut bids: Vec<u32> = winning_bids.iter().copied().collect();
+        bids.sort_unstable();
+        let idx = (bids.len() - 1) * self.percentile.min(100) as usize / 100;
+        Some(bids[idx])
+    }
+}
+
+impl TipsPolicy for CompetitionTipsPolicy {
+    fn tips_pct(&self, _profit_eth: &U256, requested_pct: Option<u32>) -> u32 {
+        let competitive_pct = self.winning_bid_percentile().map_or(0, |bid_pct| bid_pct + self.overbid_bps);
+        cap_pct(self.min_pct.max(competitive_pct).min(self.max_pct), requested_pct)
+    }
+
+    fn observe_winning_bid(&self, profit_eth: U256, tips_eth: U256) {
+        if profit_eth.is_zero() || self.window == 0 {
+            return;
+        }
+        let bid_pct = (tips_eth * U256::from(PCT_DENOMINATOR) / profit_eth).min(U256::from(PCT_DENOMINATOR)).to::<u32>();
+        if let Ok(mut winning_bids) = self.winning_bids.write() {
+            if winning_bids.len() >= self.window {
+                winning_bids.pop_front();
+            }
+            winning_bids.push_back(bid_pct);
         }
-        start_point = *x;
-        start_pct = *y;
     }
-    start_pct.to()
+}
+
+fn default_randomize_bps() -> u32 {
+    50
+}
+
+fn default_percentile() -> u32 {
+    50
+}
+
+fn default_window() -> usize {
+    100
+}
+
+/// Tips policy section of a strategy config, profit thresholds are in ETH and shares in basis points
+#[derive(Clone, Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TipsPolicyConfig {
+    PiecewiseLinear {
+        start_pct: u32,
+        slopes: Vec<(f64, u32)>,
+        #[serde(default = "default_randomize_bps")]
+        randomize_bps: u32,
+    },
+    Fixed {
+        pct: u32,
+        #[serde(default)]
+        randomize_bps: u32,
+    },
+    Competition {
+        min_pct: u32,
+        max_pct: u32,
+        overbid_bps: u32,
+        #[serde(default = "default_percentile")]
+        percentile: u32,
+        #[serde(default = "default_window")]
+        window: usize,
+    },
+}
+
+impl TipsPolicyConfig {
+    pub fn build(&self) -> Result<Arc<dyn TipsPolicy>> {
+        let check_pct = |pct: u32| if pct > PCT_DENOMINATOR { Err(eyre!("TIPS_PCT_EXCEEDS_PROFIT")) } else { Ok(pct) };
+
+        let policy: Arc<dyn TipsPolicy> = match self {
+            TipsPolicyConfig::PiecewiseLinear { start_pct, slopes, randomize_bps } => {
+                let mut points = Vec::with_capacity(slopes.len());
+                for (profit_eth, pct) in slopes.iter() {
+                    let profit_wei = U256::from((*profit_eth * 1e18) as u128);
+                    if profit_wei.is_zero() || points.last().is_some_and(|(prev, _)| *prev >= profit_wei) {
+                        return Err(eyre!("TIPS_SLOPES_NOT_INCREASING"));
+                    }
+                    points.push((profit_wei, check_pct(*pct)?));
+                }
+                Arc::new(PiecewiseLinearTipsPolicy::new(check_pct(*start_pct)?, points, *randomize_bps))
+            }
+            TipsPolicyConfig::Fixed { pct, randomize_bps } => Arc::new(FixedTipsPolicy::new(check_pct(*pct)?, *randomize_bps)),
+            TipsPolicyConfig::Competition { min_pct, max_pct, overbid_bps, percentile, window } => {
+                if min_pct > max_pct {
+                    return Err(eyre!("TIPS_MIN_PCT_EXCEEDS_MAX_PCT"));
+                }
+                Arc::new(CompetitionTipsPolicy::new(check_pct(*min_pct)?, check_pct(*max_pct)?, *overbid_bps, *percentile, *window))
+            }
+        };
+        Ok(policy)
+    }
+}
+
+pub fn tips_pct_advanced(profit: &U256) -> u32 {
+    PiecewiseLinearTipsPolicy::default().interpolate(profit)
 }
 
 pub fn randomize_tips_pct(tips_pct: u32) -> u32 {
-    let rnd: u32 = random::<u32>() % 50;
-    tips_pct - rnd
+    randomize_pct(tips_pct, default_randomize_bps())
 }
 
 pub fn tips_and_value_for_swap_type(
     swap: &Swap,
     tips_pct: Option<u32>,
+    tips_policy: &dyn TipsPolicy,
     gas_cost: Option<U256>,
     eth_balance: U256,
 ) -> Result<(Vec<Tips>, U256)> {
     let total_profit_eth = swap.abs_profit_eth();
     info!("Total profit eth : {}", format_units(total_profit_eth, "ether").unwrap_or_default());
-    let tips_pct = randomize_tips_pct(tips_pct.unwrap_or(tips_pct_advanced(&total_profit_eth)));
+    let tips_pct = tips_policy.tips_pct(&total_profit_eth, tips_pct);
 
     if let Some(gas_cost) = gas_cost {
         if total_profit_eth < gas_cost {
@@ -202,3 +403,73 @@ pub fn tips_and_value_for_swap_type(
         _ => Err(eyre!("NOT_IMPLEMENTED")),
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn eth(value: u64) -> U256 {
+        U256::from(value) * U256::from(10).pow(U256::from(18))
+    }
+
+    #[test]
+    fn test_piecewise_linear() {
+        let policy = PiecewiseLinearTipsPolicy::default();
+        assert_eq!(policy.interpolate(&U256::ZERO), 9900);
+        assert_eq!(policy.interpolate(&eth(5)), 8450);
+        assert_eq!(policy.interpolate(&eth(10)), 7000);
+        assert_eq!(policy.interpolate(&eth(30)), 6000);
+        assert_eq!(policy.interpolate(&eth(100)), 5000);
+
+        // requested share only caps the table
+        let tips_pct = policy.tips_pct(&eth(100), Some(9000));
+        assert!(tips_pct <= 5000 && tips_pct > 5000 - 50);
+        assert_eq!(policy.tips_pct(&eth(100), Some(4000)), 4000);
+    }
+
+    #[test]
+    fn test_legacy() {
+        let policy = LegacyTipsPolicy::default();
+
+        // requested share is not capped by the table
+        let tips_pct = policy.tips_pct(&eth(100), Some(9000));
+        assert!(tips_pct <= 9000 && tips_pct > 9000 - 50);
+        let tips_pct = policy.tips_pct(&eth(100), None);
+        assert!(tips_pct <= 5000 && tips_pct > 5000 - 50);
+    }
+
+    #[test]
+    fn test_competition() {
+        let policy = CompetitionTipsPolicy::new(5000, 9500, 100, 50, 3);
+        assert_eq!(policy.tips_pct(&eth(1), None), 5000);
+        assert_eq!(policy.tips_pct(&eth(1), Some(9000)), 5000);
+        assert_eq!(policy.tips_pct(&eth(1), Some(4000)), 4000);
+
+        for tips in [8, 6, 9, 7] {
+            policy.observe_winning_bid(eth(10), eth(tips));
+        }
+        // window keeps 6, 9 and 7 ETH bids, median is 70%
+        assert_eq!(policy.clone().tips_pct(&eth(1), None), 7100);
+        assert_eq!(policy.tips_pct(&eth(1), Some(9000)), 7100);
+        assert_eq!(policy.tips_pct(&eth(1), Some(7000)), 7000);
+    }
+
+    #[test]
+    fn test_config() {
+        #[derive(Deserialize)]
+        struct Section {
+            tips: TipsPolicyConfig,
+        }
+
+        let config: Section = toml::from_str("tips = { type = \"fixed\", pct = 8000 }").unwrap();
+        assert_eq!(config.tips.build().unwrap().tips_pct(&eth(1), Some(9000)), 8000);
+        assert_eq!(config.tips.build().unwrap().tips_pct(&eth(1), Some(7000)), 7000);
+
+        let config: Section =
+            toml::from_str("tips = { type = \"piecewise_linear\", start_pct = 9000, slopes = [[1.0, 8000]], randomize_bps = 0 }").unwrap();
+        assert_eq!(config.tips.build().unwrap().tips_pct(&U256::from(5).pow(U256::from(17)), None), 8500);
+
+        let config: Section = toml::from_str("tips = { type = \"fixed\", pct = 10001 }").unwrap();
+        assert!(config.tips.build().is_err());
+    }
+}
@@ -4,11 +4,35 @@ use loom_types_entities::PoolId;
 
 #[derive(Clone, Debug)]
 pub enum MarketEvents<LDT: LoomDataTypes = LoomDataTypesEthereum> {
-    BlockHeaderUpdate { block_number: BlockNumber, block_hash: LDT::BlockHash, timestamp: u64, base_fee: u64, next_base_fee: u64 },
-    BlockTxUpdate { block_number: BlockNumber, block_hash: LDT::BlockHash },
-    BlockLogsUpdate { block_number: BlockNumber, block_hash: LDT::BlockHash },
-    BlockStateUpdate { block_hash: LDT::BlockHash },
-    NewPoolLoaded { pool_id: PoolId<LDT>, swap_path_idx_vec: Vec<usize> },
+    BlockHeaderUpdate {
+        block_number: BlockNumber,
+        block_hash: LDT::BlockHash,
+        timestamp: u64,
+        base_fee: u64,
+        next_base_fee: u64,
+    },
+    BlockTxUpdate {
+        block_number: BlockNumber,
+        block_hash: LDT::BlockHash,
+    },
+    BlockLogsUpdate {
+        block_number: BlockNumber,
+        block_hash: LDT::BlockHash,
+    },
+    BlockStateUpdate {
+        block_hash: LDT::BlockHash,
+    },
+    NewPoolLoaded {
+        pool_id: PoolId<LDT>,
+        swap_path_idx_vec: Vec<usize>,
+    },
+    /// Orphaned blocks are ordered from the old head down to the child of the common ancestor
+    Reorg {
+        depth: usize,
+        old_head: LDT::BlockHash,
+        new_head: LDT::BlockHash,
+        orphaned_blocks: Vec<LDT::BlockHash>,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -1,4 +1,5 @@
 use crate::Message;
+use loom_core_actors::SupervisorEvent;
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
 use loom_types_entities::{EstimationError, SwapError};
 
@@ -7,6 +8,7 @@ pub enum HealthEvent<LDT: LoomDataTypes = LoomDataTypesEthereum> {
     PoolSwapError(SwapError<LDT>),
     SwapLineEstimationError(EstimationError<LDT>),
     MonitorTx(LDT::TxHash),
+    Supervisor(SupervisorEvent),
 }
 
 pub type MessageHealthEvent<LDT = LoomDataTypesEthereum> = Message<HealthEvent<LDT>>;
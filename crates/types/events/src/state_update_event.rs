@@ -12,6 +12,7 @@ use loom_types_entities::{PoolWrapper, SwapDirection};
 #[derive(Clone)]
 pub struct StateUpdateEvent<DB, LDT: LoomDataTypes = LoomDataTypesEthereum> {
     pub next_block_number: u64,
+    pub parent_block_hash: Option<LDT::BlockHash>,
     pub next_block_timestamp: u64,
     pub next_base_fee: u64,
     market_state: DB,
@@ -41,6 +42,7 @@ impl<DB: DatabaseRef, LDT: LoomDataTypes> StateUpdateEvent<DB, LDT> {
     ) -> StateUpdateEvent<DB, LDT> {
         StateUpdateEvent {
             next_block_number: next_block,
+            parent_block_hash: None,
             next_block_timestamp,
             next_base_fee,
             state_update,
@@ -54,6 +56,10 @@ impl<DB: DatabaseRef, LDT: LoomDataTypes> StateUpdateEvent<DB, LDT> {
         }
     }
 
+    pub fn with_parent_block_hash(self, parent_block_hash: LDT::BlockHash) -> Self {
+        Self { parent_block_hash: Some(parent_block_hash), ..self }
+    }
+
     pub fn evm_env(&self) -> Env {
         env_for_block(self.next_block_number, self.next_block_timestamp)
     }
@@ -4,9 +4,11 @@ use alloy_eips::eip2718::Encodable2718;
 use alloy_primitives::{Bytes, U256};
 use eyre::{eyre, Result};
 use loom_types_blockchain::{LoomDataTypes, LoomDataTypesEthereum};
+use loom_types_entities::tips::TipsPolicy;
 use loom_types_entities::{PoolId, Swap};
 use revm::DatabaseRef;
 use std::ops::Deref;
+use std::sync::Arc;
 
 #[derive(Clone, Debug)]
 pub enum TxState<LDT: LoomDataTypes = LoomDataTypesEthereum> {
@@ -58,6 +60,8 @@ pub struct SwapComposeData<DB, LDT: LoomDataTypes = LoomDataTypesEthereum> {
     pub poststate_update: Option<Vec<LDT::StateUpdate>>,
     pub origin: Option<String>,
     pub tips_pct: Option<u32>,
+    pub tips_policy: Option<Arc<dyn TipsPolicy>>,
+    /// Tips paid to block builder, gas cost excluded
     pub tips: Option<U256>,
 }
 
@@ -117,6 +121,7 @@ impl<DB: DatabaseRef + Send + Sync + Clone + 'static, LDT: LoomDataTypes> Defaul
             poststate_update: None,
             origin: None,
             tips_pct: None,
+            tips_policy: None,
             tips: None,
         }
     }
@@ -44,13 +44,18 @@ pub struct TxComposeData<LDT: LoomDataTypes = LoomDataTypesEthereum> {
     pub stuffing_txs_hashes: Vec<LDT::TxHash>,
     pub stuffing_txs: Vec<LDT::Transaction>,
     pub next_block_number: BlockNumber,
+    /// Hash of the block the swap was calculated on, swaps on orphaned blocks are dropped
+    pub parent_block_hash: Option<LDT::BlockHash>,
     pub next_block_timestamp: u64,
     pub next_block_base_fee: u64,
     pub tx_bundle: Option<Vec<TxState<LDT>>>,
     pub rlp_bundle: Option<Vec<RlpState>>,
     pub origin: Option<String>,
     pub swap: Option<Swap>,
+    /// Tips paid to block builder, gas cost excluded
     pub tips: Option<U256>,
+    /// Gas cost estimated for the transaction
+    pub gas_cost: Option<U256>,
 }
 
 impl<LDT: LoomDataTypes> Default for TxComposeData<LDT> {
@@ -67,12 +72,14 @@ impl<LDT: LoomDataTypes> Default for TxComposeData<LDT> {
             stuffing_txs_hashes: Vec::new(),
             stuffing_txs: Vec::new(),
             next_block_number: Default::default(),
+            parent_block_hash: None,
             next_block_timestamp: Default::default(),
             tx_bundle: None,
             rlp_bundle: None,
             origin: None,
             swap: None,
             tips: None,
+            gas_cost: None,
         }
     }
 }